@@ -201,47 +201,56 @@ fn analyze_color(pix: &Pix) -> ColorResult<ColorStats> {
 
 /// Count the number of unique colors in an image
 pub fn count_colors(pix: &Pix) -> ColorResult<u32> {
-    match pix.depth() {
-        PixelDepth::Bit1 => Ok(2), // Binary always has 2 possible values
-        PixelDepth::Bit8 => count_colors_8bpp(pix),
-        PixelDepth::Bit32 => count_colors_32bpp(pix),
-        _ => Err(ColorError::UnsupportedDepth {
-            expected: "1, 8, or 32 bpp",
-            actual: pix.depth().bits(),
-        }),
-    }
+    count_colors_capped(pix, u32::MAX)
 }
 
-fn count_colors_8bpp(pix: &Pix) -> ColorResult<u32> {
+/// Count unique colors, stopping as soon as `cap` is exceeded
+///
+/// Returns the exact count if it is at most `cap`, and `cap + 1` as soon
+/// as more than `cap` distinct values have been seen.  This answers
+/// "does this image have few colors?" without enumerating every color of
+/// a photo.  Depth handling matches [`count_colors`].
+pub fn count_colors_capped(pix: &Pix, cap: u32) -> ColorResult<u32> {
+    let over = cap.saturating_add(1);
     let w = pix.width();
     let h = pix.height();
-    let mut seen = [false; 256];
-
-    for y in 0..h {
-        for x in 0..w {
-            let pixel = pix.get_pixel_unchecked(x, y) as usize;
-            seen[pixel] = true;
+    match pix.depth() {
+        PixelDepth::Bit1 => Ok(2.min(over)),
+        PixelDepth::Bit8 => {
+            let mut seen = [false; 256];
+            let mut n = 0u32;
+            for y in 0..h {
+                for x in 0..w {
+                    let v = pix.get_pixel_unchecked(x, y) as usize;
+                    if !seen[v] {
+                        seen[v] = true;
+                        n += 1;
+                        if n > cap {
+                            return Ok(over);
+                        }
+                    }
+                }
+            }
+            Ok(n)
         }
-    }
-
-    Ok(seen.iter().filter(|&&s| s).count() as u32)
-}
-
-fn count_colors_32bpp(pix: &Pix) -> ColorResult<u32> {
-    let w = pix.width();
-    let h = pix.height();
-    let mut colors: HashSet<u32> = HashSet::new();
-
-    for y in 0..h {
-        for x in 0..w {
-            let pixel = pix.get_pixel_unchecked(x, y);
-            let (r, g, b) = pixel::extract_rgb(pixel);
-            let rgb_key = ((r as u32) << 16) | ((g as u32) << 8) | (b as u32);
-            colors.insert(rgb_key);
+        PixelDepth::Bit32 => {
+            let mut colors: HashSet<u32> = HashSet::new();
+            for y in 0..h {
+                for x in 0..w {
+                    let (r, g, b) = pixel::extract_rgb(pix.get_pixel_unchecked(x, y));
+                    let rgb_key = ((r as u32) << 16) | ((g as u32) << 8) | (b as u32);
+                    if colors.insert(rgb_key) && colors.len() as u32 > cap {
+                        return Ok(over);
+                    }
+                }
+            }
+            Ok(colors.len() as u32)
         }
+        _ => Err(ColorError::UnsupportedDepth {
+            expected: "1, 8, or 32 bpp",
+            actual: pix.depth().bits(),
+        }),
     }
-
-    Ok(colors.len() as u32)
 }
 
 /// Check if an image is grayscale
@@ -481,6 +490,47 @@ pub fn mask_over_color_range(
     Ok(mask_mut.into())
 }
 
+/// Generate a 1bpp mask over "colorful" pixels.
+///
+/// A pixel is set if its HSV saturation is at least `min_saturation` and
+/// its value is at least `min_value`, both in `[0.0, 1.0]`.  The value
+/// threshold rejects dark pixels, whose saturation is unreliable, so
+/// colored stamps and highlights are separated from black or gray text.
+///
+/// # Errors
+///
+/// Returns an error if the image is not 32 bpp or a threshold is
+/// outside `[0.0, 1.0]`.
+pub fn colorful_mask(pix: &Pix, min_saturation: f32, min_value: f32) -> ColorResult<Pix> {
+    if pix.depth() != PixelDepth::Bit32 {
+        return Err(ColorError::UnsupportedDepth {
+            expected: "32 bpp",
+            actual: pix.depth().bits(),
+        });
+    }
+    if !(0.0..=1.0).contains(&min_saturation) || !(0.0..=1.0).contains(&min_value) {
+        return Err(ColorError::InvalidParameters(format!(
+            "thresholds must be in [0, 1]: min_saturation = {min_saturation}, min_value = {min_value}"
+        )));
+    }
+    let w = pix.width();
+    let h = pix.height();
+    let mask = Pix::new(w, h, PixelDepth::Bit1).map_err(ColorError::Core)?;
+    let mut mask_mut = mask.try_into_mut().unwrap();
+
+    for y in 0..h {
+        for x in 0..w {
+            let (r, g, b) = pixel::extract_rgb(pix.get_pixel_unchecked(x, y));
+            let hsv = rgb_to_hsv(r, g, b);
+            if hsv.s >= min_saturation && hsv.v >= min_value {
+                mask_mut.set_pixel_unchecked(x, y, 1);
+            }
+        }
+    }
+
+    Ok(mask_mut.into())
+}
+
 /// Determine the fraction of pixels that are colored vs gray.
 ///
 /// Returns `(pix_fract, color_fract)` where:
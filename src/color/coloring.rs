@@ -22,6 +22,9 @@
 //! 5. **Hue-Invariant Mapping** ([`pix_map_with_invariant_hue`]):
 //!    Change saturation/brightness while preserving hue
 //!
+//! 6. **Chroma Keying** ([`chroma_key`]):
+//!    Make a background color transparent, with feathered edges
+//!
 //! # Examples
 //!
 //! ```no_run
@@ -38,8 +41,9 @@
 //! let colored = pix_color_gray(&pix, None, &options).unwrap();
 //! ```
 
+use crate::color::colorspace::rgb_to_hsv;
 use crate::color::{ColorError, ColorResult};
-use crate::core::{Box, Pix, PixelDepth, pixel};
+use crate::core::{Box, Color, Pix, PixelDepth, pixel};
 
 /// Paint type for gray colorization
 ///
@@ -736,6 +740,79 @@ pub fn pix_map_with_invariant_hue(pix: &Pix, src_color: u32, fract: f32) -> Colo
     pix_linear_map_to_target_color(pix, src_color, dst_color)
 }
 
+/// Make pixels close to a key color transparent (chroma keying)
+///
+/// Returns a 32 bpp RGBA copy of the image whose alpha is 0 for pixels
+/// within `tolerance` of `key`, 255 for pixels farther than
+/// `tolerance + softness`, and ramps linearly in between, which feathers
+/// the edges of the foreground.  If the input already has an alpha
+/// channel, the result keeps the smaller of the two alphas.
+///
+/// Distances are measured in the HSV cone: a color with hue `h`,
+/// saturation `s` and value `v` is the point
+/// `(s*v*cos(2*pi*h), s*v*sin(2*pi*h), v)`.  Hue therefore matters for
+/// saturated, bright colors but not for grays or dark pixels, so shadows
+/// on a green screen stay keyed only if `tolerance` covers their drop in
+/// value.  Distances range from 0 to about 2.2; a tolerance around 0.3
+/// with softness 0.1 suits a typical green screen.
+///
+/// # Arguments
+///
+/// * `pix` - Input image of any depth; converted to 32 bpp
+/// * `key` - Background color to remove
+/// * `tolerance` - Largest distance that is fully transparent (>= 0)
+/// * `softness` - Width of the partially transparent band (>= 0)
+pub fn chroma_key(pix: &Pix, key: Color, tolerance: f32, softness: f32) -> ColorResult<Pix> {
+    if !(tolerance >= 0.0 && softness >= 0.0) {
+        return Err(ColorError::InvalidParameters(
+            "tolerance and softness must be >= 0".to_string(),
+        ));
+    }
+    let pix32 = if pix.depth() == PixelDepth::Bit32 {
+        pix.clone()
+    } else {
+        pix.convert_to_32()?
+    };
+    let has_alpha = pix32.spp() == 4;
+
+    let hsv_point = |r: u8, g: u8, b: u8| {
+        let hsv = rgb_to_hsv(r, g, b);
+        let chroma = hsv.s * hsv.v;
+        let angle = 2.0 * std::f32::consts::PI * hsv.h;
+        [chroma * angle.cos(), chroma * angle.sin(), hsv.v]
+    };
+    let key_point = hsv_point(key.r, key.g, key.b);
+
+    let w = pix32.width();
+    let h = pix32.height();
+    let mut out = Pix::new(w, h, PixelDepth::Bit32)?.to_mut();
+    out.set_spp(4);
+    for y in 0..h {
+        for x in 0..w {
+            let (r, g, b, a) = pixel::extract_rgba(pix32.get_pixel_unchecked(x, y));
+            let p = hsv_point(r, g, b);
+            let dist = p
+                .iter()
+                .zip(&key_point)
+                .map(|(u, v)| (u - v) * (u - v))
+                .sum::<f32>()
+                .sqrt();
+            let mut alpha = if dist <= tolerance {
+                0
+            } else if dist >= tolerance + softness {
+                255
+            } else {
+                (255.0 * (dist - tolerance) / softness).round() as u8
+            };
+            if has_alpha {
+                alpha = alpha.min(a);
+            }
+            out.set_pixel_unchecked(x, y, pixel::compose_rgba(r, g, b, alpha));
+        }
+    }
+    Ok(out.into())
+}
+
 /// Colorize gray regions of an RGB image with specified color.
 ///
 /// Only near-gray pixels (where max component difference < `thresh`) that fall
@@ -4,7 +4,7 @@
 //!
 //! - **Color space conversion** ([`colorspace`]): RGB ↔ HSV, LAB, XYZ, YUV
 //! - **Thresholding** ([`threshold`]): Binary conversion, Otsu's method, adaptive thresholding
-//! - **Color quantization** ([`quantize`]): Median cut, octree algorithms
+//! - **Color quantization** ([`quantize`]): Median cut, octree, k-means algorithms
 //! - **Color segmentation** ([`segment`]): Unsupervised color segmentation
 //! - **Color analysis** ([`analysis`]): Statistics, color counting, grayscale detection
 //! - **Color fill** ([`colorfill`]): Flood fill for RGB images based on color similarity
@@ -46,6 +46,8 @@ pub use colorspace::{
     RegionFlag,
     Xyz,
     Yuv,
+    // Stain separation
+    color_deconvolve,
     // HSV histogram peak finding
     find_histo_peaks_hsv,
     // FPix-level conversions
@@ -93,14 +95,19 @@ pub use threshold::{
     // Types
     AdaptiveMethod,
     AdaptiveThresholdOptions,
+    AutoBinarizeMethod,
+    BayerSize,
     // Functions
     adapt_threshold_to_binary_gen,
     adaptive_threshold,
+    auto_binarize,
+    auto_binarize_with_method,
     compute_otsu_threshold,
     dither_to_2bpp,
     dither_to_2bpp_spec,
     dither_to_binary,
     dither_to_binary_with_threshold,
+    edge_guided_binarize,
     generate_mask_by_band,
     generate_mask_by_band_32,
     generate_mask_by_discr_32,
@@ -109,6 +116,8 @@ pub use threshold::{
     gray_quant_from_histo,
     masked_thresh_on_background_norm,
     ordered_dither,
+    ordered_dither_bayer,
+    otsu_adaptive,
     otsu_adaptive_threshold,
     otsu_thresh_on_background_norm,
     sauvola_binarize_tiled,
@@ -139,6 +148,7 @@ pub use quantize::{
     few_colors_octcube_quant2,
     fixed_octcube_quant_256,
     fixed_octcube_quant_gen_rgb,
+    kmeans_quant,
     median_cut_quant,
     median_cut_quant_mixed,
     median_cut_quant_simple,
@@ -152,6 +162,7 @@ pub use quantize::{
     octree_quant_by_population,
     octree_quant_num_colors,
     quant_from_cmap,
+    quantize_to_depth,
     remove_unused_colors,
 };
 
@@ -165,9 +176,11 @@ pub use analysis::{
     color_fraction,
     color_magnitude,
     color_shift_white_point,
+    colorful_mask,
     colors_for_quantization,
     convert_rgb_to_cmap_lossless,
     count_colors,
+    count_colors_capped,
     find_color_regions,
     grayscale_histogram,
     has_highlight_red,
@@ -214,6 +227,7 @@ pub use coloring::{
     ColorGrayOptions,
     PaintType,
     // Image-level functions
+    chroma_key,
     color_gray_regions,
     pix_color_gray,
     pix_color_gray_masked,
@@ -3,11 +3,12 @@
 //! Reduces the number of colors in an image while preserving visual quality:
 //! - Median cut algorithm
 //! - Octree quantization
+//! - K-means clustering
 
 use crate::color::{ColorError, ColorResult};
 use crate::core::{Pix, PixColormap, PixelDepth, pixel};
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
 
 // =============================================================================
 // Median Cut Quantization
@@ -136,6 +137,54 @@ pub fn median_cut_quant_simple(pix: &Pix, max_colors: u32) -> ColorResult<Pix> {
     )
 }
 
+/// Median cut quantization to a fixed output depth
+///
+/// Quantizes to the number of colors a colormap of `target_depth` can
+/// hold (2 bpp → 4, 4 bpp → 16, 8 bpp → 256) and stores the result at
+/// that depth with a colormap.  Useful for small indexed output files.
+///
+/// `target_depth` must be 2, 4 or 8.
+pub fn quantize_to_depth(pix: &Pix, target_depth: u32) -> ColorResult<Pix> {
+    let out_depth = match target_depth {
+        2 => PixelDepth::Bit2,
+        4 => PixelDepth::Bit4,
+        8 => PixelDepth::Bit8,
+        _ => {
+            return Err(ColorError::InvalidParameters(format!(
+                "target_depth must be 2, 4 or 8; got {target_depth}"
+            )));
+        }
+    };
+
+    let quantized = median_cut_quant_simple(pix, 1 << target_depth)?;
+    if out_depth == PixelDepth::Bit8 {
+        return Ok(quantized);
+    }
+
+    // Repack the 8 bpp indices at the target depth
+    let src_cmap = quantized
+        .colormap()
+        .ok_or_else(|| ColorError::InvalidParameters("median cut produced no colormap".into()))?;
+    let mut cmap = PixColormap::new(target_depth)?;
+    for i in 0..src_cmap.len() {
+        if let Some((r, g, b)) = src_cmap.get_rgb(i) {
+            cmap.add_rgb(r, g, b)?;
+        }
+    }
+
+    let w = pix.width();
+    let h = pix.height();
+    let mut out_mut = Pix::new(w, h, out_depth)?.to_mut();
+    out_mut.set_colormap(Some(cmap))?;
+    for y in 0..h {
+        for x in 0..w {
+            out_mut.set_pixel_unchecked(x, y, quantized.get_pixel_unchecked(x, y));
+        }
+    }
+
+    Ok(out_mut.into())
+}
+
 /// A box in RGB color space containing pixel indices
 #[derive(Clone)]
 struct ColorBox {
@@ -2033,6 +2082,199 @@ pub fn few_colors_median_cut_quant_mixed(pix: &Pix, ncolors: u32, ngray: u32) ->
     median_cut_quant_mixed(pix, ncolors, ngray, dark_thresh, light_thresh, diff_thresh)
 }
 
+// =============================================================================
+// K-means Quantization
+// =============================================================================
+
+/// Quantize a 32-bit color image by k-means clustering in RGB space
+///
+/// Returns an 8 bpp colormapped image with at most `k` colors.  Fewer
+/// colors are produced when the image has fewer than `k` distinct colors
+/// or when clusters become empty during iteration.
+///
+/// Centroids are initialized with k-means++ seeding driven by `seed`, so
+/// the result is deterministic for a given seed.  Lloyd iterations run
+/// until assignments stop changing or `max_iter` is reached.  Clustering
+/// operates on the histogram of distinct colors, weighted by pixel count.
+pub fn kmeans_quant(pix: &Pix, k: u32, max_iter: u32, seed: u64) -> ColorResult<Pix> {
+    if pix.depth() != PixelDepth::Bit32 {
+        return Err(ColorError::UnsupportedDepth {
+            expected: "32 bpp",
+            actual: pix.depth().bits(),
+        });
+    }
+    if k == 0 || k > 256 {
+        return Err(ColorError::InvalidParameters(
+            "k must be between 1 and 256".to_string(),
+        ));
+    }
+
+    let w = pix.width();
+    let h = pix.height();
+
+    // Histogram of distinct colors, sorted so the result does not depend
+    // on hash iteration order
+    let mut counts: HashMap<u32, u32> = HashMap::new();
+    for y in 0..h {
+        for x in 0..w {
+            let (r, g, b) = pixel::extract_rgb(pix.get_pixel_unchecked(x, y));
+            *counts.entry(pixel::compose_rgb(r, g, b)).or_insert(0) += 1;
+        }
+    }
+    if counts.is_empty() {
+        return Err(ColorError::EmptyImage);
+    }
+    let mut colors: Vec<(u32, u32)> = counts.into_iter().collect();
+    colors.sort_unstable();
+    let points: Vec<[f64; 3]> = colors
+        .iter()
+        .map(|&(c, _)| {
+            let (r, g, b) = pixel::extract_rgb(c);
+            [r as f64, g as f64, b as f64]
+        })
+        .collect();
+    let weights: Vec<f64> = colors.iter().map(|&(_, n)| n as f64).collect();
+
+    let mut centroids = if points.len() <= k as usize {
+        points.clone()
+    } else {
+        kmeans_plus_plus_init(&points, &weights, k as usize, seed)
+    };
+
+    // Lloyd iterations
+    let mut assignment: Vec<usize> = vec![usize::MAX; points.len()];
+    for _ in 0..max_iter.max(1) {
+        let mut changed = false;
+        for (i, p) in points.iter().enumerate() {
+            let nearest = nearest_centroid(p, &centroids);
+            if nearest != assignment[i] {
+                assignment[i] = nearest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![[0.0f64; 3]; centroids.len()];
+        let mut totals = vec![0.0f64; centroids.len()];
+        for (i, p) in points.iter().enumerate() {
+            let c = assignment[i];
+            for ch in 0..3 {
+                sums[c][ch] += p[ch] * weights[i];
+            }
+            totals[c] += weights[i];
+        }
+        for (c, centroid) in centroids.iter_mut().enumerate() {
+            if totals[c] > 0.0 {
+                for ch in 0..3 {
+                    centroid[ch] = sums[c][ch] / totals[c];
+                }
+            }
+        }
+    }
+
+    // Final assignment, then drop empty clusters
+    for (i, p) in points.iter().enumerate() {
+        assignment[i] = nearest_centroid(p, &centroids);
+    }
+    let mut used = vec![false; centroids.len()];
+    for &c in &assignment {
+        used[c] = true;
+    }
+    let mut remap = vec![0u32; centroids.len()];
+    let mut colormap = PixColormap::new(8)?;
+    for (c, centroid) in centroids.iter().enumerate() {
+        if used[c] {
+            remap[c] = colormap.len() as u32;
+            let [r, g, b] = centroid.map(|v| v.round().clamp(0.0, 255.0) as u8);
+            colormap.add_rgb(r, g, b)?;
+        }
+    }
+
+    let lut: HashMap<u32, u32> = colors
+        .iter()
+        .zip(&assignment)
+        .map(|(&(color, _), &c)| (color, remap[c]))
+        .collect();
+
+    let mut out_mut = Pix::new(w, h, PixelDepth::Bit8)?.to_mut();
+    out_mut.set_colormap(Some(colormap))?;
+    for y in 0..h {
+        for x in 0..w {
+            let (r, g, b) = pixel::extract_rgb(pix.get_pixel_unchecked(x, y));
+            out_mut.set_pixel_unchecked(x, y, lut[&pixel::compose_rgb(r, g, b)]);
+        }
+    }
+
+    Ok(out_mut.into())
+}
+
+/// Choose `k` initial centroids from `points` with weighted k-means++.
+fn kmeans_plus_plus_init(
+    points: &[[f64; 3]],
+    weights: &[f64],
+    k: usize,
+    seed: u64,
+) -> Vec<[f64; 3]> {
+    // Linear congruential generator (Knuth MMIX constants)
+    let mut state = seed;
+    let mut next_unit = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+    let pick = |scores: &[f64], u: f64| {
+        let total: f64 = scores.iter().sum();
+        let mut target = u * total;
+        for (i, &s) in scores.iter().enumerate() {
+            if target < s {
+                return i;
+            }
+            target -= s;
+        }
+        scores.iter().rposition(|&s| s > 0.0).unwrap_or(0)
+    };
+
+    let mut centroids = vec![points[pick(weights, next_unit())]];
+    let mut dist2: Vec<f64> = points
+        .iter()
+        .map(|p| color_dist2(p, &centroids[0]))
+        .collect();
+    while centroids.len() < k {
+        let scores: Vec<f64> = dist2.iter().zip(weights).map(|(d, w)| d * w).collect();
+        if scores.iter().all(|&s| s == 0.0) {
+            break;
+        }
+        let c = points[pick(&scores, next_unit())];
+        for (d, p) in dist2.iter_mut().zip(points) {
+            *d = d.min(color_dist2(p, &c));
+        }
+        centroids.push(c);
+    }
+    centroids
+}
+
+/// Index of the centroid closest to `p`.
+fn nearest_centroid(p: &[f64; 3], centroids: &[[f64; 3]]) -> usize {
+    let mut best = 0;
+    let mut best_dist = f64::MAX;
+    for (i, c) in centroids.iter().enumerate() {
+        let d = color_dist2(p, c);
+        if d < best_dist {
+            best_dist = d;
+            best = i;
+        }
+    }
+    best
+}
+
+/// Squared Euclidean distance between two RGB points.
+fn color_dist2(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,6 +13,7 @@ use crate::core::{Pix, PixColormap, PixelDepth};
 use crate::filter::adaptmap::{
     BackgroundNormOptions, ContrastNormOptions, background_norm, contrast_norm,
 };
+use crate::filter::{EdgeOrientation, sobel_edge};
 use crate::region::conncomp::{ConnectivityType, count_conn_comp};
 
 // =============================================================================
@@ -327,6 +328,194 @@ fn compute_mean_from_integral_f64(
     sum as f64 / count
 }
 
+// =============================================================================
+// Automatic method selection
+// =============================================================================
+
+/// Binarization method picked by [`auto_binarize_with_method`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoBinarizeMethod {
+    /// Global Otsu threshold, used when the background is uniform
+    Otsu,
+    /// Local Sauvola threshold, used under uneven illumination
+    Sauvola,
+}
+
+/// Tile grid used to sample the background level
+const AUTO_TILES: u32 = 4;
+/// Background spread, relative to the global contrast, above which the
+/// illumination is considered uneven
+const AUTO_BG_SPREAD_FRACT: f64 = 0.1;
+/// Sauvola sensitivity used by [`auto_binarize`]
+const AUTO_SAUVOLA_K: f32 = 0.35;
+
+/// Binarize an image, choosing between Otsu and Sauvola by content
+///
+/// Equivalent to [`auto_binarize_with_method`] without reporting the
+/// chosen method.
+pub fn auto_binarize(pix: &Pix) -> ColorResult<Pix> {
+    auto_binarize_with_method(pix).map(|(pixd, _)| pixd)
+}
+
+/// Binarize an image, choosing between Otsu and Sauvola by content
+///
+/// Decision heuristic:
+///
+/// 1. The global contrast is the spread between the 5th and 95th
+///    percentiles of the gray histogram.
+/// 2. The image is divided into a 4x4 grid of tiles, and the background
+///    level of each tile is taken as its 90th percentile (the paper is
+///    assumed lighter than the ink).
+/// 3. If the standard deviation of the tile backgrounds exceeds 10% of
+///    the global contrast, the illumination is uneven and Sauvola is used
+///    with a window of about 1/8 of the smaller image dimension (odd,
+///    between 15 and 63) and k = 0.35.  Otherwise a global Otsu threshold
+///    is used.
+///
+/// # Arguments
+///
+/// * `pix` - 8 bpp grayscale, 32 bpp RGB, or colormapped image
+///
+/// # Returns
+///
+/// The 1 bpp image (dark foreground ON) and the method that was applied.
+pub fn auto_binarize_with_method(pix: &Pix) -> ColorResult<(Pix, AutoBinarizeMethod)> {
+    let gray = ensure_grayscale(pix)?;
+    let w = gray.width();
+    let h = gray.height();
+
+    let mut histo = [0u64; 256];
+    for y in 0..h {
+        for x in 0..w {
+            histo[gray.get_pixel_unchecked(x, y) as usize] += 1;
+        }
+    }
+    let total = w as u64 * h as u64;
+    let contrast = (histo_percentile(&histo, total, 0.95) as f64
+        - histo_percentile(&histo, total, 0.05) as f64)
+        .max(1.0);
+
+    let nx = AUTO_TILES.min(w);
+    let ny = AUTO_TILES.min(h);
+    let mut backgrounds = Vec::with_capacity((nx * ny) as usize);
+    for ty in 0..ny {
+        for tx in 0..nx {
+            let (x0, x1) = (tx * w / nx, (tx + 1) * w / nx);
+            let (y0, y1) = (ty * h / ny, (ty + 1) * h / ny);
+            let mut tile_histo = [0u64; 256];
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    tile_histo[gray.get_pixel_unchecked(x, y) as usize] += 1;
+                }
+            }
+            let count = (x1 - x0) as u64 * (y1 - y0) as u64;
+            backgrounds.push(histo_percentile(&tile_histo, count, 0.9) as f64);
+        }
+    }
+    let n = backgrounds.len() as f64;
+    let mean = backgrounds.iter().sum::<f64>() / n;
+    let var = backgrounds.iter().map(|b| (b - mean).powi(2)).sum::<f64>() / n;
+
+    if var.sqrt() > AUTO_BG_SPREAD_FRACT * contrast {
+        let window = ((w.min(h) / 8).clamp(15, 63)) | 1;
+        let pixd = sauvola_threshold(&gray, window, AUTO_SAUVOLA_K, 128.0)?;
+        Ok((pixd, AutoBinarizeMethod::Sauvola))
+    } else {
+        Ok((threshold_otsu(&gray)?, AutoBinarizeMethod::Otsu))
+    }
+}
+
+/// Smallest gray value whose cumulative count reaches `fract` of `total`
+fn histo_percentile(histo: &[u64; 256], total: u64, fract: f64) -> u32 {
+    let target = (fract * total as f64).ceil().max(1.0) as u64;
+    let mut sum = 0;
+    for (val, &count) in histo.iter().enumerate() {
+        sum += count;
+        if sum >= target {
+            return val as u32;
+        }
+    }
+    255
+}
+
+// =============================================================================
+// Edge-guided binarization
+// =============================================================================
+
+/// Half-width of the window whose min/max midpoint classifies pixels
+/// near a strong edge
+const EDGE_LOCAL_HALFWIDTH: i32 = 2;
+
+/// Binarize with a global Otsu threshold, recovering strokes at strong edges
+///
+/// Faint thin strokes on a textured or unevenly dark background often fall
+/// on the wrong side of a global threshold, yet still produce a clear
+/// gradient.  This computes the Sobel edge magnitude (as [`sobel_edge`])
+/// and, for every pixel within one pixel of an edge of magnitude at least
+/// `edge_strength`, sets it to foreground if it is darker than the
+/// midpoint of the min and max values in its 5x5 neighborhood, i.e. if it
+/// lies on the dark side of the edge.  The result is the union of that
+/// mask and the Otsu binarization.
+///
+/// A step of contrast `c` gives a Sobel magnitude of about `c / 2`, so
+/// `edge_strength` should be set above the response of the background
+/// texture.
+///
+/// [`sobel_edge`]: crate::filter::sobel_edge
+///
+/// # Arguments
+///
+/// * `pix` - 8 bpp grayscale, 32 bpp RGB, or colormapped image
+/// * `edge_strength` - Minimum Sobel magnitude of a strong edge, 1..=255
+///
+/// # Returns
+///
+/// 1 bpp image with dark foreground ON.
+pub fn edge_guided_binarize(pix: &Pix, edge_strength: u32) -> ColorResult<Pix> {
+    if !(1..=255).contains(&edge_strength) {
+        return Err(ColorError::InvalidParameters(format!(
+            "edge_strength must be in 1..=255, got {edge_strength}"
+        )));
+    }
+    let gray = ensure_grayscale(pix)?;
+    let edges = sobel_edge(&gray, EdgeOrientation::All)
+        .map_err(|e| ColorError::InvalidParameters(format!("sobel_edge failed: {e}")))?;
+    let w = gray.width() as i32;
+    let h = gray.height() as i32;
+
+    let mut pixd = threshold_otsu(&gray)?.to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            let near_edge = (y - 1..=y + 1).any(|ny| {
+                (x - 1..=x + 1).any(|nx| {
+                    nx >= 0
+                        && ny >= 0
+                        && nx < w
+                        && ny < h
+                        && edges.get_pixel_unchecked(nx as u32, ny as u32) >= edge_strength
+                })
+            });
+            if !near_edge {
+                continue;
+            }
+
+            let (mut lo, mut hi) = (u32::MAX, 0);
+            for ny in (y - EDGE_LOCAL_HALFWIDTH).max(0)..=(y + EDGE_LOCAL_HALFWIDTH).min(h - 1) {
+                for nx in (x - EDGE_LOCAL_HALFWIDTH).max(0)..=(x + EDGE_LOCAL_HALFWIDTH).min(w - 1)
+                {
+                    let v = gray.get_pixel_unchecked(nx as u32, ny as u32);
+                    lo = lo.min(v);
+                    hi = hi.max(v);
+                }
+            }
+            if 2 * gray.get_pixel_unchecked(x as u32, y as u32) < lo + hi {
+                pixd.set_pixel_unchecked(x as u32, y as u32, 1);
+            }
+        }
+    }
+    Ok(pixd.into())
+}
+
 // =============================================================================
 // Error-diffusion dithering (C leptonica kernel)
 // =============================================================================
@@ -432,30 +621,69 @@ pub fn dither_to_binary_with_threshold(pix: &Pix, threshold: u8) -> ColorResult<
 // Ordered Dithering (Bayer Matrix)
 // =============================================================================
 
+/// Bayer matrix size for ordered dithering
+///
+/// Larger matrices give more gray levels and a finer, less regular pattern;
+/// smaller matrices give a coarser, more stylized look.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BayerSize {
+    /// 2x2 matrix (5 gray levels)
+    N2,
+    /// 4x4 matrix (17 gray levels)
+    #[default]
+    N4,
+    /// 8x8 matrix (65 gray levels)
+    N8,
+}
+
+impl BayerSize {
+    /// Side length of the Bayer matrix.
+    pub fn dimension(self) -> u32 {
+        match self {
+            BayerSize::N2 => 2,
+            BayerSize::N4 => 4,
+            BayerSize::N8 => 8,
+        }
+    }
+
+    fn from_dimension(n: u32) -> Option<Self> {
+        match n {
+            2 => Some(BayerSize::N2),
+            4 => Some(BayerSize::N4),
+            8 => Some(BayerSize::N8),
+            _ => None,
+        }
+    }
+}
+
 /// Apply ordered dithering using a Bayer matrix
 ///
 /// Creates a halftone-like pattern with less visible artifacts than
-/// Floyd-Steinberg for some images.
+/// Floyd-Steinberg for some images.  `matrix_size` must be 2, 4 or 8;
+/// see [`ordered_dither_bayer`] for the typed variant.
 pub fn ordered_dither(pix: &Pix, matrix_size: u32) -> ColorResult<Pix> {
+    let size = BayerSize::from_dimension(matrix_size).ok_or_else(|| {
+        ColorError::InvalidParameters("matrix_size must be 2, 4, or 8".to_string())
+    })?;
+    ordered_dither_bayer(pix, size)
+}
+
+/// Apply ordered dithering using a Bayer matrix of the given size
+///
+/// A pixel becomes foreground (1) when its gray value is below the
+/// matrix threshold at `(x mod n, y mod n)`, so the output tiles with
+/// period `n` on flat input.
+pub fn ordered_dither_bayer(pix: &Pix, size: BayerSize) -> ColorResult<Pix> {
     let gray_pix = ensure_grayscale(pix)?;
 
-    let matrix = match matrix_size {
-        2 => BAYER_2X2.as_slice(),
-        4 => BAYER_4X4.as_slice(),
-        8 => BAYER_8X8.as_slice(),
-        _ => {
-            return Err(ColorError::InvalidParameters(
-                "matrix_size must be 2, 4, or 8".to_string(),
-            ));
-        }
-    };
+    let n = size.dimension() as usize;
+    let matrix = bayer_matrix(n);
 
     let w = gray_pix.width();
     let h = gray_pix.height();
     let out_pix = Pix::new(w, h, PixelDepth::Bit1)?;
     let mut out_mut = out_pix.try_into_mut().unwrap();
 
-    let n = matrix_size as usize;
     let scale = 256.0 / (n * n) as f32;
 
     for y in 0..h {
@@ -474,16 +702,31 @@ pub fn ordered_dither(pix: &Pix, matrix_size: u32) -> ColorResult<Pix> {
     Ok(out_mut.into())
 }
 
-// Bayer dithering matrices
-const BAYER_2X2: [u8; 4] = [0, 2, 3, 1];
-
-const BAYER_4X4: [u8; 16] = [0, 8, 2, 10, 12, 4, 14, 6, 3, 11, 1, 9, 15, 7, 13, 5];
-
-const BAYER_8X8: [u8; 64] = [
-    0, 32, 8, 40, 2, 34, 10, 42, 48, 16, 56, 24, 50, 18, 58, 26, 12, 44, 4, 36, 14, 46, 6, 38, 60,
-    28, 52, 20, 62, 30, 54, 22, 3, 35, 11, 43, 1, 33, 9, 41, 51, 19, 59, 27, 49, 17, 57, 25, 15,
-    47, 7, 39, 13, 45, 5, 37, 63, 31, 55, 23, 61, 29, 53, 21,
-];
+/// Build an `n x n` Bayer index matrix (row-major), `n` a power of 2.
+///
+/// Uses the recursive construction
+/// `M(2k) = [[4M(k), 4M(k)+2], [4M(k)+3, 4M(k)+1]]` starting from
+/// `M(1) = [0]`.
+fn bayer_matrix(n: usize) -> Vec<u32> {
+    let mut m = vec![0u32];
+    let mut k = 1;
+    while k < n {
+        let k2 = k * 2;
+        let mut next = vec![0u32; k2 * k2];
+        for y in 0..k {
+            for x in 0..k {
+                let v = 4 * m[y * k + x];
+                next[y * k2 + x] = v;
+                next[y * k2 + x + k] = v + 2;
+                next[(y + k) * k2 + x] = v + 3;
+                next[(y + k) * k2 + x + k] = v + 1;
+            }
+        }
+        m = next;
+        k = k2;
+    }
+    m
+}
 
 // =============================================================================
 // Helper Functions
@@ -830,6 +1073,91 @@ pub fn otsu_adaptive_threshold(
     Ok((thresh_mut.into(), binary_mut.into()))
 }
 
+/// Binarize with per-tile Otsu thresholds interpolated across the image.
+///
+/// Splits the image into an `nx × ny` grid of tiles and computes an Otsu
+/// threshold for each tile with [`compute_otsu_threshold`].  The tile
+/// thresholds are optionally box-smoothed over the grid (`smooth` is the
+/// half-width in tiles; 0 disables smoothing) and then bilinearly
+/// interpolated between tile centers, so the threshold varies smoothly
+/// instead of jumping at tile boundaries as in [`otsu_adaptive_threshold`].
+///
+/// Uses document binarization convention: dark pixels (val < threshold)
+/// become foreground (1).  A tile whose pixels are all one value gets a
+/// threshold of 0 and contributes no foreground, so tiles should be large
+/// enough to contain both text and background.
+pub fn otsu_adaptive(pix: &Pix, nx: u32, ny: u32, smooth: u32) -> ColorResult<Pix> {
+    let gray = ensure_grayscale(pix)?;
+    let w = gray.width();
+    let h = gray.height();
+    if nx == 0 || ny == 0 || nx > w || ny > h {
+        return Err(ColorError::InvalidParameters(format!(
+            "tile grid {nx}x{ny} invalid for {w}x{h} image"
+        )));
+    }
+
+    // Tile boundaries: tile i covers [i*w/nx, (i+1)*w/nx)
+    let (nxu, nyu) = (nx as usize, ny as usize);
+    let mut thresholds = vec![0f32; nxu * nyu];
+    for ty in 0..ny {
+        let y0 = ty * h / ny;
+        let y1 = (ty + 1) * h / ny;
+        for tx in 0..nx {
+            let x0 = tx * w / nx;
+            let x1 = (tx + 1) * w / nx;
+            let tile = gray.clip_rectangle(x0, y0, x1 - x0, y1 - y0)?;
+            thresholds[ty as usize * nxu + tx as usize] = compute_otsu_threshold(&tile)? as f32;
+        }
+    }
+
+    if smooth > 0 {
+        let r = smooth as usize;
+        let mut smoothed = vec![0f32; nxu * nyu];
+        for ty in 0..nyu {
+            for tx in 0..nxu {
+                let (mut sum, mut count) = (0f32, 0f32);
+                for sy in ty.saturating_sub(r)..(ty + r + 1).min(nyu) {
+                    for sx in tx.saturating_sub(r)..(tx + r + 1).min(nxu) {
+                        sum += thresholds[sy * nxu + sx];
+                        count += 1.0;
+                    }
+                }
+                smoothed[ty * nxu + tx] = sum / count;
+            }
+        }
+        thresholds = smoothed;
+    }
+
+    // Fractional tile-center coordinate of each pixel along one axis
+    let grid_pos = |p: u32, size: u32, n: usize| -> (usize, usize, f32) {
+        let f = ((p as f32 + 0.5) * n as f32 / size as f32 - 0.5).clamp(0.0, (n - 1) as f32);
+        let i0 = f.floor() as usize;
+        (i0, (i0 + 1).min(n - 1), f - i0 as f32)
+    };
+    let xpos: Vec<_> = (0..w).map(|x| grid_pos(x, w, nxu)).collect();
+
+    let out = Pix::new(w, h, PixelDepth::Bit1)?;
+    let mut out_mut = out.try_into_mut().unwrap();
+    for y in 0..h {
+        let (ty0, ty1, fy) = grid_pos(y, h, nyu);
+        for (x, &(tx0, tx1, fx)) in xpos.iter().enumerate() {
+            let t00 = thresholds[ty0 * nxu + tx0];
+            let t01 = thresholds[ty0 * nxu + tx1];
+            let t10 = thresholds[ty1 * nxu + tx0];
+            let t11 = thresholds[ty1 * nxu + tx1];
+            let top = t00 + (t01 - t00) * fx;
+            let bottom = t10 + (t11 - t10) * fx;
+            let thresh = top + (bottom - top) * fy;
+            let val = gray.get_pixel_unchecked(x as u32, y) as f32;
+            if val < thresh {
+                out_mut.set_pixel_unchecked(x as u32, y, 1);
+            }
+        }
+    }
+
+    Ok(out_mut.into())
+}
+
 /// Perform tiled Sauvola binarization.
 ///
 /// Divides the image into `nx × ny` tiles with overlap, applies Sauvola
@@ -2042,6 +2370,19 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_bayer_matrix_recursive() {
+        assert_eq!(bayer_matrix(2), vec![0, 2, 3, 1]);
+        assert_eq!(
+            bayer_matrix(4),
+            vec![0, 8, 2, 10, 12, 4, 14, 6, 3, 11, 1, 9, 15, 7, 13, 5]
+        );
+        let m8 = bayer_matrix(8);
+        let mut sorted = m8.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..64).collect::<Vec<u32>>());
+    }
+
     #[test]
     fn test_threshold_from_color() {
         let pix = Pix::new(10, 10, PixelDepth::Bit32).unwrap();
@@ -233,6 +233,19 @@ impl Box {
         self.overlap_area(other) as f64 / self_area as f64
     }
 
+    /// Compute the intersection-over-union of two boxes.
+    ///
+    /// Returns the overlap area divided by the area of the union, in
+    /// `[0.0, 1.0]`.  Returns 0.0 if the union has zero area.
+    pub fn iou(&self, other: &Box) -> f64 {
+        let inter = self.overlap_area(other);
+        let union = self.area() + other.area() - inter;
+        if union <= 0 {
+            return 0.0;
+        }
+        inter as f64 / union as f64
+    }
+
     /// Create a new Box with updated geometry.
     ///
     /// Fields set to a negative value are left unchanged.
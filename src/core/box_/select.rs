@@ -169,6 +169,45 @@ impl Boxa {
         (widths, heights)
     }
 
+    /// Greedy non-maximum suppression of overlapping detections.
+    ///
+    /// Boxes are visited in order of decreasing score; each one is kept
+    /// unless its [`Box::iou`](super::Box::iou) with an already-kept box
+    /// exceeds `iou_threshold`.  Returns the kept boxes, highest score
+    /// first, together with their indices in `self`.  Equal scores keep
+    /// the lower index first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParameter`] if `scores` and `self` differ
+    /// in length.
+    pub fn nms(&self, scores: &Numa, iou_threshold: f64) -> Result<(Boxa, Vec<usize>)> {
+        if scores.len() != self.len() {
+            return Err(Error::InvalidParameter(format!(
+                "scores length {} != boxa length {}",
+                scores.len(),
+                self.len()
+            )));
+        }
+
+        let boxes = self.boxes();
+        let s = scores.as_slice();
+        let mut order: Vec<usize> = (0..boxes.len()).collect();
+        order.sort_by(|&a, &b| s[b].total_cmp(&s[a]));
+
+        let mut kept: Vec<usize> = Vec::new();
+        for i in order {
+            if kept
+                .iter()
+                .all(|&k| boxes[k].iou(&boxes[i]) <= iou_threshold)
+            {
+                kept.push(i);
+            }
+        }
+        let out = kept.iter().map(|&i| boxes[i]).collect();
+        Ok((out, kept))
+    }
+
     /// Compute the total area of all boxes (sum of w*h).
     ///
     /// Does not account for overlaps.
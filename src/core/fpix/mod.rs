@@ -539,6 +539,64 @@ impl FPix {
         Ok(pix_mut.into())
     }
 
+    /// Convert FPix to Pix, stretching the value range to the full depth
+    ///
+    /// Unlike [`FPix::to_pix`], which clamps, this maps the range
+    /// `[min, max]` of the values linearly onto `[0, max_val]` of the
+    /// output depth, so the full dynamic range is used (e.g. for
+    /// heatmaps).  `neg_handling` is applied before the range is
+    /// measured.  A constant image maps to 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `out_depth` - Output depth (8, 16, or 32). 0 selects 8.
+    /// * `neg_handling` - How to handle negative values
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidParameter` if out_depth is not 0, 8, 16, or 32.
+    pub fn to_pix_scaled(&self, out_depth: u32, neg_handling: NegativeHandling) -> Result<Pix> {
+        let depth = match out_depth {
+            0 => 8,
+            8 | 16 | 32 => out_depth,
+            _ => {
+                return Err(Error::InvalidParameter(format!(
+                    "out_depth must be 0, 8, 16, or 32, got {out_depth}"
+                )));
+            }
+        };
+        let max_val = match depth {
+            8 => 255.0,
+            16 => 65535.0,
+            _ => u32::MAX as f64,
+        };
+
+        let mut scaled = self.clone();
+        for v in scaled.data.iter_mut() {
+            if *v < 0.0 {
+                *v = match neg_handling {
+                    NegativeHandling::ClipToZero => 0.0,
+                    NegativeHandling::TakeAbsValue => v.abs(),
+                };
+            }
+        }
+        let (min, max) = scaled
+            .data
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            });
+        let range = (max - min) as f64;
+        for v in scaled.data.iter_mut() {
+            *v = if range > 0.0 {
+                ((*v - min) as f64 * max_val / range) as f32
+            } else {
+                0.0
+            };
+        }
+        scaled.to_pix(depth, NegativeHandling::ClipToZero)
+    }
+
     /// Auto-detect appropriate output depth based on pixel values
     fn auto_detect_depth(&self) -> u32 {
         let mut max_val: f32 = 0.0;
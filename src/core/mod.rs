@@ -58,11 +58,15 @@ pub use pix::statistics::{
     RowColumnStats, StatsRequest,
 };
 pub use pix::{
-    BlendMode, Color, ColorHistogram, CompareResult, CompareType, ContourOutput, GrayBlendType,
-    ImageFormat, InColor, InitColor, MaskBlendType, Pix, PixMut, PixelDepth, PixelDiffResult,
-    PixelOp, RopOp, ScanDirection, blend_with_gray_mask, correlation_binary,
+    BlendMode, CanvasAnchor, Color, ColorHistogram, CompareResult, CompareType, ContourOutput,
+    GrayBlendType, ImageFormat, InColor, InitColor, MaskBlendType, Pix, PixMut, PixelDepth,
+    PixelDiffResult, PixelOp, RopOp, ScanDirection, TestPattern, blend_over_checkerboard,
+    blend_with_gray_mask, blend_with_gray_mask_feathered, correlation_binary,
+};
+pub use pixa::{
+    AlignMode, HAlign, Pixa, PixaSortType, Pixaa, VAlign, pixa_max, pixa_mean, pixa_median,
+    pixa_min,
 };
-pub use pixa::{Pixa, PixaSortType, Pixaa};
 pub use pixacc::PixAcc;
 pub use pixcomp::{PixComp, PixaComp};
 pub use pixtiling::PixTiling;
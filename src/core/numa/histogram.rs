@@ -341,6 +341,34 @@ impl Numa {
         result.set_parameters(startx, deltax);
         Some(result)
     }
+
+    /// Build a histogram-equalization lookup table
+    ///
+    /// Treats the Numa as a 256-bin gray histogram and maps each input
+    /// value `i` to `round(255 * cdf(i))`, where `cdf` is the normalized
+    /// cumulative sum of the bins.  Bins beyond the length of the Numa are
+    /// treated as empty; entries past 256 are ignored.
+    ///
+    /// Returns the identity mapping if the histogram sums to zero.
+    ///
+    /// The result can be applied with `filter::apply_lut`.
+    pub fn make_equalization_lut(&self) -> [u8; 256] {
+        let mut lut = [0u8; 256];
+        let total: f32 = self.iter().take(256).sum();
+        if total == 0.0 || !total.is_normal() {
+            for (i, entry) in lut.iter_mut().enumerate() {
+                *entry = i as u8;
+            }
+            return lut;
+        }
+
+        let mut cumul = 0.0f32;
+        for (i, entry) in lut.iter_mut().enumerate() {
+            cumul += self.get(i).unwrap_or(0.0);
+            *entry = (255.0 * cumul / total + 0.5).clamp(0.0, 255.0) as u8;
+        }
+        lut
+    }
 }
 
 #[cfg(test)]
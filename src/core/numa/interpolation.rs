@@ -178,6 +178,60 @@ impl Numa {
         Ok(nady)
     }
 
+    /// Build a 256-entry tone curve through control points.
+    ///
+    /// This is the "curves" tool of photo editors: `points` are
+    /// `(input, output)` pairs in any order, and the lookup table is the
+    /// piecewise-linear curve through them.  The curve must be monotone:
+    /// outputs may not decrease as inputs increase.  Inputs below the first or
+    /// above the last control point follow the first or last segment
+    /// extended, clamped to [0, 255].  Entries are rounded to the nearest
+    /// integer.
+    ///
+    /// The result can be applied with `filter::apply_lut`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two control points, two
+    /// points share an input value, or an output is less than the output
+    /// of a point with a smaller input.
+    pub fn from_control_points(points: &[(u8, u8)]) -> Result<[u8; 256]> {
+        if points.len() < 2 {
+            return Err(Error::InvalidParameter(format!(
+                "need at least 2 control points, got {}",
+                points.len()
+            )));
+        }
+        let mut pts = points.to_vec();
+        pts.sort_by_key(|p| p.0);
+        if let Some(w) = pts.windows(2).find(|w| w[0].0 == w[1].0) {
+            return Err(Error::InvalidParameter(format!(
+                "duplicate control point input {}",
+                w[0].0
+            )));
+        }
+        if let Some(w) = pts.windows(2).find(|w| w[1].1 < w[0].1) {
+            return Err(Error::InvalidParameter(format!(
+                "control points {:?} and {:?} are decreasing",
+                w[0], w[1]
+            )));
+        }
+
+        let mut lut = [0u8; 256];
+        let mut seg = 0;
+        for (i, entry) in lut.iter_mut().enumerate() {
+            // Segment containing i; the end segments are extended
+            while seg + 2 < pts.len() && i > pts[seg + 1].0 as usize {
+                seg += 1;
+            }
+            let (x0, y0) = (pts[seg].0 as f32, pts[seg].1 as f32);
+            let (x1, y1) = (pts[seg + 1].0 as f32, pts[seg + 1].1 as f32);
+            let y = y0 + (i as f32 - x0) * (y1 - y0) / (x1 - x0);
+            *entry = y.round().clamp(0.0, 255.0) as u8;
+        }
+        Ok(lut)
+    }
+
     /// Find the maximum value and its location using quadratic interpolation.
     ///
     /// Returns `(max_val, max_loc)`. If `naloc` is `None`, `max_loc` is an
@@ -399,6 +399,20 @@ impl Numa {
         self.max().map(|(v, _)| v)
     }
 
+    /// Get the index of the minimum value
+    ///
+    /// Ties return the first occurrence. Returns `None` if the array is empty.
+    pub fn arg_min(&self) -> Option<usize> {
+        self.min().map(|(_, i)| i)
+    }
+
+    /// Get the index of the maximum value
+    ///
+    /// Ties return the first occurrence. Returns `None` if the array is empty.
+    pub fn arg_max(&self) -> Option<usize> {
+        self.max().map(|(_, i)| i)
+    }
+
     /// Get the sum of all values
     ///
     /// Returns `None` if the array is empty.
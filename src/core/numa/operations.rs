@@ -279,6 +279,14 @@ impl Numa {
         result
     }
 
+    /// Running total of the elements; an alias of [`Numa::partial_sums`].
+    ///
+    /// Normalizing the result by its last element gives the CDF used for
+    /// histogram equalization.
+    pub fn cumulative_sum(&self) -> Numa {
+        self.partial_sums()
+    }
+
     // ====================================================================
     // Join (append)
     // ====================================================================
@@ -1516,6 +1524,56 @@ impl Numa {
         Ok(())
     }
 
+    /// Element-wise sum `self + other` as a new Numa.
+    ///
+    /// Returns an error if the lengths differ. See [`Numa::arith_op`] for
+    /// the in-place form.
+    pub fn add(&self, other: &Numa) -> Result<Numa> {
+        self.arith(ArithOp::Add, other)
+    }
+
+    /// Element-wise difference `self - other` as a new Numa.
+    ///
+    /// Returns an error if the lengths differ.
+    pub fn sub(&self, other: &Numa) -> Result<Numa> {
+        self.arith(ArithOp::Subtract, other)
+    }
+
+    /// Element-wise product `self * other` as a new Numa.
+    ///
+    /// Returns an error if the lengths differ.
+    pub fn mul(&self, other: &Numa) -> Result<Numa> {
+        self.arith(ArithOp::Multiply, other)
+    }
+
+    /// Element-wise quotient `self / other` as a new Numa.
+    ///
+    /// Returns an error if the lengths differ or any element of `other`
+    /// is zero.
+    pub fn div(&self, other: &Numa) -> Result<Numa> {
+        self.arith(ArithOp::Divide, other)
+    }
+
+    fn arith(&self, op: ArithOp, other: &Numa) -> Result<Numa> {
+        let mut result = self.clone();
+        result.arith_op(op, other)?;
+        Ok(result)
+    }
+
+    /// Multiply every element by `factor` in-place.
+    pub fn scale(&mut self, factor: f32) {
+        for v in self.as_slice_mut() {
+            *v *= factor;
+        }
+    }
+
+    /// Add `delta` to every element in-place.
+    pub fn offset(&mut self, delta: f32) {
+        for v in self.as_slice_mut() {
+            *v += delta;
+        }
+    }
+
     /// Apply an element-wise logical operation in-place.
     ///
     /// Treats non-zero as `true` and zero as `false`.
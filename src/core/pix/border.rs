@@ -7,6 +7,51 @@ use super::{Pix, PixMut, PixelDepth};
 use crate::core::error::{Error, Result};
 use crate::core::pixel;
 
+/// Placement of the source image in [`Pix::resize_canvas`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CanvasAnchor {
+    /// Upper-left corners coincide
+    #[default]
+    TopLeft,
+    /// Centered horizontally, top edges coincide
+    Top,
+    /// Upper-right corners coincide
+    TopRight,
+    /// Centered vertically, left edges coincide
+    Left,
+    /// Centered in both directions
+    Center,
+    /// Centered vertically, right edges coincide
+    Right,
+    /// Lower-left corners coincide
+    BottomLeft,
+    /// Centered horizontally, bottom edges coincide
+    Bottom,
+    /// Lower-right corners coincide
+    BottomRight,
+}
+
+impl CanvasAnchor {
+    /// Offset of the source within the canvas along x and y.
+    ///
+    /// Negative offsets mean the source is cropped on that side.
+    fn offsets(self, ws: u32, hs: u32, wd: u32, hd: u32) -> (i64, i64) {
+        let dw = wd as i64 - ws as i64;
+        let dh = hd as i64 - hs as i64;
+        let x = match self {
+            Self::TopLeft | Self::Left | Self::BottomLeft => 0,
+            Self::Top | Self::Center | Self::Bottom => dw / 2,
+            Self::TopRight | Self::Right | Self::BottomRight => dw,
+        };
+        let y = match self {
+            Self::TopLeft | Self::Top | Self::TopRight => 0,
+            Self::Left | Self::Center | Self::Right => dh / 2,
+            Self::BottomLeft | Self::Bottom | Self::BottomRight => dh,
+        };
+        (x, y)
+    }
+}
+
 impl Pix {
     /// Add a uniform border of `npix` pixels on all sides.
     ///
@@ -123,6 +168,69 @@ impl Pix {
         Ok(pixd_mut.into())
     }
 
+    /// Place the image on a canvas of a new size without scaling.
+    ///
+    /// The image is positioned according to `anchor`; canvas pixels not
+    /// covered by it are set to `fill`, and parts of the image that fall
+    /// outside a smaller canvas are cropped.  Works at all depths; the
+    /// colormap, spp and resolution are preserved (for colormapped images
+    /// `fill` is a colormap index).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `new_w` or `new_h` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use leptonica::core::{CanvasAnchor, Pix, PixelDepth};
+    ///
+    /// let pix = Pix::new(10, 10, PixelDepth::Bit8).unwrap();
+    /// let padded = pix.resize_canvas(20, 16, CanvasAnchor::Center, 255).unwrap();
+    /// assert_eq!((padded.width(), padded.height()), (20, 16));
+    /// assert_eq!(padded.get_pixel(4, 3), Some(255));
+    /// assert_eq!(padded.get_pixel(5, 3), Some(0));
+    /// ```
+    pub fn resize_canvas(
+        &self,
+        new_w: u32,
+        new_h: u32,
+        anchor: CanvasAnchor,
+        fill: u32,
+    ) -> Result<Pix> {
+        let ws = self.width();
+        let hs = self.height();
+        let mut pixd_mut = Pix::new(new_w, new_h, self.depth())?
+            .try_into_mut()
+            .unwrap();
+        pixd_mut.set_resolution(self.xres(), self.yres());
+        pixd_mut.set_spp(self.spp());
+        pixd_mut.copy_colormap_from(self);
+
+        if fill != 0 {
+            for y in 0..new_h {
+                for x in 0..new_w {
+                    pixd_mut.set_pixel_unchecked(x, y, fill);
+                }
+            }
+        }
+
+        // Copy the overlap of source and canvas
+        let (ox, oy) = anchor.offsets(ws, hs, new_w, new_h);
+        let x0 = ox.max(0);
+        let y0 = oy.max(0);
+        let x1 = (ox + ws as i64).min(new_w as i64);
+        let y1 = (oy + hs as i64).min(new_h as i64);
+        for yd in y0..y1 {
+            for xd in x0..x1 {
+                let pixel = self.get_pixel_unchecked((xd - ox) as u32, (yd - oy) as u32);
+                pixd_mut.set_pixel_unchecked(xd as u32, yd as u32, pixel);
+            }
+        }
+
+        Ok(pixd_mut.into())
+    }
+
     /// Remove a uniform border of `npix` pixels from all sides.
     ///
     /// Creates a new image containing only the interior region, with the
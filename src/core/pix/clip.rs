@@ -7,7 +7,7 @@
 //!
 //! C Leptonica: `pix2.c`, `pix5.c`
 
-use super::{Pix, PixelDepth};
+use super::{Color, Pix, PixelDepth};
 use crate::core::Box;
 use crate::core::error::{Error, Result};
 
@@ -1204,6 +1204,136 @@ impl Pix {
         self.clip_rectangle(0, 0, wd, hd)
     }
 
+    /// Estimate the background (paper) color from the image borders.
+    ///
+    /// Samples a band around the edges whose width is 1/20 of the smaller
+    /// dimension (at least 1 pixel), groups the samples into coarse color
+    /// bins (8 levels per component), and returns the average color of
+    /// the most populated bin.  Noise and a few foreground pixels
+    /// touching the edge therefore do not shift the result.
+    ///
+    /// For 1 bpp images the majority border value is returned as
+    /// [`Color::WHITE`] (0) or [`Color::BLACK`] (1).  Colormapped images
+    /// use their colormap colors; 8 bpp grayscale returns a gray color.
+    /// The result can be passed to [`Pix::trim_borders_by_color`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for 2 or 4 bpp without colormap and for 16 bpp.
+    pub fn estimate_background_color(&self) -> Result<Color> {
+        let depth = self.depth();
+        let cmap = self.colormap();
+        let to_rgb = |val: u32| -> (u8, u8, u8) {
+            if let Some(cmap) = cmap {
+                cmap.get_rgb(val as usize).unwrap_or((0, 0, 0))
+            } else if depth == PixelDepth::Bit32 {
+                crate::core::pixel::extract_rgb(val)
+            } else {
+                (val as u8, val as u8, val as u8)
+            }
+        };
+        let supported = match depth {
+            PixelDepth::Bit1 | PixelDepth::Bit8 | PixelDepth::Bit32 => true,
+            PixelDepth::Bit2 | PixelDepth::Bit4 => cmap.is_some(),
+            _ => false,
+        };
+        if !supported {
+            return Err(Error::UnsupportedDepth(depth.bits()));
+        }
+
+        let w = self.width();
+        let h = self.height();
+        let band = (w.min(h) / 20).max(1);
+        let in_band = |x: u32, y: u32| x < band || y < band || x >= w - band || y >= h - band;
+
+        if depth == PixelDepth::Bit1 && cmap.is_none() {
+            let (mut ones, mut total) = (0u64, 0u64);
+            for y in 0..h {
+                for x in 0..w {
+                    if in_band(x, y) {
+                        ones += self.get_pixel_unchecked(x, y) as u64;
+                        total += 1;
+                    }
+                }
+            }
+            return Ok(if 2 * ones > total {
+                Color::BLACK
+            } else {
+                Color::WHITE
+            });
+        }
+
+        // Per bin: count and component sums
+        let mut bins = vec![(0u64, 0u64, 0u64, 0u64); 512];
+        for y in 0..h {
+            for x in 0..w {
+                if !in_band(x, y) {
+                    continue;
+                }
+                let (r, g, b) = to_rgb(self.get_pixel_unchecked(x, y));
+                let idx = ((r as usize >> 5) << 6) | ((g as usize >> 5) << 3) | (b as usize >> 5);
+                let bin = &mut bins[idx];
+                bin.0 += 1;
+                bin.1 += r as u64;
+                bin.2 += g as u64;
+                bin.3 += b as u64;
+            }
+        }
+        let &(n, rs, gs, bs) = bins.iter().max_by_key(|bin| bin.0).unwrap();
+        let avg = |sum: u64| ((sum + n / 2) / n) as u8;
+        Ok(Color::new(avg(rs), avg(gs), avg(bs)))
+    }
+
+    /// Trim border rows and columns whose pixels are all close to `bg`.
+    ///
+    /// Unlike [`Pix::clip_to_foreground`], the background need not match
+    /// exactly: a pixel is background if it is within `tolerance` of `bg`
+    /// (in each of R, G and B for 32 bpp; against `bg.to_gray()` for
+    /// 8 bpp).  This removes slightly-off-white scan margins and JPEG
+    /// noise around a page.
+    ///
+    /// Returns a clone of the original if no border rows or columns are
+    /// background, or if the whole image is background.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is not 8 bpp without colormap or 32 bpp.
+    pub fn trim_borders_by_color(&self, bg: Color, tolerance: u8) -> Result<Pix> {
+        let depth = self.depth();
+        if !matches!(depth, PixelDepth::Bit8 | PixelDepth::Bit32) || self.has_colormap() {
+            return Err(Error::UnsupportedDepth(depth.bits()));
+        }
+        let tol = tolerance as i32;
+        let gray = bg.to_gray() as i32;
+        let is_bg = |x: u32, y: u32| {
+            let val = self.get_pixel_unchecked(x, y);
+            if depth == PixelDepth::Bit8 {
+                (val as i32 - gray).abs() <= tol
+            } else {
+                let (r, g, b) = crate::core::pixel::extract_rgb(val);
+                (r as i32 - bg.r as i32).abs() <= tol
+                    && (g as i32 - bg.g as i32).abs() <= tol
+                    && (b as i32 - bg.b as i32).abs() <= tol
+            }
+        };
+
+        let w = self.width();
+        let h = self.height();
+        let row_is_bg = |y: u32| (0..w).all(|x| is_bg(x, y));
+        let Some(top) = (0..h).find(|&y| !row_is_bg(y)) else {
+            return Ok(self.clone());
+        };
+        let bottom = (top..h).rev().find(|&y| !row_is_bg(y)).unwrap_or(top);
+        let col_is_bg = |x: u32| (top..=bottom).all(|y| is_bg(x, y));
+        let left = (0..w).find(|&x| !col_is_bg(x)).unwrap_or(0);
+        let right = (left..w).rev().find(|&x| !col_is_bg(x)).unwrap_or(left);
+
+        if top == 0 && left == 0 && bottom == h - 1 && right == w - 1 {
+            return Ok(self.clone());
+        }
+        self.clip_rectangle(left, top, right - left + 1, bottom - top + 1)
+    }
+
     /// Resize to exactly `w × h` without scaling.
     ///
     /// Crops if larger; replicates the last row/column if smaller.
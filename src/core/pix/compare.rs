@@ -2,17 +2,19 @@
 //!
 //! This module provides functions for comparing two images:
 //!
-//! - Equality testing (`equals`, `equals_with_alpha`)
+//! - Equality testing (`equals`, `equals_with_alpha`, `content_eq`)
 //! - Pixel-wise difference (`diff`, `subtract`, `abs_diff`)
 //! - Statistical comparison (`rms_diff`, `mean_abs_diff`, `compare`)
 //! - Binary image correlation (`correlation_binary`)
+//! - Shift-tolerant comparison (`compare_aligned`)
+//! - Perceptual hashing (`average_hash`, `difference_hash`)
 //!
 //! These correspond to Leptonica's compare.c functions including
 //! pixEqual, pixSubtract, pixAbsDifference, pixGetRMSDiff, and
 //! pixCorrelationBinary.
 
 use super::graphics::Color;
-use super::{Pix, PixelDepth};
+use super::{Pix, PixelDepth, RemoveColormapTarget};
 use crate::core::error::{Error, Result};
 use crate::core::numa::Numa;
 use crate::core::pixel;
@@ -378,6 +380,119 @@ impl Pix {
         true
     }
 
+    /// Check exact content equality, ignoring row padding.
+    ///
+    /// Two images are content-equal when width, height, depth, spp and
+    /// colormap (all RGBA entries) agree and every pixel value is identical.
+    /// Unlike comparing [`data()`](Pix::data) directly, the unused bits at
+    /// the end of each row (present for 1/2/4/8/16 bpp when the row does
+    /// not fill its last 32-bit word) are masked out.  Resolution, text and
+    /// input format are not compared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use leptonica::core::{Pix, PixelDepth};
+    ///
+    /// let pix1 = Pix::new(10, 10, PixelDepth::Bit1).unwrap();
+    /// let pix2 = pix1.deep_clone();
+    /// assert!(pix1.content_eq(&pix2));
+    /// assert_eq!(pix1.content_hash(), pix2.content_hash());
+    /// ```
+    pub fn content_eq(&self, other: &Pix) -> bool {
+        if self.width() != other.width()
+            || self.height() != other.height()
+            || self.depth() != other.depth()
+            || self.spp() != other.spp()
+        {
+            return false;
+        }
+
+        match (self.colormap(), other.colormap()) {
+            (None, None) => {}
+            (Some(c1), Some(c2)) => {
+                if c1.len() != c2.len() || (0..c1.len()).any(|i| c1.get_rgba(i) != c2.get_rgba(i)) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+
+        let (full_words, end_mask) = self.row_word_mask();
+        for y in 0..self.height() {
+            let line1 = self.row_data(y);
+            let line2 = other.row_data(y);
+            if line1[..full_words] != line2[..full_words] {
+                return false;
+            }
+            if end_mask != 0 && (line1[full_words] ^ line2[full_words]) & end_mask != 0 {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Compute a stable 64-bit hash of the image content.
+    ///
+    /// Covers exactly the fields compared by [`content_eq`](Pix::content_eq)
+    /// (FNV-1a over the dimensions, depth, spp, colormap entries and the
+    /// pad-masked pixel words), so content-equal images always hash
+    /// identically.  The value does not depend on the platform or the
+    /// process, and can be used as a cache key.
+    pub fn content_hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+
+        let mut h = FNV_OFFSET_BASIS;
+        let mut feed = |word: u32| {
+            for b in word.to_le_bytes() {
+                h ^= b as u64;
+                h = h.wrapping_mul(FNV_PRIME);
+            }
+        };
+
+        feed(self.width());
+        feed(self.height());
+        feed(self.depth().bits());
+        feed(self.spp());
+        match self.colormap() {
+            Some(cmap) => {
+                feed(cmap.len() as u32);
+                for i in 0..cmap.len() {
+                    let (r, g, b, a) = cmap.get_rgba(i).unwrap_or((0, 0, 0, 0));
+                    feed(pixel::compose_rgba(r, g, b, a));
+                }
+            }
+            None => feed(u32::MAX),
+        }
+
+        let (full_words, end_mask) = self.row_word_mask();
+        for y in 0..self.height() {
+            let line = self.row_data(y);
+            for &word in &line[..full_words] {
+                feed(word);
+            }
+            if end_mask != 0 {
+                feed(line[full_words] & end_mask);
+            }
+        }
+        h
+    }
+
+    /// Number of fully used 32-bit words per row, and the MSB-aligned mask
+    /// of the used bits in the following partial word (0 if there is none).
+    fn row_word_mask(&self) -> (usize, u32) {
+        let row_bits = self.width() as u64 * self.depth().bits() as u64;
+        let full_words = (row_bits / 32) as usize;
+        let bits_used = (row_bits % 32) as u32;
+        let end_mask = if bits_used == 0 {
+            0
+        } else {
+            !((1u32 << (32 - bits_used)) - 1)
+        };
+        (full_words, end_mask)
+    }
+
     /// Compute difference image.
     ///
     /// Creates a new image containing the pixel-wise difference between
@@ -1676,6 +1791,80 @@ pub fn compare_with_translation(pix1: &Pix, pix2: &Pix, thresh: u32) -> Result<T
     Ok(TranslationMatch { delx, dely, score })
 }
 
+/// Result of [`compare_aligned`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedCompareResult {
+    /// X shift of `pix2` that best aligns it with `pix1`.
+    pub delx: i32,
+    /// Y shift of `pix2` that best aligns it with `pix1`.
+    pub dely: i32,
+    /// Comparison of the overlapping region at that shift.
+    pub compare: CompareResult,
+}
+
+/// Compare two images, tolerating a small registration offset.
+///
+/// Every integer shift `(delx, dely)` with `|delx|, |dely| <= max_shift` is
+/// tried; at each one the overlapping region `pix1(x, y)` vs
+/// `pix2(x - delx, y - dely)` is compared with [`Pix::compare`].  The shift
+/// with the smallest mean absolute difference wins; ties go to the shift
+/// closest to zero, so identical images always report `(0, 0)`.
+///
+/// This keeps regression comparisons from failing on one-pixel rendering
+/// jitter.  Pixels shifted out of the overlap are not counted.
+///
+/// # Errors
+///
+/// Returns an error if the images differ in size or depth, or if
+/// `max_shift` leaves no overlap.
+pub fn compare_aligned(pix1: &Pix, pix2: &Pix, max_shift: u32) -> Result<AlignedCompareResult> {
+    let (w, h) = (pix1.width(), pix1.height());
+    if w != pix2.width() || h != pix2.height() {
+        return Err(Error::DimensionMismatch {
+            expected: (w, h),
+            actual: (pix2.width(), pix2.height()),
+        });
+    }
+    if pix1.depth() != pix2.depth() {
+        return Err(Error::IncompatibleDepths(
+            pix1.depth().bits(),
+            pix2.depth().bits(),
+        ));
+    }
+    if max_shift >= w || max_shift >= h {
+        return Err(Error::InvalidParameter(format!(
+            "max_shift {max_shift} leaves no overlap in {w}x{h} image"
+        )));
+    }
+
+    // Visit shifts nearest-first so ties resolve toward (0, 0)
+    let m = max_shift as i32;
+    let mut shifts: Vec<(i32, i32)> = (-m..=m)
+        .flat_map(|dy| (-m..=m).map(move |dx| (dx, dy)))
+        .collect();
+    shifts.sort_by_key(|&(dx, dy)| dx.abs() + dy.abs());
+
+    let mut best: Option<AlignedCompareResult> = None;
+    for (dx, dy) in shifts {
+        let ow = w - dx.unsigned_abs();
+        let oh = h - dy.unsigned_abs();
+        let c1 = pix1.clip_rectangle(dx.max(0) as u32, dy.max(0) as u32, ow, oh)?;
+        let c2 = pix2.clip_rectangle((-dx).max(0) as u32, (-dy).max(0) as u32, ow, oh)?;
+        let compare = c1.compare(&c2)?;
+        if best
+            .as_ref()
+            .is_none_or(|b| compare.mean_abs_diff < b.compare.mean_abs_diff)
+        {
+            best = Some(AlignedCompareResult {
+                delx: dx,
+                dely: dy,
+                compare,
+            });
+        }
+    }
+    Ok(best.expect("at least the zero shift is tried"))
+}
+
 /// Maximize correlation score between two 1bpp images by sliding `pix2` over
 /// a `(2*maxshift+1)^2` grid centered on `(etransx, etransy)`.
 ///
@@ -2555,6 +2744,75 @@ pub fn compare_tiles_by_histo(
     Ok(minscore)
 }
 
+impl Pix {
+    /// 64-bit average hash (aHash) of the image.
+    ///
+    /// The image is converted to gray and area-averaged down to an 8x8
+    /// thumbnail. Bit `63 - (8 * y + x)` is set when the thumbnail pixel at
+    /// `(x, y)` is brighter than the thumbnail mean, so the most significant
+    /// bit corresponds to the top-left cell and rows follow in raster order.
+    ///
+    /// Similar images give hashes with a small Hamming distance
+    /// (`(a ^ b).count_ones()`). Returns 0 if the thumbnail cannot be made.
+    pub fn average_hash(&self) -> u64 {
+        let Ok(thumb) = gray_thumbnail(self, 8, 8) else {
+            return 0;
+        };
+        let sum: u32 = thumb.iter().sum();
+        let mut hash = 0u64;
+        for (i, &v) in thumb.iter().enumerate() {
+            // v > mean, without rounding the mean
+            if 64 * v > sum {
+                hash |= 1u64 << (63 - i);
+            }
+        }
+        hash
+    }
+
+    /// 64-bit difference hash (dHash) of the image.
+    ///
+    /// The image is converted to gray and area-averaged down to a 9x8
+    /// thumbnail (9 columns, 8 rows). Bit `63 - (8 * y + x)` is set when the
+    /// thumbnail pixel at `(x, y)` is brighter than its right neighbor at
+    /// `(x + 1, y)`, for `x` in `0..8`. As with [`Pix::average_hash`], the
+    /// most significant bit corresponds to the top-left cell. Returns 0 if
+    /// the thumbnail cannot be made.
+    pub fn difference_hash(&self) -> u64 {
+        let Ok(thumb) = gray_thumbnail(self, 9, 8) else {
+            return 0;
+        };
+        let mut hash = 0u64;
+        for y in 0..8 {
+            for x in 0..8 {
+                if thumb[y * 9 + x] > thumb[y * 9 + x + 1] {
+                    hash |= 1u64 << (63 - (8 * y + x));
+                }
+            }
+        }
+        hash
+    }
+}
+
+/// Area-averaged gray thumbnail of size `tw` x `th`, in raster order.
+///
+/// Colormaps are removed to gray, the image is converted with
+/// [`Pix::convert_to_8`] and then area-mapped to the thumbnail size.
+fn gray_thumbnail(pix: &Pix, tw: u32, th: u32) -> Result<Vec<u32>> {
+    use crate::transform::scale::scale_area_map_to_size;
+
+    let gray = if pix.has_colormap() {
+        pix.remove_colormap(RemoveColormapTarget::ToGrayscale)?
+    } else {
+        pix.convert_to_8()?
+    };
+    let thumb = scale_area_map_to_size(&gray, tw, th)
+        .map_err(|e| Error::InvalidParameter(format!("scale_area_map_to_size: {e}")))?;
+    Ok((0..th)
+        .flat_map(|y| (0..tw).map(move |x| (x, y)))
+        .map(|(x, y)| thumb.get_pixel_unchecked(x, y))
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,7 +6,7 @@
 //!
 //! C Leptonica: `pixconv.c` (`pixConvertTo8`, `pixConvertTo32`, etc.)
 
-use super::{Pix, PixelDepth};
+use super::{Color, Pix, PixelDepth};
 use crate::core::colormap::PixColormap;
 use crate::core::error::{Error, Result};
 use crate::core::pixel;
@@ -346,6 +346,40 @@ impl Pix {
         }
     }
 
+    /// Convert any image to an opaque 32 bpp RGB copy.
+    ///
+    /// Unlike [`Pix::convert_to_32`], colormaps are always expanded
+    /// through their palette and the result is normalized: every pixel
+    /// has alpha 255 and spp is 3, whatever the input depth.
+    ///
+    /// - **1/2/4/8 bpp**: colormap colors if present; otherwise gray
+    ///   replicated into R=G=B (1 bpp: ON -> white, OFF -> black)
+    /// - **16 bpp**: MSB replicated into R=G=B
+    /// - **32 bpp**: copy with alpha forced to 255
+    ///
+    /// Resolution is preserved.
+    ///
+    /// Note that binary images come out inverted compared with
+    /// [`Pix::convert_to_32`], which follows the Leptonica convention of
+    /// ON pixels being black.  To render a binary image black on white,
+    /// invert it first: `pix.invert().to_rgb()`.
+    pub fn to_rgb(&self) -> Result<Pix> {
+        let rgb = if self.has_colormap() {
+            self.remove_colormap(RemoveColormapTarget::ToFullColor)?
+        } else if self.depth() == PixelDepth::Bit1 {
+            self.invert().convert_to_32()?
+        } else {
+            self.convert_to_32()?
+        };
+        let mut rm = rgb.to_mut();
+        for word in rm.data_mut() {
+            *word |= 0xff;
+        }
+        rm.set_spp(3);
+        rm.copy_resolution_from(self);
+        Ok(rm.into())
+    }
+
     /// Convert 32 bpp RGB to 8 bpp grayscale using standard luminance weights.
     ///
     /// Uses the default perceptual weights: 0.3R + 0.5G + 0.2B.
@@ -1088,6 +1122,43 @@ impl Pix {
         Ok(result_mut.into())
     }
 
+    /// Remove alpha channel by compositing over a solid background color.
+    ///
+    /// Like [`Pix::remove_alpha`], which always uses white.  For a 32 bpp
+    /// RGBA image (spp=4), each pixel becomes
+    /// `(alpha * color + (255 - alpha) * background) / 255`, rounded; the
+    /// result has spp=3 and its alpha bytes set to 255.  Any other image
+    /// is returned as a deep clone.
+    pub fn remove_alpha_with_background(&self, background: Color) -> Result<Pix> {
+        if self.depth() != PixelDepth::Bit32 || self.spp() != 4 {
+            return Ok(self.deep_clone());
+        }
+
+        let w = self.width();
+        let h = self.height();
+        let mut result_mut = Pix::new(w, h, PixelDepth::Bit32)?.to_mut();
+        result_mut.set_resolution(self.xres(), self.yres());
+        result_mut.set_spp(3);
+
+        let blend =
+            |fg: u8, bg: u8, a: u32| ((a * fg as u32 + (255 - a) * bg as u32 + 127) / 255) as u8;
+        for y in 0..h {
+            for x in 0..w {
+                let (r, g, b, a) = pixel::extract_rgba(self.get_pixel_unchecked(x, y));
+                let a = a as u32;
+                let val = pixel::compose_rgba(
+                    blend(r, background.r, a),
+                    blend(g, background.g, a),
+                    blend(b, background.b, a),
+                    255,
+                );
+                result_mut.set_pixel_unchecked(x, y, val);
+            }
+        }
+
+        Ok(result_mut.into())
+    }
+
     /// Unpack 1 bpp binary image to a higher depth.
     ///
     /// Maps 0-bits and 1-bits to the appropriate max values for the
@@ -2800,6 +2871,57 @@ impl Pix {
     }
 }
 
+/// Convert 32 bpp RGB to 8 bpp grayscale with user weights, in integer
+/// arithmetic.
+///
+/// The weights are normalized to sum to 1 and quantized to 1/256 steps,
+/// and each output is `(wr * r + wg * g + wb * b) >> 8`.  This is faster
+/// than [`Pix::convert_rgb_to_gray`], and with the BT.601 weights
+/// `(0.299, 0.587, 0.114)` reproduces `color::pix_convert_to_gray` exactly.
+/// Other useful choices are equal weights or BT.709
+/// `(0.2126, 0.7152, 0.0722)`.
+///
+/// # Errors
+///
+/// Returns [`Error::UnsupportedDepth`] if the image is not 32 bpp.
+/// Returns [`Error::InvalidParameter`] if any weight is negative or all
+/// are zero.
+pub fn rgb_to_gray_weighted(pix: &Pix, wr: f32, wg: f32, wb: f32) -> Result<Pix> {
+    if pix.depth() != PixelDepth::Bit32 {
+        return Err(Error::UnsupportedDepth(pix.depth().bits()));
+    }
+    if !(wr >= 0.0 && wg >= 0.0 && wb >= 0.0) {
+        return Err(Error::InvalidParameter("weights must all be >= 0.0".into()));
+    }
+    let sum = wr + wg + wb;
+    if sum <= 0.0 || !sum.is_finite() {
+        return Err(Error::InvalidParameter(
+            "weights must have a positive finite sum".into(),
+        ));
+    }
+
+    // Fixed-point weights summing to exactly 256; rounding slack goes to
+    // the largest weight so no weight can go negative.
+    let mut iw = [wr, wg, wb].map(|v| (256.0 * v / sum).round() as i32);
+    let largest = (0..3).max_by_key(|&i| iw[i]).unwrap_or(0);
+    iw[largest] += 256 - iw.iter().sum::<i32>();
+    let [iwr, iwg, iwb] = iw.map(|v| v as u32);
+
+    let w = pix.width();
+    let h = pix.height();
+    let mut result_mut = Pix::new(w, h, PixelDepth::Bit8)?.to_mut();
+    result_mut.set_resolution(pix.xres(), pix.yres());
+    for y in 0..h {
+        for x in 0..w {
+            let (r, g, b) = pixel::extract_rgb(pix.get_pixel_unchecked(x, y));
+            let gray = (iwr * r as u32 + iwg * g as u32 + iwb * b as u32) >> 8;
+            result_mut.set_pixel_unchecked(x, y, gray);
+        }
+    }
+
+    Ok(result_mut.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
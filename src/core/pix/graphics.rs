@@ -1455,6 +1455,35 @@ impl PixMut {
         self.render_pta_color(&pta, color)
     }
 
+    /// Draw a measurement grid with a line every `spacing` pixels.
+    ///
+    /// Lines are drawn at x = 0, `spacing`, 2 * `spacing`, ... and likewise
+    /// in y.  Every `major_every`-th line, starting with the one at 0, is a
+    /// major line drawn 3 pixels wide (centered on its coordinate); the
+    /// others are 1 pixel wide.  `major_every` = 0 draws no major lines.
+    /// Lines are clipped to the image.
+    pub fn draw_grid(&mut self, spacing: u32, color: Color, major_every: u32) -> Result<()> {
+        if spacing == 0 {
+            return Err(Error::InvalidParameter("spacing must be > 0".to_string()));
+        }
+        let w = self.width() as i32;
+        let h = self.height() as i32;
+        let line_width = |index: u32| {
+            if major_every > 0 && index.is_multiple_of(major_every) {
+                3
+            } else {
+                1
+            }
+        };
+        for (i, x) in (0..w).step_by(spacing as usize).enumerate() {
+            self.render_line_color(x, 0, x, h - 1, line_width(i as u32), color)?;
+        }
+        for (i, y) in (0..h).step_by(spacing as usize).enumerate() {
+            self.render_line_color(0, y, w - 1, y, line_width(i as u32), color)?;
+        }
+        Ok(())
+    }
+
     /// Render a plot of Numa values onto the image at the specified location.
     ///
     /// C equivalent: `pixRenderPlotFromNuma()` in `graphics.c`
@@ -2192,6 +2221,39 @@ mod tests {
         assert!(any_set);
     }
 
+    #[test]
+    fn test_draw_grid() {
+        let pix = Pix::new(50, 35, super::super::PixelDepth::Bit8).unwrap();
+        let mut pm = pix.to_mut();
+        pm.draw_grid(10, Color::new(255, 255, 255), 2).unwrap();
+        let on = |pm: &PixMut, x: u32, y: u32| pm.get_pixel(x, y) == Some(255);
+
+        // Minor lines at x = 10, 30 and y = 10, 30 are one pixel wide
+        for x in [10, 30] {
+            assert!(on(&pm, x, 5) && !on(&pm, x - 1, 5) && !on(&pm, x + 1, 5));
+        }
+        assert!(on(&pm, 5, 30) && !on(&pm, 5, 29) && !on(&pm, 5, 31));
+        // Major lines at x = 0, 20, 40 and y = 0, 20 are three pixels wide
+        for x in [20, 40] {
+            assert!(on(&pm, x - 1, 5) && on(&pm, x, 5) && on(&pm, x + 1, 5));
+            assert!(!on(&pm, x - 2, 5) && !on(&pm, x + 2, 5));
+        }
+        assert!(on(&pm, 5, 19) && on(&pm, 5, 20) && on(&pm, 5, 21));
+        // The major line at 0 is clipped to its inner half
+        assert!(on(&pm, 0, 5) && on(&pm, 1, 5) && !on(&pm, 2, 5));
+        // Cell interiors are untouched
+        assert!(!on(&pm, 5, 5) && !on(&pm, 15, 15) && !on(&pm, 45, 33));
+
+        // No major lines
+        let mut plain = Pix::new(50, 35, super::super::PixelDepth::Bit8)
+            .unwrap()
+            .to_mut();
+        plain.draw_grid(10, Color::new(255, 255, 255), 0).unwrap();
+        assert!(on(&plain, 20, 5) && !on(&plain, 21, 5) && !on(&plain, 1, 5));
+
+        assert!(pm.draw_grid(0, Color::new(0, 0, 0), 2).is_err());
+    }
+
     #[test]
     fn test_render_random_cmap_ptaa() {
         use crate::core::pta::Ptaa;
@@ -2,8 +2,8 @@
 //!
 //! Functions to compute pixel value distributions from images.
 
-use super::statistics::{PixelStatType, clip_box_to_rect};
-use super::{Pix, PixelDepth};
+use super::statistics::{PixelStatType, StatAccumulator, clip_box_to_rect};
+use super::{Pix, PixelDepth, RgbComponent};
 use crate::core::Box;
 use crate::core::error::{Error, Result};
 use crate::core::numa::{Numa, Numaa};
@@ -749,6 +749,69 @@ impl Pix {
         Ok(result)
     }
 
+    /// Joint histogram of two channels of a 32 bpp image.
+    ///
+    /// Each channel's 256 values are divided into `bins` equal-width bins
+    /// (value `v` falls in bin `v * bins / 256`).  Row `i` of the result
+    /// holds the counts for pixels whose `chan_a` value is in bin `i`,
+    /// indexed by the bin of their `chan_b` value, so the Numaa is
+    /// `bins` Numa of `bins` entries each.
+    ///
+    /// # Arguments
+    ///
+    /// * `chan_a` - Channel indexing the rows.
+    /// * `chan_b` - Channel indexing the entries of each row; must differ
+    ///   from `chan_a`.
+    /// * `bins` - Number of bins per channel, in `[1, 256]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is not 32 bpp, if the two channels
+    /// are the same, or if `bins` is out of range.
+    pub fn histogram_2d(
+        &self,
+        chan_a: RgbComponent,
+        chan_b: RgbComponent,
+        bins: u32,
+    ) -> Result<Numaa> {
+        if self.depth() != PixelDepth::Bit32 {
+            return Err(Error::UnsupportedDepth(self.depth().bits()));
+        }
+        if chan_a == chan_b {
+            return Err(Error::InvalidParameter(
+                "chan_a and chan_b must be different channels".into(),
+            ));
+        }
+        if bins == 0 || bins > 256 {
+            return Err(Error::InvalidParameter(format!(
+                "bins must be in [1, 256], got {bins}"
+            )));
+        }
+
+        let channel = |val: u32, chan: RgbComponent| match chan {
+            RgbComponent::Red => pixel::red(val),
+            RgbComponent::Green => pixel::green(val),
+            RgbComponent::Blue => pixel::blue(val),
+            RgbComponent::Alpha => pixel::alpha(val),
+        };
+        let n = bins as usize;
+        let mut counts = vec![0.0f32; n * n];
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let val = self.get_pixel_unchecked(x, y);
+                let a = channel(val, chan_a) as usize * n / 256;
+                let b = channel(val, chan_b) as usize * n / 256;
+                counts[a * n + b] += 1.0;
+            }
+        }
+
+        let mut result = Numaa::with_capacity(n);
+        for row in counts.chunks(n) {
+            result.push(Numa::from_vec(row.to_vec()));
+        }
+        Ok(result)
+    }
+
     /// Histogram of colormap indices.
     ///
     /// Returns a Numa counting occurrences of each colormap index.
@@ -1022,9 +1085,7 @@ impl Pix {
 
         let w = self.width() as i32;
         let h = self.height() as i32;
-        let mut sum1 = 0.0f64;
-        let mut sum2 = 0.0f64;
-        let mut count = 0u64;
+        let mut acc = StatAccumulator::new();
 
         if let Some(m) = mask {
             let wm = m.width() as i32;
@@ -1037,10 +1098,7 @@ impl Pix {
                     while ix < wm {
                         let sx = x + ix;
                         if sx >= 0 && sx < w && m.get_pixel_unchecked(ix as u32, iy as u32) != 0 {
-                            let val = self.get_pixel_unchecked(sx as u32, sy as u32) as f64;
-                            sum1 += val;
-                            sum2 += val * val;
-                            count += 1;
+                            acc.add(self.get_pixel_unchecked(sx as u32, sy as u32));
                         }
                         ix += factor as i32;
                     }
@@ -1052,30 +1110,16 @@ impl Pix {
             while iy < h as u32 {
                 let mut ix = 0u32;
                 while ix < w as u32 {
-                    let val = self.get_pixel_unchecked(ix, iy) as f64;
-                    sum1 += val;
-                    sum2 += val * val;
-                    count += 1;
+                    acc.add(self.get_pixel_unchecked(ix, iy));
                     ix += factor;
                 }
                 iy += factor;
             }
         }
 
-        if count == 0 {
-            return Err(Error::InvalidParameter("no pixels sampled".into()));
-        }
-
-        let mean = sum1 / count as f64;
-        let mean_sq = sum2 / count as f64;
-        let variance = (mean_sq - mean * mean).max(0.0);
-
-        let result = match stat_type {
-            PixelStatType::MeanAbsVal => mean,
-            PixelStatType::RootMeanSquare => mean_sq.sqrt(),
-            PixelStatType::StandardDeviation => variance.sqrt(),
-            PixelStatType::Variance => variance,
-        };
+        let result = acc
+            .finish(stat_type)
+            .ok_or_else(|| Error::InvalidParameter("no pixels sampled".into()))?;
         Ok(result as f32)
     }
 
@@ -1157,34 +1201,122 @@ impl Pix {
             for ix in 0..nx {
                 let x0 = ix * sx;
                 let y0 = iy * sy;
-                let mut sum1 = 0.0f64;
-                let mut sum2 = 0.0f64;
-                let count = (sx * sy) as f64;
-
+                let mut acc = StatAccumulator::new();
                 for dy in 0..sy {
                     for dx in 0..sx {
-                        let val = self.get_pixel_unchecked(x0 + dx, y0 + dy) as f64;
-                        sum1 += val;
-                        sum2 += val * val;
+                        acc.add(self.get_pixel_unchecked(x0 + dx, y0 + dy));
                     }
                 }
+                let result = acc.finish(stat_type).unwrap_or(0.0);
+                pm.set_pixel_unchecked(ix, iy, (result + 0.5).min(255.0) as u32);
+            }
+        }
+        Ok(pm.into())
+    }
 
-                let mean = sum1 / count;
-                let mean_sq = sum2 / count;
-                let variance = (mean_sq - mean * mean).max(0.0);
+    /// Compute a statistic over non-overlapping square tiles of an 8bpp image.
+    ///
+    /// Returns an 8bpp image of size `ceil(w / tile) x ceil(h / tile)` in
+    /// which each pixel holds the statistic of the corresponding tile.
+    /// Unlike [`average_tiled`](Self::average_tiled), partial tiles at the
+    /// right and bottom edges are kept and evaluated over the pixels they
+    /// contain. Values are rounded and clamped to 255, so large variances
+    /// saturate.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile` - Tile side length in pixels.
+    /// * `stat_type` - The statistic to compute ([`PixelStatType`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image depth is not 8 bpp or `tile` is 0.
+    pub fn tile_stats(&self, tile: u32, stat_type: PixelStatType) -> Result<Pix> {
+        if self.depth() != PixelDepth::Bit8 {
+            return Err(Error::UnsupportedDepth(self.depth().bits()));
+        }
+        if tile == 0 {
+            return Err(Error::InvalidParameter("tile size must be >= 1".into()));
+        }
 
-                let result = match stat_type {
-                    PixelStatType::MeanAbsVal => mean,
-                    PixelStatType::RootMeanSquare => mean_sq.sqrt(),
-                    PixelStatType::StandardDeviation => variance.sqrt(),
-                    PixelStatType::Variance => variance,
-                };
+        let w = self.width();
+        let h = self.height();
+        let nx = w.div_ceil(tile);
+        let ny = h.div_ceil(tile);
+
+        let out = Pix::new(nx, ny, PixelDepth::Bit8)?;
+        let mut pm = out.try_into_mut().unwrap();
+        for iy in 0..ny {
+            let y0 = iy * tile;
+            let y1 = (y0 + tile).min(h);
+            for ix in 0..nx {
+                let x0 = ix * tile;
+                let x1 = (x0 + tile).min(w);
+                let mut acc = StatAccumulator::new();
+                for y in y0..y1 {
+                    for x in x0..x1 {
+                        acc.add(self.get_pixel_unchecked(x, y));
+                    }
+                }
+                let result = acc.finish(stat_type).unwrap_or(0.0);
                 pm.set_pixel_unchecked(ix, iy, (result + 0.5).min(255.0) as u32);
             }
         }
         Ok(pm.into())
     }
 
+    /// Compute the mean of each cell of an `nx` x `ny` grid over an 8bpp image.
+    ///
+    /// Returns one [`Numa`] per grid row (top to bottom), each holding the
+    /// `nx` cell means from left to right, as raw data for custom
+    /// background interpolation.
+    ///
+    /// Cells are `w / nx` by `h / ny` pixels; when the image size is not
+    /// divisible, the last column and row of cells absorb the remainder.
+    /// This differs from [`tile_stats`](Self::tile_stats), which uses square
+    /// tiles of a given size and adds a smaller partial tile at the right
+    /// and bottom edges.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image depth is not 8 bpp, or if `nx` or `ny`
+    /// is 0 or exceeds the image width or height.
+    pub fn tiled_mean_numaa(&self, nx: u32, ny: u32) -> Result<Numaa> {
+        if self.depth() != PixelDepth::Bit8 {
+            return Err(Error::UnsupportedDepth(self.depth().bits()));
+        }
+        let w = self.width();
+        let h = self.height();
+        if nx == 0 || ny == 0 || nx > w || ny > h {
+            return Err(Error::InvalidParameter(format!(
+                "grid {nx}x{ny} invalid for {w}x{h} image"
+            )));
+        }
+
+        let tw = w / nx;
+        let th = h / ny;
+        let mut naa = Numaa::with_capacity(ny as usize);
+        for iy in 0..ny {
+            let y0 = iy * th;
+            let y1 = if iy + 1 == ny { h } else { y0 + th };
+            let mut na = Numa::with_capacity(nx as usize);
+            for ix in 0..nx {
+                let x0 = ix * tw;
+                let x1 = if ix + 1 == nx { w } else { x0 + tw };
+                let mut acc = StatAccumulator::new();
+                for y in y0..y1 {
+                    for x in x0..x1 {
+                        acc.add(self.get_pixel_unchecked(x, y));
+                    }
+                }
+                let mean = acc.finish(PixelStatType::MeanAbsVal).unwrap_or(0.0);
+                na.push(mean as f32);
+            }
+            naa.push(na);
+        }
+        Ok(naa)
+    }
+
     /// Compute tile-based per-channel statistics for a 32bpp RGB image.
     ///
     /// Returns three 8bpp images (R, G, B) where each pixel
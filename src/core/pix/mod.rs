@@ -33,20 +33,26 @@ pub mod graphics;
 mod histogram;
 mod mask;
 mod measurement;
+mod pattern;
 mod rgb;
 pub mod rop;
 pub mod serial;
 pub mod statistics;
+mod text;
 
 pub use access::*;
 pub use arith::{RgbScaleType, linear_scale_rgb_val, log_scale_rgb_val};
-pub use blend::{BlendMode, GrayBlendType, MaskBlendType, blend_with_gray_mask};
+pub use blend::{
+    BlendMode, GrayBlendType, MaskBlendType, blend_over_checkerboard, blend_with_gray_mask,
+    blend_with_gray_mask_feathered,
+};
+pub use border::CanvasAnchor;
 pub use clip::ScanDirection;
 pub use compare::{
-    CompareResult, CompareType, PixelDiffResult, TranslationMatch, best_correlation,
-    compare_tiles_by_histo, compare_with_translation, correlation_binary,
-    pix_compare_gray_by_histo, pix_compare_photo_regions_by_histo, pix_decide_if_photo_image,
-    pix_gen_photo_histos, pixa_compare_photo_regions_by_histo,
+    AlignedCompareResult, CompareResult, CompareType, PixelDiffResult, TranslationMatch,
+    best_correlation, compare_aligned, compare_tiles_by_histo, compare_with_translation,
+    correlation_binary, pix_compare_gray_by_histo, pix_compare_photo_regions_by_histo,
+    pix_decide_if_photo_image, pix_gen_photo_histos, pixa_compare_photo_regions_by_histo,
 };
 pub use convert::{
     Convert16To8Type, Convert32To16Type, GrayConversionType, MinMaxType, RemoveColormapTarget,
@@ -56,6 +62,7 @@ pub use graphics::{
     generate_pta_line_from_pt, locate_pt_radially, make_plot_pta_from_numa, render_polygon,
 };
 pub use histogram::ColorHistogram;
+pub use pattern::TestPattern;
 pub use rgb::RgbComponent;
 pub use rop::{InColor, RopOp};
 
@@ -1164,6 +1171,8 @@ impl PixMut {
 
     /// Clear pixels in a rectangular region (set to 0).
     ///
+    /// The rectangle is clipped to the image; see [`Self::set_in_rect_arbitrary`].
+    ///
     /// # See also
     ///
     /// C Leptonica: `pixClearInRect()` in `pix2.c`
@@ -1173,6 +1182,8 @@ impl PixMut {
 
     /// Set pixels in a rectangular region to max value.
     ///
+    /// The rectangle is clipped to the image; see [`Self::set_in_rect_arbitrary`].
+    ///
     /// # See also
     ///
     /// C Leptonica: `pixSetInRect()` in `pix2.c`
@@ -1183,18 +1194,24 @@ impl PixMut {
 
     /// Set pixels in a rectangular region to an arbitrary value.
     ///
+    /// The rectangle is clipped to the image, so it may start at negative
+    /// coordinates or extend past the right and bottom edges.  A rectangle
+    /// entirely outside the image changes nothing.  `val` is masked to the
+    /// image depth.
+    ///
     /// # See also
     ///
     /// C Leptonica: `pixSetInRectArbitrary()` in `pix2.c`
     pub fn set_in_rect_arbitrary(&mut self, rect: &crate::core::Box, val: u32) -> Result<()> {
         let w = self.width();
         let h = self.height();
+        let val = val & self.depth().max_value();
 
         // Clip rect to image bounds
         let x0 = rect.x.max(0) as u32;
         let y0 = rect.y.max(0) as u32;
-        let x1 = ((rect.x + rect.w).max(0) as u32).min(w);
-        let y1 = ((rect.y + rect.h).max(0) as u32).min(h);
+        let x1 = (rect.x.saturating_add(rect.w).max(0) as u32).min(w);
+        let y1 = (rect.y.saturating_add(rect.h).max(0) as u32).min(h);
 
         if x0 >= x1 || y0 >= y1 {
             return Ok(()); // Empty intersection
@@ -1257,6 +1274,12 @@ impl PixMut {
 
     /// Set or clear border pixels using raster operations.
     ///
+    /// The outermost `left`, `right`, `top` and `bot` columns and rows are
+    /// set to black or white for the image depth (see
+    /// [`Self::get_black_or_white_val`]).  Widths larger than the image are
+    /// clamped, and each border pixel is written once even where the
+    /// borders overlap.
+    ///
     /// # See also
     ///
     /// C Leptonica: `pixSetOrClearBorder()` in `pix2.c`
@@ -1297,21 +1320,21 @@ impl PixMut {
                 self.set_pixel_unchecked(x, y, val);
             }
         }
-        // Bottom rows
-        let bot_start = h.saturating_sub(bot);
+        // Bottom rows, below any top rows
+        let y_start = top.min(h);
+        let bot_start = h.saturating_sub(bot).max(y_start);
         for y in bot_start..h {
             for x in 0..w {
                 self.set_pixel_unchecked(x, y, val);
             }
         }
-        // Left and right columns (middle rows)
-        let y_start = top.min(h);
-        let y_end = bot_start.max(y_start);
-        for y in y_start..y_end {
-            for x in 0..left.min(w) {
+        // Left and right columns (middle rows), right of any left columns
+        let left_end = left.min(w);
+        let right_start = w.saturating_sub(right).max(left_end);
+        for y in y_start..bot_start {
+            for x in 0..left_end {
                 self.set_pixel_unchecked(x, y, val);
             }
-            let right_start = w.saturating_sub(right);
             for x in right_start..w {
                 self.set_pixel_unchecked(x, y, val);
             }
@@ -1912,6 +1935,44 @@ mod tests {
         assert_eq!(pm.get_pixel(0, 0), Some(0));
     }
 
+    #[test]
+    fn test_clear_and_set_arbitrary_in_rect_past_edges() {
+        let pix = Pix::new(20, 20, PixelDepth::Bit8).unwrap();
+        let mut pm = pix.try_into_mut().unwrap();
+        pm.set_all_arbitrary(200).unwrap();
+
+        // Extends past the right and bottom edges: x=[12,20), y=[16,20)
+        let rect = crate::core::Box::new(12, 16, 30, 30).unwrap();
+        pm.clear_in_rect(&rect).unwrap();
+        assert_eq!(pm.get_pixel(12, 16), Some(0));
+        assert_eq!(pm.get_pixel(19, 19), Some(0));
+        assert_eq!(pm.get_pixel(11, 19), Some(200));
+        assert_eq!(pm.get_pixel(19, 15), Some(200));
+
+        // Huge extent does not overflow
+        let rect = crate::core::Box::new(18, 0, i32::MAX, 2).unwrap();
+        pm.set_in_rect_arbitrary(&rect, 7).unwrap();
+        assert_eq!(pm.get_pixel(18, 0), Some(7));
+        assert_eq!(pm.get_pixel(19, 1), Some(7));
+        assert_eq!(pm.get_pixel(17, 0), Some(200));
+
+        // Fully outside past the bottom-right corner is a no-op
+        let rect = crate::core::Box::new(20, 20, 5, 5).unwrap();
+        pm.clear_in_rect(&rect).unwrap();
+        assert_eq!(pm.get_pixel(19, 19), Some(0));
+        assert_eq!(pm.get_pixel(0, 0), Some(200));
+    }
+
+    #[test]
+    fn test_set_in_rect_arbitrary_masks_value() {
+        let pix = Pix::new(8, 8, PixelDepth::Bit4).unwrap();
+        let mut pm = pix.try_into_mut().unwrap();
+        let rect = crate::core::Box::new(0, 0, 4, 4).unwrap();
+        pm.set_in_rect_arbitrary(&rect, 0x1a).unwrap();
+        assert_eq!(pm.get_pixel(0, 0), Some(0xa));
+        assert_eq!(pm.get_pixel(4, 4), Some(0));
+    }
+
     #[test]
     fn test_set_or_clear_border_32bpp() {
         let pix = Pix::new(20, 20, PixelDepth::Bit32).unwrap();
@@ -1930,6 +1991,30 @@ mod tests {
         assert_eq!(pm.get_pixel(1, 0), Some(0));
     }
 
+    #[test]
+    fn test_set_or_clear_border_1bpp_and_clamping() {
+        // 1 bpp: black is 1
+        let pix = Pix::new(10, 8, PixelDepth::Bit1).unwrap();
+        let mut pm = pix.try_into_mut().unwrap();
+        pm.set_or_clear_border(1, 2, 1, 3, InitColor::Black);
+        assert_eq!(pm.get_pixel(0, 4), Some(1));
+        assert_eq!(pm.get_pixel(8, 4), Some(1));
+        assert_eq!(pm.get_pixel(7, 4), Some(0));
+        assert_eq!(pm.get_pixel(5, 0), Some(1));
+        assert_eq!(pm.get_pixel(5, 5), Some(1));
+        assert_eq!(pm.get_pixel(5, 4), Some(0));
+        let framed: Pix = pm.into();
+        assert_eq!(framed.count_pixels(), 10 * 8 - 7 * 4);
+
+        // Borders wider than the image clamp and cover everything
+        let pix = Pix::new(6, 5, PixelDepth::Bit8).unwrap();
+        let mut pm = pix.try_into_mut().unwrap();
+        pm.set_or_clear_border(4, 4, 0, 0, InitColor::White);
+        assert!((0..5).all(|y| (0..6).all(|x| pm.get_pixel(x, y) == Some(255))));
+        pm.set_or_clear_border(0, 0, 100, 100, InitColor::Black);
+        assert!((0..5).all(|y| (0..6).all(|x| pm.get_pixel(x, y) == Some(0))));
+    }
+
     #[test]
     fn test_set_black_or_white_32bpp_white_value() {
         let pix = Pix::new(2, 2, PixelDepth::Bit32).unwrap();
@@ -0,0 +1,149 @@
+//! Synthetic test patterns
+//!
+//! Generators for standard images used as test inputs and demos.
+
+use super::{Pix, PixelDepth};
+use crate::core::error::{Error, Result};
+use crate::core::pixel;
+
+/// Pattern drawn by [`Pix::test_pattern`]
+///
+/// Two-level patterns use 0 and the maximum value of the depth (white for
+/// 8 and 32 bpp, black for 1 bpp).  Tonal patterns (the gradients and
+/// color bars) are inverted at 1 bpp, where 0 is white, so that they look
+/// the same as at the other depths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestPattern {
+    /// Every pixel set to the given value (packed RGBA for 32 bpp)
+    Solid(u32),
+    /// Squares of `cell` pixels alternating 0 and max, starting with 0
+    /// at the upper-left corner
+    Checkerboard {
+        /// Side of each square in pixels
+        cell: u32,
+    },
+    /// Ramp from black at the left column to white at the right column
+    HorizontalGradient,
+    /// Ramp from black at the top row to white at the bottom row
+    VerticalGradient,
+    /// Eight vertical bars: white, yellow, cyan, green, magenta, red, blue,
+    /// black.  Below 32 bpp each bar is its luminance.
+    ColorBars,
+    /// Rings of width `spacing` about the image center, alternating max
+    /// (innermost) and 0
+    ConcentricCircles {
+        /// Ring width in pixels
+        spacing: u32,
+    },
+    /// One-pixel lines at max every `spacing` pixels on a 0 background,
+    /// starting at row and column 0
+    Grid {
+        /// Distance between lines in pixels
+        spacing: u32,
+    },
+}
+
+/// Colors of [`TestPattern::ColorBars`], left to right
+const COLOR_BARS: [(u8, u8, u8); 8] = [
+    (255, 255, 255),
+    (255, 255, 0),
+    (0, 255, 255),
+    (0, 255, 0),
+    (255, 0, 255),
+    (255, 0, 0),
+    (0, 0, 255),
+    (0, 0, 0),
+];
+
+impl Pix {
+    /// Generate a standard test pattern.
+    ///
+    /// Gray depths (1, 2, 4, 8, 16 bpp) produce gray values scaled to the
+    /// depth; 32 bpp produces RGB with gray patterns in R = G = B.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the size is zero, if a cell or spacing is zero,
+    /// or if a [`TestPattern::Solid`] value does not fit the depth.
+    pub fn test_pattern(
+        kind: TestPattern,
+        width: u32,
+        height: u32,
+        depth: PixelDepth,
+    ) -> Result<Pix> {
+        // Gray maximum; 32 bpp patterns are drawn with 8-bit components
+        let max = if depth == PixelDepth::Bit32 {
+            255
+        } else {
+            depth.max_value()
+        };
+        match kind {
+            TestPattern::Solid(val) if depth != PixelDepth::Bit32 && val > max => {
+                return Err(Error::InvalidParameter(format!(
+                    "solid value {val} exceeds {max} for {} bpp",
+                    depth.bits()
+                )));
+            }
+            TestPattern::Checkerboard { cell: 0 }
+            | TestPattern::ConcentricCircles { spacing: 0 }
+            | TestPattern::Grid { spacing: 0 } => {
+                return Err(Error::InvalidParameter(
+                    "pattern cell/spacing must be > 0".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        let mut pm = Pix::new(width, height, depth)?.to_mut();
+        // Scale a gray level in [0, 1] to a pixel value of this depth
+        let gray = |f: f64| {
+            let v = (f * max as f64).round() as u32;
+            if depth == PixelDepth::Bit32 {
+                pixel::compose_rgb(v as u8, v as u8, v as u8)
+            } else {
+                v
+            }
+        };
+        // Gray level of a tonal pattern, inverting at 1 bpp where 1 is black
+        let tone = |f: f64| {
+            gray(if depth == PixelDepth::Bit1 {
+                1.0 - f
+            } else {
+                f
+            })
+        };
+        let (cx, cy) = ((width as f64 - 1.0) / 2.0, (height as f64 - 1.0) / 2.0);
+
+        for y in 0..height {
+            for x in 0..width {
+                let val = match kind {
+                    TestPattern::Solid(val) => val,
+                    TestPattern::Checkerboard { cell } => gray(((x / cell + y / cell) % 2) as f64),
+                    TestPattern::HorizontalGradient => tone(x as f64 / (width - 1).max(1) as f64),
+                    TestPattern::VerticalGradient => tone(y as f64 / (height - 1).max(1) as f64),
+                    TestPattern::ColorBars => {
+                        let bar = (x as u64 * 8 / width as u64) as usize;
+                        let (r, g, b) = COLOR_BARS[bar];
+                        if depth == PixelDepth::Bit32 {
+                            pixel::compose_rgb(r, g, b)
+                        } else {
+                            let luma = 0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64;
+                            tone(luma / 255.0)
+                        }
+                    }
+                    TestPattern::ConcentricCircles { spacing } => {
+                        let dist = (x as f64 - cx).hypot(y as f64 - cy);
+                        let ring = (dist / spacing as f64) as u64;
+                        gray(if ring.is_multiple_of(2) { 1.0 } else { 0.0 })
+                    }
+                    TestPattern::Grid { spacing } => {
+                        let on = x % spacing == 0 || y % spacing == 0;
+                        gray(if on { 1.0 } else { 0.0 })
+                    }
+                };
+                pm.set_pixel_unchecked(x, y, val);
+            }
+        }
+        Ok(pm.into())
+    }
+}
@@ -104,6 +104,77 @@ impl Pix {
 
         Ok(result_mut.into())
     }
+
+    /// Split a 32 bpp image into its R, G, B and A channels.
+    ///
+    /// Each channel is returned as an 8 bpp image; the alpha channel is
+    /// extracted regardless of the samples per pixel.
+    pub fn split_channels(&self) -> Result<[Pix; 4]> {
+        if self.depth() != PixelDepth::Bit32 {
+            return Err(Error::UnsupportedDepth(self.depth().bits()));
+        }
+
+        let w = self.width();
+        let h = self.height();
+        let mut channels: [PixMut; 4] = std::array::from_fn(|_| {
+            let mut pm = Pix::new(w, h, PixelDepth::Bit8)
+                .unwrap()
+                .try_into_mut()
+                .unwrap();
+            pm.set_resolution(self.xres(), self.yres());
+            pm
+        });
+
+        for y in 0..h {
+            for x in 0..w {
+                let (r, g, b, a) = pixel::extract_rgba(self.get_pixel_unchecked(x, y));
+                for (ch, val) in channels.iter_mut().zip([r, g, b, a]) {
+                    ch.set_pixel_unchecked(x, y, val as u32);
+                }
+            }
+        }
+
+        Ok(channels.map(Into::into))
+    }
+
+    /// Merge 8 bpp channel images into a 32 bpp image.
+    ///
+    /// All channels must have the same dimensions.  With an alpha channel
+    /// the result has 4 samples per pixel; without one, alpha is set to
+    /// 255 and the result has 3.
+    pub fn merge_channels(r: &Pix, g: &Pix, b: &Pix, a: Option<&Pix>) -> Result<Pix> {
+        let w = r.width();
+        let h = r.height();
+        for ch in [r, g, b].into_iter().chain(a) {
+            if ch.depth() != PixelDepth::Bit8 {
+                return Err(Error::UnsupportedDepth(ch.depth().bits()));
+            }
+            if ch.width() != w || ch.height() != h {
+                return Err(Error::InvalidParameter(
+                    "all channel images must have the same dimensions".into(),
+                ));
+            }
+        }
+
+        let result = Pix::new(w, h, PixelDepth::Bit32)?;
+        let mut result_mut = result.try_into_mut().unwrap();
+        result_mut.set_resolution(r.xres(), r.yres());
+        if a.is_some() {
+            result_mut.set_spp(4);
+        }
+
+        for y in 0..h {
+            for x in 0..w {
+                let rv = r.get_pixel_unchecked(x, y) as u8;
+                let gv = g.get_pixel_unchecked(x, y) as u8;
+                let bv = b.get_pixel_unchecked(x, y) as u8;
+                let av = a.map_or(255, |a| a.get_pixel_unchecked(x, y) as u8);
+                result_mut.set_pixel_unchecked(x, y, pixel::compose_rgba(rv, gv, bv, av));
+            }
+        }
+
+        Ok(result_mut.into())
+    }
 }
 
 impl Pix {
@@ -354,6 +354,46 @@ impl Pix {
         Ok(pm.into())
     }
 
+    /// Shift an image by `(dx, dy)`, optionally wrapping around the edges.
+    ///
+    /// With `wrap = true` the shift is cyclic: pixels leaving one edge
+    /// re-enter on the opposite edge, so shifting by a multiple of the
+    /// image size is the identity.  With `wrap = false` the exposed region
+    /// is filled with 0.  Works for all depths; colormap, spp and
+    /// resolution are preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `dx` - Horizontal shift (positive = right, negative = left)
+    /// * `dy` - Vertical shift (positive = down, negative = up)
+    /// * `wrap` - Whether to shift cyclically
+    pub fn shift(&self, dx: i32, dy: i32, wrap: bool) -> Result<Pix> {
+        let w = self.width() as i64;
+        let h = self.height() as i64;
+        let mut pm = self.create_template().try_into_mut().unwrap();
+
+        for y in 0..h {
+            let mut sy = y - dy as i64;
+            if wrap {
+                sy = sy.rem_euclid(h);
+            } else if !(0..h).contains(&sy) {
+                continue;
+            }
+            for x in 0..w {
+                let mut sx = x - dx as i64;
+                if wrap {
+                    sx = sx.rem_euclid(w);
+                } else if !(0..w).contains(&sx) {
+                    continue;
+                }
+                let val = self.get_pixel_unchecked(sx as u32, sy as u32);
+                pm.set_pixel_unchecked(x as u32, y as u32, val);
+            }
+        }
+
+        Ok(pm.into())
+    }
+
     /// Apply a rasterop between `self` and `src` across their full extents,
     /// aligned at top-left corner.
     ///
@@ -91,6 +91,21 @@ pub(crate) fn clip_box_to_rect(
     }
 }
 
+/// Add 1 to `counts[x]` for every set bit `x` of a packed 1 bpp row.
+///
+/// Bits beyond `counts.len()` (row padding) are ignored.
+fn accumulate_set_bits(counts: &mut [f32], words: impl Iterator<Item = u32>) {
+    for (i, mut bits) in words.enumerate() {
+        while bits != 0 {
+            let bit = bits.leading_zeros();
+            if let Some(c) = counts.get_mut(i * 32 + bit as usize) {
+                *c += 1.0;
+            }
+            bits &= !(0x8000_0000u32 >> bit);
+        }
+    }
+}
+
 impl Pix {
     /// Count the foreground (non-zero) pixels in the image.
     ///
@@ -276,6 +291,82 @@ impl Pix {
         Ok(Numa::from_vec(counts))
     }
 
+    /// Count ON/OFF transitions along each row of a 1 bpp image.
+    ///
+    /// Both 0→1 and 1→0 changes are counted, treating the pixels just
+    /// outside the image as OFF. A row containing one solid run therefore
+    /// reports 2 transitions, even if the run touches the image edge, while
+    /// rows crossing text report many. Few transitions together with a high
+    /// [`count_by_row`](Self::count_by_row) value indicate a ruling line.
+    ///
+    /// Rows are processed a word at a time (XOR with the row shifted by one
+    /// pixel, then popcount).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is not 1 bpp.
+    pub fn horizontal_transitions(&self) -> Result<Numa> {
+        if self.depth() != PixelDepth::Bit1 {
+            return Err(Error::UnsupportedDepth(self.depth().bits()));
+        }
+        let w = self.width();
+        let nwords = w.div_ceil(32) as usize;
+        let last_mask = match w % 32 {
+            0 => !0u32,
+            r => !0u32 << (32 - r),
+        };
+
+        let mut na = Numa::with_capacity(self.height() as usize);
+        for y in 0..self.height() {
+            let row = &self.row_data(y)[..nwords];
+            let mut carry = 0u32;
+            let mut count = 0u32;
+            for (i, &word) in row.iter().enumerate() {
+                let word = if i + 1 == nwords {
+                    word & last_mask
+                } else {
+                    word
+                };
+                count += (word ^ ((word >> 1) | (carry << 31))).count_ones();
+                carry = word & 1;
+            }
+            na.push((count + carry) as f32);
+        }
+        Ok(na)
+    }
+
+    /// Count ON/OFF transitions down each column of a 1 bpp image.
+    ///
+    /// The column counterpart of
+    /// [`horizontal_transitions`](Self::horizontal_transitions): pixels
+    /// above the first row and below the last row are treated as OFF.
+    /// Consecutive rows are XORed a word at a time and the set bits are
+    /// accumulated per column.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is not 1 bpp.
+    pub fn vertical_transitions(&self) -> Result<Numa> {
+        if self.depth() != PixelDepth::Bit1 {
+            return Err(Error::UnsupportedDepth(self.depth().bits()));
+        }
+        let w = self.width();
+        let h = self.height();
+        let nwords = w.div_ceil(32) as usize;
+
+        let mut counts = vec![0.0f32; w as usize];
+        let zeros = vec![0u32; nwords];
+        let mut prev: &[u32] = &zeros;
+        for y in 0..h {
+            let row = &self.row_data(y)[..nwords];
+            let diff = row.iter().zip(prev).map(|(a, b)| a ^ b);
+            accumulate_set_bits(&mut counts, diff);
+            prev = row;
+        }
+        accumulate_set_bits(&mut counts, prev.iter().copied());
+        Ok(Numa::from_vec(counts))
+    }
+
     /// Check if all pixels in the image are zero.
     ///
     /// C equivalent: `pixZero()` in `pix3.c`
@@ -1190,6 +1281,60 @@ pub enum PixelStatType {
     StandardDeviation,
     /// Variance.
     Variance,
+    /// Minimum value.
+    Min,
+    /// Maximum value.
+    Max,
+}
+
+/// Running sums for evaluating a [`PixelStatType`] over sampled values.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StatAccumulator {
+    sum1: f64,
+    sum2: f64,
+    count: u64,
+    min: u32,
+    max: u32,
+}
+
+impl StatAccumulator {
+    pub(crate) fn new() -> Self {
+        Self {
+            sum1: 0.0,
+            sum2: 0.0,
+            count: 0,
+            min: u32::MAX,
+            max: 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn add(&mut self, val: u32) {
+        let v = val as f64;
+        self.sum1 += v;
+        self.sum2 += v * v;
+        self.count += 1;
+        self.min = self.min.min(val);
+        self.max = self.max.max(val);
+    }
+
+    /// Evaluate `stat_type`; returns `None` if no value was added.
+    pub(crate) fn finish(&self, stat_type: PixelStatType) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        let mean = self.sum1 / self.count as f64;
+        let mean_sq = self.sum2 / self.count as f64;
+        let variance = (mean_sq - mean * mean).max(0.0);
+        Some(match stat_type {
+            PixelStatType::MeanAbsVal => mean,
+            PixelStatType::RootMeanSquare => mean_sq.sqrt(),
+            PixelStatType::StandardDeviation => variance.sqrt(),
+            PixelStatType::Variance => variance,
+            PixelStatType::Min => self.min as f64,
+            PixelStatType::Max => self.max as f64,
+        })
+    }
 }
 
 /// Per-row or per-column statistics output.
@@ -1845,35 +1990,20 @@ impl Pix {
     fn get_pixel_stats_gray(&self, factor: u32, stat_type: PixelStatType) -> Result<u32> {
         let w = self.width();
         let h = self.height();
-        let mut sum1 = 0.0f64;
-        let mut sum2 = 0.0f64;
-        let mut count = 0u64;
+        let mut acc = StatAccumulator::new();
 
         let mut y = 0u32;
         while y < h {
             let mut x = 0u32;
             while x < w {
-                let val = self.get_pixel_unchecked(x, y) as f64;
-                sum1 += val;
-                sum2 += val * val;
-                count += 1;
+                acc.add(self.get_pixel_unchecked(x, y));
                 x += factor;
             }
             y += factor;
         }
-        if count == 0 {
-            return Err(Error::InvalidParameter("no pixels sampled".into()));
-        }
-        let mean = sum1 / count as f64;
-        let mean_sq = sum2 / count as f64;
-        let variance = (mean_sq - mean * mean).max(0.0);
-
-        let result = match stat_type {
-            PixelStatType::MeanAbsVal => mean,
-            PixelStatType::RootMeanSquare => mean_sq.sqrt(),
-            PixelStatType::StandardDeviation => variance.sqrt(),
-            PixelStatType::Variance => variance,
-        };
+        let result = acc
+            .finish(stat_type)
+            .ok_or_else(|| Error::InvalidParameter("no pixels sampled".into()))?;
         Ok((result + 0.5) as u32)
     }
 
@@ -2189,6 +2319,112 @@ impl Pix {
         }
         Ok(result)
     }
+
+    /// Compute the mean intensity of an 8 bpp image as a function of
+    /// distance from `(cx, cy)`.
+    ///
+    /// The radius range from 0 to the distance of the farthest image corner
+    /// is divided into `nbins` equal bins.  Each bin holds the mean of the
+    /// in-bounds pixels whose distance falls in it, so bins that extend past
+    /// the image edge are averaged over the pixels that exist; bins with no
+    /// pixels are 0.  The returned Numa has parameters `(0.0, bin_width)`.
+    ///
+    /// Useful for dot and ring metrology around a known center.
+    pub fn radial_profile(&self, cx: f32, cy: f32, nbins: u32) -> Result<Numa> {
+        self.check_profile_input(nbins)?;
+        let w = self.width();
+        let h = self.height();
+        let rmax = [
+            (0.0, 0.0),
+            (w as f32, 0.0),
+            (0.0, h as f32),
+            (w as f32, h as f32),
+        ]
+        .iter()
+        .map(|&(x, y)| (x - cx).hypot(y - cy))
+        .fold(0.0f32, f32::max);
+        let bin_width = if rmax > 0.0 { rmax / nbins as f32 } else { 1.0 };
+
+        let mut sums = vec![0.0f64; nbins as usize];
+        let mut counts = vec![0u64; nbins as usize];
+        for y in 0..h {
+            for x in 0..w {
+                let r = (x as f32 - cx).hypot(y as f32 - cy);
+                let bin = ((r / bin_width) as usize).min(nbins as usize - 1);
+                sums[bin] += self.get_pixel_unchecked(x, y) as f64;
+                counts[bin] += 1;
+            }
+        }
+
+        let mut na = profile_means(&sums, &counts);
+        na.set_parameters(0.0, bin_width);
+        Ok(na)
+    }
+
+    /// Compute the mean intensity of an 8 bpp image as a function of
+    /// angle around `(cx, cy)`.
+    ///
+    /// The full circle `[0, 2π)` is divided into `nbins` equal bins.  Angles
+    /// are measured from the +x axis toward +y; since image rows increase
+    /// downward, this is clockwise on screen.  The pixel exactly at the
+    /// center is ignored, and bins with no in-bounds pixels are 0.  The
+    /// returned Numa has parameters `(0.0, 2π / nbins)` (radians).
+    pub fn angular_profile(&self, cx: f32, cy: f32, nbins: u32) -> Result<Numa> {
+        self.check_profile_input(nbins)?;
+        let w = self.width();
+        let h = self.height();
+        let bin_width = std::f32::consts::TAU / nbins as f32;
+
+        let mut sums = vec![0.0f64; nbins as usize];
+        let mut counts = vec![0u64; nbins as usize];
+        for y in 0..h {
+            for x in 0..w {
+                let dx = x as f32 - cx;
+                let dy = y as f32 - cy;
+                if dx == 0.0 && dy == 0.0 {
+                    continue;
+                }
+                let theta = dy.atan2(dx).rem_euclid(std::f32::consts::TAU);
+                let bin = ((theta / bin_width) as usize).min(nbins as usize - 1);
+                sums[bin] += self.get_pixel_unchecked(x, y) as f64;
+                counts[bin] += 1;
+            }
+        }
+
+        let mut na = profile_means(&sums, &counts);
+        na.set_parameters(0.0, bin_width);
+        Ok(na)
+    }
+
+    /// Validate input for [`radial_profile`](Self::radial_profile) and
+    /// [`angular_profile`](Self::angular_profile).
+    fn check_profile_input(&self, nbins: u32) -> Result<()> {
+        if self.depth() != PixelDepth::Bit8 {
+            return Err(Error::UnsupportedDepth(self.depth().bits()));
+        }
+        if self.has_colormap() {
+            return Err(Error::InvalidParameter(
+                "profile requires an image without colormap".into(),
+            ));
+        }
+        if nbins == 0 {
+            return Err(Error::InvalidParameter("nbins must be > 0".into()));
+        }
+        Ok(())
+    }
+}
+
+/// Per-bin means, with empty bins set to 0.
+fn profile_means(sums: &[f64], counts: &[u64]) -> Numa {
+    let mut na = Numa::with_capacity(sums.len());
+    for (&sum, &count) in sums.iter().zip(counts) {
+        na.push(if count > 0 {
+            (sum / count as f64) as f32
+        } else {
+            0.0
+        });
+    }
+    na
 }
 
 /// Compute a single statistic from a histogram.
@@ -8,6 +8,7 @@ mod properties;
 pub use properties::SizeIndicatorAxis;
 mod select;
 mod serial;
+mod stack;
 mod transform;
 
 pub use select::{
@@ -15,11 +16,12 @@ pub use select::{
     pix_select_by_area_fraction, pix_select_by_perim_size_ratio, pix_select_by_perim_to_area_ratio,
     pix_select_by_width_height_ratio,
 };
+pub use stack::{pixa_max, pixa_mean, pixa_median, pixa_min};
 
 use crate::core::box_::{Box, Boxa, SizeRelation};
 use crate::core::error::{Error, Result};
 use crate::core::numa::{Numa, SortOrder};
-use crate::core::pix::{Pix, PixMut, PixelDepth, statistics::RowColStatType};
+use crate::core::pix::{CanvasAnchor, Pix, PixMut, PixelDepth, statistics::RowColStatType};
 
 /// Sort key for Pixa sorting operations.
 ///
@@ -55,6 +57,56 @@ pub enum PixaSortType {
     ByAspectRatio,
 }
 
+/// Target size rule for [`Pixa::align_sizes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlignMode {
+    /// Pad every image to the maximum width and height in the Pixa,
+    /// placing it by `anchor` and filling new pixels with `fill`
+    Pad {
+        /// Placement of each image on the enlarged canvas
+        anchor: CanvasAnchor,
+        /// Fill value for added pixels
+        fill: u32,
+    },
+    /// Crop every image to the minimum width and height in the Pixa,
+    /// keeping the region selected by `anchor`
+    Crop {
+        /// Part of each image that is kept
+        anchor: CanvasAnchor,
+    },
+    /// Scale every image to exactly `width` x `height`
+    Scale {
+        /// Target width
+        width: u32,
+        /// Target height
+        height: u32,
+    },
+}
+
+/// Vertical placement of each image in [`Pixa::concat_h`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VAlign {
+    /// Top edges coincide
+    #[default]
+    Top,
+    /// Centered vertically
+    Center,
+    /// Bottom edges coincide
+    Bottom,
+}
+
+/// Horizontal placement of each image in [`Pixa::concat_v`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HAlign {
+    /// Left edges coincide
+    #[default]
+    Left,
+    /// Centered horizontally
+    Center,
+    /// Right edges coincide
+    Right,
+}
+
 /// Array of Pix images
 ///
 /// `Pixa` manages a collection of `Pix` images along with optional
@@ -711,6 +763,56 @@ impl Pixa {
         Ok(result)
     }
 
+    /// Group images into a Pixaa by a relation between their boxes.
+    ///
+    /// Images are visited in order.  Each joins the first existing group
+    /// that has a member `m` with `f(box_of_m, box_of_image)` true, and
+    /// otherwise starts a new group.  The grouping is greedy: an image is
+    /// never used to merge two groups that already exist.  Groups appear
+    /// in the order of their first member, and each keeps its boxes.
+    ///
+    /// A typical use is collecting word boxes into text lines with a
+    /// vertical-overlap test.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if some image has no box.
+    pub fn group_by<F>(&self, f: F) -> Result<Pixaa>
+    where
+        F: Fn(&Box, &Box) -> bool,
+    {
+        let n = self.pix.len();
+        if self.boxa.len() < n {
+            return Err(Error::InvalidParameter(format!(
+                "group_by requires a box for every pix ({} boxes, {} pix)",
+                self.boxa.len(),
+                n
+            )));
+        }
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for i in 0..n {
+            let b = self.boxa.get(i).unwrap();
+            match groups
+                .iter_mut()
+                .find(|g| g.iter().any(|&m| f(self.boxa.get(m).unwrap(), b)))
+            {
+                Some(g) => g.push(i),
+                None => groups.push(vec![i]),
+            }
+        }
+
+        let mut pixaa = Pixaa::with_capacity(groups.len());
+        for g in groups {
+            let mut pixa = Pixa::with_capacity(g.len());
+            for i in g {
+                pixa.push_with_box(self.pix[i].clone(), *self.boxa.get(i).unwrap());
+            }
+            pixaa.push(pixa);
+        }
+        Ok(pixaa)
+    }
+
     // ========================================================================
     // Pixel counting functions
     // ========================================================================
@@ -1026,6 +1128,143 @@ impl Pixa {
         Ok(canvas_mut.into())
     }
 
+    /// Place all images side by side in a single row.
+    ///
+    /// Images are placed left-to-right with no spacing and no wrapping
+    /// (see [`Pixa::display_tiled`] for a wrapping layout).  The canvas is
+    /// as tall as the tallest image; shorter images are placed according
+    /// to `align` and the rest of the canvas is set to `fill`.
+    ///
+    /// The canvas depth is taken from the first image. All images should
+    /// have the same depth for correct rendering.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Pixa is empty.
+    pub fn concat_h(&self, align: VAlign, fill: u32) -> Result<Pix> {
+        if self.pix.is_empty() {
+            return Err(Error::NullInput("pixa is empty"));
+        }
+        let width = self.pix.iter().map(|p| p.width()).sum();
+        let height = self.pix.iter().map(|p| p.height()).max().unwrap();
+        let mut canvas = new_filled_canvas(width, height, self.pix[0].depth(), fill)?;
+
+        let mut x = 0;
+        for pix in &self.pix {
+            let y = match align {
+                VAlign::Top => 0,
+                VAlign::Center => (height - pix.height()) / 2,
+                VAlign::Bottom => height - pix.height(),
+            };
+            blit_pix(&mut canvas, pix, x as i32, y as i32);
+            x += pix.width();
+        }
+        Ok(canvas.into())
+    }
+
+    /// Stack all images in a single column.
+    ///
+    /// Images are placed top-to-bottom with no spacing.  The canvas is as
+    /// wide as the widest image; narrower images are placed according to
+    /// `align` and the rest of the canvas is set to `fill`.
+    ///
+    /// The canvas depth is taken from the first image. All images should
+    /// have the same depth for correct rendering.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Pixa is empty.
+    pub fn concat_v(&self, align: HAlign, fill: u32) -> Result<Pix> {
+        if self.pix.is_empty() {
+            return Err(Error::NullInput("pixa is empty"));
+        }
+        let width = self.pix.iter().map(|p| p.width()).max().unwrap();
+        let height = self.pix.iter().map(|p| p.height()).sum();
+        let mut canvas = new_filled_canvas(width, height, self.pix[0].depth(), fill)?;
+
+        let mut y = 0;
+        for pix in &self.pix {
+            let x = match align {
+                HAlign::Left => 0,
+                HAlign::Center => (width - pix.width()) / 2,
+                HAlign::Right => width - pix.width(),
+            };
+            blit_pix(&mut canvas, pix, x as i32, y as i32);
+            y += pix.height();
+        }
+        Ok(canvas.into())
+    }
+
+    /// Pack all images into a single atlas image.
+    ///
+    /// Images are sorted by decreasing height and placed left-to-right on
+    /// horizontal shelves, starting a new shelf below the tallest image of
+    /// the current one when the next image would make the shelf wider than
+    /// `max_width`.  `padding` pixels separate neighboring images; there is
+    /// none around the outside.  The atlas is cropped to the space used and
+    /// the uncovered area is 0.
+    ///
+    /// The atlas depth is taken from the first image. All images should
+    /// have the same depth for correct rendering.
+    ///
+    /// # Returns
+    ///
+    /// The atlas and, in the order of the Pixa, the placement of each
+    /// image in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Pixa is empty or an image is wider than
+    /// `max_width`.
+    pub fn pack_atlas(&self, max_width: u32, padding: u32) -> Result<(Pix, Boxa)> {
+        if self.pix.is_empty() {
+            return Err(Error::NullInput("pixa is empty"));
+        }
+        if let Some(pix) = self.pix.iter().find(|p| p.width() > max_width) {
+            return Err(Error::InvalidParameter(format!(
+                "image width {} exceeds atlas max_width {max_width}",
+                pix.width()
+            )));
+        }
+
+        let mut order: Vec<usize> = (0..self.pix.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.pix[i].height()));
+
+        // Shelf layout
+        let mut places = vec![(0u32, 0u32); self.pix.len()];
+        let (mut x, mut y, mut shelf_h) = (0u32, 0u32, 0u32);
+        let mut atlas_w = 0;
+        for i in order {
+            let (pw, ph) = (self.pix[i].width(), self.pix[i].height());
+            if x > 0 && x + padding + pw > max_width {
+                y += shelf_h + padding;
+                x = 0;
+                shelf_h = 0;
+            }
+            if x > 0 {
+                x += padding;
+            }
+            places[i] = (x, y);
+            x += pw;
+            shelf_h = shelf_h.max(ph);
+            atlas_w = atlas_w.max(x);
+        }
+        let atlas_h = y + shelf_h;
+
+        let mut canvas = Pix::new(atlas_w, atlas_h, self.pix[0].depth())?.to_mut();
+        let mut boxa = Boxa::with_capacity(self.pix.len());
+        for (pix, &(px, py)) in self.pix.iter().zip(&places) {
+            blit_pix(&mut canvas, pix, px as i32, py as i32);
+            boxa.push(Box::new_unchecked(
+                px as i32,
+                py as i32,
+                pix.width() as i32,
+                pix.height() as i32,
+            ));
+        }
+        Ok((canvas.into(), boxa))
+    }
+
     /// Scale each Pix to a target size preserving aspect ratio.
     ///
     /// If `wd` is 0, scale to height `hd` (preserving aspect ratio).
@@ -1062,6 +1301,51 @@ impl Pixa {
         out
     }
 
+    /// Bring all images to a common size.
+    ///
+    /// Produces a new Pixa whose images all have the same dimensions, as
+    /// required by [`Pixa::aligned_stats`] and other stack operations.
+    /// Padding and cropping do not resample; see [`AlignMode`].  Boxes
+    /// are not carried over.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Pixa is empty or a `Scale` target
+    /// dimension is 0.
+    pub fn align_sizes(&self, mode: AlignMode) -> Result<Pixa> {
+        if self.pix.is_empty() {
+            return Err(Error::InvalidParameter("pixa is empty".into()));
+        }
+        let mut out = Pixa::with_capacity(self.len());
+        match mode {
+            AlignMode::Pad { anchor, fill } => {
+                let wd = self.pix.iter().map(|p| p.width()).max().unwrap();
+                let hd = self.pix.iter().map(|p| p.height()).max().unwrap();
+                for pix in &self.pix {
+                    out.push(pix.resize_canvas(wd, hd, anchor, fill)?);
+                }
+            }
+            AlignMode::Crop { anchor } => {
+                let wd = self.pix.iter().map(|p| p.width()).min().unwrap();
+                let hd = self.pix.iter().map(|p| p.height()).min().unwrap();
+                for pix in &self.pix {
+                    out.push(pix.resize_canvas(wd, hd, anchor, 0)?);
+                }
+            }
+            AlignMode::Scale { width, height } => {
+                if width == 0 || height == 0 {
+                    return Err(Error::InvalidParameter(
+                        "scale target dimensions must be > 0".into(),
+                    ));
+                }
+                for pix in &self.pix {
+                    out.push(scale_pix_to_size(pix, width, height));
+                }
+            }
+        }
+        Ok(out)
+    }
+
     /// Scale each Pix to `tile_width` pixels wide (maintaining aspect ratio),
     /// optionally add a border, convert to `outdepth`, then composite into a
     /// tiled layout with `ncols` columns.
@@ -1174,6 +1458,88 @@ impl Pixa {
         Ok(dst.into())
     }
 
+    /// Tile images into a labeled grid for debugging sheets.
+    ///
+    /// Images are laid out row-major in `ncols` columns.  Every cell has
+    /// the size of the largest image plus `cell_pad` on each side, with a
+    /// strip of `label_height` pixels under the image in which
+    /// `labels[i]` is drawn, centered, with the built-in bitmap font
+    /// (see [`PixMut::draw_text`]).  The glyph scale is chosen to fit the
+    /// strip.  Fewer labels than images is allowed; the remaining cells
+    /// are unlabeled.
+    ///
+    /// Each image is converted with [`Pix::to_rgb`] (binary images are
+    /// inverted first, so their foreground stays black) and centered
+    /// horizontally in its cell; the result is 32 bpp on white with black
+    /// labels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pixa is empty, `ncols` is 0, or there are
+    /// more labels than images.
+    pub fn montage(
+        &self,
+        ncols: u32,
+        labels: &crate::core::Sarray,
+        cell_pad: u32,
+        label_height: u32,
+    ) -> Result<Pix> {
+        if self.pix.is_empty() {
+            return Err(Error::NullInput("pixa is empty"));
+        }
+        if ncols == 0 {
+            return Err(Error::InvalidParameter("ncols must be > 0".into()));
+        }
+        if labels.len() > self.pix.len() {
+            return Err(Error::InvalidParameter(format!(
+                "{} labels for {} images",
+                labels.len(),
+                self.pix.len()
+            )));
+        }
+
+        let n = self.pix.len() as u32;
+        let ncols = ncols.min(n);
+        let nrows = n.div_ceil(ncols);
+        let max_w = self.pix.iter().map(|p| p.width()).max().unwrap_or(0);
+        let max_h = self.pix.iter().map(|p| p.height()).max().unwrap_or(0);
+        let cell_w = max_w + 2 * cell_pad;
+        let cell_h = max_h + label_height + 2 * cell_pad;
+        // Largest glyph scale whose 8-pixel cell fits in the label strip
+        let scale = label_height / 8;
+
+        let mut canvas = Pix::new(ncols * cell_w, nrows * cell_h, PixelDepth::Bit32)?.to_mut();
+        canvas.set_all_arbitrary(0xffffffff)?;
+        for (i, pix) in self.pix.iter().enumerate() {
+            let cx = (i as u32 % ncols) * cell_w;
+            let cy = (i as u32 / ncols) * cell_h;
+            // Binary images keep their usual black-on-white look
+            let rgb = if pix.depth() == PixelDepth::Bit1 && !pix.has_colormap() {
+                pix.invert().to_rgb()?
+            } else {
+                pix.to_rgb()?
+            };
+            let x = cx + cell_pad + (max_w - rgb.width()) / 2;
+            blit_pix(&mut canvas, &rgb, x as i32, (cy + cell_pad) as i32);
+
+            if let Some(label) = labels.get(i)
+                && scale > 0
+            {
+                let text_w = 8 * scale * label.chars().count() as u32;
+                let tx = cx as i32 + (cell_w as i32 - text_w as i32) / 2;
+                let ty = cy + cell_pad + max_h + (label_height - 8 * scale) / 2;
+                canvas.draw_text(
+                    label,
+                    tx.max(cx as i32),
+                    ty as i32,
+                    crate::core::pix::Color::BLACK,
+                    scale,
+                )?;
+            }
+        }
+        Ok(canvas.into())
+    }
+
     /// Create a deep copy of this Pixa
     ///
     /// Unlike `clone()` which shares Pix data via Arc, this creates
@@ -1261,6 +1627,19 @@ impl Pixa {
 
 use crate::core::box_::{compare_relation, compare_relation_i64};
 
+/// Create a canvas with every pixel set to `fill`.
+fn new_filled_canvas(width: u32, height: u32, depth: PixelDepth, fill: u32) -> Result<PixMut> {
+    let mut canvas = Pix::new(width, height, depth)?.to_mut();
+    if fill != 0 {
+        for y in 0..height {
+            for x in 0..width {
+                canvas.set_pixel_unchecked(x, y, fill);
+            }
+        }
+    }
+    Ok(canvas)
+}
+
 /// Copy pixels from `src` onto `dst` at offset (ox, oy).
 ///
 /// Uses per-pixel get/set; sufficient for small component images.
@@ -1616,6 +1995,57 @@ impl Pixaa {
         Ok(())
     }
 
+    /// Render the Pixaa as a grid of grids.
+    ///
+    /// Each non-empty inner Pixa is tiled into a single row with
+    /// [`Pixa::display_tiled`] using `col_spacing` between images, and the
+    /// rows are stacked top to bottom with `row_spacing` of background
+    /// between them, so the grouping is visible at a glance. Empty inner
+    /// Pixa are skipped.
+    ///
+    /// The canvas depth is taken from the first image of the first
+    /// non-empty Pixa; all images should share that depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `col_spacing` - Pixels between images within a row
+    /// * `row_spacing` - Pixels between consecutive rows
+    /// * `background` - Background pixel value (0 for black, 255 for white)
+    pub fn display(&self, col_spacing: u32, row_spacing: u32, background: u32) -> Result<Pix> {
+        let rows: Vec<Pix> = self
+            .pixas
+            .iter()
+            .filter(|pixa| !pixa.is_empty())
+            .map(|pixa| pixa.display_tiled(u32::MAX, background, col_spacing))
+            .collect::<Result<_>>()?;
+        if rows.is_empty() {
+            return Err(Error::NullInput("pixaa has no images"));
+        }
+
+        let depth = rows[0].depth();
+        let total_width = rows.iter().map(|r| r.width()).max().unwrap_or(0);
+        let total_height =
+            rows.iter().map(|r| r.height()).sum::<u32>() + row_spacing * (rows.len() as u32 - 1);
+
+        let canvas = Pix::new(total_width, total_height, depth)?;
+        let mut canvas_mut = canvas.try_into_mut().unwrap_or_else(|p: Pix| p.to_mut());
+        if background != 0 {
+            for y in 0..total_height {
+                for x in 0..total_width {
+                    canvas_mut.set_pixel_unchecked(x, y, background);
+                }
+            }
+        }
+
+        let mut cy: u32 = 0;
+        for row in &rows {
+            blit_pix(&mut canvas_mut, row, 0, cy as i32);
+            cy += row.height() + row_spacing;
+        }
+
+        Ok(canvas_mut.into())
+    }
+
     /// Create an iterator over Pixa
     pub fn iter(&self) -> impl Iterator<Item = &Pixa> {
         self.pixas.iter()
@@ -210,6 +210,42 @@ impl Pixa {
         let indicator: Vec<bool> = metrics.iter().map(|&m| matches(m, thresh, sel)).collect();
         self.select_with_indicator(&indicator)
     }
+
+    // --------------------------------------------------------------------
+    // Near-duplicate removal
+    // --------------------------------------------------------------------
+
+    /// Drop images that are near-duplicates of an earlier image.
+    ///
+    /// Each image is reduced to its 64-bit [`Pix::average_hash`]. An image
+    /// is dropped when the Hamming distance between its hash and that of
+    /// any previously kept image is less than `threshold`; a `threshold` of
+    /// 0 keeps everything.
+    ///
+    /// Returns the deduplicated Pixa (boxes preserved) and the indices of
+    /// the kept images in the source Pixa.
+    pub fn dedupe_similar(&self, threshold: u32) -> (Self, Vec<usize>) {
+        let mut kept_hashes: Vec<u64> = Vec::new();
+        let mut kept = Vec::new();
+        for (i, pix) in self.pix_slice().iter().enumerate() {
+            let hash = pix.average_hash();
+            let is_dup = kept_hashes
+                .iter()
+                .any(|&h| (h ^ hash).count_ones() < threshold);
+            if !is_dup {
+                kept_hashes.push(hash);
+                kept.push(i);
+            }
+        }
+        let mut out = Pixa::with_capacity(kept.len());
+        for &i in &kept {
+            out.push_with_box(
+                self.pix_slice()[i].clone(),
+                self.boxa().get(i).copied().unwrap_or_default(),
+            );
+        }
+        (out, kept)
+    }
 }
 
 // ------------------------------------------------------------------------
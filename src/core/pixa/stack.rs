@@ -0,0 +1,129 @@
+//! Pixel-wise reduction of an image stack
+//!
+//! Combines a Pixa of aligned, same-size images into a single image by
+//! taking the mean, median, maximum or minimum of each pixel across the
+//! stack, e.g. to denoise a burst of exposures or to build a "lightest
+//! pixel" composite.  For the general 8 bpp statistics, including the
+//! mode, see [`Pixa::aligned_stats`].
+//!
+//! 32 bpp images are reduced per channel, so the result need not be a
+//! color present in any input image.
+
+use crate::core::error::{Error, Result};
+use crate::core::{Pix, PixelDepth, pixel};
+
+use super::Pixa;
+
+/// Pixel-wise mean of a stack of images, rounded to the nearest value.
+///
+/// All images must have the same size and depth, 8 bpp grayscale or
+/// 32 bpp RGB(A), without colormaps.
+///
+/// # Errors
+///
+/// Returns an error if the Pixa is empty, the images differ in size or
+/// depth, or the depth is not supported.
+pub fn pixa_mean(pixa: &Pixa) -> Result<Pix> {
+    stack_reduce(pixa, |vals| {
+        let sum: u32 = vals.iter().map(|&v| v as u32).sum();
+        let n = vals.len() as u32;
+        ((sum + n / 2) / n) as u8
+    })
+}
+
+/// Pixel-wise median of a stack of images.
+///
+/// For an even number of images the mean of the two middle values is
+/// used, rounded to the nearest value.  Requirements are as for
+/// [`pixa_mean`].
+///
+/// # Errors
+///
+/// Returns an error if the Pixa is empty, the images differ in size or
+/// depth, or the depth is not supported.
+pub fn pixa_median(pixa: &Pixa) -> Result<Pix> {
+    stack_reduce(pixa, |vals| {
+        vals.sort_unstable();
+        let n = vals.len();
+        if !n.is_multiple_of(2) {
+            vals[n / 2]
+        } else {
+            (vals[n / 2 - 1] as u32 + vals[n / 2] as u32).div_ceil(2) as u8
+        }
+    })
+}
+
+/// Pixel-wise maximum of a stack of images.
+///
+/// Requirements are as for [`pixa_mean`].
+///
+/// # Errors
+///
+/// Returns an error if the Pixa is empty, the images differ in size or
+/// depth, or the depth is not supported.
+pub fn pixa_max(pixa: &Pixa) -> Result<Pix> {
+    stack_reduce(pixa, |vals| vals.iter().copied().max().unwrap_or(0))
+}
+
+/// Pixel-wise minimum of a stack of images.
+///
+/// Requirements are as for [`pixa_mean`].
+///
+/// # Errors
+///
+/// Returns an error if the Pixa is empty, the images differ in size or
+/// depth, or the depth is not supported.
+pub fn pixa_min(pixa: &Pixa) -> Result<Pix> {
+    stack_reduce(pixa, |vals| vals.iter().copied().min().unwrap_or(0))
+}
+
+/// Reduce each pixel (each channel at 32 bpp) of the stack with `reduce`,
+/// which receives one value per image.
+fn stack_reduce(pixa: &Pixa, reduce: impl Fn(&mut [u8]) -> u8) -> Result<Pix> {
+    let images = pixa.pix_slice();
+    let Some(first) = images.first() else {
+        return Err(Error::NullInput("pixa is empty"));
+    };
+    let depth = first.depth();
+    let (w, h) = (first.width(), first.height());
+    for pix in images {
+        if pix.depth() != depth || pix.width() != w || pix.height() != h {
+            return Err(Error::InvalidParameter(
+                "all pix must have identical dimensions and depth".into(),
+            ));
+        }
+        if !matches!(depth, PixelDepth::Bit8 | PixelDepth::Bit32) || pix.has_colormap() {
+            return Err(Error::UnsupportedDepth(depth.bits()));
+        }
+    }
+
+    let mut out = Pix::new(w, h, depth)?.to_mut();
+    out.set_spp(first.spp());
+    let mut vals = vec![0u8; images.len()];
+    let mut rgba = vec![[0u8; 4]; images.len()];
+    for y in 0..h {
+        for x in 0..w {
+            let val = if depth == PixelDepth::Bit8 {
+                for (v, pix) in vals.iter_mut().zip(images) {
+                    *v = pix.get_pixel_unchecked(x, y) as u8;
+                }
+                reduce(&mut vals) as u32
+            } else {
+                for (p, pix) in rgba.iter_mut().zip(images) {
+                    let (r, g, b, a) = pixel::extract_rgba(pix.get_pixel_unchecked(x, y));
+                    *p = [r, g, b, a];
+                }
+                let mut channels = [0u8; 4];
+                for (c, channel) in channels.iter_mut().enumerate() {
+                    for (v, p) in vals.iter_mut().zip(&rgba) {
+                        *v = p[c];
+                    }
+                    *channel = reduce(&mut vals);
+                }
+                pixel::compose_rgba(channels[0], channels[1], channels[2], channels[3])
+            };
+            out.set_pixel_unchecked(x, y, val);
+        }
+    }
+    Ok(out.into())
+}
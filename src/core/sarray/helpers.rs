@@ -4,8 +4,11 @@
 //!
 //! - `stringCompareLexical` -> [`string_compare_lexical`]
 //! - `splitStringToParagraphs` -> [`split_string_to_paragraphs`]
+//!
+//! Also: [`string_compare_natural`] for natural sort order.
 
 use crate::core::sarray::Sarray;
+use std::cmp::Ordering;
 
 /// How [`split_string_to_paragraphs`] decides where one paragraph ends
 /// and the next begins.
@@ -43,6 +46,47 @@ pub fn string_compare_lexical(str1: &str, str2: &str) -> i32 {
     if b1.len() > b2.len() { 1 } else { 0 }
 }
 
+/// Natural ("human") ordering of two strings.
+///
+/// Runs of ASCII digits are compared by numeric value, so `"img2"` sorts
+/// before `"img10"`; everything else is compared character by character.
+/// Numbers of any length are supported.  When two strings differ only in
+/// leading zeros (`"a01"` vs `"a1"`), plain lexical order breaks the tie
+/// so the ordering is total.
+pub fn string_compare_natural(str1: &str, str2: &str) -> Ordering {
+    let (b1, b2) = (str1.as_bytes(), str2.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    while i < b1.len() && j < b2.len() {
+        if b1[i].is_ascii_digit() && b2[j].is_ascii_digit() {
+            let end1 = i + b1[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+            let end2 = j + b2[j..].iter().take_while(|c| c.is_ascii_digit()).count();
+            fn trim(run: &[u8]) -> &[u8] {
+                let zeros = run.iter().take_while(|&&c| c == b'0').count();
+                &run[zeros..]
+            }
+            let (n1, n2) = (trim(&b1[i..end1]), trim(&b2[j..end2]));
+            // Without leading zeros, a longer run is a larger number
+            let ord = n1.len().cmp(&n2.len()).then_with(|| n1.cmp(n2));
+            if ord != Ordering::Equal {
+                return ord;
+            }
+            (i, j) = (end1, end2);
+        } else {
+            // Compare whole characters so multi-byte text stays in
+            // code point order
+            let c1 = str1[i..].chars().next().unwrap();
+            let c2 = str2[j..].chars().next().unwrap();
+            if c1 != c2 {
+                return c1.cmp(&c2);
+            }
+            (i, j) = (i + c1.len_utf8(), j + c2.len_utf8());
+        }
+    }
+    (b1.len() - i)
+        .cmp(&(b2.len() - j))
+        .then_with(|| str1.cmp(str2))
+}
+
 /// Split `textstr` into paragraph strings, using `split` to choose
 /// the paragraph boundary.
 ///
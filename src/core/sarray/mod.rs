@@ -7,7 +7,9 @@
 pub mod helpers;
 pub mod serial;
 
-pub use helpers::{ParagraphSplit, split_string_to_paragraphs, string_compare_lexical};
+pub use helpers::{
+    ParagraphSplit, split_string_to_paragraphs, string_compare_lexical, string_compare_natural,
+};
 
 use crate::core::error::{Error, Result};
 use std::collections::HashSet;
@@ -646,6 +648,26 @@ impl Sarray {
         self.data.sort_by(|a, b| b.cmp(a));
     }
 
+    /// Sort strings in natural order
+    ///
+    /// Embedded numbers are ordered by value, so page files such as
+    /// `"img2"` and `"img10"` come out in sequence.  See
+    /// [`string_compare_natural`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use leptonica::core::Sarray;
+    ///
+    /// let mut sa = Sarray::from_str_slice(&["img2", "img10", "img1"]);
+    /// sa.sort_natural();
+    /// assert_eq!(sa.get(0), Some("img1"));
+    /// assert_eq!(sa.get(2), Some("img10"));
+    /// ```
+    pub fn sort_natural(&mut self) {
+        self.data.sort_by(|a, b| string_compare_natural(a, b));
+    }
+
     /// Sort by a custom comparator
     pub fn sort_by<F>(&mut self, compare: F)
     where
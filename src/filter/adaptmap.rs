@@ -22,7 +22,7 @@
 //! let enhanced = contrast_norm_simple(&pix)?;
 //! ```
 
-use crate::core::{Pix, PixelDepth, pixel};
+use crate::core::{FPix, Pix, PixelDepth, pixel};
 use crate::filter::{FilterError, FilterResult};
 
 // ============================================================================
@@ -2019,6 +2019,66 @@ pub fn apply_variable_gray_map(pix: &Pix, pixg: &Pix, target: u32) -> FilterResu
     Ok(out_mut.into())
 }
 
+/// Remap an image against a precomputed floating-point background.
+///
+/// Each pixel is divided by the background value at the same location and
+/// rescaled so that the background maps to `target`: for a source value `s`
+/// and background `b` the output is `s * target / b`, clamped to [0, 255].
+/// For 32bpp RGB each channel is divided by the same background; alpha is
+/// kept. Background values below 1.0 are treated as 1.0.
+///
+/// Computing the background once and applying it with this function lets
+/// the same illumination correction be reused across several images of
+/// the same scene.
+///
+/// # Arguments
+///
+/// * `pix` - 8bpp grayscale or 32bpp RGB source image, without colormap
+/// * `bg` - Background estimate in pixel units (same dimensions as `pix`)
+/// * `target` - Output value for background pixels (e.g. 200)
+pub fn background_remap(pix: &Pix, bg: &FPix, target: u8) -> FilterResult<Pix> {
+    let depth = pix.depth();
+    if depth != PixelDepth::Bit8 && depth != PixelDepth::Bit32 {
+        return Err(FilterError::UnsupportedDepth {
+            expected: "8 or 32 bpp",
+            actual: depth.bits(),
+        });
+    }
+    if pix.has_colormap() {
+        return Err(FilterError::InvalidParameters(
+            "colormapped images not supported".into(),
+        ));
+    }
+    let w = pix.width();
+    let h = pix.height();
+    if bg.dimensions() != (w, h) {
+        return Err(FilterError::InvalidParameters(
+            "pix and background must have the same dimensions".into(),
+        ));
+    }
+
+    let target_f = target as f32;
+    let remap = |s: u8, factor: f32| ((s as f32) * factor + 0.5).min(255.0) as u8;
+
+    let mut out_mut = pix.create_template().try_into_mut().unwrap();
+    for y in 0..h {
+        let bg_row = bg.row(y);
+        for x in 0..w {
+            let factor = target_f / bg_row[x as usize].max(1.0);
+            let val = pix.get_pixel_unchecked(x, y);
+            let vald = if depth == PixelDepth::Bit8 {
+                remap(val as u8, factor) as u32
+            } else {
+                let (r, g, b, a) = pixel::extract_rgba(val);
+                pixel::compose_rgba(remap(r, factor), remap(g, factor), remap(b, factor), a)
+            };
+            out_mut.set_pixel_unchecked(x, y, vald);
+        }
+    }
+
+    Ok(out_mut.into())
+}
+
 /// Global RGB normalization using per-channel TRC mapping.
 ///
 /// C版: `pixGlobalNormRGB()` in `adaptmap.c`
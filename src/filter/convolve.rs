@@ -5,11 +5,63 @@
 use crate::core::{FPix, Pix, PixelDepth, pix::RgbComponent, pixel};
 use crate::filter::{FilterError, FilterResult, Kernel};
 
+/// Border handling for convolution
+///
+/// Determines the value used for kernel taps that fall outside the image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BorderMode {
+    /// Pixels outside the image are 0.
+    Zero,
+    /// Replicate the nearest edge pixel (`a a | a b c | c c`).
+    #[default]
+    Clamp,
+    /// Reflect about the edge pixel without repeating it (`c b | a b c | b a`).
+    Mirror,
+    /// Wrap around periodically (`b c | a b c | a b`).
+    Wrap,
+}
+
+impl BorderMode {
+    /// Map a possibly out-of-range coordinate into `0..n`.
+    ///
+    /// Returns `None` when the tap should contribute 0 (`Zero` mode).
+    #[inline]
+    fn resolve(self, i: i32, n: i32) -> Option<u32> {
+        if (0..n).contains(&i) {
+            return Some(i as u32);
+        }
+        match self {
+            BorderMode::Zero => None,
+            BorderMode::Clamp => Some(i.clamp(0, n - 1) as u32),
+            BorderMode::Mirror => {
+                if n == 1 {
+                    return Some(0);
+                }
+                let period = 2 * (n - 1);
+                let m = i.rem_euclid(period);
+                Some(if m < n { m } else { period - m } as u32)
+            }
+            BorderMode::Wrap => Some(i.rem_euclid(n) as u32),
+        }
+    }
+}
+
 /// Convolve an 8-bit grayscale image with a kernel
 ///
 /// Uses replicate (clamp) border handling: pixels outside the image boundary
 /// are treated as having the same value as the nearest edge pixel.
 pub fn convolve_gray(pix: &Pix, kernel: &Kernel) -> FilterResult<Pix> {
+    convolve_gray_with_border(pix, kernel, BorderMode::Clamp)
+}
+
+/// Convolve an 8-bit grayscale image with a kernel and explicit border mode
+///
+/// Kernel taps outside the image are resolved according to `border`.
+pub fn convolve_gray_with_border(
+    pix: &Pix,
+    kernel: &Kernel,
+    border: BorderMode,
+) -> FilterResult<Pix> {
     check_grayscale(pix)?;
 
     let w = pix.width();
@@ -27,13 +79,13 @@ pub fn convolve_gray(pix: &Pix, kernel: &Kernel) -> FilterResult<Pix> {
             let mut sum = 0.0f32;
 
             for ky in 0..kh {
+                let Some(sy) = border.resolve(y as i32 + (ky as i32 - kcy), h as i32) else {
+                    continue;
+                };
                 for kx in 0..kw {
-                    let sx = x as i32 + (kx as i32 - kcx);
-                    let sy = y as i32 + (ky as i32 - kcy);
-
-                    // Clamp to image boundaries (replicate border)
-                    let sx = sx.clamp(0, w as i32 - 1) as u32;
-                    let sy = sy.clamp(0, h as i32 - 1) as u32;
+                    let Some(sx) = border.resolve(x as i32 + (kx as i32 - kcx), w as i32) else {
+                        continue;
+                    };
 
                     let pixel = pix.get_pixel_unchecked(sx, sy) as f32;
                     let k = kernel.get(kx, ky).unwrap_or(0.0);
@@ -54,6 +106,18 @@ pub fn convolve_gray(pix: &Pix, kernel: &Kernel) -> FilterResult<Pix> {
 /// Uses replicate (clamp) border handling: pixels outside the image boundary
 /// are treated as having the same value as the nearest edge pixel.
 pub fn convolve_color(pix: &Pix, kernel: &Kernel) -> FilterResult<Pix> {
+    convolve_color_with_border(pix, kernel, BorderMode::Clamp)
+}
+
+/// Convolve a 32-bit color image with a kernel and explicit border mode
+///
+/// Each of R, G, B and A is convolved independently; kernel taps outside
+/// the image are resolved according to `border`.
+pub fn convolve_color_with_border(
+    pix: &Pix,
+    kernel: &Kernel,
+    border: BorderMode,
+) -> FilterResult<Pix> {
     check_color(pix)?;
 
     let w = pix.width();
@@ -75,12 +139,13 @@ pub fn convolve_color(pix: &Pix, kernel: &Kernel) -> FilterResult<Pix> {
             let mut sum_a = 0.0f32;
 
             for ky in 0..kh {
+                let Some(sy) = border.resolve(y as i32 + (ky as i32 - kcy), h as i32) else {
+                    continue;
+                };
                 for kx in 0..kw {
-                    let sx = x as i32 + (kx as i32 - kcx);
-                    let sy = y as i32 + (ky as i32 - kcy);
-
-                    let sx = sx.clamp(0, w as i32 - 1) as u32;
-                    let sy = sy.clamp(0, h as i32 - 1) as u32;
+                    let Some(sx) = border.resolve(x as i32 + (kx as i32 - kcx), w as i32) else {
+                        continue;
+                    };
 
                     let pixel = pix.get_pixel_unchecked(sx, sy);
                     let (r, g, b, a) = pixel::extract_rgba(pixel);
@@ -108,9 +173,14 @@ pub fn convolve_color(pix: &Pix, kernel: &Kernel) -> FilterResult<Pix> {
 
 /// Convolve an image (auto-dispatch based on depth)
 pub fn convolve(pix: &Pix, kernel: &Kernel) -> FilterResult<Pix> {
+    convolve_with_border(pix, kernel, BorderMode::Clamp)
+}
+
+/// Convolve an image with an explicit border mode (auto-dispatch based on depth)
+pub fn convolve_with_border(pix: &Pix, kernel: &Kernel, border: BorderMode) -> FilterResult<Pix> {
     match pix.depth() {
-        PixelDepth::Bit8 => convolve_gray(pix, kernel),
-        PixelDepth::Bit32 => convolve_color(pix, kernel),
+        PixelDepth::Bit8 => convolve_gray_with_border(pix, kernel, border),
+        PixelDepth::Bit32 => convolve_color_with_border(pix, kernel, border),
         _ => Err(FilterError::UnsupportedDepth {
             expected: "8 or 32 bpp",
             actual: pix.depth().bits(),
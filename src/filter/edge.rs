@@ -46,6 +46,41 @@ pub fn sobel_edge(pix: &Pix, orientation: EdgeOrientation) -> FilterResult<Pix>
     }
 }
 
+/// Signed 3x3 Sobel gradients with replicated borders
+///
+/// Returns `(gx, gy)` in row-major order, one value per pixel, without
+/// the normalization of [`sobel_edge`].  `gx` increases to the right and
+/// `gy` increases downward, following image rows.
+///
+/// `pix` must be 8 bpp.
+pub(crate) fn sobel_gradients(pix: &Pix) -> (Vec<f32>, Vec<f32>) {
+    let w = pix.width() as i32;
+    let h = pix.height() as i32;
+    let at = |x: i32, y: i32| {
+        pix.get_pixel_unchecked(x.clamp(0, w - 1) as u32, y.clamp(0, h - 1) as u32) as f32
+    };
+    let n = (w * h) as usize;
+    let mut gx = Vec::with_capacity(n);
+    let mut gy = Vec::with_capacity(n);
+    for y in 0..h {
+        for x in 0..w {
+            gx.push(
+                at(x + 1, y - 1) + 2.0 * at(x + 1, y) + at(x + 1, y + 1)
+                    - at(x - 1, y - 1)
+                    - 2.0 * at(x - 1, y)
+                    - at(x - 1, y + 1),
+            );
+            gy.push(
+                at(x - 1, y + 1) + 2.0 * at(x, y + 1) + at(x + 1, y + 1)
+                    - at(x - 1, y - 1)
+                    - 2.0 * at(x, y - 1)
+                    - at(x + 1, y - 1),
+            );
+        }
+    }
+    (gx, gy)
+}
+
 /// Apply Laplacian edge detection
 pub fn laplacian_edge(pix: &Pix) -> FilterResult<Pix> {
     check_grayscale(pix)?;
@@ -53,6 +88,161 @@ pub fn laplacian_edge(pix: &Pix) -> FilterResult<Pix> {
     convolve_and_abs(pix, &kernel)
 }
 
+/// Measure image sharpness (focus) as the variance of the Laplacian
+///
+/// Computes the population variance of the [`laplacian_edge`] response
+/// over all pixels.  Sharp images have strong, localized second
+/// derivatives and score high; blurred images score low.
+///
+/// The value is scale-dependent: it varies with image content, contrast
+/// and resolution, so it is only meaningful when comparing versions of
+/// the same scene (e.g. picking the sharpest frame or detecting blur
+/// against a threshold tuned for a given capture setup).
+///
+/// # Arguments
+/// * `pix` - 8 bpp grayscale image
+pub fn focus_measure(pix: &Pix) -> FilterResult<f64> {
+    let lap = laplacian_edge(pix)?;
+    let n = lap.width() as f64 * lap.height() as f64;
+    let mut sum = 0.0f64;
+    let mut sumsq = 0.0f64;
+    for y in 0..lap.height() {
+        for x in 0..lap.width() {
+            let v = lap.get_pixel_unchecked(x, y) as f64;
+            sum += v;
+            sumsq += v * v;
+        }
+    }
+    let mean = sum / n;
+    Ok((sumsq / n - mean * mean).max(0.0))
+}
+
+/// Estimate the standard deviation of additive Gaussian noise
+///
+/// Uses Immerkær's noise-estimation kernel
+///
+/// ```text
+///    1 -2  1
+///   -2  4 -2
+///    1 -2  1
+/// ```
+///
+/// which is the difference of two Laplacians and so cancels smooth image
+/// structure, leaving mostly noise.  For Gaussian noise of deviation
+/// sigma the response has deviation 6 * sigma; the estimate is taken from
+/// the median absolute response over the interior pixels (median
+/// absolute deviation scaled by 1 / 0.6745), which is robust to the
+/// minority of pixels that lie on edges.
+///
+/// Returns 0.0 for a noise-free smooth image.  The estimate includes the
+/// quantization noise of 8-bit samples (about 0.3).
+///
+/// # Arguments
+/// * `pix` - 8 bpp grayscale image, at least 3x3
+pub fn estimate_noise(pix: &Pix) -> FilterResult<f64> {
+    check_grayscale(pix)?;
+    let (w, h) = (pix.width(), pix.height());
+    if w < 3 || h < 3 {
+        return Err(FilterError::InvalidParameters(format!(
+            "image must be at least 3x3 (got {w}x{h})"
+        )));
+    }
+
+    // Histogram of |response|; the largest possible value is 16 * 255
+    let mut hist = vec![0u64; 16 * 255 + 1];
+    for y in 1..h - 1 {
+        for x in 1..w - 1 {
+            let p = |dx: u32, dy: u32| pix.get_pixel_unchecked(x + dx - 1, y + dy - 1) as i32;
+            let corners = p(0, 0) + p(2, 0) + p(0, 2) + p(2, 2);
+            let edges = p(1, 0) + p(0, 1) + p(2, 1) + p(1, 2);
+            let r = corners - 2 * edges + 4 * p(1, 1);
+            hist[r.unsigned_abs() as usize] += 1;
+        }
+    }
+
+    // Median, interpolated within its histogram bin
+    let half = ((w - 2) as f64 * (h - 2) as f64) / 2.0;
+    let mut below = 0.0;
+    let mut median = 0.0;
+    for (v, &count) in hist.iter().enumerate() {
+        let count = count as f64;
+        if below + count >= half {
+            median = v as f64 - 0.5 + (half - below) / count;
+            break;
+        }
+        below += count;
+    }
+    Ok(median.max(0.0) / (6.0 * 0.6745))
+}
+
+/// Find the dominant line orientation from a gradient-direction histogram
+///
+/// Computes Sobel gradients at each interior pixel and accumulates the
+/// gradient direction, folded into [0, 180), into `nbins` bins weighted
+/// by gradient magnitude.  The peak bin is refined with the weighted
+/// mean of its two (circular) neighbours.  Since gradients are normal to
+/// strokes, the result is rotated by 90 degrees to give the orientation
+/// of the lines themselves.
+///
+/// Complements text skew detection for images with long strokes or
+/// ruled lines that are far from horizontal.
+///
+/// # Arguments
+/// * `pix` - 8 bpp grayscale image
+/// * `nbins` - Number of histogram bins over 180 degrees (>= 2)
+///
+/// # Returns
+/// Line angle in degrees in [0, 180), measured counter-clockwise from
+/// the positive x axis with y pointing up (0 = horizontal, 90 = vertical).
+/// Returns 0.0 if the image has no gradients.
+pub fn dominant_orientation(pix: &Pix, nbins: u32) -> FilterResult<f32> {
+    check_grayscale(pix)?;
+    if nbins < 2 {
+        return Err(FilterError::InvalidParameters(
+            "nbins must be >= 2".to_string(),
+        ));
+    }
+
+    let w = pix.width() as usize;
+    let h = pix.height() as usize;
+    let nb = nbins as usize;
+    let binsize = 180.0 / nbins as f64;
+    let mut hist = vec![0.0f64; nb];
+    let (sx, sy) = sobel_gradients(pix);
+
+    for y in 1..h.saturating_sub(1) {
+        for x in 1..w.saturating_sub(1) {
+            let gx = sx[y * w + x] as f64;
+            // Image rows increase downward; flip so that y points up
+            let gy = -sy[y * w + x] as f64;
+            let mag = gx.hypot(gy);
+            if mag == 0.0 {
+                continue;
+            }
+            let angle = gy.atan2(gx).to_degrees().rem_euclid(180.0);
+            let bin = ((angle / binsize) as usize).min(nb - 1);
+            hist[bin] += mag;
+        }
+    }
+
+    let (peak, &peakval) = hist
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .unwrap();
+    if peakval == 0.0 {
+        return Ok(0.0);
+    }
+
+    // Refine with the neighbouring bins, wrapping around at 180 degrees
+    let left = hist[(peak + nb - 1) % nb];
+    let right = hist[(peak + 1) % nb];
+    let offset = (right - left) / (left + peakval + right);
+    let grad_angle = (peak as f64 + 0.5 + offset) * binsize;
+
+    Ok((grad_angle + 90.0).rem_euclid(180.0) as f32)
+}
+
 /// Sobel-specific convolution: convolve bordered image, take absolute
 /// value, and normalize by >>3 (divide by 8).
 ///
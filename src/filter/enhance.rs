@@ -138,24 +138,12 @@ pub fn equalize_trc(pix: &Pix, fract: f32, factor: u32) -> FilterResult<TrcLut>
         return Err(FilterError::InvalidParameters("factor must be >= 1".into()));
     }
 
-    let hist = pix.gray_histogram(factor)?;
-    let sum: f32 = hist.sum().unwrap_or(0.0);
-
-    // Handle empty histogram case (sum == 0.0) by returning identity mapping
-    if sum == 0.0 || !sum.is_normal() {
-        let mut lut = [0u8; 256];
-        for (i, entry) in lut.iter_mut().enumerate() {
-            *entry = i as u8;
-        }
-        return Ok(lut);
-    }
-
-    let partial = hist.partial_sums();
+    // An empty histogram yields the identity mapping
+    let target = pix.gray_histogram(factor)?.make_equalization_lut();
 
     let mut lut = [0u8; 256];
     for (iin, entry) in lut.iter_mut().enumerate() {
-        let cumul = partial.get(iin).unwrap_or(0.0);
-        let itarg = (255.0 * cumul / sum + 0.5) as i32;
+        let itarg = target[iin] as i32;
         let iout = iin as i32 + (fract * (itarg - iin as i32) as f32) as i32;
         *entry = iout.clamp(0, 255) as u8;
     }
@@ -331,6 +319,132 @@ pub fn trc_map_general(
     Ok(())
 }
 
+// =========================================================================
+//  Explicit lookup-table remapping
+// =========================================================================
+
+/// Remap an 8 or 32 bpp image through a 256-entry lookup table.
+///
+/// Every 8 bpp value `v` becomes `lut[v]`; for 32 bpp images the same table
+/// is applied to each of R, G and B, and alpha is preserved.  For
+/// colormapped images the colormap entries are remapped instead of the
+/// pixel indices.  This is the primitive underneath the gamma and contrast
+/// curves, exposed for custom curves.
+///
+/// Returns a new image; the input is unchanged.
+pub fn apply_lut(pix: &Pix, lut: &TrcLut) -> FilterResult<Pix> {
+    apply_lut_rgb(pix, lut, lut, lut)
+}
+
+/// Remap a 32 bpp image through separate R, G and B lookup tables.
+///
+/// Like [`apply_lut`], but each color channel uses its own table.  Alpha is
+/// preserved.  For 8 bpp grayscale input only `lut_r` is used (all three
+/// tables are used for colormap entries).
+pub fn apply_lut_rgb(
+    pix: &Pix,
+    lut_r: &TrcLut,
+    lut_g: &TrcLut,
+    lut_b: &TrcLut,
+) -> FilterResult<Pix> {
+    let d = pix.depth();
+    let mut pm = pix.deep_clone().try_into_mut().unwrap();
+
+    if let Some(cmap) = pm.colormap_mut() {
+        for i in 0..cmap.len() {
+            let (r, g, b, a) = cmap.get_rgba(i).unwrap_or((0, 0, 0, 255));
+            cmap.reset_color(i, lut_r[r as usize], lut_g[g as usize], lut_b[b as usize])?;
+            cmap.set_alpha(i, a)?;
+        }
+        return Ok(pm.into());
+    }
+
+    let w = pm.width();
+    let h = pm.height();
+    match d {
+        PixelDepth::Bit8 => {
+            for y in 0..h {
+                for x in 0..w {
+                    let val = pm.get_pixel_unchecked(x, y) as usize;
+                    pm.set_pixel_unchecked(x, y, lut_r[val] as u32);
+                }
+            }
+        }
+        PixelDepth::Bit32 => {
+            for y in 0..h {
+                for x in 0..w {
+                    let (r, g, b, a) = pixel::extract_rgba(pm.get_pixel_unchecked(x, y));
+                    let val = pixel::compose_rgba(
+                        lut_r[r as usize],
+                        lut_g[g as usize],
+                        lut_b[b as usize],
+                        a,
+                    );
+                    pm.set_pixel_unchecked(x, y, val);
+                }
+            }
+        }
+        _ => {
+            return Err(FilterError::UnsupportedDepth {
+                expected: "8 or 32 bpp",
+                actual: d.bits(),
+            });
+        }
+    }
+
+    Ok(pm.into())
+}
+
+/// Solarize an 8 or 32 bpp image.
+///
+/// Values at or above `threshold` are inverted (`255 - v`); values below
+/// are unchanged.  32 bpp images are processed per channel.
+pub fn solarize(pix: &Pix, threshold: u8) -> FilterResult<Pix> {
+    let lut: TrcLut = std::array::from_fn(|v| {
+        let v = v as u8;
+        if v >= threshold { 255 - v } else { v }
+    });
+    apply_lut(pix, &lut)
+}
+
+/// Posterize an 8 or 32 bpp image to `levels` evenly spaced values.
+///
+/// The input range is split into `levels` equal bins, and each bin maps to
+/// one of `levels` output values spread evenly over [0, 255] (so 0 and 255
+/// are always reachable).  32 bpp images are processed per channel.
+///
+/// # Arguments
+///
+/// * `levels` - Number of output levels, in [2, 256]
+pub fn posterize(pix: &Pix, levels: u32) -> FilterResult<Pix> {
+    if !(2..=256).contains(&levels) {
+        return Err(FilterError::InvalidParameters(format!(
+            "levels must be in [2, 256], got {levels}"
+        )));
+    }
+    let lut: TrcLut = std::array::from_fn(|v| {
+        let bin = v as u32 * levels / 256;
+        ((bin * 255 + (levels - 1) / 2) / (levels - 1)) as u8
+    });
+    apply_lut(pix, &lut)
+}
+
+/// Keep only values within `[low, high]`; all other values become 0.
+///
+/// 32 bpp images are processed per channel.
+pub fn threshold_band(pix: &Pix, low: u8, high: u8) -> FilterResult<Pix> {
+    if low > high {
+        return Err(FilterError::InvalidParameters(format!(
+            "low ({low}) must not exceed high ({high})"
+        )));
+    }
+    let lut: TrcLut = std::array::from_fn(|v| {
+        let v = v as u8;
+        if (low..=high).contains(&v) { v } else { 0 }
+    });
+    apply_lut(pix, &lut)
+}
+
 // =========================================================================
 //  High-level gamma / contrast / equalization wrappers
 // =========================================================================
@@ -483,6 +597,156 @@ pub fn equalize_trc_pix(pix: &Pix, fract: f32, factor: u32) -> FilterResult<Pix>
     Ok(pm.into())
 }
 
+/// Local histogram equalization with bilinearly blended tile LUTs.
+///
+/// The image is divided into a `tiles_x` x `tiles_y` grid; an
+/// equalization LUT is built from each tile's histogram with
+/// [`Numa::make_equalization_lut`](crate::core::Numa::make_equalization_lut).
+/// Every output pixel is the bilinear blend of the LUTs of the four
+/// tiles whose centers surround it, so there are no seams at tile
+/// boundaries.  This is CLAHE without the clip limit: cheaper, but noise
+/// in flat tiles is amplified.
+///
+/// # Errors
+///
+/// Returns an error if the image is not 8 bpp, has a colormap, or if
+/// `tiles_x`/`tiles_y` is 0 or larger than the image dimension.
+pub fn local_histogram_equalize(pix: &Pix, tiles_x: u32, tiles_y: u32) -> FilterResult<Pix> {
+    if pix.depth() != PixelDepth::Bit8 || pix.has_colormap() {
+        return Err(FilterError::UnsupportedDepth {
+            expected: "8 bpp without colormap",
+            actual: pix.depth().bits(),
+        });
+    }
+    let w = pix.width();
+    let h = pix.height();
+    if tiles_x == 0 || tiles_y == 0 || tiles_x > w || tiles_y > h {
+        return Err(FilterError::InvalidParameters(format!(
+            "tile grid {tiles_x}x{tiles_y} invalid for {w}x{h} image"
+        )));
+    }
+
+    // Tile i spans [i * w / tiles_x, (i + 1) * w / tiles_x)
+    let tile_x = |x: u32| (x as u64 * tiles_x as u64 / w as u64) as usize;
+    let tile_y = |y: u32| (y as u64 * tiles_y as u64 / h as u64) as usize;
+
+    let nx = tiles_x as usize;
+    let ny = tiles_y as usize;
+    let mut hists = vec![[0.0f32; 256]; nx * ny];
+    for y in 0..h {
+        let row = tile_y(y) * nx;
+        for x in 0..w {
+            let v = pix.get_pixel_unchecked(x, y) as usize;
+            hists[row + tile_x(x)][v] += 1.0;
+        }
+    }
+    let luts: Vec<[u8; 256]> = hists
+        .into_iter()
+        .map(|hist| crate::core::Numa::from_vec(hist.to_vec()).make_equalization_lut())
+        .collect();
+
+    // Position in tile-center coordinates: integer values are tile centers
+    let grid = |p: u32, size: u32, n: usize| -> (usize, usize, f32) {
+        let g = (p as f32 + 0.5) * n as f32 / size as f32 - 0.5;
+        let i0 = (g.floor().max(0.0) as usize).min(n - 1);
+        let i1 = (i0 + 1).min(n - 1);
+        let frac = (g - i0 as f32).clamp(0.0, 1.0);
+        (i0, i1, frac)
+    };
+
+    let mut pm = pix.deep_clone().try_into_mut().unwrap();
+    for y in 0..h {
+        let (y0, y1, fy) = grid(y, h, ny);
+        for x in 0..w {
+            let (x0, x1, fx) = grid(x, w, nx);
+            let v = pix.get_pixel_unchecked(x, y) as usize;
+            let v00 = luts[y0 * nx + x0][v] as f32;
+            let v01 = luts[y0 * nx + x1][v] as f32;
+            let v10 = luts[y1 * nx + x0][v] as f32;
+            let v11 = luts[y1 * nx + x1][v] as f32;
+            let top = v00 + (v01 - v00) * fx;
+            let bot = v10 + (v11 - v10) * fx;
+            let out = top + (bot - top) * fy;
+            pm.set_pixel_unchecked(x, y, (out + 0.5).clamp(0.0, 255.0) as u32);
+        }
+    }
+    Ok(pm.into())
+}
+
+/// Remap an image so that its histogram matches that of a reference.
+///
+/// Histogram specification: each input value `v` is mapped to the
+/// smallest reference value whose cumulative fraction reaches the
+/// cumulative fraction of `v` in `pix`.  For 32 bpp images each of R, G
+/// and B is matched independently and alpha is preserved.  Matching an
+/// image to itself leaves it unchanged.  The images need not have the same
+/// size.
+///
+/// # Errors
+///
+/// Returns an error if the images are not both 8 bpp or both 32 bpp, or
+/// if either has a colormap.
+pub fn match_histogram(pix: &Pix, reference: &Pix) -> FilterResult<Pix> {
+    let d = pix.depth();
+    if (d != PixelDepth::Bit8 && d != PixelDepth::Bit32) || pix.has_colormap() {
+        return Err(FilterError::UnsupportedDepth {
+            expected: "8 or 32 bpp without colormap",
+            actual: d.bits(),
+        });
+    }
+    if reference.depth() != d || reference.has_colormap() {
+        return Err(FilterError::InvalidParameters(format!(
+            "reference must match the {} bpp input without colormap, got {} bpp",
+            d.bits(),
+            reference.depth().bits()
+        )));
+    }
+
+    if d == PixelDepth::Bit8 {
+        let lut = histogram_match_lut(&value_histogram(pix, 0), &value_histogram(reference, 0));
+        return apply_lut(pix, &lut);
+    }
+    let luts: Vec<TrcLut> = [24, 16, 8]
+        .into_iter()
+        .map(|shift| {
+            histogram_match_lut(
+                &value_histogram(pix, shift),
+                &value_histogram(reference, shift),
+            )
+        })
+        .collect();
+    apply_lut_rgb(pix, &luts[0], &luts[1], &luts[2])
+}
+
+/// Histogram of the byte at `shift` in each pixel
+fn value_histogram(pix: &Pix, shift: u32) -> [u64; 256] {
+    let mut histo = [0u64; 256];
+    for y in 0..pix.height() {
+        for x in 0..pix.width() {
+            histo[((pix.get_pixel_unchecked(x, y) >> shift) & 0xff) as usize] += 1;
+        }
+    }
+    histo
+}
+
+/// CDF-to-CDF lookup table taking the `src` distribution to `reference`
+fn histogram_match_lut(src: &[u64; 256], reference: &[u64; 256]) -> TrcLut {
+    let n_src: u64 = src.iter().sum();
+    let n_ref: u64 = reference.iter().sum();
+    let mut lut = [0u8; 256];
+    let (mut cum_src, mut cum_ref, mut r) = (0u64, reference[0], 0usize);
+    for (v, entry) in lut.iter_mut().enumerate() {
+        cum_src += src[v];
+        // Compare cum_src / n_src <= cum_ref / n_ref without rounding
+        while r < 255 && (cum_ref as u128) * (n_src as u128) < (cum_src as u128) * (n_ref as u128) {
+            r += 1;
+            cum_ref += reference[r];
+        }
+        *entry = r as u8;
+    }
+    lut
+}
+
 // =========================================================================
 //  HSV modification
 // =========================================================================
@@ -634,6 +898,74 @@ pub fn modify_brightness(pix: &Pix, fract: f32) -> FilterResult<Pix> {
     Ok(pm.into())
 }
 
+/// Rotate the hue of a 32 bpp RGB image by an angle in degrees.
+///
+/// The hue wheel is cyclic: `degrees` is taken modulo 360 (negative values
+/// rotate the other way), so 120 takes red to green and 240 (or -120)
+/// takes red to blue.  Internally hue uses Leptonica's 0-239 scale, so the
+/// rotation is rounded to the nearest 1.5 degrees.  Saturation, value and
+/// alpha are unchanged.
+///
+/// Unlike [`modify_hue`], whose argument is a fraction of a full turn,
+/// this takes degrees.
+pub fn shift_hue(pix: &Pix, degrees: f32) -> FilterResult<Pix> {
+    if pix.depth() != PixelDepth::Bit32 {
+        return Err(FilterError::UnsupportedDepth {
+            expected: "32 bpp",
+            actual: pix.depth().bits(),
+        });
+    }
+    if !degrees.is_finite() {
+        return Err(FilterError::InvalidParameters(
+            "degrees must be finite".into(),
+        ));
+    }
+
+    let delhue = (degrees.rem_euclid(360.0) * 240.0 / 360.0).round() as i32 % 240;
+    map_hsv(pix, |hsv| hsv.h = (hsv.h + delhue) % 240)
+}
+
+/// Scale the saturation of a 32 bpp RGB image by a factor.
+///
+/// Each pixel's saturation (0-255) is multiplied by `factor`, rounded and
+/// clamped: 0.0 gives a gray image, 1.0 leaves it unchanged and values
+/// above 1.0 make colors more vivid.  Hue, value and alpha are unchanged.
+///
+/// Unlike [`modify_saturation`], whose argument moves saturation a
+/// fraction of the way toward 0 or 255, this is a plain multiplier.
+pub fn scale_saturation(pix: &Pix, factor: f32) -> FilterResult<Pix> {
+    if pix.depth() != PixelDepth::Bit32 {
+        return Err(FilterError::UnsupportedDepth {
+            expected: "32 bpp",
+            actual: pix.depth().bits(),
+        });
+    }
+    if !factor.is_finite() || factor < 0.0 {
+        return Err(FilterError::InvalidParameters(
+            "factor must be finite and >= 0.0".into(),
+        ));
+    }
+
+    map_hsv(pix, |hsv| {
+        hsv.s = (hsv.s as f32 * factor).round().clamp(0.0, 255.0) as i32
+    })
+}
+
+/// Apply `f` to the HSV value of every pixel, preserving alpha
+fn map_hsv(pix: &Pix, f: impl Fn(&mut pixel::Hsv)) -> FilterResult<Pix> {
+    let mut pm = pix.deep_clone().try_into_mut().unwrap();
+    for y in 0..pm.height() {
+        for x in 0..pm.width() {
+            let (r, g, b, a) = pixel::extract_rgba(pm.get_pixel_unchecked(x, y));
+            let mut hsv = pixel::rgb_to_hsv(r, g, b);
+            f(&mut hsv);
+            let (nr, ng, nb) = pixel::hsv_to_rgb(hsv);
+            pm.set_pixel_unchecked(x, y, pixel::compose_rgba(nr, ng, nb, a));
+        }
+    }
+    Ok(pm.into())
+}
+
 /// Measure the average saturation of a 32 bpp RGB image.
 ///
 /// Returns the mean saturation value (in [0..255]) computed over a
@@ -1083,6 +1415,123 @@ pub fn unsharp_masking(pix: &Pix, halfwidth: u32, fract: f32) -> FilterResult<Pi
     }
 }
 
+/// Apply unsharp masking whose strength follows the local variance.
+///
+/// Plain unsharp masking amplifies noise in flat areas as much as it
+/// sharpens real detail.  Here the amount added back at each pixel is
+/// `max_amount * var / (var + variance_ref)`, where `var` is the variance
+/// of the luminance over a `(2*radius + 1)` square window.  Smooth areas
+/// (`var` much less than `variance_ref`) are left nearly untouched, and
+/// textured areas approach the full `max_amount`.
+///
+/// The blur uses the same window as the variance, except that the
+/// variance window is never smaller than 5x5.  For 32 bpp input the
+/// amount is computed from the luminance and applied to each color
+/// channel; alpha is preserved.
+///
+/// # Arguments
+/// * `pix` - 8 bpp grayscale without colormap, or 32 bpp RGB
+/// * `radius` - Half-width of the smoothing window; must be >= 1
+/// * `max_amount` - Fraction of the high-pass signal added back in the
+///   most textured areas; must be >= 0.0
+/// * `variance_ref` - Variance at which half of `max_amount` is applied;
+///   must be > 0.0
+///
+/// # Errors
+///
+/// Returns an error if the depth is not 8 or 32 bpp, if an 8 bpp image
+/// has a colormap, or if a parameter is out of range.
+pub fn adaptive_unsharp(
+    pix: &Pix,
+    radius: u32,
+    max_amount: f32,
+    variance_ref: f32,
+) -> FilterResult<Pix> {
+    let d = pix.depth();
+    if d != PixelDepth::Bit8 && d != PixelDepth::Bit32 {
+        return Err(FilterError::UnsupportedDepth {
+            expected: "8 or 32 bpp",
+            actual: d.bits(),
+        });
+    }
+    if d == PixelDepth::Bit8 && pix.has_colormap() {
+        return Err(FilterError::InvalidParameters(
+            "8bpp input must not have a colormap".to_string(),
+        ));
+    }
+    if radius == 0 {
+        return Err(FilterError::InvalidParameters(
+            "radius must be >= 1".to_string(),
+        ));
+    }
+    if !max_amount.is_finite()
+        || max_amount < 0.0
+        || !variance_ref.is_finite()
+        || variance_ref <= 0.0
+    {
+        return Err(FilterError::InvalidParameters(
+            "max_amount must be >= 0 and variance_ref must be > 0".to_string(),
+        ));
+    }
+
+    let pixg = if d == PixelDepth::Bit32 {
+        pix.convert_rgb_to_luminance()?
+    } else {
+        pix.clone()
+    };
+    let wc = radius.max(2);
+    let stats = crate::filter::windowed_stats(&pixg, wc, wc)?;
+    let variance = stats.variance;
+
+    let w = pix.width();
+    let h = pix.height();
+    let amount_at = |x: u32, y: u32| {
+        let var = variance.get_pixel_unchecked(x, y);
+        max_amount * var / (var + variance_ref)
+    };
+    let sharpen = |src: u32, blur: u32, amount: f32| {
+        let src = src as f32;
+        let result = (src + amount * (src - blur as f32) + 0.5).floor() as i32;
+        result.clamp(0, 255) as u32
+    };
+
+    if d == PixelDepth::Bit8 {
+        let blurred = crate::filter::blockconv_gray(pix, None, radius, radius)?;
+        let mut out_mut = Pix::new(w, h, PixelDepth::Bit8)?.try_into_mut().unwrap();
+        for y in 0..h {
+            for x in 0..w {
+                let val = sharpen(
+                    pix.get_pixel_unchecked(x, y),
+                    blurred.get_pixel_unchecked(x, y),
+                    amount_at(x, y),
+                );
+                out_mut.set_pixel_unchecked(x, y, val);
+            }
+        }
+        return Ok(out_mut.into());
+    }
+
+    let blur_channel = |comp: RgbComponent| -> FilterResult<Pix> {
+        let channel = pix.get_rgb_component(comp)?;
+        crate::filter::blockconv_gray(&channel, None, radius, radius)
+    };
+    let br = blur_channel(RgbComponent::Red)?;
+    let bg = blur_channel(RgbComponent::Green)?;
+    let bb = blur_channel(RgbComponent::Blue)?;
+    let mut out_mut = pix.deep_clone().try_into_mut().unwrap();
+    for y in 0..h {
+        for x in 0..w {
+            let amount = amount_at(x, y);
+            let (r, g, b, a) = pixel::extract_rgba(pix.get_pixel_unchecked(x, y));
+            let nr = sharpen(r as u32, br.get_pixel_unchecked(x, y), amount);
+            let ng = sharpen(g as u32, bg.get_pixel_unchecked(x, y), amount);
+            let nb = sharpen(b as u32, bb.get_pixel_unchecked(x, y), amount);
+            out_mut.set_pixel_unchecked(x, y, pixel::compose_rgba(nr as u8, ng as u8, nb as u8, a));
+        }
+    }
+    Ok(out_mut.into())
+}
+
 /// Scale type for `max_dynamic_range`.
 ///
 /// C Leptonica: `L_LINEAR_SCALE` and `L_LOG_SCALE` in `leptonica.h`
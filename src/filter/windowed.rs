@@ -1,7 +1,9 @@
 //! Windowed statistics using integral images
 //!
 //! Computes local mean, mean-square, variance and RMS deviation over
-//! rectangular sliding windows in O(1) per pixel, using summed area tables.
+//! rectangular sliding windows in O(1) per pixel, using summed area tables,
+//! and normalizes images to zero mean and unit variance, globally or per
+//! window.
 //!
 //! # See also
 //!
@@ -339,6 +341,114 @@ pub fn windowed_stats(pix: &Pix, wc: u32, hc: u32) -> FilterResult<WindowedStats
     })
 }
 
+/// Normalize an 8 bpp image to zero mean and unit standard deviation.
+///
+/// Each output pixel is `(value - mean) / std`, with the mean and
+/// population standard deviation taken over the whole image.  This is the
+/// usual input normalization for learned models.  A constant image has no
+/// spread and normalizes to all zeros.
+pub fn variance_normalize(pix: &Pix) -> FilterResult<FPix> {
+    check_8bpp(pix)?;
+    let w = pix.width();
+    let h = pix.height();
+
+    let n = w as f64 * h as f64;
+    let (mut sum, mut sum_sq) = (0.0f64, 0.0f64);
+    for y in 0..h {
+        for x in 0..w {
+            let v = pix.get_pixel_unchecked(x, y) as f64;
+            sum += v;
+            sum_sq += v * v;
+        }
+    }
+    let mean = sum / n;
+    let std = (sum_sq / n - mean * mean).max(0.0).sqrt();
+    let scale = if std > 0.0 { 1.0 / std } else { 0.0 };
+
+    let mut fpix = FPix::new(w, h)?;
+    for y in 0..h {
+        for x in 0..w {
+            let v = pix.get_pixel_unchecked(x, y) as f64;
+            fpix.set_pixel_unchecked(x, y, ((v - mean) * scale) as f32);
+        }
+    }
+    Ok(fpix)
+}
+
+/// Normalize an 8 bpp image to zero mean and unit standard deviation locally.
+///
+/// Like [`variance_normalize`], but the mean and standard deviation are
+/// taken over the `(2*radius + 1) x (2*radius + 1)` window around each
+/// pixel, clipped to the image.  This evens out illumination and contrast
+/// across the image.  Pixels whose window is constant normalize to 0.
+///
+/// Window statistics come from `f64` integral images, so the cost does not
+/// depend on `radius`.  [`windowed_mean`] and [`windowed_variance`] are not
+/// used because they pad the image with a zero border, which pulls the mean
+/// down near the edges instead of clipping the window, round the mean to
+/// 8 bits, and require a half-size of at least 2.
+pub fn local_variance_normalize(pix: &Pix, radius: u32) -> FilterResult<FPix> {
+    check_8bpp(pix)?;
+    let w = pix.width() as usize;
+    let h = pix.height() as usize;
+    let r = radius as usize;
+
+    let vals: Vec<f64> = (0..h)
+        .flat_map(|y| (0..w).map(move |x| pix.get_pixel_unchecked(x as u32, y as u32) as f64))
+        .collect();
+    let squares: Vec<f64> = vals.iter().map(|v| v * v).collect();
+    let mean = clipped_box_mean(&vals, w, h, r);
+    let mean_sq = clipped_box_mean(&squares, w, h, r);
+
+    let mut fpix = FPix::new(w as u32, h as u32)?;
+    for y in 0..h {
+        for x in 0..w {
+            let k = y * w + x;
+            let var = mean_sq[k] - mean[k] * mean[k];
+            // Allow for rounding in the integral images of constant windows
+            let z = if var > 1e-6 {
+                (vals[k] - mean[k]) / var.sqrt()
+            } else {
+                0.0
+            };
+            fpix.set_pixel_unchecked(x as u32, y as u32, z as f32);
+        }
+    }
+    Ok(fpix)
+}
+
+/// Mean of the row-major `w x h` values in `data` over the
+/// `(2r+1) x (2r+1)` window around each pixel, with the window clipped
+/// to the image.
+///
+/// Uses an `f64` integral image, so the cost does not depend on `r`.
+pub(crate) fn clipped_box_mean(data: &[f64], w: usize, h: usize, r: usize) -> Vec<f64> {
+    // Integral image with a zero first row and column
+    let iw = w + 1;
+    let mut integral = vec![0.0f64; iw * (h + 1)];
+    for y in 0..h {
+        let mut run = 0.0;
+        for x in 0..w {
+            run += data[y * w + x];
+            integral[(y + 1) * iw + x + 1] = integral[y * iw + x + 1] + run;
+        }
+    }
+
+    let mut out = vec![0.0f64; w * h];
+    for y in 0..h {
+        let y0 = y.saturating_sub(r);
+        let y1 = (y + r + 1).min(h);
+        for x in 0..w {
+            let x0 = x.saturating_sub(r);
+            let x1 = (x + r + 1).min(w);
+            let sum = integral[y1 * iw + x1] - integral[y0 * iw + x1] - integral[y1 * iw + x0]
+                + integral[y0 * iw + x0];
+            out[y * w + x] = sum / ((y1 - y0) * (x1 - x0)) as f64;
+        }
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -714,4 +824,78 @@ mod tests {
         let pix = create_uniform_gray(30, 30, 100);
         assert!(windowed_stats(&pix, 1, 3).is_err());
     }
+
+    // ---- variance_normalize tests ----
+
+    #[test]
+    fn test_variance_normalize_zero_mean_unit_std() {
+        let pix = Pix::new(40, 30, PixelDepth::Bit8).unwrap();
+        let mut pm = pix.try_into_mut().unwrap();
+        for y in 0..30 {
+            for x in 0..40 {
+                pm.set_pixel_unchecked(x, y, (x * 5 + y * 3) % 256);
+            }
+        }
+        let pix: Pix = pm.into();
+        let z = variance_normalize(&pix).unwrap();
+
+        assert_eq!(z.width(), 40);
+        assert_eq!(z.height(), 30);
+        let n = z.data().len() as f64;
+        let mean = z.data().iter().map(|&v| v as f64).sum::<f64>() / n;
+        let var = z
+            .data()
+            .iter()
+            .map(|&v| (v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        assert!(mean.abs() < 1e-5, "mean = {mean}");
+        assert!((var.sqrt() - 1.0).abs() < 1e-5, "std = {}", var.sqrt());
+
+        // Order of values is preserved
+        assert!(z.get_pixel_unchecked(0, 0) < z.get_pixel_unchecked(39, 29));
+    }
+
+    #[test]
+    fn test_variance_normalize_constant() {
+        let pix = create_uniform_gray(10, 10, 77);
+        let z = variance_normalize(&pix).unwrap();
+        assert!(z.data().iter().all(|&v| v == 0.0));
+
+        let z = local_variance_normalize(&pix, 2).unwrap();
+        assert!(z.data().iter().all(|&v| v == 0.0));
+    }
+
+    #[test]
+    fn test_local_variance_normalize() {
+        // Left half alternates 100/110, right half alternates 0/200: both
+        // normalize to about +-1 despite very different contrast
+        let pix = Pix::new(40, 20, PixelDepth::Bit8).unwrap();
+        let mut pm = pix.try_into_mut().unwrap();
+        for y in 0..20 {
+            for x in 0..40 {
+                let high = (x + y) % 2 == 0;
+                let v = match (x < 20, high) {
+                    (true, true) => 110,
+                    (true, false) => 100,
+                    (false, true) => 200,
+                    (false, false) => 0,
+                };
+                pm.set_pixel_unchecked(x, y, v);
+            }
+        }
+        let pix: Pix = pm.into();
+        let z = local_variance_normalize(&pix, 2).unwrap();
+
+        assert_eq!(z.width(), 40);
+        assert_eq!(z.height(), 20);
+        for (x, y) in [(5, 5), (6, 5), (30, 10), (31, 10)] {
+            let expected = if (x + y) % 2 == 0 { 1.0 } else { -1.0 };
+            let v = z.get_pixel_unchecked(x, y);
+            assert!((v - expected).abs() < 0.1, "z({x},{y}) = {v}");
+        }
+
+        assert!(local_variance_normalize(&Pix::new(8, 8, PixelDepth::Bit32).unwrap(), 2).is_err());
+        assert!(variance_normalize(&Pix::new(8, 8, PixelDepth::Bit1).unwrap()).is_err());
+    }
 }
@@ -2,6 +2,9 @@
 //!
 //! Supports reading and writing single-frame GIF images.
 //! Animated GIFs (multiple frames) are not supported.
+//!
+//! A transparent palette index is written by [`write_gif_with_palette`]
+//! and read back as a colormap entry with alpha 0.
 
 use crate::color::{OctreeOptions, octree_quant};
 use crate::core::{ImageFormat, Pix, PixColormap, PixelDepth};
@@ -110,6 +113,13 @@ pub fn read_gif<R: Read>(reader: R) -> IoResult<Pix> {
                 .map_err(IoError::Core)?;
         }
     }
+    // A transparent index from the graphic control extension becomes
+    // a fully transparent colormap entry
+    if let Some(t) = frame.transparent
+        && (t as usize) < cmap.len()
+    {
+        cmap.set_alpha(t as usize, 0).map_err(IoError::Core)?;
+    }
     pix_mut.set_colormap(Some(cmap)).map_err(IoError::Core)?;
 
     // Copy pixel data
@@ -138,9 +148,72 @@ pub fn read_gif<R: Read>(reader: R) -> IoResult<Pix> {
 pub fn write_gif<W: Write>(pix: &Pix, mut writer: W) -> IoResult<()> {
     // Convert to 8bpp with colormap if needed
     let (write_pix, cmap) = prepare_pix_for_gif(pix)?;
+    encode_indexed(&write_pix, &cmap, None, &mut writer)
+}
+
+/// Write an 8 bpp colormapped image as GIF with an explicit palette
+///
+/// Unlike [`write_gif`], the image is never converted or quantized: its
+/// pixel values are written as indices into `palette`, which replaces
+/// the image's own colormap in the output.  If `transparent` is given,
+/// that palette index is marked transparent in the graphic control
+/// extension; [`read_gif`] reports it as an entry with alpha 0.
+///
+/// # Errors
+///
+/// Returns an error if the image is not 8 bpp with a colormap, if the
+/// palette is empty or has more than 256 entries, or if a pixel value or
+/// the transparent index is outside the palette.
+pub fn write_gif_with_palette<W: Write>(
+    pix: &Pix,
+    mut writer: W,
+    palette: &PixColormap,
+    transparent: Option<u8>,
+) -> IoResult<()> {
+    if pix.depth() != PixelDepth::Bit8 || !pix.has_colormap() {
+        return Err(IoError::InvalidData(
+            "write_gif_with_palette requires an 8 bpp colormapped image".to_string(),
+        ));
+    }
+    let ncolors = palette.len();
+    if ncolors == 0 || ncolors > 256 {
+        return Err(IoError::InvalidData(format!(
+            "invalid palette size: {}",
+            ncolors
+        )));
+    }
+    if let Some(t) = transparent
+        && t as usize >= ncolors
+    {
+        return Err(IoError::InvalidData(format!(
+            "transparent index {} outside palette of {} colors",
+            t, ncolors
+        )));
+    }
+    for y in 0..pix.height() {
+        for x in 0..pix.width() {
+            let val = pix.get_pixel_unchecked(x, y);
+            if val as usize >= ncolors {
+                return Err(IoError::InvalidData(format!(
+                    "pixel value {} at ({}, {}) outside palette of {} colors",
+                    val, x, y, ncolors
+                )));
+            }
+        }
+    }
+
+    encode_indexed(pix, palette, transparent, &mut writer)
+}
 
-    let width = write_pix.width() as u16;
-    let height = write_pix.height() as u16;
+/// Encode an indexed image as a single-frame GIF with a global palette
+fn encode_indexed<W: Write>(
+    pix: &Pix,
+    cmap: &PixColormap,
+    transparent: Option<u8>,
+    writer: &mut W,
+) -> IoResult<()> {
+    let width = pix.width() as u16;
+    let height = pix.height() as u16;
 
     // Build GIF palette (must be power of 2 size)
     let cmap_len = cmap.len();
@@ -162,7 +235,7 @@ pub fn write_gif<W: Write>(pix: &Pix, mut writer: W) -> IoResult<()> {
     }
 
     // Create encoder
-    let mut encoder = Encoder::new(&mut writer, width, height, &palette)
+    let mut encoder = Encoder::new(writer, width, height, &palette)
         .map_err(|e| IoError::EncodeError(format!("GIF encoder error: {}", e)))?;
 
     encoder
@@ -174,13 +247,13 @@ pub fn write_gif<W: Write>(pix: &Pix, mut writer: W) -> IoResult<()> {
 
     for y in 0..(height as u32) {
         for x in 0..(width as u32) {
-            let val = write_pix.get_pixel(x, y).unwrap_or(0);
+            let val = pix.get_pixel(x, y).unwrap_or(0);
             buffer.push(val as u8);
         }
     }
 
     // Create and write frame
-    let mut frame = Frame::from_indexed_pixels(width, height, buffer, None);
+    let mut frame = Frame::from_indexed_pixels(width, height, buffer, transparent);
     frame.palette = None; // Use global palette
 
     encoder
@@ -2,7 +2,7 @@
 //!
 //! Reading and writing JPEG images using the jpeg-decoder and jpeg-encoder crates.
 
-use crate::core::{ImageFormat, Pix, PixelDepth, pix::RemoveColormapTarget, pixel};
+use crate::core::{Boxa, ImageFormat, Pix, PixelDepth, pix::RemoveColormapTarget, pixel};
 use crate::io::{IoError, IoResult, header::ImageHeader};
 use jpeg_decoder::{Decoder, PixelFormat};
 use std::io::{Read, Write};
@@ -85,8 +85,46 @@ pub fn set_chroma_sampling(pix: &mut crate::core::PixMut, sampling: bool) {
 
 /// Read a JPEG image
 pub fn read_jpeg<R: Read>(reader: R) -> IoResult<Pix> {
+    decode_jpeg(Decoder::new(reader))
+}
+
+/// Read a JPEG image, reduced during decoding toward a target size
+///
+/// Uses DCT-domain scaling by 1/2, 1/4 or 1/8 to pick the smallest
+/// decode whose larger dimension is still at least `target_max_dim`, so
+/// the result's larger dimension lies in `[target_max_dim,
+/// 2 * target_max_dim)` whenever the image is big enough.  Images whose
+/// larger dimension is at most `target_max_dim`, or a `target_max_dim`
+/// of 0, are decoded at full size.  Even at 1/8 the result may still
+/// exceed `2 * target_max_dim`; no further scaling is done here.
+pub fn read_jpeg_scaled<R: Read>(reader: R, target_max_dim: u32) -> IoResult<Pix> {
     let mut decoder = Decoder::new(reader);
+    decoder
+        .read_info()
+        .map_err(|e| IoError::DecodeError(format!("JPEG decode error: {}", e)))?;
+    let info = decoder
+        .info()
+        .ok_or_else(|| IoError::InvalidData("missing JPEG info".to_string()))?;
+    let (w, h) = (info.width as u32, info.height as u32);
+    let max_dim = w.max(h);
+    if target_max_dim > 0 && max_dim > target_max_dim {
+        // The decoder picks the largest reduction that keeps either axis
+        // at or above its request, so only the larger axis is constrained
+        let target = target_max_dim as u16;
+        let (req_w, req_h) = if w >= h {
+            (target, u16::MAX)
+        } else {
+            (u16::MAX, target)
+        };
+        decoder
+            .scale(req_w, req_h)
+            .map_err(|e| IoError::DecodeError(format!("JPEG scale error: {}", e)))?;
+    }
+    decode_jpeg(decoder)
+}
 
+/// Decode the (possibly scaled) image from a prepared decoder
+fn decode_jpeg<R: Read>(mut decoder: Decoder<R>) -> IoResult<Pix> {
     let pixels = decoder
         .decode()
         .map_err(|e| IoError::DecodeError(format!("JPEG decode error: {}", e)))?;
@@ -154,7 +192,8 @@ pub fn read_jpeg<R: Read>(reader: R) -> IoResult<Pix> {
 ///
 /// Conversion rules:
 /// - **1/2/4 bpp**: converted to 8bpp grayscale
-/// - **8 bpp with colormap**: colormap removed (grayscale or RGB based on content)
+/// - **colormapped (any depth)**: expanded through the colormap, to RGB if
+///   any entry has color and to 8bpp grayscale otherwise
 /// - **8 bpp grayscale**: encoded directly as grayscale
 /// - **16 bpp**: converted to 8bpp grayscale (upper byte)
 /// - **32 bpp (spp=3/4)**: encoded as RGB (alpha ignored)
@@ -168,11 +207,16 @@ pub fn write_jpeg<W: Write>(pix: &Pix, writer: W, options: &JpegOptions) -> IoRe
     // produces a fresh Pix that drops the original `special` value.
     let pix_orig_special = pix.special();
 
-    // Convert pix to a form suitable for JPEG encoding.
-    // Following C version logic: remove colormap based on source content,
-    // then ensure depth is 8 (grayscale) or 32 (RGB).
-    let pix = if pix.has_colormap() {
-        pix.remove_colormap(RemoveColormapTarget::BasedOnSrc)
+    // Convert pix to a form suitable for JPEG encoding: depth 8 (grayscale)
+    // or 32 (RGB). Colormaps are always expanded to one of those two, never
+    // to 1 bpp, so that e.g. a black/white 1 bpp palette still encodes.
+    let pix = if let Some(cmap) = pix.colormap() {
+        let target = if cmap.has_color() {
+            RemoveColormapTarget::ToFullColor
+        } else {
+            RemoveColormapTarget::ToGrayscale
+        };
+        pix.remove_colormap(target)
             .map_err(|e| IoError::EncodeError(format!("colormap removal failed: {}", e)))?
     } else {
         match pix.depth() {
@@ -268,6 +312,109 @@ pub fn write_jpeg<W: Write>(pix: &Pix, writer: W, options: &JpegOptions) -> IoRe
     Ok(())
 }
 
+/// Quality steps per unit of pre-blur half-width in
+/// [`write_jpeg_region_adaptive`]
+const QUALITY_PER_BLUR_STEP: u32 = 20;
+
+/// Write a JPEG whose effective quality varies by region
+///
+/// A JPEG stream has a single set of quantization tables, so the image is
+/// encoded once at the highest of `base_quality` and `qualities`.  Regions
+/// that ask for a lower quality are low-pass filtered beforehand, which
+/// removes the high-frequency detail that dominates the coded size: the
+/// area outside all boxes is smoothed according to `base_quality`, and the
+/// area inside `boxes[i]` according to `qualities[i]`.  The smoothing is a
+/// box filter of half-width `ceil((q_max - q) / 20)`, so a region at the
+/// top quality is left untouched.  Where boxes overlap, the higher quality
+/// wins.
+///
+/// Typical use keeps text boxes at a high quality and lets halftones and
+/// background take a low `base_quality`.
+///
+/// # Arguments
+///
+/// * `pix` - 8 bpp grayscale or 32 bpp RGB image, without colormap
+/// * `boxes` - Regions to encode at their own quality; clipped to the image
+/// * `qualities` - Quality (1-100) for each box
+/// * `base_quality` - Quality (1-100) for the area outside all boxes
+/// * `writer` - Destination of the JPEG stream
+///
+/// # Errors
+///
+/// Returns an error if the image is not 8 or 32 bpp or has a colormap, if
+/// `qualities` and `boxes` differ in length, or if a quality is outside
+/// 1..=100.
+pub fn write_jpeg_region_adaptive<W: Write>(
+    pix: &Pix,
+    boxes: &Boxa,
+    qualities: &[u8],
+    base_quality: u8,
+    writer: W,
+) -> IoResult<()> {
+    if pix.has_colormap() || !matches!(pix.depth(), PixelDepth::Bit8 | PixelDepth::Bit32) {
+        return Err(IoError::EncodeError(format!(
+            "region-adaptive JPEG requires 8 or 32 bpp without colormap, got {} bpp",
+            pix.depth().bits()
+        )));
+    }
+    if qualities.len() != boxes.len() {
+        return Err(IoError::EncodeError(format!(
+            "{} qualities given for {} boxes",
+            qualities.len(),
+            boxes.len()
+        )));
+    }
+    if let Some(q) = qualities
+        .iter()
+        .chain(std::iter::once(&base_quality))
+        .find(|q| !(1..=100).contains(*q))
+    {
+        return Err(IoError::EncodeError(format!(
+            "JPEG quality {q} is outside 1..=100"
+        )));
+    }
+
+    let top = qualities.iter().copied().fold(base_quality, u8::max);
+    let mut smoothed: Vec<(u32, Pix)> = Vec::new();
+    let mut smooth_for = |q: u8| -> IoResult<Pix> {
+        let half = (top - q) as u32;
+        let half = half.div_ceil(QUALITY_PER_BLUR_STEP);
+        if half == 0 {
+            return Ok(pix.clone());
+        }
+        if let Some((_, p)) = smoothed.iter().find(|(h, _)| *h == half) {
+            return Ok(p.clone());
+        }
+        let p = crate::filter::blockconv(pix, half, half)
+            .map_err(|e| IoError::EncodeError(format!("pre-blur failed: {e}")))?;
+        smoothed.push((half, p.clone()));
+        Ok(p)
+    };
+
+    let mut out = smooth_for(base_quality)?.deep_clone().to_mut();
+    // Paste boxes in increasing quality so that the best one wins overlaps
+    let mut order: Vec<usize> = (0..boxes.len()).collect();
+    order.sort_by_key(|&i| qualities[i]);
+    let (w, h) = (pix.width() as i32, pix.height() as i32);
+    for i in order {
+        let b = boxes.get(i).expect("index within boxa");
+        let (x0, y0) = (b.x.max(0), b.y.max(0));
+        let (x1, y1) = ((b.x + b.w).min(w), (b.y + b.h).min(h));
+        if x0 >= x1 || y0 >= y1 {
+            continue;
+        }
+        let src = smooth_for(qualities[i])?;
+        for y in y0 as u32..y1 as u32 {
+            for x in x0 as u32..x1 as u32 {
+                out.set_pixel_unchecked(x, y, src.get_pixel_unchecked(x, y));
+            }
+        }
+    }
+    out.set_special(pix.special());
+
+    write_jpeg(&out.into(), writer, &JpegOptions { quality: top })
+}
+
 /// Extract JPEG comment (COM marker) from data
 ///
 /// Returns `None` if no comment marker is found.
@@ -36,6 +36,8 @@ pub mod pdf;
 pub mod ps;
 
 pub mod header;
+pub mod npy;
+pub mod raw;
 pub mod spix;
 
 pub mod convertfiles;
@@ -47,8 +49,10 @@ pub use format::{detect_format, detect_format_from_bytes};
 pub use header::{
     ImageHeader, choose_output_format, read_image_header, read_image_header_mem, write_image_auto,
 };
+pub use npy::{read_npy, read_npy_file, write_npy, write_npy_file};
 #[cfg(feature = "pnm")]
 pub use pnm::{read_pam, write_pam, write_pnm_ascii};
+pub use raw::{read_raw, write_raw};
 
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Read, Seek, Write};
@@ -82,6 +86,54 @@ pub fn read_image_mem(data: &[u8]) -> IoResult<Pix> {
     read_image_format(std::io::Cursor::new(data), format)
 }
 
+/// Read an image reduced to at most about twice a target size
+///
+/// Intended for previews of large images.  The larger dimension of the
+/// result is at most `2 * target_max_dim`, and at least `target_max_dim`
+/// unless the image itself is smaller; callers do the final scaling to
+/// the exact display size.  Images that are already small enough are
+/// returned at full size.
+///
+/// JPEG images are reduced during decoding (see
+/// [`jpeg::read_jpeg_scaled`]), which saves memory and time; other
+/// formats are decoded at full size and then scaled.
+///
+/// # Errors
+///
+/// Returns an error if `target_max_dim` is 0 or the image cannot be read.
+pub fn read_image_scaled<P: AsRef<Path>>(path: P, target_max_dim: u32) -> IoResult<Pix> {
+    if target_max_dim == 0 {
+        return Err(IoError::InvalidData(
+            "target_max_dim must be > 0".to_string(),
+        ));
+    }
+    let path = path.as_ref();
+    let file = File::open(path).map_err(IoError::Io)?;
+    let mut reader = BufReader::new(file);
+
+    let mut header = [0u8; 12];
+    let bytes_read = reader.read(&mut header).map_err(IoError::Io)?;
+    let format = detect_format_from_bytes(&header[..bytes_read])?;
+    reader
+        .seek(std::io::SeekFrom::Start(0))
+        .map_err(IoError::Io)?;
+
+    let pix = match format {
+        #[cfg(feature = "jpeg")]
+        ImageFormat::Jpeg => jpeg::read_jpeg_scaled(reader, target_max_dim)?,
+        _ => read_image_format(reader, format)?,
+    };
+
+    let limit = 2 * target_max_dim as u64;
+    let max_dim = pix.width().max(pix.height());
+    if max_dim as u64 <= limit {
+        return Ok(pix);
+    }
+    let factor = limit as f32 / max_dim as f32;
+    crate::transform::scale(&pix, factor, factor, crate::transform::ScaleMethod::Auto)
+        .map_err(|e| IoError::InvalidData(format!("scale failed: {e}")))
+}
+
 /// Read an image with a specific format
 pub fn read_image_format<R: Read + Seek + std::io::BufRead>(
     reader: R,
@@ -265,6 +317,46 @@ pub fn pixa_write_files(
     Ok(())
 }
 
+/// Read a Pta from a file in the Leptonica text format
+///
+/// Accepts both the float and integer variants written by C Leptonica.
+///
+/// # See also
+/// C Leptonica: `ptaRead()` in `ptabasic.c`
+pub fn read_pta(path: impl AsRef<Path>) -> IoResult<crate::core::Pta> {
+    Ok(crate::core::Pta::read_from_file(path)?)
+}
+
+/// Write a Pta to a file in the Leptonica text format
+///
+/// Coordinates are always written in float format, with enough digits
+/// to read back the identical `f32` values.
+///
+/// # See also
+/// C Leptonica: `ptaWrite()` in `ptabasic.c`
+pub fn write_pta(pta: &crate::core::Pta, path: impl AsRef<Path>) -> IoResult<()> {
+    Ok(pta.write_to_file(path)?)
+}
+
+/// Read a Ptaa from a file in the Leptonica text format
+///
+/// # See also
+/// C Leptonica: `ptaaRead()` in `ptabasic.c`
+pub fn read_ptaa(path: impl AsRef<Path>) -> IoResult<crate::core::Ptaa> {
+    Ok(crate::core::Ptaa::read_from_file(path)?)
+}
+
+/// Write a Ptaa to a file in the Leptonica text format
+///
+/// Each Pta is written as an embedded Pta, so the grouping of points
+/// is preserved.
+///
+/// # See also
+/// C Leptonica: `ptaaWrite()` in `ptabasic.c`
+pub fn write_ptaa(ptaa: &crate::core::Ptaa, path: impl AsRef<Path>) -> IoResult<()> {
+    Ok(ptaa.write_to_file(path)?)
+}
+
 /// Get the file extension for an image format
 ///
 /// # See also
@@ -0,0 +1,333 @@
+//! NumPy `.npy` format support
+//!
+//! Reads and writes image data as NumPy arrays so images can be exchanged
+//! with Python (`numpy.load` / `numpy.save`) without an image codec.
+//!
+//! | Pix                      | dtype | shape       |
+//! | ------------------------ | ----- | ----------- |
+//! | 8 bpp                    | `u1`  | `(h, w)`    |
+//! | 16 bpp                   | `<u2` | `(h, w)`    |
+//! | 32 bpp, spp = 4          | `u1`  | `(h, w, 4)` |
+//! | 32 bpp, spp = 3          | `u1`  | `(h, w, 3)` |
+//!
+//! Channels are stored in R, G, B(, A) order.  Colormapped images are
+//! expanded to RGB before writing; 1/2/4 bpp images without a colormap are
+//! rejected (convert them to 8 bpp first).
+
+use crate::core::pix::RemoveColormapTarget;
+use crate::core::{Pix, PixMut, PixelDepth, pixel};
+use crate::io::{IoError, IoResult};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic string at the start of every `.npy` file
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// Header (magic + version + length + dict) is padded to this alignment
+const NPY_ALIGN: usize = 64;
+
+/// Largest header dict accepted (numpy's own `max_header_size` default)
+const NPY_MAX_HEADER: usize = 10_000;
+
+/// Maximum allowed image width
+const NPY_MAX_WIDTH: u32 = 1_000_000;
+/// Maximum allowed image height
+const NPY_MAX_HEIGHT: u32 = 1_000_000;
+/// Maximum allowed image area (width * height)
+const NPY_MAX_AREA: u64 = 400_000_000;
+
+/// Read a `.npy` array as a Pix
+///
+/// Accepts C-ordered `uint8` arrays of shape `(h, w)`, `(h, w, 3)` or
+/// `(h, w, 4)` and `uint16` arrays of shape `(h, w)` in either byte order.
+pub fn read_npy<R: Read>(mut reader: R) -> IoResult<Pix> {
+    let mut preamble = [0u8; 8];
+    reader.read_exact(&mut preamble).map_err(IoError::Io)?;
+    if &preamble[..6] != NPY_MAGIC {
+        return Err(IoError::InvalidData("not a .npy file".to_string()));
+    }
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len).map_err(IoError::Io)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0u8; 4];
+            reader.read_exact(&mut len).map_err(IoError::Io)?;
+            u32::from_le_bytes(len) as usize
+        }
+        v => {
+            return Err(IoError::UnsupportedFormat(format!(
+                "npy format version {v} not supported"
+            )));
+        }
+    };
+    if header_len > NPY_MAX_HEADER {
+        return Err(IoError::InvalidData(format!(
+            "npy header length {header_len} too large"
+        )));
+    }
+    let mut header = vec![0u8; header_len];
+    reader.read_exact(&mut header).map_err(IoError::Io)?;
+    let header = String::from_utf8_lossy(&header);
+
+    let descr = header_value(&header, "descr")
+        .and_then(|v| {
+            let quote = v.chars().next().filter(|&c| c == '\'' || c == '"')?;
+            let v = &v[1..];
+            Some(v[..v.find(quote)?].to_string())
+        })
+        .ok_or_else(|| IoError::InvalidData("npy header missing 'descr'".to_string()))?;
+    if header_value(&header, "fortran_order").is_some_and(|v| v.starts_with("True")) {
+        return Err(IoError::UnsupportedFormat(
+            "Fortran-ordered npy arrays not supported".to_string(),
+        ));
+    }
+    let shape = parse_shape(&header)?;
+
+    match (descr.as_str(), shape.as_slice()) {
+        ("|u1" | "u1" | "<u1" | ">u1", &[h, w]) => {
+            let mut pm = new_npy_pix(w, h, PixelDepth::Bit8)?;
+            let data = read_payload(&mut reader, &[w, h])?;
+            for (i, &v) in data.iter().enumerate() {
+                pm.set_pixel_unchecked((i % w) as u32, (i / w) as u32, v as u32);
+            }
+            Ok(pm.into())
+        }
+        ("<u2" | ">u2", &[h, w]) => {
+            let mut pm = new_npy_pix(w, h, PixelDepth::Bit16)?;
+            let data = read_payload(&mut reader, &[w, h, 2])?;
+            let little = descr.starts_with('<');
+            for (i, b) in data.chunks_exact(2).enumerate() {
+                let v = if little {
+                    u16::from_le_bytes([b[0], b[1]])
+                } else {
+                    u16::from_be_bytes([b[0], b[1]])
+                };
+                pm.set_pixel_unchecked((i % w) as u32, (i / w) as u32, v as u32);
+            }
+            Ok(pm.into())
+        }
+        ("|u1" | "u1" | "<u1" | ">u1", &[h, w, nc @ (3 | 4)]) => {
+            let mut pm = new_npy_pix(w, h, PixelDepth::Bit32)?;
+            pm.set_spp(nc as u32);
+            let data = read_payload(&mut reader, &[w, h, nc])?;
+            for (i, c) in data.chunks_exact(nc).enumerate() {
+                let val = if nc == 4 {
+                    pixel::compose_rgba(c[0], c[1], c[2], c[3])
+                } else {
+                    pixel::compose_rgb(c[0], c[1], c[2])
+                };
+                pm.set_pixel_unchecked((i % w) as u32, (i / w) as u32, val);
+            }
+            Ok(pm.into())
+        }
+        _ => Err(IoError::UnsupportedFormat(format!(
+            "npy dtype {descr} with shape {shape:?} not supported"
+        ))),
+    }
+}
+
+/// Write a Pix as a `.npy` array
+///
+/// See the [module documentation](self) for the depth → dtype/shape mapping.
+pub fn write_npy<W: Write>(pix: &Pix, mut writer: W) -> IoResult<()> {
+    let expanded;
+    let pix = if pix.has_colormap() {
+        expanded = pix.remove_colormap(RemoveColormapTarget::ToFullColor)?;
+        &expanded
+    } else {
+        pix
+    };
+
+    let w = pix.width() as usize;
+    let h = pix.height() as usize;
+    let (descr, shape, data) = match pix.depth() {
+        PixelDepth::Bit8 => {
+            let mut data = Vec::with_capacity(w * h);
+            for y in 0..pix.height() {
+                for x in 0..pix.width() {
+                    data.push(pix.get_pixel_unchecked(x, y) as u8);
+                }
+            }
+            ("|u1", format!("({h}, {w})"), data)
+        }
+        PixelDepth::Bit16 => {
+            let mut data = Vec::with_capacity(w * h * 2);
+            for y in 0..pix.height() {
+                for x in 0..pix.width() {
+                    data.extend_from_slice(&(pix.get_pixel_unchecked(x, y) as u16).to_le_bytes());
+                }
+            }
+            ("<u2", format!("({h}, {w})"), data)
+        }
+        PixelDepth::Bit32 => {
+            let nc = if pix.spp() == 4 { 4 } else { 3 };
+            let mut data = Vec::with_capacity(w * h * nc);
+            for y in 0..pix.height() {
+                for x in 0..pix.width() {
+                    let (r, g, b, a) = pixel::extract_rgba(pix.get_pixel_unchecked(x, y));
+                    data.extend_from_slice(&[r, g, b, a][..nc]);
+                }
+            }
+            ("|u1", format!("({h}, {w}, {nc})"), data)
+        }
+        d => {
+            return Err(IoError::UnsupportedFormat(format!(
+                "npy output requires 8, 16 or 32 bpp (got {} bpp)",
+                d.bits()
+            )));
+        }
+    };
+
+    let mut header = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}");
+    // Pad with spaces so the payload starts on an aligned offset; the
+    // header always ends with a newline.
+    let unpadded = NPY_MAGIC.len() + 2 + 2 + header.len() + 1;
+    let padding = (NPY_ALIGN - unpadded % NPY_ALIGN) % NPY_ALIGN;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+
+    writer.write_all(NPY_MAGIC).map_err(IoError::Io)?;
+    writer.write_all(&[1, 0]).map_err(IoError::Io)?;
+    writer
+        .write_all(&(header.len() as u16).to_le_bytes())
+        .map_err(IoError::Io)?;
+    writer.write_all(header.as_bytes()).map_err(IoError::Io)?;
+    writer.write_all(&data).map_err(IoError::Io)?;
+    Ok(())
+}
+
+/// Read a `.npy` file from a path
+pub fn read_npy_file<P: AsRef<Path>>(path: P) -> IoResult<Pix> {
+    let file = File::open(path).map_err(IoError::Io)?;
+    read_npy(BufReader::new(file))
+}
+
+/// Write a Pix to a `.npy` file at a path
+pub fn write_npy_file<P: AsRef<Path>>(pix: &Pix, path: P) -> IoResult<()> {
+    let file = File::create(path).map_err(IoError::Io)?;
+    let mut writer = BufWriter::new(file);
+    write_npy(pix, &mut writer)?;
+    writer.flush().map_err(IoError::Io)
+}
+
+/// Raw text of the value for `key` in the npy header dict.
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let single = format!("'{key}'");
+    let double = format!("\"{key}\"");
+    let start = header
+        .find(&single)
+        .map(|i| i + single.len())
+        .or_else(|| header.find(&double).map(|i| i + double.len()))?;
+    let rest = header[start..].trim_start();
+    Some(rest.strip_prefix(':')?.trim_start())
+}
+
+/// Parse the `shape` tuple of the npy header dict.
+fn parse_shape(header: &str) -> IoResult<Vec<usize>> {
+    let invalid = || IoError::InvalidData("npy header has invalid 'shape'".to_string());
+    let value = header_value(header, "shape").ok_or_else(invalid)?;
+    let inner = value.strip_prefix('(').ok_or_else(invalid)?;
+    let inner = &inner[..inner.find(')').ok_or_else(invalid)?];
+    let shape = inner
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<usize>().map_err(|_| invalid()))
+        .collect::<IoResult<Vec<_>>>()?;
+    if shape.iter().any(|&d| d == 0 || d > i32::MAX as usize) {
+        return Err(invalid());
+    }
+    Ok(shape)
+}
+
+/// Create the destination Pix for an `h x w` array, rejecting sizes that
+/// do not fit in a Pix or exceed the reader's limits.
+fn new_npy_pix(w: usize, h: usize, depth: PixelDepth) -> IoResult<PixMut> {
+    let too_large = || IoError::InvalidData(format!("npy array {h}x{w} too large"));
+    let w = u32::try_from(w).map_err(|_| too_large())?;
+    let h = u32::try_from(h).map_err(|_| too_large())?;
+    if w > NPY_MAX_WIDTH || h > NPY_MAX_HEIGHT || w as u64 * h as u64 > NPY_MAX_AREA {
+        return Err(too_large());
+    }
+    Ok(Pix::new(w, h, depth)?.to_mut())
+}
+
+/// Read the array payload, whose length is the product of `dims` bytes.
+fn read_payload<R: Read>(reader: &mut R, dims: &[usize]) -> IoResult<Vec<u8>> {
+    let len = dims
+        .iter()
+        .try_fold(1usize, |acc, &d| acc.checked_mul(d))
+        .ok_or_else(|| IoError::InvalidData("npy payload size overflows".to_string()))?;
+    let mut data = vec![0u8; len];
+    reader
+        .read_exact(&mut data)
+        .map_err(|_| IoError::InvalidData("npy payload truncated".to_string()))?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_npy_header_is_aligned() {
+        let pix = Pix::new(7, 3, PixelDepth::Bit8).unwrap();
+        let mut buf = Vec::new();
+        write_npy(&pix, &mut buf).unwrap();
+        let header_len = u16::from_le_bytes([buf[8], buf[9]]) as usize;
+        assert_eq!((10 + header_len) % NPY_ALIGN, 0);
+        assert_eq!(buf[10 + header_len - 1], b'\n');
+        assert_eq!(buf.len(), 10 + header_len + 21);
+    }
+
+    #[test]
+    fn test_parse_shape() {
+        let h = "{'descr': '|u1', 'fortran_order': False, 'shape': (4, 5, 3), }";
+        assert_eq!(parse_shape(h).unwrap(), vec![4, 5, 3]);
+        let h = "{'descr': '<u2', 'fortran_order': False, 'shape': (2,), }";
+        assert_eq!(parse_shape(h).unwrap(), vec![2]);
+    }
+
+    /// `.npy` bytes with the given header dict and no payload.
+    fn npy_with_header(dict: &str) -> Vec<u8> {
+        let mut buf = NPY_MAGIC.to_vec();
+        buf.extend_from_slice(&[1, 0]);
+        buf.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+        buf.extend_from_slice(dict.as_bytes());
+        buf
+    }
+
+    #[test]
+    fn test_read_npy_rejects_huge_shape() {
+        // Each dimension is valid on its own, but the image is far too large;
+        // this must fail cleanly instead of allocating or overflowing
+        for shape in [
+            "(2000000000, 2000000000)",
+            "(1000000, 1000000, 4)",
+            "(3, 2000000)",
+        ] {
+            let dict = format!("{{'descr': '|u1', 'fortran_order': False, 'shape': {shape}, }}");
+            let err = read_npy(npy_with_header(&dict).as_slice()).unwrap_err();
+            assert!(matches!(err, IoError::InvalidData(_)), "{shape}: {err:?}");
+        }
+
+        // Declared size within limits but payload missing
+        let dict = "{'descr': '<u2', 'fortran_order': False, 'shape': (10, 10), }";
+        assert!(read_npy(npy_with_header(dict).as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_read_npy_rejects_huge_header() {
+        let mut buf = NPY_MAGIC.to_vec();
+        buf.extend_from_slice(&[2, 0]);
+        buf.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            read_npy(buf.as_slice()).unwrap_err(),
+            IoError::InvalidData(_)
+        ));
+    }
+}
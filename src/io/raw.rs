@@ -0,0 +1,101 @@
+//! Headerless raw pixel dumps
+//!
+//! The simplest possible exchange format: pixel values in row-major order
+//! with no header, so the reader must be told the dimensions and depth.
+//!
+//! | Pix           | bytes per pixel | layout                                  |
+//! | ------------- | --------------- | --------------------------------------- |
+//! | 1, 2, 4 bpp   | packed          | MSB first, each row padded to a byte    |
+//! | 8 bpp         | 1               | value                                   |
+//! | 16 bpp        | 2               | big-endian value                        |
+//! | 32 bpp        | 4               | R, G, B, A                              |
+//!
+//! Apart from the byte padding of sub-byte rows there is no row padding, so
+//! a `w x h` image at 8 bpp is exactly `w * h` bytes.  Colormaps are not
+//! stored; colormapped images are written as their indices.
+
+use crate::core::{Pix, PixelDepth, pixel};
+use crate::io::{IoError, IoResult};
+use std::io::{Read, Write};
+
+/// Read a raw pixel dump of known size
+///
+/// `reader` must supply exactly the bytes [`write_raw`] produces for a
+/// `width x height` image at `depth`; trailing data is left unread.
+///
+/// # Errors
+///
+/// Returns an error if the size is zero or the data is truncated.
+pub fn read_raw<R: Read>(
+    mut reader: R,
+    width: u32,
+    height: u32,
+    depth: PixelDepth,
+) -> IoResult<Pix> {
+    let mut pm = Pix::new(width, height, depth)?.to_mut();
+    let row_bytes = raw_row_bytes(width, depth);
+    let mut row = vec![0u8; row_bytes];
+    for y in 0..height {
+        reader
+            .read_exact(&mut row)
+            .map_err(|_| IoError::InvalidData(format!("raw data truncated at row {y}")))?;
+        for x in 0..width {
+            let xi = x as usize;
+            let val = match depth {
+                PixelDepth::Bit1 | PixelDepth::Bit2 | PixelDepth::Bit4 => {
+                    let bits = depth.bits() as usize;
+                    let bit = xi * bits;
+                    let shift = 8 - bits - bit % 8;
+                    ((row[bit / 8] >> shift) as u32) & depth.max_value()
+                }
+                PixelDepth::Bit8 => row[xi] as u32,
+                PixelDepth::Bit16 => u16::from_be_bytes([row[2 * xi], row[2 * xi + 1]]) as u32,
+                PixelDepth::Bit32 => {
+                    let c = &row[4 * xi..4 * xi + 4];
+                    pixel::compose_rgba(c[0], c[1], c[2], c[3])
+                }
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    Ok(pm.into())
+}
+
+/// Write a Pix as a raw pixel dump
+///
+/// See the [module documentation](self) for the byte layout.  The
+/// dimensions and depth are not recorded; keep them alongside the data to
+/// read it back with [`read_raw`].
+pub fn write_raw<W: Write>(pix: &Pix, mut writer: W) -> IoResult<()> {
+    let depth = pix.depth();
+    let mut row = vec![0u8; raw_row_bytes(pix.width(), depth)];
+    for y in 0..pix.height() {
+        row.fill(0);
+        for x in 0..pix.width() {
+            let xi = x as usize;
+            let val = pix.get_pixel_unchecked(x, y);
+            match depth {
+                PixelDepth::Bit1 | PixelDepth::Bit2 | PixelDepth::Bit4 => {
+                    let bits = depth.bits() as usize;
+                    let bit = xi * bits;
+                    row[bit / 8] |= (val as u8) << (8 - bits - bit % 8);
+                }
+                PixelDepth::Bit8 => row[xi] = val as u8,
+                PixelDepth::Bit16 => {
+                    row[2 * xi..2 * xi + 2].copy_from_slice(&(val as u16).to_be_bytes())
+                }
+                PixelDepth::Bit32 => {
+                    let (r, g, b, a) = pixel::extract_rgba(val);
+                    row[4 * xi..4 * xi + 4].copy_from_slice(&[r, g, b, a]);
+                }
+            }
+        }
+        writer.write_all(&row).map_err(IoError::Io)?;
+    }
+    Ok(())
+}
+
+/// Bytes in one row of a raw dump.
+fn raw_row_bytes(width: u32, depth: PixelDepth) -> usize {
+    (width as usize * depth.bits() as usize).div_ceil(8)
+}
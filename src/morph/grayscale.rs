@@ -299,6 +299,55 @@ pub fn close_gray(pix: &Pix, hsize: u32, vsize: u32) -> MorphResult<Pix> {
     erode_gray(&dilated, hsize, vsize)
 }
 
+/// Open a grayscale image with a replicated border
+///
+/// Like [`open_gray`], but the image is first extended by the SE
+/// half-size on each side with copies of its edge pixels, and the
+/// border is removed afterwards.  Pixels near the image edge are then
+/// computed as if the image continued unchanged beyond it, instead of
+/// being affected by the implicit out-of-bounds values.
+///
+/// # Arguments
+///
+/// * `pix` - 8-bpp grayscale image
+/// * `hsize` - Horizontal size of the brick SE
+/// * `vsize` - Vertical size of the brick SE
+pub fn open_gray_safe(pix: &Pix, hsize: u32, vsize: u32) -> MorphResult<Pix> {
+    with_continued_border(pix, hsize, vsize, open_gray)
+}
+
+/// Close a grayscale image with a replicated border
+///
+/// Like [`close_gray`], but the image is first extended by the SE
+/// half-size on each side with copies of its edge pixels, and the
+/// border is removed afterwards.  This avoids darkening or brightening
+/// a frame along the image edges.
+///
+/// # Arguments
+///
+/// * `pix` - 8-bpp grayscale image
+/// * `hsize` - Horizontal size of the brick SE
+/// * `vsize` - Vertical size of the brick SE
+pub fn close_gray_safe(pix: &Pix, hsize: u32, vsize: u32) -> MorphResult<Pix> {
+    with_continued_border(pix, hsize, vsize, close_gray)
+}
+
+/// Apply a brick operation to `pix` extended by a replicated border of
+/// the SE half-size, then strip the border.
+fn with_continued_border(
+    pix: &Pix,
+    hsize: u32,
+    vsize: u32,
+    op: fn(&Pix, u32, u32) -> MorphResult<Pix>,
+) -> MorphResult<Pix> {
+    check_grayscale(pix)?;
+    let (hsize, vsize) = ensure_odd(hsize, vsize)?;
+    let (bx, by) = (hsize / 2, vsize / 2);
+    let padded = pix.add_continued_border(bx, bx, by, by)?;
+    let result = op(&padded, hsize, vsize)?;
+    Ok(result.remove_border_general(bx, bx, by, by)?)
+}
+
 /// Grayscale morphological gradient (dilation - erosion)
 ///
 /// Highlights edges and boundaries in the image.
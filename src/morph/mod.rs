@@ -40,13 +40,14 @@ pub use binreduce::{make_subsample_tab_2x, reduce_binary_2};
 
 // Re-export commonly used grayscale morphology functions
 pub use grayscale::{
-    bottom_hat_gray, close_gray, dilate_gray, erode_gray, gradient_gray, open_gray, top_hat_gray,
+    bottom_hat_gray, close_gray, close_gray_safe, dilate_gray, erode_gray, gradient_gray,
+    open_gray, open_gray_safe, top_hat_gray,
 };
 
 // Re-export commonly used color morphology functions
 pub use color::{
     ColorChannel, bottom_hat_color, close_color, dilate_color, erode_color, gradient_color,
-    open_color, top_hat_color,
+    morph_gradient_color, open_color, top_hat_color,
 };
 
 // Re-export thinning functions
@@ -3,7 +3,7 @@
 //! This module provides functions to apply the built disparity models
 //! to dewarp images.
 
-use crate::core::{FPix, Pix, PixelDepth};
+use crate::core::{FPix, Pix, PixelDepth, Pta};
 use crate::recog::{RecogError, RecogResult};
 
 use super::types::Dewarp;
@@ -235,6 +235,62 @@ pub fn apply_disparity(pix: &Pix, dewarp: &Dewarp, gray_in: u8) -> RecogResult<P
     }
 }
 
+/// Straighten a single curved text line given its sampled baseline
+///
+/// Each column is shifted vertically so that the baseline, linearly
+/// interpolated between the points of `baseline`, lands on row
+/// `target_y`.  Columns left of the first point or right of the last
+/// use the nearest end point.  Pixels brought in from outside the image
+/// are white.
+///
+/// This is a lightweight alternative to a full [`Dewarp`] model when the
+/// baseline of a line is already known.
+///
+/// # Arguments
+///
+/// * `pix` - Input image (1, 8, or 32 bpp)
+/// * `baseline` - Baseline points `(x, y)`, in any order
+/// * `target_y` - Row on which the straightened baseline lies
+///
+/// # Errors
+///
+/// Returns an error if `baseline` is empty or the depth is unsupported.
+pub fn straighten_line(pix: &Pix, baseline: &Pta, target_y: f32) -> RecogResult<Pix> {
+    if baseline.is_empty() {
+        return Err(RecogError::InvalidParameter(
+            "baseline has no points".to_string(),
+        ));
+    }
+    let mut pts: Vec<(f32, f32)> = baseline.iter().collect();
+    pts.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let w = pix.width();
+    let h = pix.height();
+    let mut disparity = FPix::new(w, h)?;
+    let mut seg = 0;
+    for x in 0..w {
+        let xf = x as f32;
+        while seg + 1 < pts.len() && pts[seg + 1].0 <= xf {
+            seg += 1;
+        }
+        let base_y = if xf <= pts[0].0 {
+            pts[0].1
+        } else if seg + 1 == pts.len() {
+            pts[seg].1
+        } else {
+            let (x0, y0) = pts[seg];
+            let (x1, y1) = pts[seg + 1];
+            y0 + (y1 - y0) * (xf - x0) / (x1 - x0)
+        };
+        let d = target_y - base_y;
+        for y in 0..h {
+            disparity.set_pixel_unchecked(x, y, d);
+        }
+    }
+
+    apply_vertical_disparity(pix, &disparity, 255)
+}
+
 /// Estimate the required disparity from text line analysis
 ///
 /// This provides a quick estimate without building a full model.
@@ -70,7 +70,7 @@ mod types;
 
 pub use apply::{
     apply_disparity, apply_horizontal_disparity, apply_vertical_disparity,
-    estimate_disparity_magnitude,
+    estimate_disparity_magnitude, straighten_line,
 };
 pub use dewarpa::Dewarpa;
 pub use model::{build_horizontal_disparity, build_vertical_disparity, populate_full_resolution};
@@ -0,0 +1,162 @@
+//! Document outline detection
+//!
+//! Finds the outline of a photographed page so it can be cut out and
+//! rectified with [`crate::transform::unwarp_quad`].
+
+use crate::color::{compute_otsu_threshold, threshold_to_binary};
+use crate::core::{Pix, PixelDepth, Pta};
+use crate::filter::{EdgeOrientation, sobel_edge};
+use crate::morph::close_brick;
+use crate::recog::RecogResult;
+use crate::region::{ConnectivityType, conncomp_pixa, fill_holes, mask_to_polygons};
+
+/// Sobel responses below this are never treated as edges
+const DOC_MIN_EDGE_STRENGTH: u8 = 16;
+
+/// Size of the closing that joins broken outline edges
+const DOC_CLOSE_SIZE: u32 = 5;
+
+/// Minimum fraction of the image a document must cover
+const DOC_MIN_AREA_FRACTION: f64 = 0.1;
+
+/// Douglas-Peucker tolerances tried, as fractions of the region's
+/// bounding box half-perimeter
+const DOC_POLY_TOLERANCES: [f64; 4] = [0.01, 0.02, 0.03, 0.05];
+
+/// Find the four corners of a document in a photograph
+///
+/// Looks for the largest region enclosed by strong edges (Sobel edges,
+/// thresholded with Otsu's method, closed and hole-filled), traces its
+/// outline and approximates it by a polygon.  If the polygon is a convex
+/// quadrilateral that covers at least 10% of the image and fits the
+/// region closely, its corners are returned ordered top-left, top-right,
+/// bottom-right, bottom-left, as expected by
+/// [`crate::transform::unwarp_quad`].
+///
+/// The document must lie entirely inside the image and contrast with the
+/// background, e.g. a white sheet on a darker table.
+///
+/// # Arguments
+/// * `pix` - Image of any depth; converted to 8 bpp grayscale
+///
+/// # Returns
+/// The four corners, or `None` if no suitable quadrilateral is found.
+pub fn find_document_corners(pix: &Pix) -> RecogResult<Option<Pta>> {
+    let gray = if pix.depth() == PixelDepth::Bit8 && !pix.has_colormap() {
+        pix.deep_clone()
+    } else {
+        pix.convert_to_8()?
+    };
+    if gray.width() < 3 || gray.height() < 3 {
+        return Ok(None);
+    }
+
+    // Binary edge map
+    let edges = sobel_edge(&gray, EdgeOrientation::All)?;
+    let thresh = compute_otsu_threshold(&edges)?.max(DOC_MIN_EDGE_STRENGTH);
+    // Edges are the bright pixels, so invert the dark-is-ON threshold
+    let binary = threshold_to_binary(&edges, thresh)?.invert();
+    let closed = close_brick(&binary, DOC_CLOSE_SIZE, DOC_CLOSE_SIZE)?;
+    let regions = fill_holes(&closed, ConnectivityType::FourWay)?;
+
+    // Largest enclosed region
+    let (boxa, pixa) = conncomp_pixa(&regions, ConnectivityType::EightWay)?;
+    let Some((index, area)) = pixa
+        .iter()
+        .map(|p| p.count_pixels())
+        .enumerate()
+        .max_by_key(|&(_, n)| n)
+    else {
+        return Ok(None);
+    };
+    let image_area = gray.width() as f64 * gray.height() as f64;
+    if (area as f64) < DOC_MIN_AREA_FRACTION * image_area {
+        return Ok(None);
+    }
+    let (Some(region), Some(bounds)) = (pixa.get(index), boxa.get(index)) else {
+        return Ok(None);
+    };
+
+    for tolerance in DOC_POLY_TOLERANCES {
+        let epsilon = tolerance * (bounds.w + bounds.h) as f64;
+        let polygons = mask_to_polygons(region, ConnectivityType::EightWay, epsilon as f32)?;
+        let Some(poly) = polygons.get(0) else {
+            continue;
+        };
+        if poly.len() != 4 {
+            continue;
+        }
+        let mut corners: Vec<(f64, f64)> = poly
+            .iter()
+            .map(|(x, y)| (x as f64 + bounds.x as f64, y as f64 + bounds.y as f64))
+            .collect();
+        if !is_convex(&corners) {
+            continue;
+        }
+        // The outline is traced through boundary pixel centers, so the
+        // polygon is slightly smaller than the region
+        let fit = polygon_area(&corners) / area as f64;
+        if !(0.85..=1.1).contains(&fit) {
+            continue;
+        }
+        order_corners(&mut corners);
+        let mut pta = Pta::with_capacity(4);
+        for (x, y) in corners {
+            pta.push(x as f32, y as f32);
+        }
+        return Ok(Some(pta));
+    }
+    Ok(None)
+}
+
+/// Absolute area of a simple polygon (shoelace formula).
+fn polygon_area(pts: &[(f64, f64)]) -> f64 {
+    let n = pts.len();
+    let twice: f64 = (0..n)
+        .map(|i| {
+            let (x0, y0) = pts[i];
+            let (x1, y1) = pts[(i + 1) % n];
+            x0 * y1 - x1 * y0
+        })
+        .sum();
+    twice.abs() / 2.0
+}
+
+/// Whether all turns of a closed polygon have the same direction.
+fn is_convex(pts: &[(f64, f64)]) -> bool {
+    let n = pts.len();
+    let mut sign = 0.0f64;
+    for i in 0..n {
+        let (x0, y0) = pts[i];
+        let (x1, y1) = pts[(i + 1) % n];
+        let (x2, y2) = pts[(i + 2) % n];
+        let cross = (x1 - x0) * (y2 - y1) - (y1 - y0) * (x2 - x1);
+        if cross == 0.0 {
+            return false;
+        }
+        if sign != 0.0 && cross.signum() != sign {
+            return false;
+        }
+        sign = cross.signum();
+    }
+    true
+}
+
+/// Order quadrilateral corners TL, TR, BR, BL.
+///
+/// Corners are sorted clockwise (on screen, with y down) around their
+/// centroid, starting from the one with the smallest `x + y`.
+fn order_corners(pts: &mut [(f64, f64)]) {
+    let n = pts.len() as f64;
+    let cx = pts.iter().map(|p| p.0).sum::<f64>() / n;
+    let cy = pts.iter().map(|p| p.1).sum::<f64>() / n;
+    pts.sort_by(|a, b| {
+        let ta = (a.1 - cy).atan2(a.0 - cx);
+        let tb = (b.1 - cy).atan2(b.0 - cx);
+        ta.total_cmp(&tb)
+    });
+    let start = (0..pts.len())
+        .min_by(|&i, &j| (pts[i].0 + pts[i].1).total_cmp(&(pts[j].0 + pts[j].1)))
+        .unwrap_or(0);
+    pts.rotate_left(start);
+}
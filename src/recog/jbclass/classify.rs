@@ -651,7 +651,7 @@ impl JbData {
     }
 
     /// Extracts individual templates from the composite image
-    fn extract_templates(&self) -> RecogResult<Vec<Pix>> {
+    pub(super) fn extract_templates(&self) -> RecogResult<Vec<Pix>> {
         let cols = ((self.nclass as f32).sqrt().ceil() as usize).max(1);
 
         let mut templates = Vec::with_capacity(self.nclass);
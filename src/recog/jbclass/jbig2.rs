@@ -0,0 +1,1071 @@
+//! JBIG2 embedded-stream encoding
+//!
+//! Encodes [`JbData`] as JBIG2 segments (ITU-T T.88) in the embedded
+//! stream organisation used by PDF's `/JBIG2Decode` filter: a globals
+//! stream holding one symbol dictionary with every class template, and
+//! one stream per page holding a page information segment and a text
+//! region that places the symbols.  As required for PDF, the file
+//! header, end-of-page and end-of-file segments are omitted.
+//!
+//! All coding uses the MQ arithmetic coder with generic template 0 and
+//! no refinement.  Each symbol is placed so that its template, less the
+//! [`TEMPLATE_BORDER`] added by the classifier, covers its component, so
+//! a page whose components all match their templates exactly decodes to
+//! the input page.
+//!
+//! The output corresponds to the symbol mode of jbig2enc.
+
+use crate::core::Pix;
+use crate::recog::error::{RecogError, RecogResult};
+
+use super::types::{JbData, TEMPLATE_BORDER};
+
+/// Segment type: symbol dictionary
+const SEG_SYMBOL_DICT: u8 = 0;
+
+/// Segment type: immediate text region
+const SEG_TEXT_REGION: u8 = 6;
+
+/// Segment type: page information
+const SEG_PAGE_INFO: u8 = 48;
+
+/// Adaptive template pixels for generic template 0
+const GB_AT: [(i8, i8); 4] = [(3, -1), (-3, -1), (2, -2), (-2, -2)];
+
+/// Text region flags: REFCORNER = TOPLEFT, one strip, OR combination
+const TEXT_REGION_FLAGS: u16 = 1 << 4;
+
+/// MQ coder probability estimation table: (Qe, NMPS, NLPS, SWITCH)
+const QE_TABLE: [(u32, u8, u8, bool); 47] = [
+    (0x5601, 1, 1, true),
+    (0x3401, 2, 6, false),
+    (0x1801, 3, 9, false),
+    (0x0AC1, 4, 12, false),
+    (0x0521, 5, 29, false),
+    (0x0221, 38, 33, false),
+    (0x5601, 7, 6, true),
+    (0x5401, 8, 14, false),
+    (0x4801, 9, 14, false),
+    (0x3801, 10, 14, false),
+    (0x3001, 11, 17, false),
+    (0x2401, 12, 18, false),
+    (0x1C01, 13, 20, false),
+    (0x1601, 29, 21, false),
+    (0x5601, 15, 14, true),
+    (0x5401, 16, 14, false),
+    (0x5101, 17, 15, false),
+    (0x4801, 18, 16, false),
+    (0x3801, 19, 17, false),
+    (0x3401, 20, 18, false),
+    (0x3001, 21, 19, false),
+    (0x2801, 22, 19, false),
+    (0x2401, 23, 20, false),
+    (0x2201, 24, 21, false),
+    (0x1C01, 25, 22, false),
+    (0x1801, 26, 23, false),
+    (0x1601, 27, 24, false),
+    (0x1401, 28, 25, false),
+    (0x1201, 29, 26, false),
+    (0x1101, 30, 27, false),
+    (0x0AC1, 31, 28, false),
+    (0x09C1, 32, 29, false),
+    (0x08A1, 33, 30, false),
+    (0x0521, 34, 31, false),
+    (0x0441, 35, 32, false),
+    (0x02A1, 36, 33, false),
+    (0x0221, 37, 34, false),
+    (0x0141, 38, 35, false),
+    (0x0111, 39, 36, false),
+    (0x0085, 40, 37, false),
+    (0x0049, 41, 38, false),
+    (0x0025, 42, 39, false),
+    (0x0015, 43, 40, false),
+    (0x0009, 44, 41, false),
+    (0x0005, 45, 42, false),
+    (0x0001, 45, 43, false),
+    (0x5601, 46, 46, false),
+];
+
+/// Integer arithmetic coding ranges: (prefix bits, value bits, offset)
+const INT_RANGES: [(&[u8], u32, u32); 6] = [
+    (&[0], 2, 0),
+    (&[1, 0], 4, 4),
+    (&[1, 1, 0], 6, 20),
+    (&[1, 1, 1, 0], 8, 84),
+    (&[1, 1, 1, 1, 0], 12, 340),
+    (&[1, 1, 1, 1, 1], 32, 4436),
+];
+
+/// MQ arithmetic encoder (T.88 Annex E.2)
+///
+/// Each context is one byte holding `(index << 1) | mps`.
+struct MqEncoder {
+    a: u32,
+    c: u32,
+    ct: u32,
+    /// Output bytes; `buf[0]` is the byte before the stream start, which
+    /// only absorbs carries and is dropped on flush.
+    buf: Vec<u8>,
+}
+
+impl MqEncoder {
+    fn new() -> Self {
+        Self {
+            a: 0x8000,
+            c: 0,
+            ct: 12,
+            buf: vec![0],
+        }
+    }
+
+    fn encode(&mut self, cx: &mut u8, d: u8) {
+        let (qe, nmps, nlps, switch) = QE_TABLE[(*cx >> 1) as usize];
+        let mps = *cx & 1;
+        self.a -= qe;
+        if d == mps {
+            if self.a & 0x8000 != 0 {
+                self.c += qe;
+                return;
+            }
+            if self.a < qe {
+                self.a = qe;
+            } else {
+                self.c += qe;
+            }
+            *cx = (nmps << 1) | mps;
+        } else {
+            if self.a < qe {
+                self.c += qe;
+            } else {
+                self.a = qe;
+            }
+            let mps = if switch { 1 - mps } else { mps };
+            *cx = (nlps << 1) | mps;
+        }
+        self.renormalize();
+    }
+
+    fn renormalize(&mut self) {
+        loop {
+            self.a <<= 1;
+            self.c <<= 1;
+            self.ct -= 1;
+            if self.ct == 0 {
+                self.byte_out();
+            }
+            if self.a & 0x8000 != 0 {
+                break;
+            }
+        }
+    }
+
+    fn byte_out(&mut self) {
+        let last = self.buf.len() - 1;
+        if self.buf[last] == 0xFF {
+            self.emit_7();
+        } else if self.c < 0x800_0000 {
+            self.emit_8();
+        } else {
+            self.buf[last] += 1;
+            if self.buf[last] == 0xFF {
+                self.c &= 0x7FF_FFFF;
+                self.emit_7();
+            } else {
+                self.emit_8();
+            }
+        }
+    }
+
+    /// Emit 7 bits after a 0xFF byte (bit stuffing)
+    fn emit_7(&mut self) {
+        self.buf.push((self.c >> 20) as u8);
+        self.c &= 0xF_FFFF;
+        self.ct = 7;
+    }
+
+    fn emit_8(&mut self) {
+        self.buf.push((self.c >> 19) as u8);
+        self.c &= 0x7_FFFF;
+        self.ct = 8;
+    }
+
+    /// Flush the coder and return the coded bytes, terminated by the
+    /// 0xFF 0xAC marker.
+    fn finish(mut self) -> Vec<u8> {
+        let temp = self.c + self.a;
+        self.c |= 0xFFFF;
+        if self.c >= temp {
+            self.c -= 0x8000;
+        }
+        self.c <<= self.ct;
+        self.byte_out();
+        self.c <<= self.ct;
+        self.byte_out();
+        if self.buf.last() != Some(&0xFF) {
+            self.buf.push(0xFF);
+        }
+        self.buf.push(0xAC);
+        self.buf.remove(0);
+        self.buf
+    }
+}
+
+/// Contexts for one integer arithmetic coding procedure (T.88 Annex A.2)
+struct IntContext([u8; 512]);
+
+impl IntContext {
+    fn new() -> Self {
+        Self([0; 512])
+    }
+
+    /// Encode `value`, or the out-of-band value for `None`
+    fn encode(&mut self, enc: &mut MqEncoder, value: Option<i32>) {
+        let (sign, mag) = match value {
+            Some(v) => (u8::from(v < 0), v.unsigned_abs()),
+            None => (1, 0),
+        };
+        let &(prefix, nbits, offset) = INT_RANGES
+            .iter()
+            .rev()
+            .find(|r| mag >= r.2)
+            .expect("first range starts at 0");
+        let mut prev = 1usize;
+        let mut put = |bit: u8| {
+            enc.encode(&mut self.0[prev], bit);
+            prev = if prev < 256 {
+                (prev << 1) | bit as usize
+            } else {
+                (((prev << 1) | bit as usize) & 511) | 256
+            };
+        };
+        put(sign);
+        for &bit in prefix {
+            put(bit);
+        }
+        let v = mag - offset;
+        for i in (0..nbits).rev() {
+            put(((v >> i) & 1) as u8);
+        }
+    }
+}
+
+/// Contexts for the symbol ID coding procedure (T.88 Annex A.3)
+struct IdContext {
+    len: u32,
+    cx: Vec<u8>,
+}
+
+impl IdContext {
+    fn new(len: u32) -> Self {
+        Self {
+            len,
+            cx: vec![0; 1 << len],
+        }
+    }
+
+    fn encode(&mut self, enc: &mut MqEncoder, id: u32) {
+        let mut prev = 1usize;
+        for i in (0..self.len).rev() {
+            let bit = ((id >> i) & 1) as u8;
+            enc.encode(&mut self.cx[prev], bit);
+            prev = (prev << 1) | bit as usize;
+        }
+    }
+}
+
+/// A symbol bitmap, one byte per pixel
+struct Bitmap {
+    w: u32,
+    h: u32,
+    data: Vec<u8>,
+}
+
+impl Bitmap {
+    /// Convert a 1 bpp template, dropping blank columns and rows at the
+    /// right and bottom so the upper-left placement is unchanged.
+    fn from_template(pix: &Pix) -> Self {
+        let mut w = 1;
+        let mut h = 1;
+        for y in 0..pix.height() {
+            for x in 0..pix.width() {
+                if pix.get_pixel_unchecked(x, y) != 0 {
+                    w = w.max(x + 1);
+                    h = h.max(y + 1);
+                }
+            }
+        }
+        let mut data = Vec::with_capacity((w * h) as usize);
+        for y in 0..h {
+            for x in 0..w {
+                data.push(pix.get_pixel_unchecked(x, y) as u8);
+            }
+        }
+        Self { w, h, data }
+    }
+
+    fn get(&self, x: i64, y: i64) -> usize {
+        if x < 0 || y < 0 || x >= self.w as i64 || y >= self.h as i64 {
+            0
+        } else {
+            self.data[(y as u32 * self.w + x as u32) as usize] as usize
+        }
+    }
+}
+
+/// Context of generic region template 0 at (x, y) (T.88 6.2.5.3)
+fn generic_context(bm: &Bitmap, x: i64, y: i64) -> usize {
+    let at = |i: usize| bm.get(x + GB_AT[i].0 as i64, y + GB_AT[i].1 as i64);
+    bm.get(x - 1, y)
+        | bm.get(x - 2, y) << 1
+        | bm.get(x - 3, y) << 2
+        | bm.get(x - 4, y) << 3
+        | at(0) << 4
+        | bm.get(x + 2, y - 1) << 5
+        | bm.get(x + 1, y - 1) << 6
+        | bm.get(x, y - 1) << 7
+        | bm.get(x - 1, y - 1) << 8
+        | bm.get(x - 2, y - 1) << 9
+        | at(1) << 10
+        | at(2) << 11
+        | bm.get(x + 1, y - 2) << 12
+        | bm.get(x, y - 2) << 13
+        | bm.get(x - 1, y - 2) << 14
+        | at(3) << 15
+}
+
+/// Generic region encoding with template 0 and no typical prediction
+fn encode_generic(enc: &mut MqEncoder, gb: &mut [u8], bm: &Bitmap) {
+    for y in 0..bm.h as i64 {
+        for x in 0..bm.w as i64 {
+            let cx = generic_context(bm, x, y);
+            enc.encode(&mut gb[cx], bm.get(x, y) as u8);
+        }
+    }
+}
+
+/// Append a segment with its header (T.88 7.2)
+fn write_segment(
+    out: &mut Vec<u8>,
+    number: u32,
+    seg_type: u8,
+    page: u32,
+    referred: &[u32],
+    data: &[u8],
+) {
+    out.extend_from_slice(&number.to_be_bytes());
+    let large_page = page > 255;
+    out.push(seg_type | if large_page { 0x40 } else { 0 });
+    out.push((referred.len() as u8) << 5);
+    for &r in referred {
+        if number <= 256 {
+            out.push(r as u8);
+        } else if number <= 65536 {
+            out.extend_from_slice(&(r as u16).to_be_bytes());
+        } else {
+            out.extend_from_slice(&r.to_be_bytes());
+        }
+    }
+    if large_page {
+        out.extend_from_slice(&page.to_be_bytes());
+    } else {
+        out.push(page as u8);
+    }
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(data);
+}
+
+impl JbData {
+    /// Encode the data as JBIG2 streams for embedding in a PDF
+    ///
+    /// Returns the globals stream, holding a symbol dictionary with all
+    /// class templates, and one stream per page, holding the page
+    /// information and a text region that references the globals.  Each
+    /// page stream is written as page 1 of its own embedded stream, as
+    /// expected for a PDF `/JBIG2Decode` image with `/JBIG2Globals`.
+    ///
+    /// Templates are placed at `ptaul` less [`TEMPLATE_BORDER`], so each
+    /// page decodes to the image given by [`JbData::render_page`] shifted
+    /// up and to the left by the border.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the page dimensions are not positive or the
+    /// component arrays are inconsistent.
+    pub fn to_pdf_streams(&self) -> RecogResult<(Vec<u8>, Vec<Vec<u8>>)> {
+        if self.w <= 0 || self.h <= 0 {
+            return Err(RecogError::InvalidParameter(format!(
+                "invalid page size {}x{}",
+                self.w, self.h
+            )));
+        }
+        let ncomp = self.naclass.len();
+        if self.napage.len() != ncomp || self.ptaul.len() != ncomp {
+            return Err(RecogError::InvalidParameter(
+                "component arrays differ in length".to_string(),
+            ));
+        }
+        if let Some(&c) = self.naclass.iter().find(|&&c| c >= self.nclass) {
+            return Err(RecogError::InvalidParameter(format!(
+                "class {c} out of range (nclass {})",
+                self.nclass
+            )));
+        }
+
+        let symbols: Vec<Bitmap> = self
+            .extract_templates()?
+            .iter()
+            .map(Bitmap::from_template)
+            .collect();
+
+        // Symbols are coded in height classes of increasing height; the
+        // symbol ID of a class is its position in that order.
+        let mut order: Vec<usize> = (0..symbols.len()).collect();
+        order.sort_by_key(|&i| (symbols[i].h, symbols[i].w));
+        let mut symbol_id = vec![0u32; symbols.len()];
+        for (id, &class) in order.iter().enumerate() {
+            symbol_id[class] = id as u32;
+        }
+
+        let mut globals = Vec::new();
+        let dict = encode_symbol_dictionary(&symbols, &order);
+        write_segment(&mut globals, 0, SEG_SYMBOL_DICT, 0, &[], &dict);
+
+        let code_len = usize::BITS - symbols.len().saturating_sub(1).leading_zeros();
+        let mut pages = Vec::with_capacity(self.npages);
+        for page in 0..self.npages {
+            let mut instances: Vec<(i32, i32, usize)> = (0..ncomp)
+                .filter(|&i| self.napage[i] == page)
+                .map(|i| {
+                    let (x, y) = self.ptaul[i];
+                    (y - TEMPLATE_BORDER, x - TEMPLATE_BORDER, self.naclass[i])
+                })
+                .collect();
+            instances.sort_unstable();
+
+            let mut info = Vec::with_capacity(19);
+            info.extend_from_slice(&(self.w as u32).to_be_bytes());
+            info.extend_from_slice(&(self.h as u32).to_be_bytes());
+            info.extend_from_slice(&[0; 8]); // resolution unknown
+            info.push(0); // default pixel 0, OR combination
+            info.extend_from_slice(&[0; 2]); // not striped
+
+            let text = self.encode_text_region(&symbols, &symbol_id, code_len, &instances);
+
+            let mut stream = Vec::new();
+            write_segment(&mut stream, 1, SEG_PAGE_INFO, 1, &[], &info);
+            write_segment(&mut stream, 2, SEG_TEXT_REGION, 1, &[0], &text);
+            pages.push(stream);
+        }
+        Ok((globals, pages))
+    }
+
+    /// Text region segment data placing `instances`, given as
+    /// (y, x, class) sorted by y then x (T.88 7.4.3, 6.4).
+    fn encode_text_region(
+        &self,
+        symbols: &[Bitmap],
+        symbol_id: &[u32],
+        code_len: u32,
+        instances: &[(i32, i32, usize)],
+    ) -> Vec<u8> {
+        let mut data = Vec::new();
+        // Region segment information: full page at the origin
+        data.extend_from_slice(&(self.w as u32).to_be_bytes());
+        data.extend_from_slice(&(self.h as u32).to_be_bytes());
+        data.extend_from_slice(&[0; 8]);
+        data.push(0);
+        data.extend_from_slice(&TEXT_REGION_FLAGS.to_be_bytes());
+        data.extend_from_slice(&(instances.len() as u32).to_be_bytes());
+
+        let mut enc = MqEncoder::new();
+        let mut iadt = IntContext::new();
+        let mut iafs = IntContext::new();
+        let mut iads = IntContext::new();
+        let mut iaid = IdContext::new(code_len);
+
+        iadt.encode(&mut enc, Some(0));
+        let mut strip_t = 0;
+        let mut first_s = 0;
+        for strip in instances.chunk_by(|a, b| a.0 == b.0) {
+            let t = strip[0].0;
+            iadt.encode(&mut enc, Some(t - strip_t));
+            strip_t = t;
+            let mut cur_s = 0;
+            for (k, &(_, x, class)) in strip.iter().enumerate() {
+                if k == 0 {
+                    iafs.encode(&mut enc, Some(x - first_s));
+                    first_s = x;
+                } else {
+                    iads.encode(&mut enc, Some(x - cur_s));
+                }
+                iaid.encode(&mut enc, symbol_id[class]);
+                cur_s = x + symbols[class].w as i32 - 1;
+            }
+            iads.encode(&mut enc, None);
+        }
+        data.extend_from_slice(&enc.finish());
+        data
+    }
+}
+
+/// Symbol dictionary segment data exporting `symbols` in the given
+/// order (T.88 7.4.2, 6.5)
+fn encode_symbol_dictionary(symbols: &[Bitmap], order: &[usize]) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&0u16.to_be_bytes()); // arithmetic, template 0
+    for (ax, ay) in GB_AT {
+        data.push(ax as u8);
+        data.push(ay as u8);
+    }
+    let n = symbols.len() as u32;
+    data.extend_from_slice(&n.to_be_bytes()); // exported
+    data.extend_from_slice(&n.to_be_bytes()); // new
+
+    let mut enc = MqEncoder::new();
+    let mut iadh = IntContext::new();
+    let mut iadw = IntContext::new();
+    let mut iaex = IntContext::new();
+    let mut gb = vec![0u8; 1 << 16];
+
+    let mut height = 0;
+    for class in order.chunk_by(|&a, &b| symbols[a].h == symbols[b].h) {
+        let h = symbols[class[0]].h as i32;
+        iadh.encode(&mut enc, Some(h - height));
+        height = h;
+        let mut width = 0;
+        for &i in class {
+            let w = symbols[i].w as i32;
+            iadw.encode(&mut enc, Some(w - width));
+            width = w;
+            encode_generic(&mut enc, &mut gb, &symbols[i]);
+        }
+        iadw.encode(&mut enc, None);
+    }
+
+    // Export flags as run lengths: none skipped, then all exported
+    iaex.encode(&mut enc, Some(0));
+    iaex.encode(&mut enc, Some(n as i32));
+    data.extend_from_slice(&enc.finish());
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    //! Round-trip tests against a minimal decoder for the subset of
+    //! T.88 that the encoder produces.
+    //!
+    //! The decoder deliberately shares no tables or context code with the
+    //! encoder: the probability table, integer ranges and template 0
+    //! neighbourhood are transcribed separately from T.88 Table E.1,
+    //! Table A.1 and Figure 3, and the adaptive pixels are read back from
+    //! the symbol dictionary header.
+
+    use super::*;
+    use crate::core::PixelDepth;
+
+    /// T.88 Table E.1, column Qe
+    #[rustfmt::skip]
+    const DEC_QE: [u32; 47] = [
+        0x5601, 0x3401, 0x1801, 0x0AC1, 0x0521, 0x0221, 0x5601, 0x5401,
+        0x4801, 0x3801, 0x3001, 0x2401, 0x1C01, 0x1601, 0x5601, 0x5401,
+        0x5101, 0x4801, 0x3801, 0x3401, 0x3001, 0x2801, 0x2401, 0x2201,
+        0x1C01, 0x1801, 0x1601, 0x1401, 0x1201, 0x1101, 0x0AC1, 0x09C1,
+        0x08A1, 0x0521, 0x0441, 0x02A1, 0x0221, 0x0141, 0x0111, 0x0085,
+        0x0049, 0x0025, 0x0015, 0x0009, 0x0005, 0x0001, 0x5601,
+    ];
+
+    /// T.88 Table E.1, column NMPS
+    #[rustfmt::skip]
+    const DEC_NMPS: [usize; 47] = [
+        1, 2, 3, 4, 5, 38, 7, 8, 9, 10, 11, 12, 13, 29, 15, 16,
+        17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+        33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 45, 46,
+    ];
+
+    /// T.88 Table E.1, column NLPS
+    #[rustfmt::skip]
+    const DEC_NLPS: [usize; 47] = [
+        1, 6, 9, 12, 29, 33, 6, 14, 14, 14, 17, 18, 20, 21, 14, 14,
+        15, 16, 17, 18, 19, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29,
+        30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 46,
+    ];
+
+    /// T.88 Table E.1: indices whose SWITCH is 1
+    const DEC_SWITCH: [usize; 3] = [0, 6, 14];
+
+    /// Nominal adaptive pixels of generic template 0 (T.88 6.2.5.3)
+    const NOMINAL_AT: [(i64, i64); 4] = [(3, -1), (-3, -1), (2, -2), (-2, -2)];
+
+    /// Decoder context: probability state index and MPS value
+    #[derive(Clone, Copy, Default)]
+    struct Cx {
+        index: usize,
+        mps: u8,
+    }
+
+    struct MqDecoder<'a> {
+        data: &'a [u8],
+        pos: usize,
+        a: u32,
+        c: u32,
+        ct: u32,
+    }
+
+    impl<'a> MqDecoder<'a> {
+        /// INITDEC (T.88 E.3.5)
+        fn new(data: &'a [u8]) -> Self {
+            let mut dec = Self {
+                data,
+                pos: 0,
+                a: 0x8000,
+                c: (data.first().copied().unwrap_or(0xFF) as u32) << 16,
+                ct: 0,
+            };
+            dec.byte_in();
+            dec.c <<= 7;
+            dec.ct -= 7;
+            dec
+        }
+
+        fn byte(&self, i: usize) -> u32 {
+            self.data.get(i).copied().unwrap_or(0xFF) as u32
+        }
+
+        /// BYTEIN (T.88 E.3.4)
+        fn byte_in(&mut self) {
+            if self.byte(self.pos) == 0xFF {
+                if self.byte(self.pos + 1) > 0x8F {
+                    self.c += 0xFF00;
+                    self.ct = 8;
+                } else {
+                    self.pos += 1;
+                    self.c += self.byte(self.pos) << 9;
+                    self.ct = 7;
+                }
+            } else {
+                self.pos += 1;
+                self.c += self.byte(self.pos) << 8;
+                self.ct = 8;
+            }
+        }
+
+        /// DECODE (T.88 E.3.2)
+        fn decode(&mut self, cx: &mut Cx) -> u8 {
+            let qe = DEC_QE[cx.index];
+            self.a -= qe;
+            let d;
+            if (self.c >> 16) < qe {
+                // LPS_EXCHANGE
+                if self.a < qe {
+                    d = cx.mps;
+                    cx.index = DEC_NMPS[cx.index];
+                } else {
+                    d = 1 - cx.mps;
+                    self.lps_transition(cx);
+                }
+                self.a = qe;
+            } else {
+                self.c -= qe << 16;
+                if self.a & 0x8000 != 0 {
+                    return cx.mps;
+                }
+                // MPS_EXCHANGE
+                if self.a < qe {
+                    d = 1 - cx.mps;
+                    self.lps_transition(cx);
+                } else {
+                    d = cx.mps;
+                    cx.index = DEC_NMPS[cx.index];
+                }
+            }
+            // RENORMD
+            loop {
+                if self.ct == 0 {
+                    self.byte_in();
+                }
+                self.a <<= 1;
+                self.c <<= 1;
+                self.ct -= 1;
+                if self.a & 0x8000 != 0 {
+                    break;
+                }
+            }
+            d
+        }
+
+        fn lps_transition(&self, cx: &mut Cx) {
+            if DEC_SWITCH.contains(&cx.index) {
+                cx.mps = 1 - cx.mps;
+            }
+            cx.index = DEC_NLPS[cx.index];
+        }
+
+        /// Integer decoding procedure (T.88 A.2, Table A.1)
+        fn decode_int(&mut self, cx: &mut [Cx; 512]) -> Option<i32> {
+            let mut prev = 1usize;
+            let mut bit = |dec: &mut Self| {
+                let d = dec.decode(&mut cx[prev]);
+                prev = if prev < 256 {
+                    (prev << 1) | d as usize
+                } else {
+                    (((prev << 1) | d as usize) & 511) | 256
+                };
+                d
+            };
+            let mut read =
+                |dec: &mut Self, n: u32| (0..n).fold(0u32, |v, _| (v << 1) | bit(dec) as u32);
+            let s = read(self, 1);
+            let v = if read(self, 1) == 0 {
+                read(self, 2)
+            } else if read(self, 1) == 0 {
+                read(self, 4) + 4
+            } else if read(self, 1) == 0 {
+                read(self, 6) + 20
+            } else if read(self, 1) == 0 {
+                read(self, 8) + 84
+            } else if read(self, 1) == 0 {
+                read(self, 12) + 340
+            } else {
+                read(self, 32) + 4436
+            } as i32;
+            match (s, v) {
+                (1, 0) => None,
+                (1, v) => Some(-v),
+                (_, v) => Some(v),
+            }
+        }
+
+        /// Symbol ID decoding procedure (T.88 A.3)
+        fn decode_id(&mut self, cx: &mut [Cx], code_len: u32) -> u32 {
+            let mut prev = 1usize;
+            for _ in 0..code_len {
+                prev = (prev << 1) | self.decode(&mut cx[prev]) as usize;
+            }
+            (prev - (1 << code_len)) as u32
+        }
+    }
+
+    /// Decoded symbol bitmap, one byte per pixel
+    struct Symbol {
+        w: i64,
+        h: i64,
+        data: Vec<u8>,
+    }
+
+    impl Symbol {
+        fn pixel(&self, x: i64, y: i64) -> usize {
+            if (0..self.w).contains(&x) && (0..self.h).contains(&y) {
+                self.data[(y * self.w + x) as usize] as usize
+            } else {
+                0
+            }
+        }
+    }
+
+    /// Generic region decoding with template 0 (T.88 6.2.5.7, Figure 3)
+    fn decode_generic(
+        dec: &mut MqDecoder,
+        gb: &mut [Cx],
+        at: &[(i64, i64); 4],
+        w: i64,
+        h: i64,
+    ) -> Symbol {
+        let mut sym = Symbol {
+            w,
+            h,
+            data: vec![0; (w * h) as usize],
+        };
+        // Context bits 0..15, least significant first
+        let template = [
+            (-1, 0),
+            (-2, 0),
+            (-3, 0),
+            (-4, 0),
+            at[0],
+            (2, -1),
+            (1, -1),
+            (0, -1),
+            (-1, -1),
+            (-2, -1),
+            at[1],
+            at[2],
+            (1, -2),
+            (0, -2),
+            (-1, -2),
+            at[3],
+        ];
+        for y in 0..h {
+            for x in 0..w {
+                let cx = template
+                    .iter()
+                    .enumerate()
+                    .fold(0, |cx, (i, &(dx, dy))| cx | sym.pixel(x + dx, y + dy) << i);
+                sym.data[(y * w + x) as usize] = dec.decode(&mut gb[cx]);
+            }
+        }
+        sym
+    }
+
+    struct Segment<'a> {
+        seg_type: u8,
+        data: &'a [u8],
+    }
+
+    fn parse_segments(mut s: &[u8]) -> Vec<Segment<'_>> {
+        let mut segs = Vec::new();
+        while !s.is_empty() {
+            let number = u32::from_be_bytes(s[0..4].try_into().unwrap());
+            let flags = s[4];
+            let nref = (s[5] >> 5) as usize;
+            let ref_size = if number <= 256 {
+                1
+            } else if number <= 65536 {
+                2
+            } else {
+                4
+            };
+            let mut p = 6 + nref * ref_size;
+            p += if flags & 0x40 != 0 { 4 } else { 1 };
+            let len = u32::from_be_bytes(s[p..p + 4].try_into().unwrap()) as usize;
+            p += 4;
+            segs.push(Segment {
+                seg_type: flags & 0x3F,
+                data: &s[p..p + len],
+            });
+            s = &s[p + len..];
+        }
+        segs
+    }
+
+    fn decode_symbol_dictionary(data: &[u8]) -> Vec<Symbol> {
+        assert_eq!(u16::from_be_bytes([data[0], data[1]]), 0);
+        let at: [(i64, i64); 4] =
+            std::array::from_fn(|i| (data[2 + 2 * i] as i8 as i64, data[3 + 2 * i] as i8 as i64));
+        assert_eq!(at, NOMINAL_AT);
+        let nnew = u32::from_be_bytes(data[14..18].try_into().unwrap()) as usize;
+        let mut dec = MqDecoder::new(&data[18..]);
+        let mut iadh = [Cx::default(); 512];
+        let mut iadw = [Cx::default(); 512];
+        let mut iaex = [Cx::default(); 512];
+        let mut gb = vec![Cx::default(); 1 << 16];
+        let mut symbols = Vec::new();
+        let mut height = 0;
+        while symbols.len() < nnew {
+            height += dec.decode_int(&mut iadh).unwrap() as i64;
+            let mut width = 0;
+            while let Some(dw) = dec.decode_int(&mut iadw) {
+                width += dw as i64;
+                symbols.push(decode_generic(&mut dec, &mut gb, &at, width, height));
+            }
+        }
+        assert_eq!(dec.decode_int(&mut iaex), Some(0));
+        assert_eq!(dec.decode_int(&mut iaex), Some(nnew as i32));
+        symbols
+    }
+
+    fn decode_page(symbols: &[Symbol], page: &[u8]) -> Pix {
+        let segs = parse_segments(page);
+        assert_eq!(segs.len(), 2);
+        assert_eq!(segs[0].seg_type, 48);
+        assert_eq!(segs[1].seg_type, 6);
+        let info = segs[0].data;
+        let w = u32::from_be_bytes(info[0..4].try_into().unwrap());
+        let h = u32::from_be_bytes(info[4..8].try_into().unwrap());
+
+        let data = segs[1].data;
+        // REFCORNER = TOPLEFT, SBSTRIPS = 1, no refinement or Huffman
+        assert_eq!(u16::from_be_bytes([data[17], data[18]]), 0x0010);
+        let ninst = u32::from_be_bytes(data[19..23].try_into().unwrap()) as usize;
+        let mut dec = MqDecoder::new(&data[23..]);
+        let mut iadt = [Cx::default(); 512];
+        let mut iafs = [Cx::default(); 512];
+        let mut iads = [Cx::default(); 512];
+        let code_len = usize::BITS - symbols.len().saturating_sub(1).leading_zeros();
+        let mut iaid = vec![Cx::default(); 1 << code_len];
+
+        let mut pm = Pix::new(w, h, PixelDepth::Bit1).unwrap().to_mut();
+        let mut strip_t = -dec.decode_int(&mut iadt).unwrap();
+        let mut first_s = 0;
+        let mut n = 0;
+        while n < ninst {
+            strip_t += dec.decode_int(&mut iadt).unwrap();
+            let mut cur_s = 0;
+            let mut first = true;
+            loop {
+                if first {
+                    first_s += dec.decode_int(&mut iafs).unwrap();
+                    cur_s = first_s;
+                    first = false;
+                } else {
+                    match dec.decode_int(&mut iads) {
+                        Some(ds) => cur_s += ds,
+                        None => break,
+                    }
+                }
+                let sym = &symbols[dec.decode_id(&mut iaid, code_len) as usize];
+                for y in 0..sym.h {
+                    for x in 0..sym.w {
+                        let (px, py) = (cur_s as i64 + x, strip_t as i64 + y);
+                        if sym.pixel(x, y) == 1
+                            && (0..w as i64).contains(&px)
+                            && (0..h as i64).contains(&py)
+                        {
+                            pm.set_pixel_unchecked(px as u32, py as u32, 1);
+                        }
+                    }
+                }
+                cur_s += sym.w as i32 - 1;
+                n += 1;
+            }
+        }
+        pm.into()
+    }
+
+    /// T.88 H.2 test sequence: 256 bits coded in a single context
+    const H2_INPUT: [u8; 32] = [
+        0x00, 0x02, 0x00, 0x51, 0x00, 0x00, 0x00, 0xC0, 0x03, 0x52, 0x87, 0x2A, 0xAA, 0xAA, 0xAA,
+        0xAA, 0x82, 0xC0, 0x20, 0x00, 0xFC, 0xD7, 0x9E, 0xF6, 0xBF, 0x7F, 0xED, 0x90, 0x4F, 0x46,
+        0xA3, 0xBF,
+    ];
+
+    /// T.88 H.2 coded output for [`H2_INPUT`], including the final marker
+    const H2_CODED: [u8; 30] = [
+        0x84, 0xC7, 0x3B, 0xFC, 0xE1, 0xA1, 0x43, 0x04, 0x02, 0x20, 0x00, 0x00, 0x41, 0x0D, 0xBB,
+        0x86, 0xF4, 0x31, 0x7F, 0xFF, 0x88, 0xFF, 0x37, 0x47, 0x1A, 0xDB, 0x6A, 0xDF, 0xFF, 0xAC,
+    ];
+
+    fn h2_bits() -> impl Iterator<Item = u8> {
+        H2_INPUT
+            .iter()
+            .flat_map(|&byte| (0..8).rev().map(move |i| (byte >> i) & 1))
+    }
+
+    #[test]
+    fn test_mq_encoder_h2_vector() {
+        let mut enc = MqEncoder::new();
+        let mut cx = 0u8;
+        for bit in h2_bits() {
+            enc.encode(&mut cx, bit);
+        }
+        assert_eq!(enc.finish(), H2_CODED);
+    }
+
+    #[test]
+    fn test_mq_decoder_h2_vector() {
+        let mut dec = MqDecoder::new(&H2_CODED);
+        let mut cx = Cx::default();
+        for (i, bit) in h2_bits().enumerate() {
+            assert_eq!(dec.decode(&mut cx), bit, "bit {i}");
+        }
+    }
+
+    #[test]
+    fn test_mq_round_trip() {
+        let bits: Vec<u8> = (0..5000u32)
+            .map(|i| u8::from(i.wrapping_mul(2654435761) >> 29 == 0 || i % 7 == 0))
+            .collect();
+        let mut enc = MqEncoder::new();
+        let mut cx = [0u8; 4];
+        for (i, &b) in bits.iter().enumerate() {
+            enc.encode(&mut cx[i % 4], b);
+        }
+        let coded = enc.finish();
+        let mut dec = MqDecoder::new(&coded);
+        let mut cx = [Cx::default(); 4];
+        for (i, &b) in bits.iter().enumerate() {
+            assert_eq!(dec.decode(&mut cx[i % 4]), b, "bit {i}");
+        }
+    }
+
+    #[test]
+    fn test_integer_round_trip() {
+        let values = [
+            Some(0),
+            Some(3),
+            Some(-4),
+            Some(19),
+            Some(-83),
+            Some(300),
+            None,
+            Some(4435),
+            Some(-100_000),
+            Some(1),
+        ];
+        let mut enc = MqEncoder::new();
+        let mut ctx = IntContext::new();
+        for &v in &values {
+            ctx.encode(&mut enc, v);
+        }
+        let coded = enc.finish();
+        let mut dec = MqDecoder::new(&coded);
+        let mut ctx = [Cx::default(); 512];
+        for &v in &values {
+            assert_eq!(dec.decode_int(&mut ctx), v);
+        }
+    }
+
+    #[test]
+    fn test_streams_decode_to_rendered_pages() {
+        // Three templates on a 6x5 lattice: a bar, a box and a dot
+        let lattice = (6, 5);
+        let mut comp = Pix::new(12, 10, PixelDepth::Bit1).unwrap().to_mut();
+        for y in 0..5 {
+            comp.set_pixel_unchecked(1, y, 1);
+        }
+        for y in 0..4 {
+            for x in 6..11 {
+                if y == 0 || y == 3 || x == 6 || x == 10 {
+                    comp.set_pixel_unchecked(x, y, 1);
+                }
+            }
+        }
+        comp.set_pixel_unchecked(0, 5, 1);
+        let data = JbData {
+            pix: comp.into(),
+            npages: 2,
+            w: 60,
+            h: 40,
+            nclass: 3,
+            lattice_w: lattice.0,
+            lattice_h: lattice.1,
+            naclass: vec![0, 1, 2, 1, 0, 2, 1],
+            napage: vec![0, 0, 0, 0, 1, 1, 1],
+            ptaul: vec![
+                (3, 2),
+                (10, 2),
+                (20, 2),
+                (4, 20),
+                (57, 37),
+                (-1, 0),
+                (30, 10),
+            ],
+        };
+
+        let (globals, pages) = data.to_pdf_streams().unwrap();
+        let segs = parse_segments(&globals);
+        assert_eq!(segs.len(), 1);
+        assert_eq!(segs[0].seg_type, SEG_SYMBOL_DICT);
+        let symbols = decode_symbol_dictionary(segs[0].data);
+        assert_eq!(symbols.len(), 3);
+
+        // Rendering with the placements moved by the template border gives
+        // the expected pages
+        let shifted = JbData {
+            pix: data.pix.clone(),
+            ptaul: data
+                .ptaul
+                .iter()
+                .map(|&(x, y)| (x - TEMPLATE_BORDER, y - TEMPLATE_BORDER))
+                .collect(),
+            naclass: data.naclass.clone(),
+            napage: data.napage.clone(),
+            ..data
+        };
+        assert_eq!(pages.len(), 2);
+        for (i, page) in pages.iter().enumerate() {
+            let decoded = decode_page(&symbols, page);
+            let expected = shifted.render_page(i).unwrap();
+            assert!(decoded.equals(&expected), "page {i} differs");
+        }
+    }
+}
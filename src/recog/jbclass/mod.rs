@@ -14,6 +14,9 @@
 //! - Rank Hausdorff distance: Robust to noise, uses morphological matching
 //! - Correlation: Uses pixel-wise correlation for matching
 //!
+//! The classified data can be encoded as JBIG2 streams for PDF embedding
+//! with [`JbData::to_pdf_streams`].
+//!
 //! # Example
 //!
 //! ```no_run
@@ -35,6 +38,7 @@
 
 mod classify;
 pub mod io;
+mod jbig2;
 mod types;
 
 pub use classify::*;
@@ -7,6 +7,7 @@
 //! - **Page segmentation**: Separate text, images, and whitespace regions
 //! - **Character recognition**: Template-based character recognition (recog)
 //! - **JBIG2 classification**: Connected component clustering for compression (jbclass)
+//! - **Document outline**: Find the corners of a photographed page (document)
 //!
 //! # Quick Start
 //!
@@ -67,17 +68,22 @@
 //! - [`jbclass`]: JBIG2 connected component classification
 //! - [`dewarp`]: Page dewarping (curvature correction)
 //! - [`barcode`]: 1D barcode detection and decoding
+//! - [`qrfinder`]: QR code finder pattern location
+//! - [`prep`]: One-stop OCR preprocessing pipeline
 
 pub mod barcode;
 pub mod baseline;
 pub mod classapp;
 pub mod correlscore;
 pub mod dewarp;
+pub mod document;
 mod error;
 pub mod finditalic;
 pub mod flipdetect;
 pub mod jbclass;
 pub mod pageseg;
+pub mod prep;
+pub mod qrfinder;
 #[allow(clippy::module_inception)]
 pub mod recog;
 pub mod skew;
@@ -96,6 +102,10 @@ pub use baseline::{BaselineOptions, BaselineResult};
 pub use pageseg::{PageSegOptions, SegmentationResult};
 pub use skew::{SkewDetectOptions, SkewResult};
 
+// Re-export OCR preprocessing pipeline
+pub use document::find_document_corners;
+pub use prep::{BinarizeMethod, PrepOptions, ink_fraction, is_blank, prepare_for_ocr};
+
 // Re-export commonly used types from Phase 2 - recog
 pub use recog::{CharsetType, Rch, Rcha, Recog, TemplateUse};
 
@@ -109,6 +119,7 @@ pub use dewarp::{Dewarp, DewarpOptions, DewarpResult, dewarp_single_page};
 pub use barcode::{
     BarcodeFormat, BarcodeOptions, BarcodeResult, DecodeMethod, decode_barcode, process_barcodes,
 };
+pub use qrfinder::find_qr_finders;
 
 // Re-export commonly used types from flipdetect
 pub use flipdetect::{
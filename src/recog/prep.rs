@@ -0,0 +1,268 @@
+//! One-stop OCR preprocessing
+//!
+//! Chains the usual clean-up steps applied to a scanned page before it is
+//! handed to a recognizer:
+//!
+//! 1. Conversion to 8 bpp grayscale
+//! 2. Binarization (global Otsu or local Sauvola)
+//! 3. Skew detection and correction
+//! 4. Speckle removal
+//! 5. Cropping to the bounding box of the remaining foreground
+//!
+//! Each step after binarization can be disabled via [`PrepOptions`].
+//!
+//! [`is_blank`] and [`ink_fraction`] use the same binarization and speckle
+//! removal to detect empty scanner pages.
+
+use crate::color::{
+    compute_otsu_threshold, sauvola_threshold, threshold_otsu, threshold_to_binary,
+};
+use crate::core::{Pix, PixelDepth};
+use crate::recog::skew::{SkewDetectOptions, find_skew_and_deskew};
+use crate::recog::{RecogError, RecogResult};
+use crate::region::{ConnectivityType, despeckle};
+
+/// Binarization method used by [`prepare_for_ocr`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinarizeMethod {
+    /// Global threshold chosen by Otsu's method
+    Otsu,
+    /// Local Sauvola threshold
+    Sauvola {
+        /// Odd window size in pixels
+        window_size: u32,
+        /// Sensitivity factor, typically 0.2 - 0.5
+        k: f32,
+    },
+}
+
+/// Options for [`prepare_for_ocr`]
+#[derive(Debug, Clone)]
+pub struct PrepOptions {
+    /// Binarization method (default: Otsu).
+    /// `None` requires the input to already be 1 bpp.
+    pub binarize: Option<BinarizeMethod>,
+
+    /// Detect and correct skew (default: true)
+    pub deskew: bool,
+
+    /// Skew detection parameters used when `deskew` is enabled
+    pub skew_options: SkewDetectOptions,
+
+    /// Remove components with fewer ON pixels than this (default: 8).
+    /// Zero disables speckle removal.
+    pub despeckle_size: u32,
+
+    /// Crop to the bounding box of the foreground (default: true)
+    pub crop: bool,
+
+    /// Border in pixels left around the cropped content (default: 0)
+    pub crop_margin: u32,
+}
+
+impl Default for PrepOptions {
+    fn default() -> Self {
+        Self {
+            binarize: Some(BinarizeMethod::Otsu),
+            deskew: true,
+            skew_options: SkewDetectOptions::default(),
+            despeckle_size: 8,
+            crop: true,
+            crop_margin: 0,
+        }
+    }
+}
+
+impl PrepOptions {
+    /// Create new options with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the binarization method (`None` to skip binarization)
+    pub fn with_binarize(mut self, method: Option<BinarizeMethod>) -> Self {
+        self.binarize = method;
+        self
+    }
+
+    /// Enable or disable deskewing
+    pub fn with_deskew(mut self, deskew: bool) -> Self {
+        self.deskew = deskew;
+        self
+    }
+
+    /// Set the skew detection parameters
+    pub fn with_skew_options(mut self, options: SkewDetectOptions) -> Self {
+        self.skew_options = options;
+        self
+    }
+
+    /// Set the minimum component size kept by speckle removal
+    pub fn with_despeckle_size(mut self, size: u32) -> Self {
+        self.despeckle_size = size;
+        self
+    }
+
+    /// Enable or disable cropping to the foreground
+    pub fn with_crop(mut self, crop: bool) -> Self {
+        self.crop = crop;
+        self
+    }
+
+    /// Set the border left around the cropped content
+    pub fn with_crop_margin(mut self, margin: u32) -> Self {
+        self.crop_margin = margin;
+        self
+    }
+
+    /// Validate options
+    fn validate(&self) -> RecogResult<()> {
+        if let Some(BinarizeMethod::Sauvola { window_size, k }) = self.binarize {
+            if window_size < 3 || window_size.is_multiple_of(2) {
+                return Err(RecogError::InvalidParameter(
+                    "sauvola window_size must be odd and >= 3".to_string(),
+                ));
+            }
+            if k <= 0.0 {
+                return Err(RecogError::InvalidParameter(
+                    "sauvola k must be positive".to_string(),
+                ));
+            }
+        }
+        if self.deskew {
+            self.skew_options.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Prepare a page image for OCR.
+///
+/// Converts to grayscale, binarizes, deskews, removes speckle and crops
+/// to the text content, as selected by `options`.  The result is a 1 bpp
+/// image with foreground (text) pixels set to 1.
+///
+/// If no foreground remains after speckle removal, the uncropped binary
+/// image is returned.
+///
+/// # Errors
+///
+/// Returns an error if the options are invalid, if `options.binarize` is
+/// `None` and the input is not 1 bpp, or if any pipeline step fails.
+pub fn prepare_for_ocr(pix: &Pix, options: &PrepOptions) -> RecogResult<Pix> {
+    options.validate()?;
+
+    let mut binary = match options.binarize {
+        None => {
+            if pix.depth() != PixelDepth::Bit1 {
+                return Err(RecogError::UnsupportedDepth {
+                    expected: "1 bpp when binarization is disabled",
+                    actual: pix.depth().bits(),
+                });
+            }
+            pix.deep_clone()
+        }
+        Some(method) => {
+            let gray = if pix.depth() == PixelDepth::Bit8 && !pix.has_colormap() {
+                pix.deep_clone()
+            } else {
+                pix.convert_to_8()?
+            };
+            match method {
+                BinarizeMethod::Otsu => threshold_otsu(&gray)?,
+                BinarizeMethod::Sauvola { window_size, k } => {
+                    sauvola_threshold(&gray, window_size, k, 128.0)?
+                }
+            }
+        }
+    };
+
+    if options.deskew {
+        let (deskewed, _) = find_skew_and_deskew(&binary, &options.skew_options)?;
+        binary = deskewed;
+    }
+
+    if options.despeckle_size > 0 {
+        binary = despeckle(&binary, options.despeckle_size, ConnectivityType::EightWay)?;
+    }
+
+    if options.crop
+        && let Some((_, bbox)) = binary.clip_to_foreground()?
+    {
+        let m = options.crop_margin as i32;
+        let x0 = (bbox.x - m).max(0);
+        let y0 = (bbox.y - m).max(0);
+        let x1 = (bbox.x + bbox.w + m).min(binary.width() as i32);
+        let y1 = (bbox.y + bbox.h + m).min(binary.height() as i32);
+        binary = binary.clip_rectangle(x0 as u32, y0 as u32, (x1 - x0) as u32, (y1 - y0) as u32)?;
+    }
+
+    Ok(binary)
+}
+
+/// Components smaller than this many pixels are ignored by [`ink_fraction`]
+const BLANK_SPECKLE_SIZE: u32 = 8;
+
+/// Minimum difference between the mean gray values of the Otsu classes
+/// for a page to be considered to have any ink at all
+const BLANK_MIN_CONTRAST: f64 = 48.0;
+
+/// Fraction of the page covered by ink.
+///
+/// The page is binarized with Otsu's method (1 bpp input is used as is)
+/// and components of fewer than 8 pixels are removed as speckle before
+/// counting ON pixels.  A grayscale page whose two Otsu classes differ by
+/// less than 48 gray levels on average is paper texture only and has
+/// ink fraction 0.
+///
+/// # Errors
+///
+/// Returns an error if the image cannot be converted to 8 bpp.
+pub fn ink_fraction(pix: &Pix) -> RecogResult<f32> {
+    let binary = if pix.depth() == PixelDepth::Bit1 {
+        pix.deep_clone()
+    } else {
+        let gray = if pix.depth() == PixelDepth::Bit8 && !pix.has_colormap() {
+            pix.deep_clone()
+        } else {
+            pix.convert_to_8()?
+        };
+        let thresh = compute_otsu_threshold(&gray)?;
+        let (mut sum, mut n) = ([0.0f64; 2], [0u64; 2]);
+        for y in 0..gray.height() {
+            for x in 0..gray.width() {
+                let v = gray.get_pixel_unchecked(x, y);
+                let class = (v >= thresh as u32) as usize;
+                sum[class] += v as f64;
+                n[class] += 1;
+            }
+        }
+        let mean = |class: usize| sum[class] / n[class].max(1) as f64;
+        if n[0] == 0 || n[1] == 0 || mean(1) - mean(0) < BLANK_MIN_CONTRAST {
+            return Ok(0.0);
+        }
+        threshold_to_binary(&gray, thresh)?
+    };
+
+    let cleaned = despeckle(&binary, BLANK_SPECKLE_SIZE, ConnectivityType::EightWay)?;
+    let area = pix.width() as f64 * pix.height() as f64;
+    Ok((cleaned.count_pixels() as f64 / area) as f32)
+}
+
+/// Whether a scanned page is blank.
+///
+/// Returns true if the [`ink_fraction`] of the page is below
+/// `ink_fraction_threshold`; 0.001 is a reasonable value for text pages.
+///
+/// # Errors
+///
+/// Returns an error if the threshold is not in `[0, 1]` or the image
+/// cannot be converted to 8 bpp.
+pub fn is_blank(pix: &Pix, ink_fraction_threshold: f32) -> RecogResult<bool> {
+    if !(0.0..=1.0).contains(&ink_fraction_threshold) {
+        return Err(RecogError::InvalidParameter(format!(
+            "ink_fraction_threshold must be in [0, 1], got {ink_fraction_threshold}"
+        )));
+    }
+    Ok(ink_fraction(pix)? < ink_fraction_threshold)
+}
@@ -0,0 +1,216 @@
+//! QR code finder pattern location
+//!
+//! Every QR code has three finder patterns at its corners: a dark 7x7
+//! module square enclosing a light 5x5 ring around a dark 3x3 center.  Any
+//! line through the center crosses dark, light, dark, light, dark runs in
+//! the ratio 1:1:3:1:1, whatever the module size.  Finding these crossings
+//! along rows and columns locates a code without decoding it.
+
+use crate::color::{compute_otsu_threshold, threshold_to_binary};
+use crate::core::{Pix, PixelDepth, Pta};
+use crate::region::{RegionError, RegionResult};
+
+/// Largest deviation of a run from its expected length, in modules
+const QR_RUN_TOLERANCE: f32 = 0.5;
+
+/// Minimum crossings in each direction for a cluster to count as a finder
+const QR_MIN_CROSSINGS: usize = 2;
+
+/// Largest ratio between the horizontal and vertical size of a finder
+const QR_MAX_ASPECT: f32 = 1.5;
+
+/// A 1:1:3:1:1 crossing found on a single row or column.
+struct Crossing {
+    /// Center of the crossing along the scan line
+    along: f32,
+    /// Index of the row or column
+    across: f32,
+    /// Length of the five runs
+    size: f32,
+}
+
+/// Locate QR code finder patterns
+///
+/// Binarizes the image (1 bpp is used as is, other depths are converted to
+/// 8 bpp grayscale and thresholded with Otsu's method), scans every row and
+/// column for dark-light-dark-light-dark runs in the ratio 1:1:3:1:1, and
+/// clusters the crossings.  A cluster crossed at least twice both
+/// horizontally and vertically, with similar horizontal and vertical
+/// sizes, is reported as a finder.
+///
+/// Patterns must be roughly axis-aligned and dark on a light background.
+///
+/// # Arguments
+/// * `pix` - Image of any depth
+///
+/// # Returns
+/// Centers of the finder patterns, ordered top to bottom then left to
+/// right.  A complete QR code gives three; the result is empty if none is
+/// found.
+pub fn find_qr_finders(pix: &Pix) -> RegionResult<Pta> {
+    let (w, h) = (pix.width() as usize, pix.height() as usize);
+    let dark = dark_mask(pix)?;
+
+    let mut horizontal = Vec::new();
+    for y in 0..h {
+        scan_line(&dark[y * w..(y + 1) * w], y, &mut horizontal);
+    }
+    let mut vertical = Vec::new();
+    let mut column = Vec::with_capacity(h);
+    for x in 0..w {
+        column.clear();
+        column.extend((0..h).map(|y| dark[y * w + x]));
+        scan_line(&column, x, &mut vertical);
+    }
+
+    // Greedy clustering on the approximate center of each crossing
+    let mut clusters: Vec<FinderCluster> = Vec::new();
+    let hits = horizontal
+        .iter()
+        .map(|c| (c.along, c.across, c, true))
+        .chain(vertical.iter().map(|c| (c.across, c.along, c, false)));
+    for (x, y, crossing, is_horizontal) in hits {
+        let found = clusters.iter_mut().find(|cl| {
+            let (cx, cy) = cl.center();
+            let radius = cl.max_size.max(crossing.size) / 7.0 * 2.0;
+            (cx - x).abs() <= radius && (cy - y).abs() <= radius
+        });
+        let cluster = match found {
+            Some(cl) => cl,
+            None => {
+                clusters.push(FinderCluster::default());
+                clusters.last_mut().unwrap()
+            }
+        };
+        cluster.add(x, y, crossing.size, is_horizontal);
+    }
+
+    let mut centers: Vec<(f32, f32)> = clusters
+        .iter()
+        .filter(|cl| cl.is_finder())
+        .map(|cl| cl.center())
+        .collect();
+    centers.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.total_cmp(&b.0)));
+
+    let mut pta = Pta::with_capacity(centers.len());
+    for (x, y) in centers {
+        pta.push(x, y);
+    }
+    Ok(pta)
+}
+
+/// Row-major mask of the dark pixels of `pix`.
+fn dark_mask(pix: &Pix) -> RegionResult<Vec<bool>> {
+    let binary = if pix.depth() == PixelDepth::Bit1 && !pix.has_colormap() {
+        pix.clone()
+    } else {
+        let gray = pix.convert_to_8()?;
+        compute_otsu_threshold(&gray)
+            .and_then(|thresh| threshold_to_binary(&gray, thresh))
+            .map_err(|e| RegionError::InvalidParameters(e.to_string()))?
+    };
+    let (w, h) = (binary.width(), binary.height());
+    let mut mask = Vec::with_capacity(w as usize * h as usize);
+    for y in 0..h {
+        mask.extend((0..w).map(|x| binary.get_pixel_unchecked(x, y) != 0));
+    }
+    Ok(mask)
+}
+
+/// Append the 1:1:3:1:1 crossings of one scan line to `out`.
+fn scan_line(line: &[bool], index: usize, out: &mut Vec<Crossing>) {
+    // Runs as (start, length, dark)
+    let mut runs: Vec<(usize, usize, bool)> = Vec::new();
+    for (i, &d) in line.iter().enumerate() {
+        match runs.last_mut() {
+            Some(run) if run.2 == d => run.1 += 1,
+            _ => runs.push((i, 1, d)),
+        }
+    }
+
+    for window in runs.windows(5) {
+        if !window[0].2 {
+            continue;
+        }
+        let lens: Vec<f32> = window.iter().map(|r| r.1 as f32).collect();
+        let total: f32 = lens.iter().sum();
+        let module = total / 7.0;
+        let fits = lens
+            .iter()
+            .zip([1.0, 1.0, 3.0, 1.0, 1.0])
+            .all(|(&len, modules)| {
+                (len - modules * module).abs() <= QR_RUN_TOLERANCE * modules * module
+            });
+        if fits {
+            let (start, len, _) = window[2];
+            out.push(Crossing {
+                along: start as f32 + (len as f32 - 1.0) / 2.0,
+                across: index as f32,
+                size: total,
+            });
+        }
+    }
+}
+
+/// Crossings grouped around one candidate finder.
+#[derive(Default)]
+struct FinderCluster {
+    /// Sums of the approximate centers of all crossings
+    sum_x: f32,
+    sum_y: f32,
+    count: usize,
+    /// Sums over the horizontal crossings
+    h_x: f32,
+    h_size: f32,
+    h_count: usize,
+    /// Sums over the vertical crossings
+    v_y: f32,
+    v_size: f32,
+    v_count: usize,
+    max_size: f32,
+}
+
+impl FinderCluster {
+    fn add(&mut self, x: f32, y: f32, size: f32, is_horizontal: bool) {
+        self.sum_x += x;
+        self.sum_y += y;
+        self.count += 1;
+        self.max_size = self.max_size.max(size);
+        if is_horizontal {
+            self.h_x += x;
+            self.h_size += size;
+            self.h_count += 1;
+        } else {
+            self.v_y += y;
+            self.v_size += size;
+            self.v_count += 1;
+        }
+    }
+
+    /// Best estimate of the finder center.
+    ///
+    /// Horizontal crossings fix x precisely but lie anywhere across the
+    /// 3-module center vertically, and vice versa.
+    fn center(&self) -> (f32, f32) {
+        let x = if self.h_count > 0 {
+            self.h_x / self.h_count as f32
+        } else {
+            self.sum_x / self.count as f32
+        };
+        let y = if self.v_count > 0 {
+            self.v_y / self.v_count as f32
+        } else {
+            self.sum_y / self.count as f32
+        };
+        (x, y)
+    }
+
+    fn is_finder(&self) -> bool {
+        if self.h_count < QR_MIN_CROSSINGS || self.v_count < QR_MIN_CROSSINGS {
+            return false;
+        }
+        let h_size = self.h_size / self.h_count as f32;
+        let v_size = self.v_size / self.v_count as f32;
+        h_size.max(v_size) <= QR_MAX_ASPECT * h_size.min(v_size)
+    }
+}
@@ -38,8 +38,8 @@
 //! assert!(!border.is_empty());
 //! ```
 
-use crate::core::{Box, Pix, PixelDepth};
-use crate::region::conncomp::{ConnectivityType, find_connected_components};
+use crate::core::{Box, Pix, PixelDepth, Pta, Ptaa};
+use crate::region::conncomp::{ConnectivityType, conncomp_pixa, find_connected_components};
 use crate::region::error::{RegionError, RegionResult};
 use crate::region::seedfill::fill_holes;
 use std::io::{Read, Write};
@@ -371,6 +371,89 @@ impl ComponentBorders {
     pub fn total_perimeter(&self) -> usize {
         self.outer.perimeter() + self.holes.iter().map(|h| h.perimeter()).sum::<usize>()
     }
+
+    /// Approximate each border by a polygon using Douglas-Peucker
+    ///
+    /// Every border point lies within `epsilon` of the returned polygon.
+    /// The result holds one Pta per border, outer border first and then
+    /// the holes, in global coordinates.  Each polygon is closed
+    /// implicitly: the last vertex connects back to the first, which is
+    /// not repeated.  Borders of one or two points are returned as is.
+    pub fn approx_polygon(&self, epsilon: f32) -> Ptaa {
+        let mut ptaa = Ptaa::with_capacity(self.border_count());
+        let (ox, oy) = (self.bounds.x, self.bounds.y);
+        for border in std::iter::once(&self.outer).chain(&self.holes) {
+            let vertices = simplify_closed(&border.points, epsilon.max(0.0) as f64);
+            let mut pta = Pta::with_capacity(vertices.len());
+            for p in vertices {
+                pta.push((p.x + ox) as f32, (p.y + oy) as f32);
+            }
+            ptaa.push(pta);
+        }
+        ptaa
+    }
+}
+
+/// Douglas-Peucker simplification of a closed contour.
+///
+/// The contour is split at its first point and the point farthest from
+/// it, and each half is simplified as an open polyline.
+fn simplify_closed(points: &[BorderPoint], epsilon: f64) -> Vec<BorderPoint> {
+    // Traced borders end by repeating the start point
+    let points = match points {
+        [first, rest @ .., last] if first == last && !rest.is_empty() => {
+            &points[..points.len() - 1]
+        }
+        _ => points,
+    };
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let first = points[0];
+    let dist2 = |p: BorderPoint| {
+        let (dx, dy) = ((p.x - first.x) as i64, (p.y - first.y) as i64);
+        dx * dx + dy * dy
+    };
+    let far = (1..points.len()).max_by_key(|&i| dist2(points[i])).unwrap();
+
+    let mut keep = vec![false; points.len() + 1];
+    keep[0] = true;
+    keep[far] = true;
+    // The closing point is index 0 again, addressed as points.len()
+    let at = |i: usize| points[i % points.len()];
+    let mut stack = vec![(0, far), (far, points.len())];
+    while let Some((lo, hi)) = stack.pop() {
+        if hi <= lo + 1 {
+            continue;
+        }
+        let (a, b) = (at(lo), at(hi));
+        let (abx, aby) = ((b.x - a.x) as f64, (b.y - a.y) as f64);
+        let len = abx.hypot(aby);
+        let (mut best, mut best_d) = (lo, 0.0);
+        for i in lo + 1..hi {
+            let p = at(i);
+            let (apx, apy) = ((p.x - a.x) as f64, (p.y - a.y) as f64);
+            let d = if len == 0.0 {
+                apx.hypot(apy)
+            } else {
+                (abx * apy - aby * apx).abs() / len
+            };
+            if d > best_d {
+                best = i;
+                best_d = d;
+            }
+        }
+        if best_d > epsilon {
+            keep[best] = true;
+            stack.push((lo, best));
+            stack.push((best, hi));
+        }
+    }
+
+    (0..points.len())
+        .filter(|&i| keep[i])
+        .map(|i| points[i])
+        .collect()
 }
 
 /// Collection of all borders in an image
@@ -1086,6 +1169,51 @@ pub fn get_outer_borders(pix: &Pix) -> RegionResult<Vec<Border>> {
     Ok(borders)
 }
 
+/// Convert a binary mask into one simplified polygon per component
+///
+/// Finds the connected components of `pix`, traces the outer border of
+/// each and approximates it with Douglas-Peucker, as in
+/// [`ComponentBorders::approx_polygon`].  Holes are ignored.  This is the
+/// usual mask-to-polygon conversion for annotation formats such as COCO.
+///
+/// # Arguments
+///
+/// * `pix` - Binary image (1-bit depth)
+/// * `connectivity` - Connectivity used to separate components
+/// * `epsilon` - Maximum distance of border points from the polygon
+///
+/// # Returns
+///
+/// One Pta per component, in the order of [`conncomp_pixa`], with
+/// vertices in global coordinates.  Polygons are closed implicitly.
+///
+/// # Errors
+///
+/// Returns an error if the image is not 1-bit depth.
+pub fn mask_to_polygons(
+    pix: &Pix,
+    connectivity: ConnectivityType,
+    epsilon: f32,
+) -> RegionResult<Ptaa> {
+    let (boxa, pixa) = conncomp_pixa(pix, connectivity)?;
+    let mut ptaa = Ptaa::with_capacity(pixa.len());
+    for (i, comp_pix) in pixa.iter().enumerate() {
+        let bounds = boxa.get(i).copied().unwrap_or_default();
+        let border = match get_outer_border(comp_pix, None) {
+            Ok(border) => border,
+            Err(RegionError::EmptyImage) => continue,
+            Err(e) => return Err(e),
+        };
+        let vertices = simplify_closed(&border.points, epsilon.max(0.0) as f64);
+        let mut pta = Pta::with_capacity(vertices.len());
+        for p in vertices {
+            pta.push((p.x + bounds.x) as f32, (p.y + bounds.y) as f32);
+        }
+        ptaa.push(pta);
+    }
+    Ok(ptaa)
+}
+
 /// Extract a sub-image for a component based on its bounding box
 fn extract_component_image(pix: &Pix, bounds: &Box) -> RegionResult<Pix> {
     let mut output = Pix::new(bounds.w as u32, bounds.h as u32, pix.depth())
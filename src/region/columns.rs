@@ -0,0 +1,213 @@
+//! Column and text line detection for page layout
+//!
+//! Finds text columns in a binary page image from its vertical projection
+//! profile.  Gutters are runs of columns with no foreground that are at
+//! least `min_gap` pixels wide; the foreground between consecutive gutters
+//! forms one column.
+//!
+//! Text lines within a page or column are found from the smoothed
+//! horizontal projection profile, splitting at the valleys between lines.
+
+use crate::core::{Box, Boxa, Pix, PixelDepth};
+use crate::region::{RegionError, RegionResult};
+
+/// Detect text columns separated by vertical gutters
+///
+/// Gaps narrower than `min_gap` (word and letter spacing) do not split
+/// a column.  Isolated noise inside a gutter prevents it from being
+/// found, so speckle should be removed first.
+///
+/// The profile is the foreground column sums from
+/// [`Pix::count_by_column`].  Gutters are found as runs of empty columns
+/// rather than with [`Numa::find_extrema`](crate::core::Numa::find_extrema):
+/// a gutter is a flat run of zeros with no single minimum to report, and
+/// its width is what decides whether it splits the page.
+///
+/// # Arguments
+/// * `pix` - 1 bpp page image, foreground ON
+/// * `min_gap` - Minimum gutter width in pixels (>= 1)
+///
+/// # Returns
+/// Tight bounding boxes of the columns, ordered left to right.  Empty if
+/// the image has no foreground.
+///
+/// # Errors
+/// Returns an error if the image is not 1 bpp or `min_gap` is 0.
+pub fn detect_columns(pix: &Pix, min_gap: u32) -> RegionResult<Boxa> {
+    if pix.depth() != PixelDepth::Bit1 {
+        return Err(RegionError::UnsupportedDepth {
+            expected: "1 bpp",
+            actual: pix.depth().bits(),
+        });
+    }
+    if min_gap == 0 {
+        return Err(RegionError::InvalidParameters(
+            "min_gap must be >= 1".into(),
+        ));
+    }
+
+    let counts = pix.count_by_column(None)?;
+    let filled: Vec<bool> = (0..counts.len())
+        .map(|x| counts.get(x).unwrap_or(0.0) > 0.0)
+        .collect();
+
+    // Split the foreground extent at gutters of at least min_gap
+    let mut spans = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut last_filled = 0;
+    for (x, &on) in filled.iter().enumerate() {
+        if !on {
+            continue;
+        }
+        match start {
+            None => start = Some(x),
+            Some(s) if x - last_filled > min_gap as usize => {
+                spans.push((s, last_filled));
+                start = Some(x);
+            }
+            Some(_) => {}
+        }
+        last_filled = x;
+    }
+    if let Some(s) = start {
+        spans.push((s, last_filled));
+    }
+
+    let mut boxa = Boxa::with_capacity(spans.len());
+    for (x0, x1) in spans {
+        let strip = Box::new(x0 as i32, 0, (x1 - x0 + 1) as i32, pix.height() as i32)?;
+        let rows = pix.count_by_row(Some(&strip))?;
+        let on_rows: Vec<usize> = (0..rows.len())
+            .filter(|&y| rows.get(y).unwrap_or(0.0) > 0.0)
+            .collect();
+        if let (Some(&y0), Some(&y1)) = (on_rows.first(), on_rows.last()) {
+            boxa.push(Box::new(
+                x0 as i32,
+                y0 as i32,
+                (x1 - x0 + 1) as i32,
+                (y1 - y0 + 1) as i32,
+            )?);
+        }
+    }
+    Ok(boxa)
+}
+
+/// Fraction of the profile peak below which a row is treated as a valley
+/// between text lines
+const LINE_VALLEY_FRACT: f32 = 0.1;
+
+/// Detect text lines from the horizontal projection profile
+///
+/// The row counts are smoothed with a 3-row window, and each run of rows
+/// above a fraction of the peak count becomes a line core.  This splits
+/// lines whose ascenders and descenders touch, as long as the profile
+/// dips in between.  Each core is then grown over adjacent rows that still
+/// contain foreground, up to the middle of the gap to the next core, and
+/// the box is fitted tightly to the foreground.
+///
+/// The profile is the row sums from [`Pix::count_by_row`], smoothed with
+/// [`Numa::windowed_mean`](crate::core::Numa::windowed_mean).  Valleys
+/// are runs below the threshold rather than minima from
+/// [`Numa::find_extrema`](crate::core::Numa::find_extrema), because the
+/// line boxes need the extent of each valley, not only its lowest row.
+///
+/// # Arguments
+/// * `pix` - 1 bpp image, foreground ON
+/// * `within` - Optional region to search, e.g. a column from
+///   [`detect_columns`]; clipped to the image
+///
+/// # Returns
+/// Line bounding boxes in image coordinates, ordered top to bottom.
+/// Empty if the region has no foreground.
+///
+/// # Errors
+/// Returns an error if the image is not 1 bpp or `within` does not
+/// overlap the image.
+pub fn detect_text_lines(pix: &Pix, within: Option<&Box>) -> RegionResult<Boxa> {
+    if pix.depth() != PixelDepth::Bit1 {
+        return Err(RegionError::UnsupportedDepth {
+            expected: "1 bpp",
+            actual: pix.depth().bits(),
+        });
+    }
+    let (w, h) = (pix.width() as i32, pix.height() as i32);
+    let region = match within {
+        Some(b) => b.clip(w, h).ok_or_else(|| {
+            RegionError::InvalidParameters("region does not overlap the image".into())
+        })?,
+        None => Box::new(0, 0, w, h)?,
+    };
+
+    let rows = pix.count_by_row(Some(&region))?;
+    let raw: Vec<f32> = (0..rows.len())
+        .map(|y| rows.get(y).unwrap_or(0.0))
+        .collect();
+    let smoothed = rows.windowed_mean(1);
+    let peak = raw.iter().copied().fold(0.0f32, f32::max);
+    if peak == 0.0 {
+        return Ok(Boxa::new());
+    }
+    let thresh = LINE_VALLEY_FRACT * peak;
+
+    // Line cores: runs of rows above the valley threshold
+    let mut cores: Vec<(usize, usize)> = Vec::new();
+    let mut start = None;
+    for y in 0..raw.len() {
+        let above = smoothed.get(y).unwrap_or(0.0) > thresh;
+        match (above, start) {
+            (true, None) => start = Some(y),
+            (false, Some(s)) => {
+                cores.push((s, y - 1));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        cores.push((s, raw.len() - 1));
+    }
+
+    let mut boxa = Boxa::with_capacity(cores.len());
+    for (i, &(mut y0, mut y1)) in cores.iter().enumerate() {
+        // Rows between two cores are shared at the middle of the gap
+        let lower = if i == 0 {
+            0
+        } else {
+            (cores[i - 1].1 + y0) / 2 + 1
+        };
+        let upper = cores.get(i + 1).map_or(raw.len() - 1, |c| (y1 + c.0) / 2);
+        while y0 > lower && raw[y0 - 1] > 0.0 {
+            y0 -= 1;
+        }
+        while y1 < upper && raw[y1 + 1] > 0.0 {
+            y1 += 1;
+        }
+        // Smoothing can extend a core into empty rows; trim them
+        while y0 < y1 && raw[y0] == 0.0 {
+            y0 += 1;
+        }
+        while y1 > y0 && raw[y1] == 0.0 {
+            y1 -= 1;
+        }
+
+        let strip = Box::new(
+            region.x,
+            region.y + y0 as i32,
+            region.w,
+            (y1 - y0 + 1) as i32,
+        )?;
+        let cols = pix.count_by_column(Some(&strip))?;
+        let on_cols: Vec<usize> = (0..cols.len())
+            .filter(|&x| cols.get(x).unwrap_or(0.0) > 0.0)
+            .collect();
+        if let (Some(&x0), Some(&x1)) = (on_cols.first(), on_cols.last()) {
+            boxa.push(Box::new(
+                region.x + x0 as i32,
+                strip.y,
+                (x1 - x0 + 1) as i32,
+                strip.h,
+            )?);
+        }
+    }
+    Ok(boxa)
+}
@@ -0,0 +1,105 @@
+//! Ruled line removal for forms
+//!
+//! Separates long horizontal and vertical rules from text in a binary
+//! image.  Rules are isolated by opening with thin line structuring
+//! elements at least `min_length` long, then subtracted from the image.
+//! Characters that were crossed by a rule lose the pixels under it; those
+//! gaps are bridged again by a small closing across the rule, restricted
+//! to the removed rule pixels.
+
+use crate::core::{Pix, PixelDepth};
+use crate::morph;
+use crate::region::{RegionError, RegionResult};
+
+/// Remove long horizontal and/or vertical rules from a binary image
+///
+/// # Arguments
+/// * `pix` - 1 bpp input image
+/// * `min_length` - Minimum run length of a rule, in pixels (>= 2)
+/// * `horizontal` - Remove horizontal rules
+/// * `vertical` - Remove vertical rules
+///
+/// # Returns
+/// `(text, lines)` - the image with the rules removed, and a 1 bpp image
+/// of the extracted rules
+///
+/// # Errors
+/// Returns an error if the image is not 1 bpp or `min_length` < 2.
+pub fn remove_lines(
+    pix: &Pix,
+    min_length: u32,
+    horizontal: bool,
+    vertical: bool,
+) -> RegionResult<(Pix, Pix)> {
+    if pix.depth() != PixelDepth::Bit1 {
+        return Err(RegionError::UnsupportedDepth {
+            expected: "1 bpp",
+            actual: pix.depth().bits(),
+        });
+    }
+    if min_length < 2 {
+        return Err(RegionError::InvalidParameters(
+            "min_length must be >= 2".into(),
+        ));
+    }
+
+    let morph_err = |e: morph::MorphError| RegionError::InvalidParameters(e.to_string());
+
+    let hlines = if horizontal {
+        Some(morph::open_brick(pix, min_length, 1).map_err(morph_err)?)
+    } else {
+        None
+    };
+    let vlines = if vertical {
+        Some(morph::open_brick(pix, 1, min_length).map_err(morph_err)?)
+    } else {
+        None
+    };
+    let lines = match (&hlines, &vlines) {
+        (Some(h), Some(v)) => h.or(v)?,
+        (Some(l), None) | (None, Some(l)) => l.clone(),
+        (None, None) => pix.create_template(),
+    };
+
+    let mut text = pix.subtract(&lines)?;
+
+    // Rejoin strokes cut by a rule: close across it, keeping only pixels
+    // that were part of the rule
+    if let Some(h) = &hlines {
+        let thickness = max_run(h, true);
+        if thickness > 0 {
+            let closed = morph::close_brick(&text, 1, thickness + 2).map_err(morph_err)?;
+            text = text.or(&closed.and(h)?)?;
+        }
+    }
+    if let Some(v) = &vlines {
+        let thickness = max_run(v, false);
+        if thickness > 0 {
+            let closed = morph::close_brick(&text, thickness + 2, 1).map_err(morph_err)?;
+            text = text.or(&closed.and(v)?)?;
+        }
+    }
+
+    Ok((text, lines))
+}
+
+/// Longest run of ON pixels across the rules: vertical runs (the
+/// thickness of horizontal rules) if `across_rows`, else horizontal runs.
+fn max_run(pix: &Pix, across_rows: bool) -> u32 {
+    let (w, h) = (pix.width(), pix.height());
+    let (outer, inner) = if across_rows { (w, h) } else { (h, w) };
+    let mut best = 0;
+    for i in 0..outer {
+        let mut run = 0;
+        for j in 0..inner {
+            let (x, y) = if across_rows { (i, j) } else { (j, i) };
+            if pix.get_pixel_unchecked(x, y) != 0 {
+                run += 1;
+                best = best.max(run);
+            } else {
+                run = 0;
+            }
+        }
+    }
+    best
+}
@@ -6,6 +6,10 @@
 //! - **Seed fill operations** - Flood fill and morphological reconstruction
 //! - **Watershed segmentation** - Image segmentation using watershed algorithm
 //! - **Pixel labeling** - High-level labeling and analysis functions
+//! - **Oriented boxes** - Minimum-area rotated bounding boxes of components
+//! - **Stroke width transform** - Per-pixel stroke widths for text detection
+//! - **Ruled line removal** - Separating form rules from text
+//! - **Column and line detection** - Splitting pages into columns and text lines
 //!
 //! # Examples
 //!
@@ -58,15 +62,19 @@
 
 pub mod ccbord;
 pub mod checkerboard;
+pub mod columns;
 pub mod conncomp;
 pub mod error;
 pub mod label;
+pub mod lineremoval;
 pub mod maze;
+pub mod orientedbox;
 pub mod partition;
 pub mod quadtree;
 pub mod rectangle;
 pub mod seedfill;
 pub mod select;
+pub mod strokewidth;
 pub mod watershed;
 
 // Re-export core types
@@ -78,8 +86,9 @@ pub use error::{RegionError, RegionResult};
 // Re-export conncomp types and functions
 pub use conncomp::{
     ConnectedComponent, ConnectivityType, component_area_transform, conncomp_pixa, count_conn_comp,
-    extract_component, filter_components_by_size, find_connected_components,
-    get_sorted_neighbor_values, label_connected_components, seedfill, seedfill_4, seedfill_8,
+    despeckle, euler_number, extract_component, filter_components_by_size,
+    find_connected_components, get_sorted_neighbor_values, label_connected_components, seedfill,
+    seedfill_4, seedfill_8,
 };
 
 // Re-export label types and functions
@@ -95,10 +104,11 @@ pub use label::{
 pub use seedfill::{
     BoundaryCondition, ExtremaType, SeedFillOptions, clear_border, distance_function,
     extract_border_conn_comps, fill_bg_from_border, fill_closed_borders, fill_holes,
-    fill_holes_to_bounding_rect, find_equal_values, floodfill, holes_by_filling, local_extrema,
-    qualify_local_minima, remove_seeded_components, seedfill_binary, seedfill_binary_restricted,
-    seedfill_gray, seedfill_gray_basin, seedfill_gray_inv, seedfill_gray_inv_simple,
-    seedfill_gray_simple, seedspread, select_min_in_conncomp, selected_local_extrema,
+    fill_holes_to_bounding_rect, find_equal_values, find_holes, floodfill, floodfill_color,
+    holes_by_filling, local_extrema, qualify_local_minima, remove_seeded_components,
+    seedfill_binary, seedfill_binary_restricted, seedfill_gray, seedfill_gray_basin,
+    seedfill_gray_inv, seedfill_gray_inv_simple, seedfill_gray_simple, seedspread,
+    select_min_in_conncomp, selected_local_extrema,
 };
 
 // Re-export watershed types and functions
@@ -112,8 +122,8 @@ pub use watershed::{
 pub use ccbord::{
     Border, BorderPoint, BorderType, ComponentBorders, Direction, ImageBorders, from_chain_code,
     get_all_borders, get_component_borders, get_cut_path_for_hole, get_outer_border,
-    get_outer_borders, locate_outside_seed_pixel, pix_get_hole_border, render_borders,
-    to_chain_code,
+    get_outer_borders, locate_outside_seed_pixel, mask_to_polygons, pix_get_hole_border,
+    render_borders, to_chain_code,
 };
 
 // Re-export select types and functions
@@ -129,6 +139,18 @@ pub use quadtree::{
 // Re-export checkerboard types and functions
 pub use checkerboard::find_checkerboard_corners;
 
+// Re-export oriented box functions
+pub use orientedbox::{min_area_rect, oriented_boxes};
+
+// Re-export column and text line detection
+pub use columns::{detect_columns, detect_text_lines};
+
+// Re-export ruled line removal
+pub use lineremoval::remove_lines;
+
+// Re-export stroke width transform
+pub use strokewidth::find_stroke_width_transform;
+
 // Re-export partition types and functions
 pub use partition::{WhiteblockSort, boxa_get_whiteblocks, boxa_prune_sorted_on_overlap};
 
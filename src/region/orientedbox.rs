@@ -0,0 +1,212 @@
+//! Minimum-area oriented bounding boxes
+//!
+//! Computes, for each connected component of a binary image, the smallest
+//! (possibly rotated) rectangle that encloses it.  This measures the
+//! orientation and extent of skewed components such as individual text
+//! lines, where the axis-aligned bounding box is too loose.
+//!
+//! The box is found by rotating calipers over the convex hull of the
+//! component's boundary pixels.  Pixels are treated as unit squares
+//! centered on integer coordinates, so a component's box encloses the
+//! full extent of its pixels.
+
+use crate::core::{Pix, Pta, Ptaa};
+use crate::region::conncomp::{ConnectivityType, label_connected_components};
+use crate::region::error::RegionResult;
+
+/// Compute the minimum-area rotated bounding box of each component
+///
+/// Components are labeled with the given connectivity and returned in
+/// raster order of their first pixel.  Each Pta holds the 4 corners of
+/// the box, in order around the rectangle.
+///
+/// # Errors
+///
+/// Returns an error if the image is not 1-bit depth.
+pub fn oriented_boxes(pix: &Pix, connectivity: ConnectivityType) -> RegionResult<Ptaa> {
+    let labeled = label_connected_components(pix, connectivity)?;
+    let w = labeled.width();
+    let h = labeled.height();
+
+    // Corners of boundary pixels, grouped by label.  Interior pixels
+    // cannot lie on the convex hull, so they are skipped.
+    let mut points: Vec<Vec<(f32, f32)>> = Vec::new();
+    for y in 0..h {
+        for x in 0..w {
+            let label = labeled.get_pixel_unchecked(x, y);
+            if label == 0 {
+                continue;
+            }
+            let same = |nx: i64, ny: i64| {
+                nx >= 0
+                    && ny >= 0
+                    && nx < w as i64
+                    && ny < h as i64
+                    && labeled.get_pixel_unchecked(nx as u32, ny as u32) == label
+            };
+            let (xi, yi) = (x as i64, y as i64);
+            if same(xi - 1, yi) && same(xi + 1, yi) && same(xi, yi - 1) && same(xi, yi + 1) {
+                continue;
+            }
+            let idx = label as usize - 1;
+            if idx >= points.len() {
+                points.resize_with(idx + 1, Vec::new);
+            }
+            let (fx, fy) = (x as f32, y as f32);
+            points[idx].extend_from_slice(&[
+                (fx - 0.5, fy - 0.5),
+                (fx + 0.5, fy - 0.5),
+                (fx + 0.5, fy + 0.5),
+                (fx - 0.5, fy + 0.5),
+            ]);
+        }
+    }
+
+    let mut ptaa = Ptaa::with_capacity(points.len());
+    for pts in &points {
+        let mut pta = Pta::with_capacity(4);
+        if let Some(corners) = min_area_rect_points(pts) {
+            for (x, y) in corners {
+                pta.push(x, y);
+            }
+        }
+        ptaa.push(pta);
+    }
+    Ok(ptaa)
+}
+
+/// Compute the minimum-area rotated rectangle enclosing a point set
+///
+/// Returns a Pta with the 4 corners in order around the rectangle, or
+/// `None` if `pta` is empty.  Degenerate inputs (a single point or
+/// collinear points) yield a rectangle with zero width.
+pub fn min_area_rect(pta: &Pta) -> Option<Pta> {
+    let pts: Vec<(f32, f32)> = pta.iter().collect();
+    let corners = min_area_rect_points(&pts)?;
+    let mut out = Pta::with_capacity(4);
+    for (x, y) in corners {
+        out.push(x, y);
+    }
+    Some(out)
+}
+
+/// Rotating calipers over the convex hull of `pts`.
+fn min_area_rect_points(pts: &[(f32, f32)]) -> Option<[(f32, f32); 4]> {
+    let hull = convex_hull(pts);
+    match hull.len() {
+        0 => return None,
+        1 => return Some([hull[0]; 4]),
+        _ => {}
+    }
+
+    // The minimum-area rectangle has a side collinear with a hull edge
+    let mut best: Option<(f64, [(f64, f64); 4])> = None;
+    for i in 0..hull.len() {
+        let (x0, y0) = (hull[i].0 as f64, hull[i].1 as f64);
+        let (x1, y1) = {
+            let p = hull[(i + 1) % hull.len()];
+            (p.0 as f64, p.1 as f64)
+        };
+        let len = (x1 - x0).hypot(y1 - y0);
+        if len == 0.0 {
+            continue;
+        }
+        // Unit vectors along and perpendicular to the edge
+        let (ux, uy) = ((x1 - x0) / len, (y1 - y0) / len);
+        let (vx, vy) = (-uy, ux);
+
+        let (mut umin, mut umax) = (f64::MAX, f64::MIN);
+        let (mut vmin, mut vmax) = (f64::MAX, f64::MIN);
+        for &(px, py) in &hull {
+            let (px, py) = (px as f64, py as f64);
+            let u = px * ux + py * uy;
+            let v = px * vx + py * vy;
+            umin = umin.min(u);
+            umax = umax.max(u);
+            vmin = vmin.min(v);
+            vmax = vmax.max(v);
+        }
+
+        let area = (umax - umin) * (vmax - vmin);
+        if best.as_ref().is_none_or(|(a, _)| area < *a) {
+            let corner = |u: f64, v: f64| (u * ux + v * vx, u * uy + v * vy);
+            best = Some((
+                area,
+                [
+                    corner(umin, vmin),
+                    corner(umax, vmin),
+                    corner(umax, vmax),
+                    corner(umin, vmax),
+                ],
+            ));
+        }
+    }
+
+    best.map(|(_, c)| c.map(|(x, y)| (x as f32, y as f32)))
+}
+
+/// Convex hull by Andrew's monotone chain, counter-clockwise, without
+/// repeated or collinear points.
+fn convex_hull(pts: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let mut sorted: Vec<(f32, f32)> = pts.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let cross = |o: (f32, f32), a: (f32, f32), b: (f32, f32)| {
+        (a.0 as f64 - o.0 as f64) * (b.1 as f64 - o.1 as f64)
+            - (a.1 as f64 - o.1 as f64) * (b.0 as f64 - o.0 as f64)
+    };
+
+    let mut hull: Vec<(f32, f32)> = Vec::with_capacity(2 * sorted.len());
+    // Lower hull, left to right
+    for &p in &sorted {
+        while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    // Upper hull, right to left, never popping into the lower hull
+    let lower_len = hull.len() + 1;
+    for &p in sorted.iter().rev().skip(1) {
+        while hull.len() >= lower_len && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    hull.pop();
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convex_hull_square_with_interior() {
+        let pts = [
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+            (1.0, 1.0),
+            (1.0, 0.0),
+        ];
+        let hull = convex_hull(&pts);
+        assert_eq!(hull.len(), 4);
+        assert!(!hull.contains(&(1.0, 1.0)));
+        assert!(!hull.contains(&(1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_min_area_rect_axis_aligned() {
+        let pts = [(0.0, 0.0), (4.0, 0.0), (4.0, 2.0), (0.0, 2.0), (2.0, 1.0)];
+        let c = min_area_rect_points(&pts).unwrap();
+        let w = (c[1].0 - c[0].0).hypot(c[1].1 - c[0].1);
+        let h = (c[2].0 - c[1].0).hypot(c[2].1 - c[1].1);
+        assert!((w * h - 8.0).abs() < 1e-4);
+    }
+}
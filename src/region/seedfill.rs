@@ -4,8 +4,8 @@
 //! and grayscale images. These are useful for region filling, hole filling,
 //! and morphological reconstruction.
 
-use crate::core::{Pix, PixMut, PixelDepth, Pta};
-use crate::region::conncomp::ConnectivityType;
+use crate::core::{Pix, PixMut, Pixa, PixelDepth, Pta};
+use crate::region::conncomp::{ConnectivityType, conncomp_pixa};
 use crate::region::error::{RegionError, RegionResult};
 use std::collections::VecDeque;
 
@@ -150,6 +150,88 @@ pub fn floodfill(
     Ok(filled_count)
 }
 
+/// Color-tolerant flood fill ("magic wand" selection)
+///
+/// Starting from the seed, collects the connected region of pixels whose
+/// value is within `tolerance` of the seed pixel and returns it as a 1 bpp
+/// mask (1 = selected). For 8 bpp images the gray difference is used; for
+/// 32 bpp images (and colormapped images) the largest per-channel RGB
+/// difference is used. The source image is not modified.
+///
+/// # Arguments
+///
+/// * `pix` - Input image (8-bit or 32-bit)
+/// * `seed_x` - X coordinate of the seed point
+/// * `seed_y` - Y coordinate of the seed point
+/// * `tolerance` - Maximum allowed difference from the seed value
+/// * `connectivity` - Connectivity type
+///
+/// # Errors
+///
+/// Returns an error if the seed position is out of bounds or the image is
+/// not 8 or 32 bpp.
+pub fn floodfill_color(
+    pix: &Pix,
+    seed_x: u32,
+    seed_y: u32,
+    tolerance: u32,
+    connectivity: ConnectivityType,
+) -> RegionResult<Pix> {
+    let depth = pix.depth();
+    if depth != PixelDepth::Bit8 && depth != PixelDepth::Bit32 {
+        return Err(RegionError::UnsupportedDepth {
+            expected: "8 or 32-bit",
+            actual: depth.bits(),
+        });
+    }
+
+    let width = pix.width();
+    let height = pix.height();
+    if seed_x >= width || seed_y >= height {
+        return Err(RegionError::InvalidSeed {
+            x: seed_x,
+            y: seed_y,
+        });
+    }
+
+    // Compare as RGB triples; gray values are replicated into all channels
+    let cmap = pix.colormap();
+    let rgb_at = |x: u32, y: u32| -> (u8, u8, u8) {
+        let val = pix.get_pixel_unchecked(x, y);
+        if let Some(cmap) = cmap {
+            cmap.get_rgb(val as usize).unwrap_or((0, 0, 0))
+        } else if depth == PixelDepth::Bit32 {
+            crate::core::pixel::extract_rgb(val)
+        } else {
+            (val as u8, val as u8, val as u8)
+        }
+    };
+    let (sr, sg, sb) = rgb_at(seed_x, seed_y);
+    let within = |x: u32, y: u32| -> bool {
+        let (r, g, b) = rgb_at(x, y);
+        let diff = (r.abs_diff(sr)).max(g.abs_diff(sg)).max(b.abs_diff(sb));
+        diff as u32 <= tolerance
+    };
+
+    let mut mask = Pix::new(width, height, PixelDepth::Bit1)?
+        .try_into_mut()
+        .unwrap();
+    let mut queue = VecDeque::new();
+    mask.set_pixel_unchecked(seed_x, seed_y, 1);
+    queue.push_back((seed_x, seed_y));
+
+    while let Some((x, y)) = queue.pop_front() {
+        for (nx, ny) in get_neighbors(x, y, width, height, connectivity) {
+            if mask.get_pixel_unchecked(nx, ny) == 0 && within(nx, ny) {
+                mask.set_pixel_unchecked(nx, ny, 1);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    Ok(mask.into())
+}
+
 /// Seed fill for binary images
 ///
 /// Creates a new image by flood filling from the seed point in a copy of the input.
@@ -1664,6 +1746,29 @@ pub fn holes_by_filling(pix: &Pix, connectivity: ConnectivityType) -> RegionResu
     Ok(out_mut.into())
 }
 
+/// Find each interior hole of a binary image as a separate component.
+///
+/// A hole is a connected region of background that does not touch the
+/// image border.  Holes are extracted with [`holes_by_filling`] and split
+/// into components, so a ring yields one hole and an "8" two.
+///
+/// # Arguments
+///
+/// * `pix` - 1-bpp input image
+/// * `connectivity` - Connectivity of the background.  `FourWay` is the
+///   usual choice for 8-connected foreground: a hole only needs to be
+///   closed off by diagonal steps.
+///
+/// # Returns
+///
+/// One 1-bpp mask per hole, clipped to its bounding box, with the boxes
+/// (in image coordinates) stored in the Pixa.
+pub fn find_holes(pix: &Pix, connectivity: ConnectivityType) -> RegionResult<Pixa> {
+    let holes = holes_by_filling(pix, connectivity)?;
+    let (_, pixa) = conncomp_pixa(&holes, connectivity)?;
+    Ok(pixa)
+}
+
 /// Private helper: iterative raster/anti-raster scan with min-clamp (grayscale reconstruction).
 ///
 /// Modifies `data` in-place. Pixels where `mask_data[i] == 0` are skipped.
@@ -0,0 +1,181 @@
+//! Stroke Width Transform for grayscale images
+//!
+//! Implements the Stroke Width Transform (SWT) of Epshtein, Ofek and
+//! Wexler, used to find text-like regions whose strokes have a nearly
+//! constant width.  For every edge pixel a ray is cast across the stroke
+//! along the gradient direction; if it reaches an edge pixel whose
+//! gradient points roughly the opposite way, every pixel on the ray is
+//! assigned the length of that ray.  A second pass caps each ray's
+//! values at their median, which corrects the overestimates at corners.
+//!
+//! Edge pixels are found from Sobel gradients with non-maximum
+//! suppression, thinned so that the edge lies on the stroke side of the
+//! boundary.  Widths count both edge pixels, so a vertical bar that is
+//! `w` pixels wide has width `w`.
+//!
+//! This differs from [`crate::filter::stroke_width_transform`], which
+//! measures run lengths in a binary image.
+
+use crate::core::{FPix, Pix, PixelDepth};
+use crate::filter::edge::sobel_gradients;
+use crate::region::error::{RegionError, RegionResult};
+
+/// Minimum Sobel gradient magnitude for an edge pixel
+const MIN_EDGE_MAGNITUDE: f32 = 64.0;
+
+/// Edge threshold as a fraction of the largest gradient magnitude
+const EDGE_FRACTION: f32 = 0.1;
+
+/// Maximum angle, in radians, between the ray and the reversed gradient
+/// at the far edge for the ray to be accepted
+const MAX_OPPOSITE_ANGLE: f32 = std::f32::consts::FRAC_PI_6;
+
+/// Compute the stroke width transform of an 8 bpp grayscale image
+///
+/// `dark_on_light` selects the polarity of the strokes: `true` for dark
+/// text on a light background, `false` for light text on a dark
+/// background.  The result has the same size as `pix`; each pixel holds
+/// the stroke width at that location, or 0.0 where no stroke was found.
+///
+/// # Errors
+///
+/// Returns an error if the image is not 8 bpp or has a colormap.
+pub fn find_stroke_width_transform(pix: &Pix, dark_on_light: bool) -> RegionResult<FPix> {
+    if pix.depth() != PixelDepth::Bit8 || pix.has_colormap() {
+        return Err(RegionError::UnsupportedDepth {
+            expected: "8 bpp without colormap",
+            actual: pix.depth().bits(),
+        });
+    }
+    let w = pix.width() as usize;
+    let h = pix.height() as usize;
+    let mut out = FPix::new(pix.width(), pix.height())?;
+    if w < 3 || h < 3 {
+        return Ok(out);
+    }
+
+    let (gx, gy) = sobel_gradients(pix);
+    let mag: Vec<f32> = gx.iter().zip(&gy).map(|(x, y)| x.hypot(*y)).collect();
+    let max_mag = mag.iter().copied().fold(0.0f32, f32::max);
+    let threshold = MIN_EDGE_MAGNITUDE.max(EDGE_FRACTION * max_mag);
+
+    // Unit direction pointing into the stroke at each pixel.  Gradients
+    // point from dark to light, so dark strokes lie against them.
+    let sign = if dark_on_light { -1.0 } else { 1.0 };
+    let dir = |i: usize| (sign * gx[i] / mag[i], sign * gy[i] / mag[i]);
+
+    let edges = thin_edges(w, h, &mag, threshold, &dir);
+
+    // First pass: cast rays and keep the minimum width at each pixel
+    let mut widths = vec![f32::INFINITY; w * h];
+    let mut rays: Vec<Vec<usize>> = Vec::new();
+    let max_steps = w + h;
+    for y in 0..h {
+        for x in 0..w {
+            let start = y * w + x;
+            if !edges[start] {
+                continue;
+            }
+            let (dx, dy) = dir(start);
+            let mut ray = vec![start];
+            let (mut fx, mut fy) = (x as f32 + 0.5, y as f32 + 0.5);
+            let mut hit = None;
+            for _ in 0..max_steps {
+                fx += dx;
+                fy += dy;
+                if fx < 0.0 || fy < 0.0 || fx >= w as f32 || fy >= h as f32 {
+                    break;
+                }
+                let idx = fy as usize * w + fx as usize;
+                if ray.last() == Some(&idx) {
+                    continue;
+                }
+                ray.push(idx);
+                if edges[idx] {
+                    hit = Some(idx);
+                    break;
+                }
+            }
+            let Some(end) = hit else {
+                continue;
+            };
+            // The far edge must face back along the ray
+            let (ex, ey) = dir(end);
+            if -(dx * ex + dy * ey) < MAX_OPPOSITE_ANGLE.cos() {
+                continue;
+            }
+            let (qx, qy) = ((end % w) as f32, (end / w) as f32);
+            let width = (qx - x as f32).hypot(qy - y as f32) + 1.0;
+            for &i in &ray {
+                widths[i] = widths[i].min(width);
+            }
+            rays.push(ray);
+        }
+    }
+
+    // Second pass: cap each ray at its median width
+    let mut values = Vec::new();
+    for ray in &rays {
+        values.clear();
+        values.extend(ray.iter().map(|&i| widths[i]));
+        values.sort_by(f32::total_cmp);
+        let median = values[values.len() / 2];
+        for &i in ray {
+            widths[i] = widths[i].min(median);
+        }
+    }
+
+    for (o, &v) in out.data_mut().iter_mut().zip(&widths) {
+        *o = if v.is_finite() { v } else { 0.0 };
+    }
+    Ok(out)
+}
+
+/// Non-maximum suppression along the stroke direction.
+///
+/// A step edge gives equal magnitudes on the pixels at either side of
+/// the boundary; ties are broken toward the stroke, so each boundary
+/// yields a single edge pixel inside the stroke.
+fn thin_edges(
+    w: usize,
+    h: usize,
+    mag: &[f32],
+    threshold: f32,
+    dir: &dyn Fn(usize) -> (f32, f32),
+) -> Vec<bool> {
+    let mut edges = vec![false; w * h];
+    let at = |x: i64, y: i64| {
+        if x < 0 || y < 0 || x >= w as i64 || y >= h as i64 {
+            0.0
+        } else {
+            mag[y as usize * w + x as usize]
+        }
+    };
+    for y in 0..h {
+        for x in 0..w {
+            let i = y * w + x;
+            if mag[i] < threshold {
+                continue;
+            }
+            let (dx, dy) = dir(i);
+            let (sx, sy) = (dx.round() as i64, dy.round() as i64);
+            let (xi, yi) = (x as i64, y as i64);
+            let inward = at(xi + sx, yi + sy);
+            let outward = at(xi - sx, yi - sy);
+            edges[i] = mag[i] > inward && mag[i] >= outward;
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_image_has_no_strokes() {
+        let pix = Pix::new(20, 20, PixelDepth::Bit8).unwrap();
+        let swt = find_stroke_width_transform(&pix, true).unwrap();
+        assert!(swt.data().iter().all(|&v| v == 0.0));
+    }
+}
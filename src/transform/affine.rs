@@ -539,23 +539,52 @@ pub fn affine_sampled_pta(
 /// * `matrix` - Affine transformation matrix (forward transform)
 /// * `fill` - Background fill color
 pub fn affine(pix: &Pix, matrix: &AffineMatrix, fill: AffineFill) -> TransformResult<Pix> {
-    let depth = pix.depth();
-
-    // For 1bpp, use sampling (interpolation doesn't make sense)
-    if depth == PixelDepth::Bit1 {
-        return affine_sampled(pix, matrix, fill);
-    }
-
     // We need the inverse matrix to map from destination to source
     let inv_matrix = matrix.inverse()?;
+    affine_with_inverse(pix, &inv_matrix, fill)
+}
 
-    match depth {
-        PixelDepth::Bit8 if pix.colormap().is_none() => affine_gray(pix, &inv_matrix, fill),
-        PixelDepth::Bit32 => affine_color(pix, &inv_matrix, fill),
+/// Apply the same affine transformation to every image in a Pixa
+///
+/// Each image is transformed as by [`affine`], with the inverse matrix
+/// computed once for the whole batch.  If the Pixa has a box for every
+/// image, the boxes are mapped through `matrix` with
+/// [`boxa_affine_transform`] so they describe the transformed images'
+/// positions; otherwise the result has no boxes.
+///
+/// # Arguments
+/// * `pixa` - Input images
+/// * `matrix` - Affine transformation matrix (forward transform)
+/// * `fill` - Background fill color
+pub fn affine_batch(
+    pixa: &crate::core::Pixa,
+    matrix: &AffineMatrix,
+    fill: AffineFill,
+) -> TransformResult<crate::core::Pixa> {
+    let inv_matrix = matrix.inverse()?;
+    let mut out = crate::core::Pixa::with_capacity(pixa.len());
+    for pix in pixa.iter() {
+        out.push(affine_with_inverse(pix, &inv_matrix, fill)?);
+    }
+    if pixa.boxa_count() == pixa.len() {
+        out.set_boxa(boxa_affine_transform(pixa.boxa(), matrix));
+    }
+    Ok(out)
+}
+
+/// Apply an interpolated affine transformation given the inverse matrix
+fn affine_with_inverse(
+    pix: &Pix,
+    inv_matrix: &AffineMatrix,
+    fill: AffineFill,
+) -> TransformResult<Pix> {
+    match pix.depth() {
+        PixelDepth::Bit8 if pix.colormap().is_none() => affine_gray(pix, inv_matrix, fill),
+        PixelDepth::Bit32 => affine_color(pix, inv_matrix, fill),
         _ => {
-            // For other depths (2bpp, 4bpp, 8bpp with colormap, 16bpp),
-            // fall back to sampling
-            affine_sampled_with_inverse(pix, &inv_matrix, fill)
+            // For 1bpp interpolation doesn't make sense; for other depths
+            // (2bpp, 4bpp, 8bpp with colormap, 16bpp), fall back to sampling
+            affine_sampled_with_inverse(pix, inv_matrix, fill)
         }
     }
 }
@@ -0,0 +1,66 @@
+//! ASCII art previews
+//!
+//! Renders an image as lines of text so it can be inspected in a terminal,
+//! e.g. over SSH where no image viewer is available.
+
+use crate::core::pix::RemoveColormapTarget;
+use crate::core::{Pix, PixelDepth};
+use crate::transform::{TransformError, TransformResult, scale_to_gray, scale_to_size};
+
+/// Characters from darkest to lightest: `@` has the most ink, space none
+const ASCII_RAMP: &[u8] = b"@%#*+=-:. ";
+
+/// Height-to-width ratio of a terminal character cell
+const CHAR_ASPECT: f64 = 2.0;
+
+/// Render an image as ASCII art
+///
+/// The image is reduced to 8 bpp grayscale `cols` characters wide, with
+/// the number of rows halved to compensate for character cells being about
+/// twice as tall as they are wide.  Each gray value is mapped to one of
+/// the characters `@%#*+=-:. `, from black (`@`) to white (space), which
+/// reads correctly as dark-on-light.
+///
+/// Binary images reduced in size go through [`scale_to_gray`], so thin
+/// strokes still show up as intermediate gray instead of disappearing.
+///
+/// # Arguments
+/// * `pix` - Image of any depth
+/// * `cols` - Number of characters per line (> 0)
+///
+/// # Returns
+/// One line per row, each exactly `cols` characters and terminated by
+/// `'\n'`.
+pub fn to_ascii_art(pix: &Pix, cols: u32) -> TransformResult<String> {
+    if cols == 0 {
+        return Err(TransformError::InvalidParameters(
+            "cols must be > 0".to_string(),
+        ));
+    }
+    let (w, h) = (pix.width(), pix.height());
+    let rows = ((cols as f64 * h as f64 / w as f64 / CHAR_ASPECT).round() as u32).max(1);
+
+    let gray = if pix.depth() == PixelDepth::Bit1 && cols < w {
+        scale_to_gray(pix, cols as f32 / w as f32)?
+    } else if pix.has_colormap() {
+        pix.remove_colormap(RemoveColormapTarget::ToGrayscale)?
+    } else {
+        pix.convert_to_8()?
+    };
+    let small = if gray.width() == cols && gray.height() == rows {
+        gray
+    } else {
+        scale_to_size(&gray, cols, rows)?
+    };
+
+    let last = (ASCII_RAMP.len() - 1) as u32;
+    let mut art = String::with_capacity(((cols + 1) * rows) as usize);
+    for y in 0..rows {
+        for x in 0..cols {
+            let v = small.get_pixel_unchecked(x, y).min(255);
+            art.push(ASCII_RAMP[((v * last + 127) / 255) as usize] as char);
+        }
+        art.push('\n');
+    }
+    Ok(art)
+}
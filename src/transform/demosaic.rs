@@ -0,0 +1,102 @@
+//! Demosaicing of raw Bayer sensor data
+//!
+//! A Bayer sensor records one color per pixel through a 2x2 repeating
+//! filter pattern with two green, one red and one blue site.  Demosaicing
+//! reconstructs the two missing colors at every pixel.
+
+use crate::core::{Pix, PixelDepth, pixel};
+use crate::transform::{TransformError, TransformResult};
+
+/// Arrangement of the 2x2 Bayer filter cell
+///
+/// Named by the colors of the top-left cell read left-to-right, then
+/// top-to-bottom; the cell at (0, 0) of the image is the first color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BayerPattern {
+    /// R G / G B
+    Rggb,
+    /// B G / G R
+    Bggr,
+    /// G R / B G
+    Grbg,
+    /// G B / R G
+    Gbrg,
+}
+
+impl BayerPattern {
+    /// Channel (0 = red, 1 = green, 2 = blue) sampled at (x, y).
+    fn channel_at(self, x: u32, y: u32) -> usize {
+        let cell = match self {
+            Self::Rggb => [0, 1, 1, 2],
+            Self::Bggr => [2, 1, 1, 0],
+            Self::Grbg => [1, 0, 2, 1],
+            Self::Gbrg => [1, 2, 0, 1],
+        };
+        cell[((y & 1) * 2 + (x & 1)) as usize]
+    }
+}
+
+/// Convert a Bayer mosaic to RGB by bilinear interpolation
+///
+/// Each missing color at a pixel is the mean of the samples of that color
+/// in its 3x3 neighborhood: the four edge neighbors for green at red and
+/// blue sites, the two neighbors in the same row or column for red and
+/// blue at green sites, and the four diagonal neighbors for red at blue
+/// sites and vice versa.  At the image border, neighbor coordinates are
+/// clamped to the image and only clamped samples of the right color are
+/// used.
+///
+/// 16 bpp samples are scaled to 8 bits (65535 maps to 255).
+///
+/// # Arguments
+/// * `pix` - 8 or 16 bpp single-channel mosaic (no colormap), at least 2x2
+/// * `pattern` - Filter arrangement of the sensor
+///
+/// # Returns
+/// 32 bpp RGB image of the same size.
+pub fn demosaic_bayer(pix: &Pix, pattern: BayerPattern) -> TransformResult<Pix> {
+    let scale = match pix.depth() {
+        PixelDepth::Bit8 if !pix.has_colormap() => 1.0,
+        PixelDepth::Bit16 => 1.0 / 257.0,
+        _ => {
+            return Err(TransformError::UnsupportedDepth(
+                "demosaic_bayer requires an 8 or 16 bpp mosaic without colormap".to_string(),
+            ));
+        }
+    };
+    let (w, h) = (pix.width(), pix.height());
+    if w < 2 || h < 2 {
+        return Err(TransformError::InvalidParameters(format!(
+            "mosaic must be at least 2x2, got {w}x{h}"
+        )));
+    }
+
+    let mut out = Pix::new(w, h, PixelDepth::Bit32)?.to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            let own = pattern.channel_at(x, y);
+            let mut rgb = [0u8; 3];
+            for (c, value) in rgb.iter_mut().enumerate() {
+                let mean = if c == own {
+                    pix.get_pixel_unchecked(x, y) as f64
+                } else {
+                    let (mut sum, mut n) = (0.0, 0u32);
+                    for dy in -1i64..=1 {
+                        for dx in -1i64..=1 {
+                            let sx = (x as i64 + dx).clamp(0, w as i64 - 1) as u32;
+                            let sy = (y as i64 + dy).clamp(0, h as i64 - 1) as u32;
+                            if pattern.channel_at(sx, sy) == c {
+                                sum += pix.get_pixel_unchecked(sx, sy) as f64;
+                                n += 1;
+                            }
+                        }
+                    }
+                    sum / n as f64
+                };
+                *value = (mean * scale).round().clamp(0.0, 255.0) as u8;
+            }
+            out.set_pixel_unchecked(x, y, pixel::compose_rgb(rgb[0], rgb[1], rgb[2]));
+        }
+    }
+    Ok(out.into())
+}
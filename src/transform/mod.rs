@@ -10,11 +10,15 @@
 //! - Projective transformations (4-point correspondence, homography)
 //! - Shear transformations (horizontal and vertical)
 //! - Warping (random harmonic, stereoscopic, horizontal stretch, quadratic shear)
+//! - Bilinear demosaicing of Bayer sensor data
+//! - ASCII art previews for terminals
 
 pub mod affine;
+pub mod ascii;
 pub mod bilinear;
 pub mod binexpand;
 pub mod binreduce;
+pub mod demosaic;
 mod error;
 pub mod projective;
 pub mod rotate;
@@ -23,20 +27,23 @@ pub mod shear;
 pub mod warper;
 
 pub use affine::{
-    AffineFill, AffineMatrix, Point, affine, affine_pta, affine_pta_with_alpha, affine_rotate,
-    affine_sampled, affine_sampled_pta, affine_scale, boxa_affine_transform, boxa_rotate,
-    boxa_scale, boxa_translate, pta_affine_transform, pta_scale, pta_translate, translate,
+    AffineFill, AffineMatrix, Point, affine, affine_batch, affine_pta, affine_pta_with_alpha,
+    affine_rotate, affine_sampled, affine_sampled_pta, affine_scale, boxa_affine_transform,
+    boxa_rotate, boxa_scale, boxa_translate, pta_affine_transform, pta_scale, pta_translate,
+    translate,
 };
+pub use ascii::to_ascii_art;
 pub use bilinear::{
     BilinearCoeffs, bilinear, bilinear_pta, bilinear_pta_with_alpha, bilinear_sampled,
     bilinear_sampled_pta,
 };
 pub use binexpand::{expand_binary_power2, expand_binary_replicate, make_subsample_tab_2x};
 pub use binreduce::{reduce_rank_binary_2, reduce_rank_binary_cascade};
+pub use demosaic::{BayerPattern, demosaic_bayer};
 pub use error::{TransformError, TransformResult};
 pub use projective::{
     ProjectiveCoeffs, projective, projective_pta, projective_pta_with_alpha, projective_sampled,
-    projective_sampled_pta,
+    projective_sampled_pta, unwarp_quad,
 };
 pub use rotate::{
     RotateFill, RotateMethod, RotateOptions, embed_for_rotation, flip_lr, flip_tb, rotate,
@@ -47,13 +54,14 @@ pub use rotate::{
     rotate_with_method,
 };
 pub use scale::{
-    GrayMinMaxMode, ScaleMethod, expand_replicate, scale, scale_area_map_2, scale_area_map_to_size,
-    scale_binary, scale_binary_with_shift, scale_by_int_sampling, scale_by_sampling,
-    scale_by_sampling_to_size, scale_by_sampling_with_shift, scale_color_2x_li, scale_color_4x_li,
-    scale_color_li, scale_general, scale_gray_2x_li, scale_gray_2x_li_dither,
-    scale_gray_2x_li_thresh, scale_gray_4x_li, scale_gray_4x_li_dither, scale_gray_4x_li_thresh,
-    scale_gray_li, scale_gray_min_max, scale_gray_min_max_2, scale_gray_rank_2,
-    scale_gray_rank_cascade, scale_li, scale_smooth, scale_smooth_to_size, scale_to_gray,
+    GrayMinMaxMode, ScaleMethod, correct_chromatic_aberration, expand_replicate,
+    rank_cascade_levels, scale, scale_area_map_2, scale_area_map_to_size, scale_binary,
+    scale_binary_with_shift, scale_by_int_sampling, scale_by_sampling, scale_by_sampling_to_size,
+    scale_by_sampling_with_shift, scale_color_2x_li, scale_color_4x_li, scale_color_li,
+    scale_general, scale_gray_2x_li, scale_gray_2x_li_dither, scale_gray_2x_li_thresh,
+    scale_gray_4x_li, scale_gray_4x_li_dither, scale_gray_4x_li_thresh, scale_gray_li,
+    scale_gray_min_max, scale_gray_min_max_2, scale_gray_rank_2, scale_gray_rank_cascade, scale_li,
+    scale_linear_light, scale_rgba_li, scale_smooth, scale_smooth_to_size, scale_to_gray,
     scale_to_gray_2, scale_to_gray_3, scale_to_gray_4, scale_to_gray_6, scale_to_gray_8,
     scale_to_gray_16, scale_to_gray_fast, scale_to_gray_mipmap, scale_to_resolution, scale_to_size,
     scale_to_size_rel, scale_with_alpha,
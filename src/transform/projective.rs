@@ -5,6 +5,7 @@
 //! - Sampled projective transformation (nearest-neighbor, like pixProjectiveSampled)
 //! - Interpolated projective transformation (like pixProjective)
 //! - Coordinate transformation
+//! - Perspective crop of a quadrilateral to a rectangle
 //!
 //! # Projective Transform
 //!
@@ -52,7 +53,7 @@
 //! let transformed = projective_sampled(&pix, &coeffs, AffineFill::White).unwrap();
 //! ```
 
-use crate::core::{Pix, PixelDepth, pixel};
+use crate::core::{Pix, PixelDepth, Pta, pixel};
 use crate::transform::affine::{AffineFill, Point};
 use crate::transform::{TransformError, TransformResult};
 
@@ -671,6 +672,119 @@ pub fn projective_pta_with_alpha(
     )
 }
 
+// ============================================================================
+// Perspective Crop
+// ============================================================================
+
+/// Map a quadrilateral region of an image onto an upright rectangle
+///
+/// This is the usual perspective crop for photographed documents: the
+/// four `corners` (in the order top-left, top-right, bottom-right,
+/// bottom-left) are mapped to the corners of an `out_w` x `out_h`
+/// output image.  8bpp grayscale and 32bpp images are interpolated
+/// bilinearly; other depths are sampled.  Output pixels that map
+/// outside the source image are white.
+///
+/// # Arguments
+/// * `pix` - Input image
+/// * `corners` - 4 source points: TL, TR, BR, BL
+/// * `out_w`, `out_h` - Output dimensions
+///
+/// # Errors
+/// Returns an error if `corners` does not hold exactly 4 points, the
+/// output size is zero, or the corners are degenerate.
+pub fn unwarp_quad(pix: &Pix, corners: &Pta, out_w: u32, out_h: u32) -> TransformResult<Pix> {
+    if corners.len() != 4 {
+        return Err(TransformError::InvalidParameters(format!(
+            "expected 4 corners, got {}",
+            corners.len()
+        )));
+    }
+    if out_w == 0 || out_h == 0 {
+        return Err(TransformError::InvalidParameters(
+            "output dimensions must be positive".to_string(),
+        ));
+    }
+
+    let src_pts: [Point; 4] = std::array::from_fn(|i| {
+        let (x, y) = corners.get(i).unwrap();
+        Point::new(x, y)
+    });
+    let (xmax, ymax) = ((out_w - 1) as f32, (out_h - 1) as f32);
+    let dst_pts = [
+        Point::new(0.0, 0.0),
+        Point::new(xmax, 0.0),
+        Point::new(xmax, ymax),
+        Point::new(0.0, ymax),
+    ];
+    // Backward mapping: output rectangle -> source quad
+    let coeffs = ProjectiveCoeffs::from_four_points(dst_pts, src_pts)?;
+
+    let depth = pix.depth();
+    let interpolate =
+        (depth == PixelDepth::Bit8 && pix.colormap().is_none()) || depth == PixelDepth::Bit32;
+    let fill_value = AffineFill::White.to_value(depth);
+
+    let out_pix = Pix::new(out_w, out_h, depth)?;
+    let mut out_mut = out_pix.try_into_mut().unwrap();
+    out_mut.set_spp(pix.spp());
+    if let Some(cmap) = pix.colormap() {
+        let _ = out_mut.set_colormap(Some(cmap.clone()));
+    }
+
+    let w = pix.width();
+    let h = pix.height();
+    // Source positions within half a pixel of the image are clamped to
+    // the edge, so the image's own corners give an exact copy.
+    let (xlim, ylim) = (w as f32 - 0.5, h as f32 - 0.5);
+
+    for j in 0..out_h {
+        for i in 0..out_w {
+            let Some((xs, ys)) = coeffs.transform_point_float(i as f32, j as f32) else {
+                out_mut.set_pixel_unchecked(i, j, fill_value);
+                continue;
+            };
+            if !(-0.5..xlim).contains(&xs) || !(-0.5..ylim).contains(&ys) {
+                out_mut.set_pixel_unchecked(i, j, fill_value);
+                continue;
+            }
+            let val = if interpolate {
+                let xs = xs.clamp(0.0, (w - 1) as f32);
+                let ys = ys.clamp(0.0, (h - 1) as f32);
+                let (x0, y0) = (xs as u32, ys as u32);
+                let (x1, y1) = ((x0 + 1).min(w - 1), (y0 + 1).min(h - 1));
+                let xf = ((xs - x0 as f32) * 16.0).round() as i32;
+                let yf = ((ys - y0 as f32) * 16.0).round() as i32;
+                let p00 = pix.get_pixel_unchecked(x0, y0);
+                let p10 = pix.get_pixel_unchecked(x1, y0);
+                let p01 = pix.get_pixel_unchecked(x0, y1);
+                let p11 = pix.get_pixel_unchecked(x1, y1);
+                if depth == PixelDepth::Bit8 {
+                    area_interp(p00 as u8, p10 as u8, p01 as u8, p11 as u8, xf, yf) as u32
+                } else {
+                    let (r00, g00, b00, a00) = pixel::extract_rgba(p00);
+                    let (r10, g10, b10, a10) = pixel::extract_rgba(p10);
+                    let (r01, g01, b01, a01) = pixel::extract_rgba(p01);
+                    let (r11, g11, b11, a11) = pixel::extract_rgba(p11);
+                    pixel::compose_rgba(
+                        area_interp(r00, r10, r01, r11, xf, yf),
+                        area_interp(g00, g10, g01, g11, xf, yf),
+                        area_interp(b00, b10, b01, b11, xf, yf),
+                        area_interp(a00, a10, a01, a11, xf, yf),
+                    )
+                }
+            } else {
+                let x = (xs.round() as i32).clamp(0, w as i32 - 1) as u32;
+                let y = (ys.round() as i32).clamp(0, h as i32 - 1) as u32;
+                pix.get_pixel_unchecked(x, y)
+            };
+            out_mut.set_pixel_unchecked(i, j, val);
+        }
+    }
+
+    Ok(out_mut.into())
+}
+
 /// Fill an image with a constant value
 fn fill_image(pix: &mut crate::core::PixMut, value: u32) {
     let w = pix.width();
@@ -146,6 +146,9 @@ impl RotateOptions {
 
 /// Rotate an image by 90-degree increments
 ///
+/// Metadata is carried over as in [`rotate_90`]; xres and yres are swapped
+/// for odd `quads`.
+///
 /// # Arguments
 /// * `pix` - Input image
 /// * `quads` - Number of 90-degree clockwise rotations (0-3)
@@ -164,6 +167,9 @@ pub fn rotate_orth(pix: &Pix, quads: u32) -> TransformResult<Pix> {
 
 /// Rotate an image 90 degrees
 ///
+/// The output keeps the colormap, spp, text and input format of the
+/// source. Since the axes are exchanged, xres and yres are swapped.
+///
 /// # Arguments
 /// * `pix` - Input image
 /// * `clockwise` - If true, rotate clockwise; otherwise counterclockwise
@@ -176,9 +182,11 @@ pub fn rotate_90(pix: &Pix, clockwise: bool) -> TransformResult<Pix> {
     let out_pix = Pix::new(h, w, depth)?;
     let mut out_mut = out_pix.try_into_mut().unwrap();
 
-    if let Some(cmap) = pix.colormap() {
-        let _ = out_mut.set_colormap(Some(cmap.clone()));
-    }
+    out_mut.copy_colormap_from(pix);
+    out_mut.copy_text_from(pix);
+    out_mut.copy_input_format_from(pix);
+    out_mut.set_spp(pix.spp());
+    out_mut.set_resolution(pix.yres(), pix.xres());
 
     rotate_90_impl(pix, &mut out_mut, clockwise, w, h);
 
@@ -211,14 +219,8 @@ pub fn rotate_180(pix: &Pix) -> TransformResult<Pix> {
 pub fn flip_lr(pix: &Pix) -> TransformResult<Pix> {
     let w = pix.width();
     let h = pix.height();
-    let depth = pix.depth();
 
-    let out_pix = Pix::new(w, h, depth)?;
-    let mut out_mut = out_pix.try_into_mut().unwrap();
-
-    if let Some(cmap) = pix.colormap() {
-        let _ = out_mut.set_colormap(Some(cmap.clone()));
-    }
+    let mut out_mut = pix.create_template().try_into_mut().unwrap();
 
     for y in 0..h {
         for x in 0..w {
@@ -235,14 +237,8 @@ pub fn flip_lr(pix: &Pix) -> TransformResult<Pix> {
 pub fn flip_tb(pix: &Pix) -> TransformResult<Pix> {
     let w = pix.width();
     let h = pix.height();
-    let depth = pix.depth();
 
-    let out_pix = Pix::new(w, h, depth)?;
-    let mut out_mut = out_pix.try_into_mut().unwrap();
-
-    if let Some(cmap) = pix.colormap() {
-        let _ = out_mut.set_colormap(Some(cmap.clone()));
-    }
+    let mut out_mut = pix.create_template().try_into_mut().unwrap();
 
     for y in 0..h {
         for x in 0..w {
@@ -5,7 +5,7 @@
 //! - Sampling (nearest neighbor)
 //! - Area mapping (for downscaling with anti-aliasing)
 
-use crate::core::{Pix, PixelDepth, pixel};
+use crate::core::{Pix, Pixa, PixelDepth, pix::RgbComponent, pixel};
 use crate::transform::{TransformError, TransformResult};
 
 /// Scaling method to use
@@ -528,6 +528,318 @@ pub fn scale_color_li(pix: &Pix, scale_x: f32, scale_y: f32) -> TransformResult<
     scale_linear_color(pix, new_w.max(1), new_h.max(1))
 }
 
+/// Scale a 32bpp RGBA image to `wd` x `hd` with alpha-aware interpolation.
+///
+/// Color channels are premultiplied by alpha before resampling and divided
+/// by the resampled alpha afterwards.  Fully transparent pixels therefore
+/// contribute nothing to their neighbors' color, which avoids the dark
+/// halos that [`scale_color_li`] produces around transparent edges.
+///
+/// Each axis is resampled independently: bilinear interpolation when
+/// enlarging (or reducing by less than 0.7), and box-filter area averaging
+/// for stronger reductions.  Fully transparent output pixels are set to 0.
+///
+/// As in [`Pix::remove_alpha`](crate::Pix::remove_alpha), only an image
+/// with spp = 4 carries alpha.  Any other 32bpp image is treated as fully
+/// opaque, whatever its alpha bytes hold.  The result keeps the spp of the
+/// input.
+pub fn scale_rgba_li(pix: &Pix, wd: u32, hd: u32) -> TransformResult<Pix> {
+    if pix.depth() != PixelDepth::Bit32 {
+        return Err(TransformError::InvalidParameters(
+            "scale_rgba_li requires 32bpp input".to_string(),
+        ));
+    }
+    if wd == 0 || hd == 0 {
+        return Err(TransformError::InvalidParameters(format!(
+            "target size must be positive: ({wd}, {hd})"
+        )));
+    }
+
+    let ws = pix.width();
+    let hs = pix.height();
+    let has_alpha = pix.spp() == 4;
+
+    // Premultiplied source, alpha in [0, 1]
+    let mut premul = Vec::with_capacity((ws * hs) as usize);
+    for y in 0..hs {
+        for x in 0..ws {
+            let (r, g, b, a) = pixel::extract_rgba(pix.get_pixel_unchecked(x, y));
+            let af = if has_alpha { a as f32 / 255.0 } else { 1.0 };
+            premul.push([r as f32 * af, g as f32 * af, b as f32 * af, af]);
+        }
+    }
+
+    let xtaps = resample_taps(ws, wd);
+    let ytaps = resample_taps(hs, hd);
+
+    let mut out_mut = Pix::new(wd, hd, PixelDepth::Bit32)?.to_mut();
+    out_mut.set_spp(pix.spp());
+    out_mut.set_resolution(
+        (pix.xres() as f32 * wd as f32 / ws as f32).round() as i32,
+        (pix.yres() as f32 * hd as f32 / hs as f32).round() as i32,
+    );
+    for (y, yt) in ytaps.iter().enumerate() {
+        for (x, xt) in xtaps.iter().enumerate() {
+            let mut acc = [0.0f32; 4];
+            for &(sy, wy) in yt {
+                let row = sy * ws as usize;
+                for &(sx, wx) in xt {
+                    let p = &premul[row + sx];
+                    let wgt = wx * wy;
+                    for c in 0..4 {
+                        acc[c] += p[c] * wgt;
+                    }
+                }
+            }
+            let val = if acc[3] * 255.0 < 0.5 {
+                0
+            } else {
+                let unpremul = |v: f32| (v / acc[3]).round().clamp(0.0, 255.0) as u8;
+                pixel::compose_rgba(
+                    unpremul(acc[0]),
+                    unpremul(acc[1]),
+                    unpremul(acc[2]),
+                    (acc[3] * 255.0).round().clamp(0.0, 255.0) as u8,
+                )
+            };
+            out_mut.set_pixel_unchecked(x as u32, y as u32, val);
+        }
+    }
+
+    Ok(out_mut.into())
+}
+
+/// Scale an 8bpp gray or 32bpp color image in linear light.
+///
+/// Pixel values are taken to be sRGB-encoded.  They are decoded to linear
+/// intensity through a lookup table, resampled as in [`scale_rgba_li`]
+/// (bilinear for enlargement, box-filter area averaging for reductions
+/// below 0.7), and re-encoded.  Averaging linear intensities keeps fine
+/// light/dark detail at its true brightness when reducing: a 50/50
+/// black-and-white pattern becomes 188 rather than the 128 produced by
+/// scaling the encoded values directly.  The alpha channel of 32bpp
+/// images is resampled without conversion.
+///
+/// The other scaling functions work on the encoded values; use this one
+/// when photometric accuracy matters.
+pub fn scale_linear_light(pix: &Pix, scale_x: f32, scale_y: f32) -> TransformResult<Pix> {
+    if scale_x <= 0.0 || scale_y <= 0.0 {
+        return Err(TransformError::InvalidScaleFactor(format!(
+            "scale factors must be positive: ({}, {})",
+            scale_x, scale_y
+        )));
+    }
+    let depth = pix.depth();
+    if !matches!(depth, PixelDepth::Bit8 | PixelDepth::Bit32) || pix.has_colormap() {
+        return Err(TransformError::InvalidParameters(
+            "scale_linear_light requires 8bpp gray or 32bpp input".to_string(),
+        ));
+    }
+
+    let ws = pix.width();
+    let hs = pix.height();
+    let wd = ((ws as f32) * scale_x).round().max(1.0) as u32;
+    let hd = ((hs as f32) * scale_y).round().max(1.0) as u32;
+    let to_linear = srgb_to_linear_lut();
+    let to_srgb = linear_to_srgb_lut();
+    let encode = |v: f32| to_srgb[(v.clamp(0.0, 1.0) * LINEAR_LUT_MAX as f32).round() as usize];
+
+    // Linear source samples: [r, g, b, a] or [gray, 0, 0, 0]
+    let mut linear = Vec::with_capacity((ws * hs) as usize);
+    for y in 0..hs {
+        for x in 0..ws {
+            let val = pix.get_pixel_unchecked(x, y);
+            linear.push(if depth == PixelDepth::Bit8 {
+                [to_linear[val as usize], 0.0, 0.0, 0.0]
+            } else {
+                let (r, g, b, a) = pixel::extract_rgba(val);
+                [
+                    to_linear[r as usize],
+                    to_linear[g as usize],
+                    to_linear[b as usize],
+                    a as f32,
+                ]
+            });
+        }
+    }
+
+    let xtaps = resample_taps(ws, wd);
+    let ytaps = resample_taps(hs, hd);
+
+    let mut out_mut = Pix::new(wd, hd, depth)?.to_mut();
+    out_mut.set_spp(pix.spp());
+    out_mut.set_resolution(
+        (pix.xres() as f32 * wd as f32 / ws as f32).round() as i32,
+        (pix.yres() as f32 * hd as f32 / hs as f32).round() as i32,
+    );
+    for (y, yt) in ytaps.iter().enumerate() {
+        for (x, xt) in xtaps.iter().enumerate() {
+            let mut acc = [0.0f32; 4];
+            for &(sy, wy) in yt {
+                let row = sy * ws as usize;
+                for &(sx, wx) in xt {
+                    let p = &linear[row + sx];
+                    let wgt = wx * wy;
+                    for c in 0..4 {
+                        acc[c] += p[c] * wgt;
+                    }
+                }
+            }
+            let val = if depth == PixelDepth::Bit8 {
+                encode(acc[0]) as u32
+            } else {
+                pixel::compose_rgba(
+                    encode(acc[0]),
+                    encode(acc[1]),
+                    encode(acc[2]),
+                    acc[3].round().clamp(0.0, 255.0) as u8,
+                )
+            };
+            out_mut.set_pixel_unchecked(x as u32, y as u32, val);
+        }
+    }
+
+    Ok(out_mut.into())
+}
+
+/// Largest index of the linear-to-sRGB lookup table
+const LINEAR_LUT_MAX: usize = 4095;
+
+/// sRGB-encoded 8-bit value to linear intensity in [0, 1]
+fn srgb_to_linear_lut() -> &'static [f32; 256] {
+    static LUT: std::sync::OnceLock<[f32; 256]> = std::sync::OnceLock::new();
+    LUT.get_or_init(|| {
+        std::array::from_fn(|i| {
+            let c = i as f64 / 255.0;
+            let lin = if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            };
+            lin as f32
+        })
+    })
+}
+
+/// Linear intensity, quantized to `0..=LINEAR_LUT_MAX`, to sRGB 8-bit value
+fn linear_to_srgb_lut() -> &'static [u8; LINEAR_LUT_MAX + 1] {
+    static LUT: std::sync::OnceLock<[u8; LINEAR_LUT_MAX + 1]> = std::sync::OnceLock::new();
+    LUT.get_or_init(|| {
+        std::array::from_fn(|i| {
+            let lin = i as f64 / LINEAR_LUT_MAX as f64;
+            let c = if lin <= 0.0031308 {
+                12.92 * lin
+            } else {
+                1.055 * lin.powf(1.0 / 2.4) - 0.055
+            };
+            (c * 255.0).round().clamp(0.0, 255.0) as u8
+        })
+    })
+}
+
+/// Source taps `(index, weight)` for each of `dst` output samples along an
+/// axis of `src` input samples; weights for each output sum to 1.
+fn resample_taps(src: u32, dst: u32) -> Vec<Vec<(usize, f32)>> {
+    let ratio = src as f32 / dst as f32;
+    (0..dst)
+        .map(|i| {
+            if ratio > 1.0 / 0.7 {
+                // Box filter over the footprint [i, i+1) * ratio
+                let start = i as f32 * ratio;
+                let end = ((i + 1) as f32 * ratio).min(src as f32);
+                let mut taps = Vec::new();
+                let mut k = start.floor() as u32;
+                while (k as f32) < end && k < src {
+                    let overlap = end.min((k + 1) as f32) - start.max(k as f32);
+                    if overlap > 0.0 {
+                        taps.push((k as usize, overlap / (end - start)));
+                    }
+                    k += 1;
+                }
+                taps
+            } else {
+                // Bilinear on pixel centers
+                let pos = ((i as f32 + 0.5) * ratio - 0.5).clamp(0.0, (src - 1) as f32);
+                let k0 = pos.floor() as u32;
+                let k1 = (k0 + 1).min(src - 1);
+                let f = pos - k0 as f32;
+                if k1 == k0 || f == 0.0 {
+                    vec![(k0 as usize, 1.0)]
+                } else {
+                    vec![(k0 as usize, 1.0 - f), (k1 as usize, f)]
+                }
+            }
+        })
+        .collect()
+}
+
+/// Correct lateral chromatic aberration by rescaling red and blue.
+///
+/// Lens dispersion images the red and blue channels at slightly different
+/// magnifications than green, which shows as color fringes that grow
+/// toward the image edges.  This magnifies the red channel by `r_scale`
+/// and the blue channel by `b_scale` about the image center, keeping the
+/// image size, and leaves green and alpha unchanged.  Factors are close to
+/// 1.0 (e.g. 0.998 - 1.002); a channel imaged too large is corrected with
+/// a factor below 1.
+///
+/// Each channel is resized with [`scale_to_size`] to the nearest whole
+/// number of pixels, so the applied magnification is rounded to that
+/// size, then cropped or padded about the center back to the image size.
+/// Padding replicates the edge pixels.
+///
+/// # Errors
+///
+/// Returns an error if the image is not 32 bpp, or a factor is not
+/// positive and finite or shrinks a channel to less than a third of its
+/// size.
+pub fn correct_chromatic_aberration(pix: &Pix, r_scale: f32, b_scale: f32) -> TransformResult<Pix> {
+    if pix.depth() != PixelDepth::Bit32 {
+        return Err(TransformError::UnsupportedDepth(format!(
+            "correct_chromatic_aberration requires 32bpp, got {}bpp",
+            pix.depth().bits()
+        )));
+    }
+    for s in [r_scale, b_scale] {
+        if !s.is_finite() || s <= 0.0 {
+            return Err(TransformError::InvalidScaleFactor(format!(
+                "channel scale factors must be positive: ({}, {})",
+                r_scale, b_scale
+            )));
+        }
+    }
+
+    let red = scale_channel_about_center(&pix.get_rgb_component(RgbComponent::Red)?, r_scale)?;
+    let green = pix.get_rgb_component(RgbComponent::Green)?;
+    let blue = scale_channel_about_center(&pix.get_rgb_component(RgbComponent::Blue)?, b_scale)?;
+    let alpha = if pix.spp() == 4 {
+        Some(pix.get_rgb_component(RgbComponent::Alpha)?)
+    } else {
+        None
+    };
+
+    let mut out_mut = Pix::merge_channels(&red, &green, &blue, alpha.as_ref())?.to_mut();
+    out_mut.set_resolution(pix.xres(), pix.yres());
+    Ok(out_mut.into())
+}
+
+/// Magnify an 8bpp channel by `s` about its center, keeping its size
+fn scale_channel_about_center(pix: &Pix, s: f32) -> TransformResult<Pix> {
+    let (w, h) = (pix.width(), pix.height());
+    let sw = ((w as f32 * s).round() as u32).max(1);
+    let sh = ((h as f32 * s).round() as u32).max(1);
+    if (sw, sh) == (w, h) {
+        return Ok(pix.clone());
+    }
+    let scaled = scale_to_size(pix, sw, sh)?;
+
+    // Crop the center when enlarged, then pad back out when reduced
+    let (cw, ch) = (sw.min(w), sh.min(h));
+    let cropped = scaled.clip_rectangle((sw - cw) / 2, (sh - ch) / 2, cw, ch)?;
+    let (left, top) = ((w - cw) / 2, (h - ch) / 2);
+    Ok(cropped.add_repeated_border(left, w - cw - left, top, h - ch - top)?)
+}
+
 /// Scale an 8bpp grayscale image using bilinear interpolation.
 ///
 /// If scale factors are both < 0.7, redirects to area mapping via [`scale_general`].
@@ -1480,6 +1792,45 @@ pub fn scale_gray_rank_cascade(
     scale_gray_rank2(&t3, level4)
 }
 
+/// Apply successive 2× rank downscales, keeping every level.
+///
+/// Like [`scale_gray_rank_cascade`], but the reduction is repeated once per
+/// entry of `levels` (each the rank in 1..=4, 1=darkest) and the image
+/// after each step is returned, giving a pyramid whose i-th entry is
+/// reduced by 2^(i+1).  The input itself is not included.
+///
+/// # Errors
+///
+/// Returns an error if the input is not 8bpp or is colormapped, if
+/// `levels` is empty or holds a rank outside 1..=4, or if the image
+/// becomes too small to reduce further.
+pub fn rank_cascade_levels(pix: &Pix, levels: &[u8]) -> TransformResult<Pixa> {
+    if pix.depth() != PixelDepth::Bit8 {
+        return Err(TransformError::UnsupportedDepth(format!(
+            "{:?}",
+            pix.depth()
+        )));
+    }
+    if pix.colormap().is_some() {
+        return Err(TransformError::InvalidParameters(
+            "cmapped input not supported".to_string(),
+        ));
+    }
+    if levels.is_empty() {
+        return Err(TransformError::InvalidParameters(
+            "levels must not be empty".to_string(),
+        ));
+    }
+
+    let mut pixa = Pixa::with_capacity(levels.len());
+    let mut current = pix.clone();
+    for &rank in levels {
+        current = scale_gray_rank2(&current, rank as i32)?;
+        pixa.push(current.clone());
+    }
+    Ok(pixa)
+}
+
 /// Scale a 1bpp image to 8bpp grayscale using mipmap-based interpolation.
 ///
 /// `scale_factor` must be in `(0, 1)`.  Two nearby scale-to-gray reductions
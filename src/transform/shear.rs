@@ -67,13 +67,18 @@ pub enum ShearFill {
     White,
     /// Fill with black pixels (L_BRING_IN_BLACK in Leptonica)
     Black,
+    /// Fill with a specific pixel value (interpretation depends on depth)
+    Color(u32),
 }
 
 impl ShearFill {
     /// Convert to the rasterop incolor convention (C L_BRING_IN_WHITE/BLACK).
+    ///
+    /// `Color` has no rasterop equivalent and maps to white; the in-place
+    /// shears repaint the exposed pixels with the color afterwards.
     pub fn to_incolor(self) -> crate::core::pix::rop::InColor {
         match self {
-            ShearFill::White => crate::core::pix::rop::InColor::White,
+            ShearFill::White | ShearFill::Color(_) => crate::core::pix::rop::InColor::White,
             ShearFill::Black => crate::core::pix::rop::InColor::Black,
         }
     }
@@ -94,6 +99,7 @@ impl ShearFill {
                 PixelDepth::Bit32 => 0x00000000,
                 _ => 0,
             },
+            ShearFill::Color(val) => val,
         }
     }
 }
@@ -426,10 +432,9 @@ pub fn h_shear_ip(
     // C pixHShearIP: rows are shifted in bands of height ≈ |1/tan(angle)|,
     // with band boundaries at trunc(invangle * (hshift ± 0.5) + 0.5). The
     // per-band shift is applied with the in-place rasterop (which also
-    // fills the exposed pixels per incolor).
+    // fills the exposed pixels with the fill color).
     let h = pix.height() as i32;
     let sign: i32 = if radang > 0.0 { 1 } else { -1 };
-    let incolor = fill.to_incolor();
     let invangle = (1.0 / tan_angle).abs();
     let inityincr = (invangle / 2.0) as i32;
 
@@ -439,7 +444,7 @@ pub fn h_shear_ip(
         let yincr = (invangle * (hshift as f32 + 0.5) + 0.5) as i32 - (y - yloc);
         if yincr != 0 {
             let yincr = yincr.min(h - y);
-            pix.rasterop_hip(y, yincr, -sign * hshift, incolor);
+            shift_rows_ip(pix, y, yincr, -sign * hshift, fill);
             y += yincr;
         }
         hshift += 1;
@@ -451,7 +456,7 @@ pub fn h_shear_ip(
         let yincr = (y - yloc) - (invangle * (hshift as f32 - 0.5) + 0.5) as i32;
         if yincr != 0 {
             let yincr = yincr.min(y);
-            pix.rasterop_hip(y - yincr, yincr, -sign * hshift, incolor);
+            shift_rows_ip(pix, y - yincr, yincr, -sign * hshift, fill);
             y -= yincr;
         }
         hshift -= 1;
@@ -460,6 +465,36 @@ pub fn h_shear_ip(
     Ok(())
 }
 
+/// Shift a band of rows horizontally in place, filling the exposed pixels
+fn shift_rows_ip(pix: &mut PixMut, by: i32, bh: i32, hshift: i32, fill: ShearFill) {
+    pix.rasterop_hip(by, bh, hshift, fill.to_incolor());
+    if let ShearFill::Color(val) = fill {
+        let (w, h) = (pix.width() as i32, pix.height() as i32);
+        let n = hshift.abs().min(w);
+        let x0 = if hshift > 0 { 0 } else { w - n };
+        for y in by.max(0)..(by + bh).min(h) {
+            for x in x0..x0 + n {
+                pix.set_pixel_unchecked(x as u32, y as u32, val);
+            }
+        }
+    }
+}
+
+/// Shift a band of columns vertically in place, filling the exposed pixels
+fn shift_columns_ip(pix: &mut PixMut, bx: i32, bw: i32, vshift: i32, fill: ShearFill) {
+    pix.rasterop_vip(bx, bw, vshift, fill.to_incolor());
+    if let ShearFill::Color(val) = fill {
+        let (w, h) = (pix.width() as i32, pix.height() as i32);
+        let n = vshift.abs().min(h);
+        let y0 = if vshift > 0 { 0 } else { h - n };
+        for y in y0..y0 + n {
+            for x in bx.max(0)..(bx + bw).min(w) {
+                pix.set_pixel_unchecked(x as u32, y as u32, val);
+            }
+        }
+    }
+}
+
 /// In-place vertical shear transformation
 ///
 /// This is equivalent to Leptonica's `pixVShearIP`.
@@ -498,7 +533,6 @@ pub fn v_shear_ip(
     // +sign*vshift (the horizontal variant uses -sign*hshift).
     let w = pix.width() as i32;
     let sign: i32 = if radang > 0.0 { 1 } else { -1 };
-    let incolor = fill.to_incolor();
     let invangle = (1.0 / tan_angle).abs();
     let initxincr = (invangle / 2.0) as i32;
 
@@ -508,7 +542,7 @@ pub fn v_shear_ip(
         let xincr = (invangle * (vshift as f32 + 0.5) + 0.5) as i32 - (x - xloc);
         if xincr != 0 {
             let xincr = xincr.min(w - x);
-            pix.rasterop_vip(x, xincr, sign * vshift, incolor);
+            shift_columns_ip(pix, x, xincr, sign * vshift, fill);
             x += xincr;
         }
         vshift += 1;
@@ -520,7 +554,7 @@ pub fn v_shear_ip(
         let xincr = (x - xloc) - (invangle * (vshift as f32 - 0.5) + 0.5) as i32;
         if xincr != 0 {
             let xincr = xincr.min(x);
-            pix.rasterop_vip(x - xincr, xincr, sign * vshift, incolor);
+            shift_columns_ip(pix, x - xincr, xincr, sign * vshift, fill);
             x -= xincr;
         }
         vshift -= 1;
@@ -119,6 +119,8 @@ pub enum WarpFill {
     White,
     /// Fill with black pixels (L_BRING_IN_BLACK)
     Black,
+    /// Fill with a specific pixel value (interpretation depends on depth)
+    Color(u32),
 }
 
 impl WarpFill {
@@ -138,6 +140,7 @@ impl WarpFill {
                 PixelDepth::Bit32 => 0x00000000,
                 _ => 0,
             },
+            WarpFill::Color(val) => val,
         }
     }
 }
@@ -1285,6 +1288,7 @@ fn h_shear_li(pix: &Pix, yloc: i32, angle: f32, fill: WarpFill) -> TransformResu
     let shear_fill = match fill {
         WarpFill::White => ShearFill::White,
         WarpFill::Black => ShearFill::Black,
+        WarpFill::Color(val) => ShearFill::Color(val),
     };
 
     shear_h_shear_li(pix, yloc, angle, shear_fill)
@@ -1382,6 +1386,7 @@ mod tests {
         assert_eq!(WarpFill::Black.to_value(PixelDepth::Bit8), 0);
         assert_eq!(WarpFill::White.to_value(PixelDepth::Bit32), 0xFFFFFF00);
         assert_eq!(WarpFill::Black.to_value(PixelDepth::Bit32), 0);
+        assert_eq!(WarpFill::Color(0x80).to_value(PixelDepth::Bit8), 0x80);
     }
 
     // ========================================================================
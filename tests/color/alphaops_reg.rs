@@ -6,15 +6,19 @@
 //!
 //! Full migration: alpha_blend_uniform, remove_alpha, multiply_by_color,
 //! blend_with_gray_mask, set_alpha_over_white, and blend_background_to_color
-//! are all tested.
+//! are all tested, as are blend_over_checkerboard,
+//! blend_with_gray_mask_feathered and remove_alpha_with_background.
 //!
 //! # See also
 //!
 //! C Leptonica: `prog/alphaops_reg.c`
 
 use crate::common::RegParams;
+use leptonica::core::pixel::{compose_rgba, extract_rgb};
 use leptonica::io::ImageFormat;
-use leptonica::{PixelDepth, blend_with_gray_mask};
+use leptonica::{
+    Pix, PixelDepth, blend_over_checkerboard, blend_with_gray_mask, blend_with_gray_mask_feathered,
+};
 
 /// Test alpha_blend_uniform (C checks 0-1, 4).
 ///
@@ -126,6 +130,56 @@ fn alphaops_reg_blend_with_mask() {
     assert!(rp.cleanup(), "alphaops blend_with_mask test failed");
 }
 
+/// Test blend_with_gray_mask_feathered.
+///
+/// A hard-edged mask blends abruptly; feathering it gives intermediate
+/// values in a band around the edge while leaving far pixels unchanged.
+#[test]
+fn alphaops_reg_blend_with_mask_feathered() {
+    let mut rp = RegParams::new("alphaops_mask_feathered");
+
+    let (w, h) = (40u32, 20u32);
+    let solid = |v: u32| {
+        let mut pm = Pix::new(w, h, PixelDepth::Bit8).unwrap().to_mut();
+        pm.set_all_arbitrary(v).unwrap();
+        Pix::from(pm)
+    };
+    let base = solid(0);
+    let overlay = solid(200);
+    // Left half opaque, right half transparent
+    let mut mask = Pix::new(w, h, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..h {
+        for x in 0..w / 2 {
+            mask.set_pixel_unchecked(x, y, 255);
+        }
+    }
+    let mask: Pix = mask.into();
+
+    let hard = blend_with_gray_mask_feathered(&base, &overlay, &mask, 0).expect("feather 0");
+    rp.compare_values(200.0, hard.get_pixel(19, 10).unwrap() as f64, 0.0);
+    rp.compare_values(0.0, hard.get_pixel(20, 10).unwrap() as f64, 0.0);
+
+    let soft = blend_with_gray_mask_feathered(&base, &overlay, &mask, 3).expect("feather 3");
+    let left = soft.get_pixel(19, 10).unwrap();
+    let right = soft.get_pixel(20, 10).unwrap();
+    rp.compare_values(1.0, (left > 100 && left < 200) as u8 as f64, 0.0);
+    rp.compare_values(1.0, (right > 0 && right < 100) as u8 as f64, 0.0);
+    // Monotonic ramp across the edge
+    let row: Vec<u32> = (14..26).map(|x| soft.get_pixel(x, 10).unwrap()).collect();
+    rp.compare_values(1.0, row.windows(2).all(|p| p[0] >= p[1]) as u8 as f64, 0.0);
+    // Far from the edge the sources are untouched
+    rp.compare_values(200.0, soft.get_pixel(5, 10).unwrap() as f64, 0.0);
+    rp.compare_values(0.0, soft.get_pixel(35, 10).unwrap() as f64, 0.0);
+
+    let mask32 = Pix::new(w, h, PixelDepth::Bit32).unwrap();
+    assert!(blend_with_gray_mask_feathered(&base, &overlay, &mask32, 3).is_err());
+
+    assert!(
+        rp.cleanup(),
+        "alphaops blend_with_mask_feathered test failed"
+    );
+}
+
 /// Test pixSetAlphaOverWhite and pixBlendBackgroundToColor (C checks 2-3).
 ///
 /// Loads blend-green1.jpg, applies set_alpha_over_white to generate alpha
@@ -188,3 +242,145 @@ fn alphaops_reg_set_alpha_over_white() {
 
     assert!(rp.cleanup(), "alphaops set_alpha_over_white test failed");
 }
+
+/// Test blend_over_checkerboard.
+///
+/// A fully transparent image yields the bare checkerboard; opaque pixels
+/// keep their source color; an spp=3 image is opaque whatever its alpha
+/// bytes hold; 8bpp input is rejected.
+#[test]
+fn alphaops_reg_blend_over_checkerboard() {
+    let mut rp = RegParams::new("alphaops_checkerboard");
+
+    let (w, h, cell) = (20u32, 12u32, 4u32);
+    let mut pm = Pix::new(w, h, PixelDepth::Bit32).unwrap().to_mut();
+    pm.set_spp(4);
+    for y in 0..h {
+        for x in 0..w {
+            // Left half fully transparent, right half opaque orange
+            let val = if x < w / 2 {
+                compose_rgba(10, 20, 30, 0)
+            } else {
+                compose_rgba(250, 120, 10, 255)
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    let pix: Pix = pm.into();
+
+    let out = blend_over_checkerboard(&pix, cell).expect("blend_over_checkerboard");
+    assert_eq!(out.depth(), PixelDepth::Bit32);
+    rp.compare_values(3.0, out.spp() as f64, 0.0);
+
+    let mut checker_ok = true;
+    let mut source_ok = true;
+    for y in 0..h {
+        for x in 0..w {
+            let (r, g, b) = extract_rgb(out.get_pixel_unchecked(x, y));
+            if x < w / 2 {
+                let expected = if ((x / cell) + (y / cell)).is_multiple_of(2) {
+                    0xcc
+                } else {
+                    0x99
+                };
+                checker_ok &= r == expected && g == expected && b == expected;
+            } else {
+                source_ok &= (r, g, b) == (250, 120, 10);
+            }
+        }
+    }
+    rp.compare_values(1.0, if checker_ok { 1.0 } else { 0.0 }, 0.0);
+    rp.compare_values(1.0, if source_ok { 1.0 } else { 0.0 }, 0.0);
+
+    // Half-transparent white over the light cell at the origin
+    let mut pm = Pix::new(1, 1, PixelDepth::Bit32).unwrap().to_mut();
+    pm.set_spp(4);
+    pm.set_pixel_unchecked(0, 0, compose_rgba(255, 255, 255, 128));
+    let out = blend_over_checkerboard(&pm.into(), cell).unwrap();
+    let (r, _, _) = extract_rgb(out.get_pixel_unchecked(0, 0));
+    rp.compare_values(230.0, r as f64, 1.0);
+
+    // The same pixel in an spp=3 image is opaque: the alpha byte is ignored
+    let mut pm = Pix::new(1, 1, PixelDepth::Bit32).unwrap().to_mut();
+    pm.set_spp(3);
+    pm.set_pixel_unchecked(0, 0, compose_rgba(40, 80, 120, 0));
+    let out = blend_over_checkerboard(&pm.into(), cell).unwrap();
+    let (r, g, b) = extract_rgb(out.get_pixel_unchecked(0, 0));
+    rp.compare_values(40.0, r as f64, 0.0);
+    rp.compare_values(80.0, g as f64, 0.0);
+    rp.compare_values(120.0, b as f64, 0.0);
+
+    let pix8 = Pix::new(8, 8, PixelDepth::Bit8).unwrap();
+    assert!(blend_over_checkerboard(&pix8, cell).is_err());
+    assert!(blend_over_checkerboard(&pix, 0).is_err());
+
+    assert!(rp.cleanup(), "alphaops blend_over_checkerboard test failed");
+}
+
+/// Test remove_alpha_with_background.
+///
+/// Semi-transparent white over a black background gives gray in
+/// proportion to alpha.
+#[test]
+fn alphaops_reg_remove_alpha_with_background() {
+    let mut rp = RegParams::new("alphaops_remove_alpha_bg");
+
+    let mut pm = Pix::new(4, 2, PixelDepth::Bit32).unwrap().to_mut();
+    pm.set_spp(4);
+    for (x, a) in [0u8, 64, 128, 255].into_iter().enumerate() {
+        pm.set_pixel_unchecked(x as u32, 0, compose_rgba(255, 255, 255, a));
+        pm.set_pixel_unchecked(x as u32, 1, compose_rgba(200, 100, 0, a));
+    }
+    let pix: Pix = pm.into();
+
+    let flat = pix
+        .remove_alpha_with_background(leptonica::Color::BLACK)
+        .expect("remove_alpha_with_background");
+    rp.compare_values(3.0, flat.spp() as f64, 0.0);
+    for (x, expected) in [0.0, 64.0, 128.0, 255.0].into_iter().enumerate() {
+        let val = flat.get_pixel_unchecked(x as u32, 0);
+        let (r, g, b) = extract_rgb(val);
+        rp.compare_values(expected, r as f64, 0.0);
+        rp.compare_values(expected, g as f64, 0.0);
+        rp.compare_values(expected, b as f64, 0.0);
+        rp.compare_values(255.0, (val & 0xff) as f64, 0.0);
+    }
+
+    // Colored background: alpha 128 mixes about half and half
+    let bg = leptonica::Color::new(0, 0, 200);
+    let flat = pix.remove_alpha_with_background(bg).expect("colored bg");
+    let (r, g, b) = extract_rgb(flat.get_pixel_unchecked(2, 1));
+    rp.compare_values(100.0, r as f64, 1.0);
+    rp.compare_values(50.0, g as f64, 1.0);
+    rp.compare_values(100.0, b as f64, 1.0);
+    let (r, g, b) = extract_rgb(flat.get_pixel_unchecked(0, 1));
+    rp.compare_values(0.0, (r as u32 + g as u32) as f64, 0.0);
+    rp.compare_values(200.0, b as f64, 0.0);
+
+    // White background matches remove_alpha
+    let white = pix
+        .remove_alpha_with_background(leptonica::Color::WHITE)
+        .expect("white bg");
+    let reference = pix.remove_alpha().expect("remove_alpha");
+    for x in 0..4 {
+        for y in 0..2 {
+            let (r1, g1, b1) = extract_rgb(white.get_pixel_unchecked(x, y));
+            let (r2, g2, b2) = extract_rgb(reference.get_pixel_unchecked(x, y));
+            rp.compare_values(r2 as f64, r1 as f64, 1.0);
+            rp.compare_values(g2 as f64, g1 as f64, 1.0);
+            rp.compare_values(b2 as f64, b1 as f64, 1.0);
+        }
+    }
+
+    // Non-RGBA input is cloned
+    let pix8 = Pix::new(4, 4, PixelDepth::Bit8).unwrap();
+    let same = pix8
+        .remove_alpha_with_background(leptonica::Color::BLACK)
+        .expect("8bpp");
+    rp.compare_values(8.0, same.depth().bits() as f64, 0.0);
+
+    assert!(
+        rp.cleanup(),
+        "alphaops remove_alpha_with_background test failed"
+    );
+}
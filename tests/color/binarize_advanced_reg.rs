@@ -6,10 +6,12 @@
 //! - pixVarThresholdToBinary, pixGenerateMaskByValue, pixGenerateMaskByBand
 //! - pixThresholdTo2bpp, pixThresholdTo4bpp
 //! - pixOtsuAdaptiveThreshold, pixSauvolaBinarizeTiled
+//! - otsu_adaptive (interpolated per-tile Otsu)
 
 use leptonica::color::threshold::{
-    generate_mask_by_band, generate_mask_by_value, otsu_adaptive_threshold, sauvola_binarize_tiled,
-    threshold_to_2bpp, threshold_to_4bpp, var_threshold_to_binary,
+    compute_otsu_threshold, generate_mask_by_band, generate_mask_by_value, otsu_adaptive,
+    otsu_adaptive_threshold, sauvola_binarize_tiled, threshold_otsu, threshold_to_2bpp,
+    threshold_to_4bpp, var_threshold_to_binary,
 };
 use leptonica::{Pix, PixelDepth};
 
@@ -281,6 +283,75 @@ fn test_otsu_adaptive_basic() {
     assert_ne!(dark_px, bright_px, "dark and bright regions should differ");
 }
 
+// ============================================================================
+// otsu_adaptive
+// ============================================================================
+
+/// Half-dark (bg 80, text 20) / half-light (bg 220, text 150) page with
+/// horizontal text-like strokes in both halves.
+fn make_two_illumination_page() -> Pix {
+    let pix = Pix::new(200, 100, PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..100u32 {
+        for x in 0..200u32 {
+            let dark_half = x < 100;
+            let is_text = y % 10 < 3 && (10..65).contains(&(x % 100));
+            let val = match (dark_half, is_text) {
+                (true, false) => 80,
+                (true, true) => 20,
+                (false, false) => 220,
+                (false, true) => 150,
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    pm.into()
+}
+
+#[test]
+fn test_otsu_adaptive_two_illuminations() {
+    let pix = make_two_illumination_page();
+
+    // A single global threshold cannot separate text from background in
+    // both halves: it lands between the two background levels.
+    let global = compute_otsu_threshold(&pix).unwrap();
+    assert!(global > 80 && global <= 150);
+    let global_bin = threshold_otsu(&pix).unwrap();
+    assert_eq!(global_bin.get_pixel_unchecked(70, 5), 1);
+
+    // With 4x2 tiles every tile lies in one half and contains text, so
+    // text is ON and background OFF on both sides (away from the
+    // interpolation zone between the two middle tile centers).
+    let binary = otsu_adaptive(&pix, 4, 2, 0).unwrap();
+    assert_eq!(binary.depth(), PixelDepth::Bit1);
+    assert_eq!(binary.width(), 200);
+    assert_eq!(binary.height(), 100);
+    for y in 0..100u32 {
+        for x in (0..75u32).chain(125..200) {
+            let expected = u32::from(y % 10 < 3 && (10..65).contains(&(x % 100)));
+            assert_eq!(
+                binary.get_pixel_unchecked(x, y),
+                expected,
+                "pixel ({x}, {y})"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_otsu_adaptive_smooth_and_params() {
+    let pix = make_two_illumination_page();
+    let smoothed = otsu_adaptive(&pix, 4, 2, 1).unwrap();
+    assert_eq!(smoothed.depth(), PixelDepth::Bit1);
+    // Dark-half text is still found after smoothing the threshold grid.
+    assert_eq!(smoothed.get_pixel_unchecked(20, 1), 1);
+
+    assert!(otsu_adaptive(&pix, 0, 2, 0).is_err());
+    assert!(otsu_adaptive(&pix, 4, 101, 0).is_err());
+    let pix1 = Pix::new(10, 10, PixelDepth::Bit1).unwrap();
+    assert!(otsu_adaptive(&pix1, 1, 1, 0).is_err());
+}
+
 // ============================================================================
 // sauvola_binarize_tiled
 // ============================================================================
@@ -5,11 +5,15 @@
 //!
 //! Expanded in Phase 5 to add tiled Sauvola, sauvola_on_contrast_norm,
 //! and thresh_on_double_norm.
+//!
+//! Also tests auto_binarize method selection and
+//! edge_guided_binarize.
 
 use crate::common::{RegParams, load_test_image};
 use leptonica::PixelDepth;
 use leptonica::color::{
-    AdaptiveThresholdOptions, adaptive_threshold, compute_otsu_threshold, dither_to_binary,
+    AdaptiveThresholdOptions, AutoBinarizeMethod, adaptive_threshold, auto_binarize,
+    auto_binarize_with_method, compute_otsu_threshold, dither_to_binary, edge_guided_binarize,
     sauvola_binarize_tiled, sauvola_on_contrast_norm, sauvola_threshold, thresh_on_double_norm,
     threshold_otsu, threshold_to_binary,
 };
@@ -237,3 +241,112 @@ fn binarize_reg_thresh_on_double_norm() {
 
     assert!(rp.cleanup(), "binarize thresh_on_double_norm test failed");
 }
+
+/// Synthetic 240x240 page of text bars with 35% of the paper reflectance.
+/// The paper is either a uniform 220 or lit unevenly, ramping from 240
+/// down to 120 across the page.  Returns the page and the number of text
+/// pixels.
+fn make_page(ramp: bool) -> (leptonica::Pix, u64) {
+    let (w, h) = (240u32, 240u32);
+    let mut pm = leptonica::Pix::new(w, h, PixelDepth::Bit8)
+        .unwrap()
+        .to_mut();
+    let mut ntext = 0;
+    for y in 0..h {
+        for x in 0..w {
+            let paper = if ramp { 240 - 120 * x / (w - 1) } else { 220 };
+            let text = (20..220).contains(&x) && y % 20 >= 8 && y % 20 < 12 && x % 12 < 8;
+            if text {
+                ntext += 1;
+            }
+            pm.set_pixel_unchecked(x, y, if text { paper * 35 / 100 } else { paper });
+        }
+    }
+    (pm.into(), ntext)
+}
+
+#[test]
+fn binarize_reg_auto() {
+    let mut rp = RegParams::new("binarize_auto");
+
+    // Uniform paper: global Otsu recovers the text exactly
+    let (page, ntext) = make_page(false);
+    let (bin, method) = auto_binarize_with_method(&page).expect("auto uniform");
+    rp.compare_values(1.0, (method == AutoBinarizeMethod::Otsu) as u8 as f64, 0.0);
+    rp.compare_values(1.0, bin.depth().bits() as f64, 0.0);
+    rp.compare_values(ntext as f64, bin.count_pixels() as f64, 0.0);
+
+    // Paper darkening across the page: Sauvola is chosen, and the dark
+    // side of the paper is not swallowed as foreground
+    let (page, ntext) = make_page(true);
+    let (bin, method) = auto_binarize_with_method(&page).expect("auto ramp");
+    rp.compare_values(
+        1.0,
+        (method == AutoBinarizeMethod::Sauvola) as u8 as f64,
+        0.0,
+    );
+    let fg = bin.count_pixels() as f64;
+    rp.compare_values(ntext as f64, fg, 0.2 * ntext as f64);
+    let global = threshold_otsu(&page).expect("otsu ramp");
+    let global_err = (global.count_pixels() as f64 - ntext as f64).abs();
+    rp.compare_values(
+        1.0,
+        ((fg - ntext as f64).abs() < global_err) as u8 as f64,
+        0.0,
+    );
+
+    // The plain variant returns the same image
+    let bin2 = auto_binarize(&page).expect("auto_binarize");
+    rp.compare_pix(&bin, &bin2);
+
+    assert!(rp.cleanup(), "binarize auto test failed");
+}
+
+#[test]
+fn binarize_reg_edge_guided() {
+    let mut rp = RegParams::new("binarize_edge");
+
+    // Textured paper (200 +- 4) with a dark block at 40 and a faint
+    // 2-pixel stroke at 170, which a global Otsu threshold drops
+    let (w, h) = (120u32, 80u32);
+    let mut pm = leptonica::Pix::new(w, h, PixelDepth::Bit8)
+        .unwrap()
+        .to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            let paper = 196 + (x * 7 + y * 13) % 9;
+            let val = if (10..40).contains(&x) && (20..60).contains(&y) {
+                40
+            } else if (80..82).contains(&x) && (10..70).contains(&y) {
+                170
+            } else {
+                paper
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    let page: leptonica::Pix = pm.into();
+    let count_stroke = |pix: &leptonica::Pix| {
+        (10..70)
+            .flat_map(|y| (80..82).map(move |x| (x, y)))
+            .filter(|&(x, y)| pix.get_pixel_unchecked(x, y) != 0)
+            .count() as f64
+    };
+
+    let otsu = threshold_otsu(&page).expect("otsu");
+    rp.compare_values(0.0, count_stroke(&otsu), 0.0);
+
+    let bin = edge_guided_binarize(&page, 12).expect("edge guided");
+    rp.compare_values(1.0, bin.depth().bits() as f64, 0.0);
+    rp.compare_values(120.0, count_stroke(&bin), 0.0);
+    // The Otsu foreground is kept and the paper texture stays background
+    rp.compare_values(1200.0 + 120.0, bin.count_pixels() as f64, 0.0);
+
+    rp.compare_values(
+        1.0,
+        edge_guided_binarize(&page, 0).is_err() as u8 as f64,
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "binarize edge-guided test failed");
+}
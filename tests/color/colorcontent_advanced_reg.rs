@@ -6,10 +6,13 @@
 //! - pixMaskOverColorPixels, pixMaskOverGrayPixels, pixMaskOverColorRange
 //! - pixColorFraction, pixNumSignificantGrayColors, pixColorsForQuantization
 //! - pixGetRGBHistogram, pixGetMostPopulatedColors
+//!
+//! Also covers HSV-based `colorful_mask`.
 
 use leptonica::color::analysis::{
-    color_fraction, colors_for_quantization, mask_over_color_pixels, mask_over_color_range,
-    mask_over_gray_pixels, most_populated_colors, num_significant_gray_colors, rgb_histogram,
+    color_fraction, colorful_mask, colors_for_quantization, mask_over_color_pixels,
+    mask_over_color_range, mask_over_gray_pixels, most_populated_colors,
+    num_significant_gray_colors, rgb_histogram,
 };
 use leptonica::core::pixel;
 use leptonica::{Pix, PixelDepth};
@@ -337,3 +340,62 @@ fn test_most_populated_colors_uniform() {
     assert!((g as i32 - 150).abs() < 10);
     assert!((b as i32 - 200).abs() < 10);
 }
+
+// ============================================================================
+// colorful_mask
+// ============================================================================
+
+#[test]
+fn test_colorful_mask_red_stamp_on_gray_text() {
+    // Light paper with dark gray "text" strokes on the left and a red
+    // stamp (with a slightly faded rim) on the right
+    let (w, h) = (80u32, 40u32);
+    let mut pm = Pix::new(w, h, PixelDepth::Bit32).unwrap().to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            let val = if (50..70).contains(&x) && (10..30).contains(&y) {
+                if x == 50 || y == 10 {
+                    pixel::compose_rgb(230, 120, 120)
+                } else {
+                    pixel::compose_rgb(200, 30, 40)
+                }
+            } else if x < 40 && y % 6 < 2 {
+                pixel::compose_rgb(60, 62, 58)
+            } else {
+                pixel::compose_rgb(235, 233, 230)
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    let pix: Pix = pm.into();
+
+    let mask = colorful_mask(&pix, 0.3, 0.3).unwrap();
+    assert_eq!(mask.depth(), PixelDepth::Bit1);
+    // The whole stamp, including the faded rim, is masked
+    for y in 10..30 {
+        for x in 50..70 {
+            assert_eq!(mask.get_pixel(x, y), Some(1), "stamp at ({x}, {y})");
+        }
+    }
+    // Text and paper are not
+    assert_eq!(mask.count_pixels(), 400);
+    assert_eq!(mask.get_pixel(5, 0), Some(0));
+    assert_eq!(mask.get_pixel(5, 3), Some(0));
+}
+
+#[test]
+fn test_colorful_mask_dark_color_rejected_by_value() {
+    let pix = make_uniform_rgb(60, 0, 0, 10, 10);
+    // Fully saturated but dark: rejected unless min_value is low
+    assert_eq!(colorful_mask(&pix, 0.5, 0.5).unwrap().count_pixels(), 0);
+    assert_eq!(colorful_mask(&pix, 0.5, 0.1).unwrap().count_pixels(), 100);
+}
+
+#[test]
+fn test_colorful_mask_invalid() {
+    let pix = make_uniform_rgb(200, 0, 0, 4, 4);
+    assert!(colorful_mask(&pix, 1.5, 0.5).is_err());
+    assert!(colorful_mask(&pix, 0.5, -0.1).is_err());
+    let gray = Pix::new(4, 4, PixelDepth::Bit8).unwrap();
+    assert!(colorful_mask(&gray, 0.3, 0.3).is_err());
+}
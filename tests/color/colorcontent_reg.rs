@@ -1,12 +1,13 @@
 //! Color content analysis regression test
 //!
 //! C version: prog/colorcontent_reg.c
-//! Tests color_content, count_colors, is_grayscale, grayscale_histogram.
+//! Tests color_content, count_colors, is_grayscale, grayscale_histogram,
+//! and count_colors_capped.
 
 use crate::common::{RegParams, load_test_image};
 use leptonica::color::{
-    color_content, color_content_by_location, count_colors, grayscale_histogram, is_grayscale,
-    is_grayscale_tolerant,
+    color_content, color_content_by_location, count_colors, count_colors_capped,
+    grayscale_histogram, is_grayscale, is_grayscale_tolerant,
 };
 use leptonica::core::pixel;
 use leptonica::io::ImageFormat;
@@ -230,3 +231,38 @@ fn colorcontent_reg() {
 
     assert!(rp.cleanup(), "colorcontent regression test failed");
 }
+
+/// Test `count_colors_capped`: exact below the cap, sentinel above it.
+#[test]
+fn colorcontent_reg_count_colors_capped() {
+    let mut rp = RegParams::new("colorcontent_capped");
+
+    let known = create_known_color_image();
+    rp.compare_values(3.0, count_colors_capped(&known, 256).unwrap() as f64, 0.0);
+    rp.compare_values(3.0, count_colors_capped(&known, 3).unwrap() as f64, 0.0);
+    rp.compare_values(3.0, count_colors_capped(&known, 2).unwrap() as f64, 0.0);
+
+    // A photo exceeds the cap and reports cap + 1
+    let marge = load_test_image("marge.jpg").expect("load marge.jpg");
+    rp.compare_values(257.0, count_colors_capped(&marge, 256).unwrap() as f64, 0.0);
+    let exact = count_colors(&marge).unwrap();
+    rp.compare_values(
+        exact as f64,
+        count_colors_capped(&marge, u32::MAX).unwrap() as f64,
+        0.0,
+    );
+
+    let gray = create_grayscale_8bpp();
+    let n8 = count_colors(&gray).unwrap();
+    rp.compare_values(
+        n8 as f64,
+        count_colors_capped(&gray, 256).unwrap() as f64,
+        0.0,
+    );
+    rp.compare_values(2.0, count_colors_capped(&gray, 1).unwrap() as f64, 0.0);
+
+    let pix4 = Pix::new(4, 4, PixelDepth::Bit4).unwrap();
+    assert!(count_colors_capped(&pix4, 10).is_err());
+
+    assert!(rp.cleanup(), "colorcontent capped count test failed");
+}
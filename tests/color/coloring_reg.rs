@@ -7,14 +7,17 @@
 //! Colormap-based shifting is not available in the Rust API.
 //! Test image harmoniam100-11.png is not available; test24.jpg is used instead.
 //!
+//! Also covers `chroma_key`.
+//!
 //! # See also
 //!
 //! C Leptonica: `prog/coloring_reg.c`
 
 use crate::common::RegParams;
-use leptonica::PixelDepth;
-use leptonica::color::pix_shift_by_component;
+use leptonica::color::{chroma_key, pix_shift_by_component};
+use leptonica::core::pixel;
 use leptonica::io::ImageFormat;
+use leptonica::{Color, Pix, PixelDepth};
 
 /// Test pix_shift_by_component for background coloring (C checks 4-7).
 ///
@@ -85,3 +88,74 @@ fn coloring_reg_colormap() {
     // pixcmapResetColor(cmap, index, rval, gval, bval) -- modify colormap entry
     // pixShiftByComponent(NULL, pix0, scolor, dcolor) -- shift on colormapped
 }
+
+/// Test chroma_key on a synthetic green-screen image.
+///
+/// A red disk with a soft edge on a green background: the background must
+/// become transparent, the disk stay opaque, and the blended edge get
+/// intermediate alpha.
+#[test]
+fn coloring_reg_chroma_key() {
+    let mut rp = RegParams::new("coloring_chroma");
+
+    let green = (30u8, 200u8, 40u8);
+    let red = (200u8, 40u8, 30u8);
+    let mut pm = Pix::new(80, 60, PixelDepth::Bit32).unwrap().to_mut();
+    for y in 0..60 {
+        for x in 0..80 {
+            // Linear blend from red to green between radius 15 and 21
+            let d = ((x as f32 - 40.0).powi(2) + (y as f32 - 30.0).powi(2)).sqrt();
+            let t = ((d - 15.0) / 6.0).clamp(0.0, 1.0);
+            let mix = |a: u8, b: u8| (a as f32 * (1.0 - t) + b as f32 * t).round() as u8;
+            let val = pixel::compose_rgb(
+                mix(red.0, green.0),
+                mix(red.1, green.1),
+                mix(red.2, green.2),
+            );
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    let pix: Pix = pm.into();
+
+    let keyed = chroma_key(&pix, Color::new(green.0, green.1, green.2), 0.2, 0.3).unwrap();
+    rp.compare_values(32.0, keyed.depth().bits() as f64, 0.0);
+    rp.compare_values(4.0, keyed.spp() as f64, 0.0);
+    let alpha = |x: u32, y: u32| pixel::extract_rgba(keyed.get_pixel_unchecked(x, y)).3;
+
+    // Background fully transparent, including every corner region
+    let bg_clear = (0..60).all(|y| {
+        (0..80).all(|x| {
+            let d = ((x as f32 - 40.0).powi(2) + (y as f32 - 30.0).powi(2)).sqrt();
+            d < 21.0 || alpha(x, y) == 0
+        })
+    });
+    rp.compare_values(1.0, bg_clear as u8 as f64, 0.0);
+
+    // Foreground opaque, with its color kept
+    rp.compare_values(255.0, alpha(40, 30) as f64, 0.0);
+    rp.compare_values(255.0, alpha(50, 30) as f64, 0.0);
+    let (r, g, b, _) = pixel::extract_rgba(keyed.get_pixel_unchecked(40, 30));
+    rp.compare_values(1.0, ((r, g, b) == red) as u8 as f64, 0.0);
+
+    // Partial alpha somewhere on the blended edge along the x axis
+    let partial = (55..62).any(|x| (1..255).contains(&alpha(x, 30)));
+    rp.compare_values(1.0, partial as u8 as f64, 0.0);
+    // Alpha decreases monotonically going out through the edge
+    let monotonic = (55..62).all(|x| alpha(x + 1, 30) <= alpha(x, 30));
+    rp.compare_values(1.0, monotonic as u8 as f64, 0.0);
+
+    // Zero softness gives a hard matte
+    let hard = chroma_key(&pix, Color::new(green.0, green.1, green.2), 0.3, 0.0).unwrap();
+    let binary_alpha = (0..60).all(|y| {
+        (0..80).all(|x| {
+            let a = pixel::extract_rgba(hard.get_pixel_unchecked(x, y)).3;
+            a == 0 || a == 255
+        })
+    });
+    rp.compare_values(1.0, binary_alpha as u8 as f64, 0.0);
+
+    assert!(chroma_key(&pix, Color::new(0, 255, 0), -0.1, 0.1).is_err());
+    assert!(chroma_key(&pix, Color::new(0, 255, 0), 0.1, f32::NAN).is_err());
+
+    assert!(rp.cleanup(), "coloring chroma key test failed");
+}
@@ -7,14 +7,16 @@
 //! - HSV colormap roundtrip (pix_colormap_convert_rgb_to_hsv / hsv_to_rgb)
 //! - Color magnitude sweep with both ColorMagnitudeType methods
 //! - HSV spectrum generation and verification
+//!
+//! Also covers color_deconvolve stain separation.
 
 use crate::common::{RegParams, load_test_image};
 use leptonica::PixelDepth;
 use leptonica::color::{
-    ColorMagnitudeType, color_magnitude, hsv_to_rgb, lab_to_rgb, pix_colormap_convert_hsv_to_rgb,
-    pix_colormap_convert_rgb_to_hsv, pix_convert_hsv_to_rgb, pix_convert_rgb_to_hsv,
-    pix_convert_to_gray, rgb_to_gray, rgb_to_hsv, rgb_to_lab, rgb_to_xyz, rgb_to_yuv, xyz_to_rgb,
-    yuv_to_rgb,
+    ColorMagnitudeType, color_deconvolve, color_magnitude, hsv_to_rgb, lab_to_rgb,
+    pix_colormap_convert_hsv_to_rgb, pix_colormap_convert_rgb_to_hsv, pix_convert_hsv_to_rgb,
+    pix_convert_rgb_to_hsv, pix_convert_to_gray, rgb_to_gray, rgb_to_hsv, rgb_to_lab, rgb_to_xyz,
+    rgb_to_yuv, xyz_to_rgb, yuv_to_rgb,
 };
 use leptonica::io::ImageFormat;
 
@@ -243,3 +245,62 @@ fn count_pixels_above(pix: &leptonica::Pix, thresh: u8) -> u32 {
     }
     count
 }
+
+#[test]
+fn colorspace_reg_color_deconvolve() {
+    let mut rp = RegParams::new("colorspace_deconvolve");
+
+    // White page with gray ink text (left), red stamp ink (right), and the
+    // two inks overlapping in the middle (transmittances multiply)
+    let ink = (120u32, 120u32, 120u32);
+    let red = (230u32, 40u32, 40u32);
+    let mul = |a: u32, b: u32| a * b / 255;
+    let pix = leptonica::Pix::new(90, 30, PixelDepth::Bit32).unwrap();
+    let mut pm = pix.to_mut();
+    for y in 0..30 {
+        for x in 0..90 {
+            let (r, g, b) = match x {
+                0..30 => ink,
+                30..60 => (mul(ink.0, red.0), mul(ink.1, red.1), mul(ink.2, red.2)),
+                _ => red,
+            };
+            let (r, g, b) = if y < 5 { (255, 255, 255) } else { (r, g, b) };
+            pm.set_pixel_unchecked(
+                x,
+                y,
+                leptonica::core::pixel::compose_rgb(r as u8, g as u8, b as u8),
+            );
+        }
+    }
+    let pix: leptonica::Pix = pm.into();
+
+    let od = |v: u32| -((v as f32 + 1.0) / 256.0).log10();
+    let red_stain = (od(red.0), od(red.1), od(red.2));
+    let planes = color_deconvolve(&pix, &[red_stain, (1.0, 1.0, 1.0)]).expect("deconvolve");
+    rp.compare_values(2.0, planes.len() as f64, 0.0);
+    let stamp = planes.get(0).unwrap();
+    let text = planes.get(1).unwrap();
+    rp.compare_values(8.0, stamp.depth().bits() as f64, 0.0);
+
+    let at = |p: &leptonica::Pix, x: u32| p.get_pixel_unchecked(x, 15) as f64;
+    // Background carries no stain
+    rp.compare_values(0.0, stamp.get_pixel_unchecked(10, 2) as f64, 1.0);
+    rp.compare_values(0.0, text.get_pixel_unchecked(10, 2) as f64, 1.0);
+    // Each ink alone shows up only in its own plane
+    rp.compare_values(0.0, at(stamp, 10), 2.0);
+    rp.compare_values(1.0, (at(text, 10) > 25.0) as u8 as f64, 0.0);
+    rp.compare_values(0.0, at(text, 80), 2.0);
+    rp.compare_values(1.0, (at(stamp, 80) > 60.0) as u8 as f64, 0.0);
+    // Where the inks overlap, each plane recovers its own ink
+    rp.compare_values(at(stamp, 80), at(stamp, 45), 3.0);
+    rp.compare_values(at(text, 10), at(text, 45), 3.0);
+
+    // Parallel stains cannot be separated
+    rp.compare_values(
+        1.0,
+        color_deconvolve(&pix, &[(1.0, 1.0, 1.0), (2.0, 2.0, 2.0)]).is_err() as u8 as f64,
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "colorspace_reg_color_deconvolve failed");
+}
@@ -16,7 +16,8 @@ use crate::common::RegParams;
 use leptonica::Pix;
 use leptonica::PixelDepth;
 use leptonica::color::{
-    dither_to_2bpp, dither_to_binary, dither_to_binary_with_threshold, ordered_dither,
+    BayerSize, dither_to_2bpp, dither_to_binary, dither_to_binary_with_threshold, ordered_dither,
+    ordered_dither_bayer,
 };
 use leptonica::filter::gamma_trc_pix;
 use leptonica::io::ImageFormat;
@@ -95,6 +96,62 @@ fn dither_reg_ordered() {
     assert!(rp.cleanup(), "ordered dither test failed");
 }
 
+/// ordered_dither_bayer with an explicit matrix size.
+///
+/// A flat mid-gray (127) image dithered with the 4x4 Bayer matrix turns
+/// exactly the 8 matrix cells with index >= 8 ON, which for the recursive
+/// Bayer layout is a 1-pixel checkerboard; the pattern repeats with period 4.
+#[test]
+fn dither_reg_ordered_bayer_size() {
+    let mut rp = RegParams::new("dither_bayer");
+
+    let mut pm = Pix::new(16, 12, PixelDepth::Bit8)
+        .unwrap()
+        .try_into_mut()
+        .unwrap();
+    for y in 0..12 {
+        for x in 0..16 {
+            pm.set_pixel(x, y, 127).unwrap();
+        }
+    }
+    let pix: Pix = pm.into();
+
+    let d4 = ordered_dither_bayer(&pix, BayerSize::N4).expect("bayer N4");
+    assert_eq!(d4.depth(), PixelDepth::Bit1);
+    let mut on = 0;
+    for y in 0..12 {
+        for x in 0..16 {
+            let v = d4.get_pixel(x, y).unwrap();
+            assert_eq!(v, (x + y) % 2, "checkerboard at ({x}, {y})");
+            assert_eq!(v, d4.get_pixel(x % 4, y % 4).unwrap(), "period 4");
+            on += v;
+        }
+    }
+    rp.compare_values((16 * 12 / 2) as f64, on as f64, 0.0);
+
+    // The integer-size entry point and the default size agree with N4.
+    let legacy = ordered_dither(&pix, 4).expect("ordered_dither 4x4");
+    let dflt = ordered_dither_bayer(&pix, BayerSize::default()).expect("default");
+    assert!(legacy.equals(&d4));
+    assert!(dflt.equals(&d4));
+
+    // Coarser and finer matrices tile with their own period.
+    for size in [BayerSize::N2, BayerSize::N8] {
+        let n = size.dimension();
+        let d = ordered_dither_bayer(&pix, size).expect("bayer");
+        for y in 0..12 {
+            for x in 0..16 {
+                assert_eq!(
+                    d.get_pixel(x, y).unwrap(),
+                    d.get_pixel(x % n, y % n).unwrap()
+                );
+            }
+        }
+    }
+
+    assert!(rp.cleanup(), "ordered bayer dither test failed");
+}
+
 /// dither_to_binary must reproduce C ditherToBinaryLineLow exactly:
 /// 3-neighbor error diffusion (3/8 right, 3/8 below, 1/4 below-right) in
 /// integer arithmetic, with no propagation when the pixel is within
@@ -6,10 +6,12 @@
 //! - pixFixedOctcubeQuant256, pixOctreeQuantByPopulation
 //! - pixOctreeQuantNumColors, pixMedianCutQuantMixed
 //! - pixQuantFromCmap, pixRemoveUnusedColors
+//!
+//! Also covers kmeans_quant and quantize_to_depth.
 
 use leptonica::color::quantize::{
-    fixed_octcube_quant_256, median_cut_quant_mixed, octree_quant_by_population,
-    octree_quant_num_colors, quant_from_cmap, remove_unused_colors,
+    fixed_octcube_quant_256, kmeans_quant, median_cut_quant_mixed, octree_quant_by_population,
+    octree_quant_num_colors, quant_from_cmap, quantize_to_depth, remove_unused_colors,
 };
 use leptonica::core::pixel;
 use leptonica::{Pix, PixColormap, PixelDepth};
@@ -330,3 +332,110 @@ fn test_remove_unused_colors_no_colormap() {
     let pix = Pix::new(10, 10, PixelDepth::Bit32).unwrap();
     assert!(remove_unused_colors(&pix).is_err());
 }
+
+// ============================================================================
+// kmeans_quant
+// ============================================================================
+
+/// Create a two-color image (reddish and bluish pixels in a 2:3 mix)
+fn make_two_color(w: u32, h: u32) -> Pix {
+    let pix = Pix::new(w, h, PixelDepth::Bit32).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..h {
+        for x in 0..w {
+            let pixel = if (x + 2 * y) % 5 < 2 {
+                pixel::compose_rgb(200, 40, 30)
+            } else {
+                pixel::compose_rgb(20, 90, 220)
+            };
+            pm.set_pixel_unchecked(x, y, pixel);
+        }
+    }
+    pm.into()
+}
+
+#[test]
+fn test_kmeans_quant_recovers_two_colors() {
+    let pix = make_two_color(40, 30);
+    let result = kmeans_quant(&pix, 2, 20, 7).unwrap();
+    assert_eq!(result.depth(), PixelDepth::Bit8);
+    let cmap = result.colormap().unwrap();
+    assert_eq!(cmap.len(), 2);
+
+    let mut found: Vec<(u8, u8, u8)> = (0..2).map(|i| cmap.get_rgb(i).unwrap()).collect();
+    found.sort();
+    assert_eq!(found, vec![(20, 90, 220), (200, 40, 30)]);
+
+    // Every pixel maps back to its original color
+    for y in 0..30 {
+        for x in 0..40 {
+            let idx = result.get_pixel_unchecked(x, y) as usize;
+            let (r, g, b) = cmap.get_rgb(idx).unwrap();
+            let (sr, sg, sb) = pixel::extract_rgb(pix.get_pixel_unchecked(x, y));
+            assert_eq!((r, g, b), (sr, sg, sb));
+        }
+    }
+}
+
+#[test]
+fn test_kmeans_quant_deterministic_and_bounded() {
+    let pix = make_color_gradient(64, 48);
+    let a = kmeans_quant(&pix, 6, 10, 42).unwrap();
+    let b = kmeans_quant(&pix, 6, 10, 42).unwrap();
+    assert!(a.colormap().unwrap().len() <= 6);
+    assert!(a.equals_with_cmap(&b));
+
+    // More clusters than distinct colors: one entry per color
+    let tri = make_tricolor(30, 10);
+    let result = kmeans_quant(&tri, 8, 10, 1).unwrap();
+    assert_eq!(result.colormap().unwrap().len(), 3);
+}
+
+#[test]
+fn test_kmeans_quant_invalid() {
+    let pix8 = Pix::new(10, 10, PixelDepth::Bit8).unwrap();
+    assert!(kmeans_quant(&pix8, 2, 10, 0).is_err());
+    let pix = make_tricolor(30, 10);
+    assert!(kmeans_quant(&pix, 0, 10, 0).is_err());
+    assert!(kmeans_quant(&pix, 257, 10, 0).is_err());
+}
+
+// ============================================================================
+// quantize_to_depth
+// ============================================================================
+
+#[test]
+fn test_quantize_to_depth_photo() {
+    let pix = crate::common::load_test_image("test24.jpg").expect("load test24.jpg");
+    assert_eq!(pix.depth(), PixelDepth::Bit32);
+
+    for (d, depth, max) in [
+        (2, PixelDepth::Bit2, 4),
+        (4, PixelDepth::Bit4, 16),
+        (8, PixelDepth::Bit8, 256),
+    ] {
+        let result = quantize_to_depth(&pix, d).unwrap();
+        assert_eq!(result.depth(), depth);
+        assert_eq!(result.width(), pix.width());
+        let cmap = result.colormap().unwrap();
+        assert!(cmap.len() <= max);
+        assert!(cmap.len() > 1);
+        // Every index refers to a valid colormap entry
+        let n = cmap.len() as u32;
+        for y in (0..result.height()).step_by(7) {
+            for x in (0..result.width()).step_by(7) {
+                assert!(result.get_pixel_unchecked(x, y) < n);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_quantize_to_depth_invalid() {
+    let pix = make_tricolor(30, 10);
+    for d in [0, 1, 3, 16, 32] {
+        assert!(quantize_to_depth(&pix, d).is_err());
+    }
+    let pix8 = Pix::new(10, 10, PixelDepth::Bit8).unwrap();
+    assert!(quantize_to_depth(&pix8, 4).is_err());
+}
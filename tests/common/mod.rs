@@ -89,3 +89,34 @@ pub fn golden_dir() -> String {
 pub fn regout_dir() -> String {
     format!("{}/tests/regout", workspace_root())
 }
+
+/// 64x64 8 bpp image: 60 on the left half, 190 on the right half
+///
+/// Shared by the edge-preserving smoothing tests.
+pub fn make_edge_image() -> leptonica::Pix {
+    let mut pm = leptonica::Pix::new(64, 64, leptonica::PixelDepth::Bit8)
+        .unwrap()
+        .to_mut();
+    for y in 0..64 {
+        for x in 0..64 {
+            pm.set_pixel_unchecked(x, y, if x < 32 { 60 } else { 190 });
+        }
+    }
+    pm.into()
+}
+
+/// Mean and standard deviation of columns `x0..x1` over all rows.
+pub fn column_stats(pix: &leptonica::Pix, x0: u32, x1: u32) -> (f64, f64) {
+    let mut sum = 0.0;
+    let mut sumsq = 0.0;
+    for y in 0..pix.height() {
+        for x in x0..x1 {
+            let v = pix.get_pixel_unchecked(x, y) as f64;
+            sum += v;
+            sumsq += v * v;
+        }
+    }
+    let n = ((x1 - x0) * pix.height()) as f64;
+    let mean = sum / n;
+    (mean, (sumsq / n - mean * mean).max(0.0).sqrt())
+}
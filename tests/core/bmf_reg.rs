@@ -1,6 +1,7 @@
 //! Bitmap font and text operations regression test
 //!
-//! Tests Bmf creation, glyph access, text measurement, and rendering.
+//! Tests Bmf creation, glyph access, text measurement, and rendering, plus
+//! `PixMut::draw_text` with the built-in 8x8 font.
 //!
 //! # See also
 //!
@@ -9,7 +10,7 @@
 use crate::common::RegParams;
 use leptonica::core::bmf;
 use leptonica::core::pixel;
-use leptonica::{Bmf, Pix, Pixa, PixelDepth, TextLocation};
+use leptonica::{Bmf, Color, Pix, Pixa, PixelDepth, TextLocation};
 
 // ==========================================================================
 // Test 1: Bmf creation at various sizes
@@ -697,3 +698,77 @@ fn add_single_textblock_clamps_out_of_range_val() {
     }
     assert!(differs, "out-of-range val should still draw text (clamped)");
 }
+
+// ==========================================================================
+// Built-in 8x8 font: PixMut::draw_text
+// ==========================================================================
+
+#[test]
+fn draw_text_glyph_pattern() {
+    let mut rp = RegParams::new("draw_text_glyph");
+
+    // Expected 'A' glyph, '#' = ON
+    let expected = [
+        "..##....", ".####...", "##..##..", "##..##..", "######..", "##..##..", "##..##..",
+        "........",
+    ];
+
+    let mut pm = Pix::new(12, 12, PixelDepth::Bit8).unwrap().to_mut();
+    pm.set_all_gray(255).unwrap();
+    pm.draw_text("A", 2, 3, Color::BLACK, 1).unwrap();
+    let pix: Pix = pm.into();
+    let mut mismatches = 0;
+    for y in 0..12 {
+        for x in 0..12 {
+            let on = (2..10).contains(&x)
+                && (3..11).contains(&y)
+                && expected[(y - 3) as usize].as_bytes()[(x - 2) as usize] == b'#';
+            let want = if on { 0 } else { 255 };
+            if pix.get_pixel(x, y).unwrap() != want {
+                mismatches += 1;
+            }
+        }
+    }
+    rp.compare_values(0.0, mismatches as f64, 0.0);
+
+    // Scale 2 doubles every glyph pixel (32bpp, colored)
+    let mut pm = Pix::new(20, 20, PixelDepth::Bit32).unwrap().to_mut();
+    pm.draw_text("A", 0, 0, Color::RED, 2).unwrap();
+    let pix: Pix = pm.into();
+    let red = pixel::compose_rgb(255, 0, 0);
+    rp.compare_values(0.0, pix.get_pixel(0, 0).unwrap() as f64, 0.0);
+    rp.compare_values(red as f64, pix.get_pixel(4, 0).unwrap() as f64, 0.0);
+    rp.compare_values(red as f64, pix.get_pixel(7, 1).unwrap() as f64, 0.0);
+    rp.compare_values(0.0, pix.get_pixel(8, 0).unwrap() as f64, 0.0);
+
+    assert!(rp.cleanup(), "draw_text glyph pattern test failed");
+}
+
+#[test]
+fn draw_text_clipping_and_errors() {
+    let mut rp = RegParams::new("draw_text_clip");
+
+    // Text running off every edge must not panic; only in-bounds pixels change
+    let mut pm = Pix::new(16, 10, PixelDepth::Bit8).unwrap().to_mut();
+    pm.draw_text("Hello\nworld", -5, -4, Color::WHITE, 1)
+        .unwrap();
+    pm.draw_text("xyz", 12, 6, Color::WHITE, 3).unwrap();
+    pm.draw_text("far", 100, 100, Color::WHITE, 1).unwrap();
+    let pix: Pix = pm.into();
+    rp.compare_values(1.0, (pix.count_pixels() > 0) as u32 as f64, 0.0);
+
+    // Non-printable characters render as '?'
+    let mut a = Pix::new(8, 8, PixelDepth::Bit8).unwrap().to_mut();
+    a.draw_text("\u{7f}", 0, 0, Color::WHITE, 1).unwrap();
+    let mut b = Pix::new(8, 8, PixelDepth::Bit8).unwrap().to_mut();
+    b.draw_text("?", 0, 0, Color::WHITE, 1).unwrap();
+    let (a, b): (Pix, Pix) = (a.into(), b.into());
+    assert!(a.equals(&b));
+
+    let mut pm = Pix::new(8, 8, PixelDepth::Bit1).unwrap().to_mut();
+    assert!(pm.draw_text("A", 0, 0, Color::BLACK, 1).is_err());
+    let mut pm = Pix::new(8, 8, PixelDepth::Bit8).unwrap().to_mut();
+    assert!(pm.draw_text("A", 0, 0, Color::BLACK, 0).is_err());
+
+    assert!(rp.cleanup(), "draw_text clipping test failed");
+}
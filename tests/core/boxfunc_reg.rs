@@ -3,8 +3,10 @@
 //! # See also
 //!
 //! C Leptonica: `boxfunc1.c`, `boxfunc4.c`
+//!
+//! Also covers `Box::iou` and `Boxa::nms`.
 
-use leptonica::{Box, Boxa, SizeRelation};
+use leptonica::{Box, Boxa, Numa, SizeRelation};
 
 // ============================================================================
 // Box::overlap_area / overlap_fraction
@@ -39,6 +41,43 @@ fn test_overlap_fraction_zero_area() {
     assert_eq!(b1.overlap_fraction(&b2), 0.0);
 }
 
+// ============================================================================
+// Box::iou / Boxa::nms
+// ============================================================================
+
+#[test]
+fn test_iou() {
+    let b1 = Box::new(0, 0, 100, 100).unwrap();
+    let b2 = Box::new(50, 0, 100, 100).unwrap();
+    // 5000 / (10000 + 10000 - 5000)
+    assert!((b1.iou(&b2) - 1.0 / 3.0).abs() < 1e-9);
+    assert_eq!(b1.iou(&b1), 1.0);
+    assert_eq!(b1.iou(&Box::new(200, 200, 10, 10).unwrap()), 0.0);
+    let empty = Box::new(0, 0, 0, 0).unwrap();
+    assert_eq!(empty.iou(&empty), 0.0);
+}
+
+#[test]
+fn test_nms_collapses_overlaps() {
+    let mut boxa = Boxa::new();
+    boxa.push(Box::new(0, 0, 100, 100).unwrap()); // 0: weaker duplicate
+    boxa.push(Box::new(300, 300, 50, 50).unwrap()); // 1: isolated
+    boxa.push(Box::new(5, 5, 100, 100).unwrap()); // 2: strongest
+    let scores = Numa::from_vec(vec![0.6, 0.3, 0.9]);
+
+    let (kept, idx) = boxa.nms(&scores, 0.5).unwrap();
+    assert_eq!(idx, vec![2, 1]);
+    assert_eq!(kept.len(), 2);
+    assert_eq!(kept.get(0).unwrap(), boxa.get(2).unwrap());
+    assert_eq!(kept.get(1).unwrap(), boxa.get(1).unwrap());
+
+    // A threshold above the overlap keeps everything, in score order
+    let (_, idx) = boxa.nms(&scores, 0.95).unwrap();
+    assert_eq!(idx, vec![2, 0, 1]);
+
+    assert!(boxa.nms(&Numa::from_vec(vec![1.0]), 0.5).is_err());
+}
+
 // ============================================================================
 // Boxa::contained_in_box
 // ============================================================================
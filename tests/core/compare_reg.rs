@@ -1,7 +1,9 @@
 //! Image comparison regression test
 //!
 //! Tests image comparison functions including pixel counting,
-//! equality checking, and binary correlation.
+//! equality checking, binary correlation, and the perceptual
+//! hashes (`average_hash`, `difference_hash`) and shift-tolerant
+//! `compare_aligned`.
 //!
 //! The C version also tests pixBestCorrelation and pixCompareWithTranslation
 //! which are not available in leptonica-core.
@@ -220,3 +222,85 @@ fn compare_reg_perceptual_diff() {
 
     assert!(rp.cleanup(), "compare perceptual diff test failed");
 }
+
+/// Test average_hash and difference_hash.
+///
+/// A JPEG round trip should leave both perceptual hashes within a few
+/// bits of the original, while an unrelated image differs substantially.
+#[test]
+fn compare_reg_perceptual_hash() {
+    use leptonica::io::jpeg::{JpegOptions, write_jpeg};
+
+    let mut rp = RegParams::new("compare_phash");
+
+    let pix = crate::common::load_test_image("marge.jpg").expect("load marge.jpg");
+    let mut buf = Vec::new();
+    write_jpeg(&pix, &mut buf, &JpegOptions { quality: 50 }).expect("write jpeg");
+    let recompressed = leptonica::io::read_image_mem(&buf).expect("read jpeg");
+
+    let a_dist = (pix.average_hash() ^ recompressed.average_hash()).count_ones();
+    let d_dist = (pix.difference_hash() ^ recompressed.difference_hash()).count_ones();
+    rp.compare_values(1.0, if a_dist <= 4 { 1.0 } else { 0.0 }, 0.0);
+    rp.compare_values(1.0, if d_dist <= 6 { 1.0 } else { 0.0 }, 0.0);
+
+    // Hashes are deterministic
+    rp.compare_values(
+        1.0,
+        (pix.average_hash() == pix.average_hash()) as u8 as f64,
+        0.0,
+    );
+
+    // An unrelated image is far away
+    let other = crate::common::load_test_image("test8.jpg").expect("load test8.jpg");
+    let far = (pix.average_hash() ^ other.average_hash()).count_ones();
+    rp.compare_values(1.0, if far > 10 { 1.0 } else { 0.0 }, 0.0);
+
+    assert!(rp.cleanup(), "compare perceptual hash test failed");
+}
+
+/// Test `compare_aligned`: a 1 px rendering shift is found and
+/// reported with ~zero difference.
+#[test]
+fn compare_reg_aligned() {
+    use leptonica::core::pix::compare_aligned;
+
+    let mut rp = RegParams::new("compare_aligned");
+
+    let pix1 = crate::common::load_test_image("test8.jpg").expect("load test8.jpg");
+    let (w, h) = (pix1.width(), pix1.height());
+
+    // pix2(x, y) = pix1(x + 1, y - 1): pix2 must move by (+1, -1) to align
+    let mut pm = Pix::new(w, h, pix1.depth()).unwrap().to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            let sx = (x + 1).min(w - 1);
+            let sy = y.saturating_sub(1);
+            pm.set_pixel_unchecked(x, y, pix1.get_pixel_unchecked(sx, sy));
+        }
+    }
+    let pix2: Pix = pm.into();
+
+    let plain = pix1.compare(&pix2).unwrap();
+    rp.compare_values(1.0, (plain.mean_abs_diff > 1.0) as u8 as f64, 0.0);
+
+    let res = compare_aligned(&pix1, &pix2, 2).expect("compare_aligned");
+    rp.compare_values(1.0, res.delx as f64, 0.0);
+    rp.compare_values(-1.0, res.dely as f64, 0.0);
+    rp.compare_values(0.0, res.compare.mean_abs_diff, 0.01);
+    rp.compare_values(1.0, res.compare.equal as u8 as f64, 0.0);
+
+    // Identical images align at zero shift
+    let same = compare_aligned(&pix1, &pix1, 3).unwrap();
+    rp.compare_values(0.0, same.delx as f64, 0.0);
+    rp.compare_values(0.0, same.dely as f64, 0.0);
+
+    // Size and depth must match
+    let small = Pix::new(w - 1, h, pix1.depth()).unwrap();
+    rp.compare_values(
+        1.0,
+        compare_aligned(&pix1, &small, 1).is_err() as u8 as f64,
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "compare aligned test failed");
+}
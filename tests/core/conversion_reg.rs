@@ -6,16 +6,21 @@
 //! The C version uses 9 different source images across all bit depths
 //! and performs 32+ regression checks covering pixConvertTo8/32/16,
 //! pixThreshold*, pixRemoveColormap, etc.
-//! This Rust port tests available depth conversion APIs.
+//! This Rust port tests available depth conversion APIs, plus the
+//! integer `rgb_to_gray_weighted`, `split_channels` / `merge_channels` and
+//! `to_rgb`.
 //!
 //! # See also
 //!
 //! C Leptonica: `prog/conversion_reg.c`
 
 use crate::common::RegParams;
-use leptonica::PixelDepth;
-use leptonica::core::pix::RemoveColormapTarget;
+use leptonica::color::pix_convert_to_gray;
+use leptonica::core::pix::convert::rgb_to_gray_weighted;
+use leptonica::core::pix::{RemoveColormapTarget, RgbComponent};
+use leptonica::core::pixel;
 use leptonica::io::ImageFormat;
+use leptonica::{Pix, PixelDepth};
 
 /// Test 1bpp → various depth conversions (C checks 0-3).
 ///
@@ -252,3 +257,163 @@ fn conversion_reg_from_32bpp() {
 
     assert!(rp.cleanup(), "conversion from 32bpp test failed");
 }
+
+/// Test rgb_to_gray_weighted integer conversion.
+///
+/// Weights (1, 0, 0) give the red channel, BT.601 weights reproduce
+/// `pix_convert_to_gray`, and invalid weights are rejected.
+#[test]
+fn conversion_reg_rgb_to_gray_weighted() {
+    let mut rp = RegParams::new("conversion_gray_weighted");
+
+    let pix32 = crate::common::load_test_image("test24.jpg").expect("load test24.jpg");
+    assert_eq!(pix32.depth(), PixelDepth::Bit32);
+
+    let red = rgb_to_gray_weighted(&pix32, 1.0, 0.0, 0.0).expect("red weights");
+    let red_channel = pix32
+        .get_rgb_component(RgbComponent::Red)
+        .expect("red channel");
+    rp.compare_pix(&red_channel, &red);
+
+    let bt601 = rgb_to_gray_weighted(&pix32, 0.299, 0.587, 0.114).expect("bt601");
+    let reference = pix_convert_to_gray(&pix32).expect("pix_convert_to_gray");
+    rp.compare_pix(&reference, &bt601);
+
+    // Weights need not be normalized
+    let equal = rgb_to_gray_weighted(&pix32, 2.0, 2.0, 2.0).expect("equal");
+    let (r, g, b) = pixel::extract_rgb(pix32.get_pixel(10, 10).unwrap());
+    let expected = (r as f64 + g as f64 + b as f64) / 3.0;
+    rp.compare_values(expected, equal.get_pixel(10, 10).unwrap() as f64, 1.0);
+
+    assert!(rgb_to_gray_weighted(&pix32, -0.1, 0.5, 0.5).is_err());
+    assert!(rgb_to_gray_weighted(&pix32, 0.0, 0.0, 0.0).is_err());
+    let pix8 = Pix::new(4, 4, PixelDepth::Bit8).unwrap();
+    assert!(rgb_to_gray_weighted(&pix8, 0.3, 0.3, 0.4).is_err());
+
+    assert!(rp.cleanup(), "conversion rgb_to_gray_weighted test failed");
+}
+
+/// Test split_channels / merge_channels round trip.
+#[test]
+fn conversion_reg_split_merge_channels() {
+    let mut rp = RegParams::new("conversion_split_merge");
+
+    // RGBA image with varying alpha
+    let pix32 = crate::common::load_test_image("test24.jpg").expect("load test24.jpg");
+    let mut pm = pix32.to_mut();
+    for y in 0..pm.height() {
+        for x in 0..pm.width() {
+            let (r, g, b) = pixel::extract_rgb(pm.get_pixel_unchecked(x, y));
+            let a = ((x + 3 * y) % 256) as u8;
+            pm.set_pixel_unchecked(x, y, pixel::compose_rgba(r, g, b, a));
+        }
+    }
+    pm.set_spp(4);
+    let rgba: Pix = pm.into();
+
+    let [r, g, b, a] = rgba.split_channels().expect("split_channels");
+    rp.compare_values(8.0, a.depth().bits() as f64, 0.0);
+    rp.compare_pix(&rgba.get_rgb_component(RgbComponent::Green).unwrap(), &g);
+
+    let merged = Pix::merge_channels(&r, &g, &b, Some(&a)).expect("merge_channels");
+    rp.compare_values(4.0, merged.spp() as f64, 0.0);
+    rp.compare_values(
+        1.0,
+        if merged.equals_with_alpha(&rgba, true) {
+            1.0
+        } else {
+            0.0
+        },
+        0.0,
+    );
+
+    // Without alpha the channel defaults to opaque
+    let opaque = Pix::merge_channels(&r, &g, &b, None).expect("merge without alpha");
+    rp.compare_values(
+        255.0,
+        pixel::alpha(opaque.get_pixel(5, 7).unwrap()) as f64,
+        0.0,
+    );
+
+    let small = Pix::new(3, 3, PixelDepth::Bit8).unwrap();
+    assert!(Pix::merge_channels(&r, &g, &small, None).is_err());
+    assert!(Pix::merge_channels(&r, &g, &rgba, None).is_err());
+    assert!(r.split_channels().is_err());
+
+    assert!(rp.cleanup(), "conversion split/merge channels test failed");
+}
+
+/// Test `Pix::to_rgb`: every input depth yields opaque 32 bpp, spp 3.
+#[test]
+fn conversion_reg_to_rgb() {
+    use leptonica::PixColormap;
+
+    let mut rp = RegParams::new("conversion_to_rgb");
+
+    let check = |rp: &mut RegParams, pix: &Pix, x: u32, y: u32, rgb: (u8, u8, u8)| {
+        let out = pix.to_rgb().expect("to_rgb");
+        rp.compare_values(32.0, out.depth().bits() as f64, 0.0);
+        rp.compare_values(3.0, out.spp() as f64, 0.0);
+        rp.compare_values(pix.width() as f64, out.width() as f64, 0.0);
+        let px = out.get_pixel_unchecked(x, y);
+        let (r, g, b) = pixel::extract_rgb(px);
+        rp.compare_values(rgb.0 as f64, r as f64, 0.0);
+        rp.compare_values(rgb.1 as f64, g as f64, 0.0);
+        rp.compare_values(rgb.2 as f64, b as f64, 0.0);
+        rp.compare_values(255.0, pixel::alpha(px) as f64, 0.0);
+    };
+
+    // 1 bpp: ON -> white, OFF -> black
+    let mut pm = Pix::new(8, 4, PixelDepth::Bit1).unwrap().to_mut();
+    pm.set_pixel_unchecked(1, 1, 1);
+    let pix1: Pix = pm.into();
+    check(&mut rp, &pix1, 1, 1, (255, 255, 255));
+    check(&mut rp, &pix1, 0, 0, (0, 0, 0));
+
+    // 2 and 4 bpp gray scale up to full range
+    let mut pm = Pix::new(8, 4, PixelDepth::Bit2).unwrap().to_mut();
+    pm.set_pixel_unchecked(2, 0, 2);
+    check(&mut rp, &pm.into(), 2, 0, (170, 170, 170));
+    let mut pm = Pix::new(8, 4, PixelDepth::Bit4).unwrap().to_mut();
+    pm.set_pixel_unchecked(2, 0, 15);
+    check(&mut rp, &pm.into(), 2, 0, (255, 255, 255));
+
+    // Colormapped inputs go through the palette at every depth
+    for depth in [
+        PixelDepth::Bit1,
+        PixelDepth::Bit2,
+        PixelDepth::Bit4,
+        PixelDepth::Bit8,
+    ] {
+        let mut cmap = PixColormap::new(depth.bits()).unwrap();
+        cmap.add_rgb(10, 20, 30).unwrap();
+        cmap.add_rgb(200, 100, 50).unwrap();
+        let mut pm = Pix::new(8, 4, depth).unwrap().to_mut();
+        pm.set_colormap(Some(cmap)).unwrap();
+        pm.set_pixel_unchecked(3, 2, 1);
+        let pix: Pix = pm.into();
+        check(&mut rp, &pix, 3, 2, (200, 100, 50));
+        check(&mut rp, &pix, 0, 0, (10, 20, 30));
+    }
+
+    // 8 bpp gray replicates
+    let mut pm = Pix::new(8, 4, PixelDepth::Bit8).unwrap().to_mut();
+    pm.set_pixel_unchecked(4, 3, 77);
+    check(&mut rp, &pm.into(), 4, 3, (77, 77, 77));
+
+    // 16 bpp uses the MSB
+    let mut pm = Pix::new(8, 4, PixelDepth::Bit16).unwrap().to_mut();
+    pm.set_pixel_unchecked(5, 1, 0xAB12);
+    check(&mut rp, &pm.into(), 5, 1, (0xAB, 0xAB, 0xAB));
+
+    // 32 bpp: colors kept, alpha forced opaque, resolution preserved
+    let mut pm = Pix::new(8, 4, PixelDepth::Bit32).unwrap().to_mut();
+    pm.set_spp(4);
+    pm.set_resolution(300, 300);
+    pm.set_pixel_unchecked(6, 2, pixel::compose_rgba(1, 2, 3, 0));
+    let pix32: Pix = pm.into();
+    check(&mut rp, &pix32, 6, 2, (1, 2, 3));
+    rp.compare_values(300.0, pix32.to_rgb().unwrap().xres() as f64, 0.0);
+
+    assert!(rp.cleanup(), "conversion to_rgb test failed");
+}
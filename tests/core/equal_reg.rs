@@ -6,7 +6,8 @@
 //!
 //! The C version tests 6 image types through colormap removal, quantization,
 //! and RGB-to-colormap conversion. This Rust port covers the available
-//! operations: remove_colormap, convert_to_8/32, and equals_with_cmap.
+//! operations: remove_colormap, convert_to_8/32, and equals_with_cmap, plus
+//! content_eq / content_hash.
 //!
 //! # See also
 //!
@@ -17,6 +18,7 @@ use leptonica::color::quantize::octree_quant_num_colors;
 use leptonica::color::threshold::threshold_to_4bpp;
 use leptonica::core::pix::RemoveColormapTarget;
 use leptonica::io::ImageFormat;
+use leptonica::{Pix, PixelDepth};
 
 /// Test 1bpp binary image: write/read round-trip preserves equality (C check 0).
 #[test]
@@ -224,3 +226,65 @@ fn equal_reg_4bpp_colormap() {
 
     assert!(rp.cleanup(), "equal 4bpp colormap test failed");
 }
+
+/// Test content_eq / content_hash ignore row pad bits.
+///
+/// For 1/2/4 bpp widths that do not fill the last 32-bit word of a row,
+/// images differing only in the unused bits are content-equal and hash
+/// identically, while a single real pixel change is detected.
+#[test]
+fn equal_reg_content_eq_ignores_pad_bits() {
+    let mut rp = RegParams::new("equal_content");
+
+    for depth in [PixelDepth::Bit1, PixelDepth::Bit2, PixelDepth::Bit4] {
+        let mut pm = Pix::new(13, 7, depth).unwrap().try_into_mut().unwrap();
+        for y in 0..7 {
+            for x in 0..13 {
+                pm.set_pixel(x, y, (x + y) % (depth.max_value() + 1))
+                    .unwrap();
+            }
+        }
+        let clean: Pix = pm.into();
+
+        let mut noisy = clean.deep_clone().try_into_mut().unwrap();
+        noisy.set_pad_bits(1);
+        let noisy: Pix = noisy.into();
+        assert_ne!(clean.data(), noisy.data(), "pad bits should differ");
+
+        rp.compare_values(1.0, clean.content_eq(&noisy) as u8 as f64, 0.0);
+        rp.compare_values(
+            1.0,
+            (clean.content_hash() == noisy.content_hash()) as u8 as f64,
+            0.0,
+        );
+
+        let mut changed = noisy.deep_clone().try_into_mut().unwrap();
+        changed.flip_pixel(12, 6).unwrap();
+        let changed: Pix = changed.into();
+        rp.compare_values(0.0, clean.content_eq(&changed) as u8 as f64, 0.0);
+        rp.compare_values(
+            0.0,
+            (clean.content_hash() == changed.content_hash()) as u8 as f64,
+            0.0,
+        );
+    }
+
+    // Colormap contents participate in equality.
+    let pix = crate::common::load_test_image("weasel4.16c.png").expect("load weasel4.16c.png");
+    let mut recolored = pix.deep_clone().try_into_mut().unwrap();
+    recolored
+        .colormap_mut()
+        .expect("colormap")
+        .reset_color(0, 1, 2, 3)
+        .unwrap();
+    let recolored: Pix = recolored.into();
+    rp.compare_values(1.0, pix.content_eq(&pix.deep_clone()) as u8 as f64, 0.0);
+    rp.compare_values(0.0, pix.content_eq(&recolored) as u8 as f64, 0.0);
+    rp.compare_values(
+        0.0,
+        (pix.content_hash() == recolored.content_hash()) as u8 as f64,
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "content equality test failed");
+}
@@ -1,7 +1,7 @@
 //! FPix (floating-point image) regression test
 //!
-//! Tests FPix creation, pixel access, arithmetic operations, Pix conversion,
-//! and statistics.
+//! Tests FPix creation, pixel access, arithmetic operations, Pix conversion
+//! (including range-stretching with `to_pix_scaled`), and statistics.
 //!
 //! # See also
 //!
@@ -249,6 +249,50 @@ fn fpix1_reg_pix_conversion() {
     assert!(rp.cleanup(), "fpix1_reg pix conversion tests failed");
 }
 
+/// Test `FPix::to_pix_scaled`: range stretched instead of clamped.
+#[test]
+fn fpix1_reg_pix_conversion_scaled() {
+    let mut rp = RegParams::new("fpix1_pix_scaled");
+
+    let mut fpix = FPix::new(4, 1).unwrap();
+    for (x, v) in [-5.0, 20.0, 50.0, 100.0].into_iter().enumerate() {
+        fpix.set_pixel(x as u32, 0, v).unwrap();
+    }
+
+    // Negatives clipped first, then [0, 100] stretched to [0, 255]
+    let pix = fpix.to_pix_scaled(8, NegativeHandling::ClipToZero).unwrap();
+    rp.compare_values(8.0, pix.depth().bits() as f64, 0.0);
+    rp.compare_values(0.0, pix.get_pixel(0, 0).unwrap() as f64, 0.0);
+    rp.compare_values(51.0, pix.get_pixel(1, 0).unwrap() as f64, 0.0);
+    rp.compare_values(128.0, pix.get_pixel(2, 0).unwrap() as f64, 0.0);
+    rp.compare_values(255.0, pix.get_pixel(3, 0).unwrap() as f64, 0.0);
+
+    // Absolute values: [5, 100] stretched to [0, 255]
+    let pix = fpix
+        .to_pix_scaled(8, NegativeHandling::TakeAbsValue)
+        .unwrap();
+    rp.compare_values(0.0, pix.get_pixel(0, 0).unwrap() as f64, 0.0);
+    rp.compare_values(255.0, pix.get_pixel(3, 0).unwrap() as f64, 0.0);
+
+    // The clamping conversion is unchanged
+    let clamped = fpix.to_pix(8, NegativeHandling::ClipToZero).unwrap();
+    rp.compare_values(100.0, clamped.get_pixel(3, 0).unwrap() as f64, 0.0);
+
+    // 16 bpp uses the full 16-bit range; constant input maps to 0
+    let pix16 = fpix
+        .to_pix_scaled(16, NegativeHandling::ClipToZero)
+        .unwrap();
+    rp.compare_values(65535.0, pix16.get_pixel(3, 0).unwrap() as f64, 0.0);
+    let flat = FPix::new_with_value(3, 3, 42.0).unwrap();
+    let pixf = flat.to_pix_scaled(0, NegativeHandling::ClipToZero).unwrap();
+    rp.compare_values(8.0, pixf.depth().bits() as f64, 0.0);
+    rp.compare_values(0.0, pixf.get_pixel(1, 1).unwrap() as f64, 0.0);
+
+    assert!(fpix.to_pix_scaled(4, NegativeHandling::ClipToZero).is_err());
+
+    assert!(rp.cleanup(), "fpix1_reg scaled pix conversion tests failed");
+}
+
 // ==========================================================================
 // Test 6: Set all / clear
 // ==========================================================================
@@ -1,6 +1,7 @@
 //! Numa (numeric array) operations regression test
 //!
-//! Tests histograms, interpolation, integration/differentiation on Numa.
+//! Tests histograms, interpolation, integration/differentiation on Numa,
+//! plus `arg_min`, `arg_max` and `cumulative_sum`.
 //!
 //! # See also
 //!
@@ -321,6 +322,28 @@ fn numa1_reg_partial_sums() {
     assert!(rp.cleanup(), "numa1_reg partial sums tests failed");
 }
 
+/// arg_min / arg_max / cumulative_sum.
+#[test]
+fn numa1_reg_arg_extrema_cumulative() {
+    let mut rp = RegParams::new("numa1_argext");
+
+    let na = Numa::from_vec(vec![1.0, 5.0, 2.0]);
+    rp.compare_values(1.0, na.arg_max().unwrap() as f64, 0.0);
+    rp.compare_values(0.0, na.arg_min().unwrap() as f64, 0.0);
+
+    // Ties return the first occurrence
+    let ties = Numa::from_vec(vec![3.0, 0.0, 7.0, 0.0, 7.0]);
+    rp.compare_values(2.0, ties.arg_max().unwrap() as f64, 0.0);
+    rp.compare_values(1.0, ties.arg_min().unwrap() as f64, 0.0);
+    rp.compare_values(1.0, Numa::new().arg_max().is_none() as u8 as f64, 0.0);
+
+    let cs = Numa::from_vec(vec![1.0, 2.0, 3.0]).cumulative_sum();
+    rp.compare_values(1.0, (cs.as_slice() == [1.0, 3.0, 6.0]) as u8 as f64, 0.0);
+    rp.compare_values(0.0, Numa::new().cumulative_sum().len() as f64, 0.0);
+
+    assert!(rp.cleanup(), "numa1_reg arg extrema tests failed");
+}
+
 // ========================================================================
 // Test: Make sequence
 // ========================================================================
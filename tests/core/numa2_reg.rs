@@ -1,6 +1,8 @@
 //! Numa windowed statistics and operations regression test
 //!
-//! Tests windowed stats, join, similar, and related operations.
+//! Tests windowed stats, join, similar, and related operations, plus
+//! element-wise arithmetic (`add`, `sub`, `mul`, `div`, `scale`,
+//! `offset`).
 //!
 //! NOTE: C version (numa2_reg.c) also tests pixel extraction on lines,
 //! row/column averages, and variance calculations which require Pix methods
@@ -171,6 +173,51 @@ fn numa2_reg_windowed_edge_behavior() {
     );
 }
 
+// ============================================================================
+// Test 4: Element-wise arithmetic
+// ============================================================================
+
+#[test]
+fn numa2_reg_elementwise_arith() {
+    let mut rp = RegParams::new("numa2_arith");
+
+    let a = Numa::from_vec(vec![1.0, 2.0, 3.0]);
+    let b = Numa::from_vec(vec![4.0, 5.0, 6.0]);
+
+    let sum = a.add(&b).unwrap();
+    rp.compare_values(1.0, (sum.as_slice() == [5.0, 7.0, 9.0]) as u8 as f64, 0.0);
+    let diff = b.sub(&a).unwrap();
+    rp.compare_values(1.0, (diff.as_slice() == [3.0, 3.0, 3.0]) as u8 as f64, 0.0);
+    let prod = a.mul(&b).unwrap();
+    rp.compare_values(
+        1.0,
+        (prod.as_slice() == [4.0, 10.0, 18.0]) as u8 as f64,
+        0.0,
+    );
+    let quot = b.div(&a).unwrap();
+    rp.compare_values(2.0, quot.get(2).unwrap() as f64, 1e-6);
+    // Inputs are untouched
+    rp.compare_values(1.0, (a.as_slice() == [1.0, 2.0, 3.0]) as u8 as f64, 0.0);
+
+    // Length mismatch and zero divisor are errors
+    let short = Numa::from_vec(vec![1.0, 2.0]);
+    rp.compare_values(1.0, a.add(&short).is_err() as u8 as f64, 0.0);
+    rp.compare_values(1.0, a.sub(&short).is_err() as u8 as f64, 0.0);
+    let zero = Numa::from_vec(vec![1.0, 0.0, 1.0]);
+    rp.compare_values(1.0, a.div(&zero).is_err() as u8 as f64, 0.0);
+
+    // In-place scale and offset: subtract a baseline of 1 after doubling
+    let mut c = a.clone();
+    c.scale(2.0);
+    c.offset(-1.0);
+    rp.compare_values(1.0, (c.as_slice() == [1.0, 3.0, 5.0]) as u8 as f64, 0.0);
+
+    assert!(
+        rp.cleanup(),
+        "numa2_reg elementwise arithmetic tests failed"
+    );
+}
+
 // ============================================================================
 // C checks not yet available in Rust
 // ============================================================================
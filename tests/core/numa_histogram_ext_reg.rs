@@ -96,3 +96,38 @@ fn rank_from_all_zero_histogram_errors() {
         "error message should mention zero sum, got: {msg}"
     );
 }
+
+// -- Numa::make_equalization_lut -----------------------------------------
+
+#[test]
+fn equalization_lut_uniform_is_near_identity() {
+    let na = Numa::from_vec(vec![10.0; 256]);
+    let lut = na.make_equalization_lut();
+    for (i, &v) in lut.iter().enumerate() {
+        assert!((v as i32 - i as i32).abs() <= 1, "lut[{i}] = {v}");
+    }
+    assert_eq!(lut[255], 255);
+}
+
+#[test]
+fn equalization_lut_spreads_skewed_histogram() {
+    // All mass in the dark range [0, 64)
+    let mut data = vec![0.0; 256];
+    for v in data.iter_mut().take(64) {
+        *v = 4.0;
+    }
+    let lut = Numa::from_vec(data).make_equalization_lut();
+    // The occupied range is stretched across the full output range
+    assert!(lut[0] <= 4);
+    assert!((lut[31] as i32 - 128).abs() <= 2);
+    assert_eq!(lut[63], 255);
+    assert!(lut.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn equalization_lut_empty_is_identity() {
+    let lut = Numa::from_vec(vec![0.0; 256]).make_equalization_lut();
+    assert!(lut.iter().enumerate().all(|(i, &v)| v as usize == i));
+    let lut = Numa::new().make_equalization_lut();
+    assert!(lut.iter().enumerate().all(|(i, &v)| v as usize == i));
+}
@@ -8,6 +8,8 @@
 //! - numaInterpolateEqxVal, numaInterpolateArbxVal
 //! - numaClipToInterval, numaMakeThresholdIndicator
 //! - numaGetNonzeroRange, numaGetCountRelativeToZero, numaSubsample
+//!
+//! Also covers `Numa::from_control_points`.
 
 use leptonica::{CountRelativeToZero, InterpolationType, Numa, SortOrder, ThresholdComparison};
 
@@ -425,3 +427,51 @@ fn test_subsample_zero_factor() {
     let na = Numa::from_vec(vec![1.0, 2.0, 3.0]);
     assert!(na.subsample(0).is_err());
 }
+
+// ============================================================================
+// Numa::from_control_points
+// ============================================================================
+
+#[test]
+fn test_from_control_points_midpoints() {
+    let lut = Numa::from_control_points(&[(0, 0), (128, 64), (255, 255)]).unwrap();
+    assert_eq!(lut[0], 0);
+    assert_eq!(lut[64], 32);
+    assert_eq!(lut[128], 64);
+    // Upper segment: 64 + (x - 128) * 191 / 127, rounded
+    assert_eq!(lut[191], 159);
+    assert_eq!(lut[192], 160);
+    assert_eq!(lut[255], 255);
+    assert!(lut.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn test_from_control_points_unsorted_and_extrapolated() {
+    // Order does not matter
+    let a = Numa::from_control_points(&[(200, 220), (50, 30)]).unwrap();
+    let b = Numa::from_control_points(&[(50, 30), (200, 220)]).unwrap();
+    assert_eq!(a, b);
+    // The single segment is extended on both sides and clamped
+    assert_eq!(a[50], 30);
+    assert_eq!(a[200], 220);
+    assert_eq!(a[0], 0);
+    assert_eq!(a[26], 0);
+    assert_eq!(a[255], 255);
+
+    // Identity through any two points on the diagonal
+    let id = Numa::from_control_points(&[(10, 10), (20, 20)]).unwrap();
+    assert!(id.iter().enumerate().all(|(i, &v)| v as usize == i));
+}
+
+#[test]
+fn test_from_control_points_invalid() {
+    assert!(Numa::from_control_points(&[]).is_err());
+    assert!(Numa::from_control_points(&[(10, 20)]).is_err());
+    assert!(Numa::from_control_points(&[(10, 20), (10, 30), (100, 100)]).is_err());
+    // Decreasing outputs, in either argument order
+    assert!(Numa::from_control_points(&[(0, 255), (255, 0)]).is_err());
+    assert!(Numa::from_control_points(&[(200, 100), (0, 0), (100, 150)]).is_err());
+    // Flat segments are allowed
+    let flat = Numa::from_control_points(&[(0, 0), (100, 128), (150, 128), (255, 255)]).unwrap();
+    assert!(flat[100..=150].iter().all(|&v| v == 128));
+}
@@ -0,0 +1,133 @@
+//! Test pattern generator regression test
+//!
+//! Tests every `Pix::test_pattern` kind at several depths, and parameter
+//! validation.
+
+use crate::common::RegParams;
+use leptonica::core::pixel;
+use leptonica::{Pix, PixelDepth, TestPattern};
+
+#[test]
+fn pattern_reg_checkerboard() {
+    let mut rp = RegParams::new("pattern_checker");
+
+    let pix = Pix::test_pattern(
+        TestPattern::Checkerboard { cell: 4 },
+        32,
+        24,
+        PixelDepth::Bit8,
+    )
+    .unwrap();
+    rp.compare_values(32.0, pix.width() as f64, 0.0);
+    rp.compare_values(24.0, pix.height() as f64, 0.0);
+    let mut alternates = true;
+    for y in 0..24 {
+        for x in 0..32 {
+            let want = if (x / 4 + y / 4) % 2 == 0 { 0 } else { 255 };
+            alternates &= pix.get_pixel_unchecked(x, y) == want;
+        }
+    }
+    rp.compare_values(1.0, alternates as u8 as f64, 0.0);
+
+    // 1 bpp: half the pixels are ON
+    let bin = Pix::test_pattern(
+        TestPattern::Checkerboard { cell: 2 },
+        16,
+        16,
+        PixelDepth::Bit1,
+    )
+    .unwrap();
+    rp.compare_values(128.0, bin.count_pixels() as f64, 0.0);
+    rp.compare_values(0.0, bin.get_pixel_unchecked(0, 0) as f64, 0.0);
+    rp.compare_values(1.0, bin.get_pixel_unchecked(2, 0) as f64, 0.0);
+
+    assert!(rp.cleanup(), "pattern checkerboard test failed");
+}
+
+#[test]
+fn pattern_reg_gradients() {
+    let mut rp = RegParams::new("pattern_gradient");
+
+    let pix =
+        Pix::test_pattern(TestPattern::HorizontalGradient, 100, 10, PixelDepth::Bit8).unwrap();
+    rp.compare_values(0.0, pix.get_pixel_unchecked(0, 5) as f64, 0.0);
+    rp.compare_values(255.0, pix.get_pixel_unchecked(99, 5) as f64, 0.0);
+    let monotone =
+        (1..100).all(|x| pix.get_pixel_unchecked(x, 0) > pix.get_pixel_unchecked(x - 1, 0));
+    rp.compare_values(1.0, monotone as u8 as f64, 0.0);
+    let constant_cols =
+        (0..100).all(|x| pix.get_pixel_unchecked(x, 0) == pix.get_pixel_unchecked(x, 9));
+    rp.compare_values(1.0, constant_cols as u8 as f64, 0.0);
+
+    // The range follows the depth
+    let pix4 = Pix::test_pattern(TestPattern::HorizontalGradient, 16, 2, PixelDepth::Bit4).unwrap();
+    rp.compare_values(15.0, pix4.get_pixel_unchecked(15, 0) as f64, 0.0);
+    let pix16 = Pix::test_pattern(TestPattern::VerticalGradient, 2, 50, PixelDepth::Bit16).unwrap();
+    rp.compare_values(0.0, pix16.get_pixel_unchecked(0, 0) as f64, 0.0);
+    rp.compare_values(65535.0, pix16.get_pixel_unchecked(0, 49) as f64, 0.0);
+
+    // 1 bpp: black (ON) at the start of the ramp, white (OFF) at the end
+    let bin = Pix::test_pattern(TestPattern::HorizontalGradient, 20, 2, PixelDepth::Bit1).unwrap();
+    rp.compare_values(1.0, bin.get_pixel_unchecked(0, 0) as f64, 0.0);
+    rp.compare_values(0.0, bin.get_pixel_unchecked(19, 0) as f64, 0.0);
+    rp.compare_values(20.0, bin.count_pixels() as f64, 0.0);
+
+    let rgb = Pix::test_pattern(TestPattern::HorizontalGradient, 64, 4, PixelDepth::Bit32).unwrap();
+    rp.compare_values(
+        1.0,
+        (pixel::extract_rgb(rgb.get_pixel_unchecked(63, 0)) == (255, 255, 255)) as u8 as f64,
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "pattern gradient test failed");
+}
+
+#[test]
+fn pattern_reg_bars_circles_grid() {
+    let mut rp = RegParams::new("pattern_misc");
+
+    let bars = Pix::test_pattern(TestPattern::ColorBars, 80, 10, PixelDepth::Bit32).unwrap();
+    let at = |x| pixel::extract_rgb(bars.get_pixel_unchecked(x, 5));
+    rp.compare_values(1.0, (at(5) == (255, 255, 255)) as u8 as f64, 0.0);
+    rp.compare_values(1.0, (at(55) == (255, 0, 0)) as u8 as f64, 0.0);
+    rp.compare_values(1.0, (at(79) == (0, 0, 0)) as u8 as f64, 0.0);
+    let gray_bars = Pix::test_pattern(TestPattern::ColorBars, 80, 10, PixelDepth::Bit8).unwrap();
+    rp.compare_values(255.0, gray_bars.get_pixel_unchecked(0, 0) as f64, 0.0);
+    rp.compare_values(29.0, gray_bars.get_pixel_unchecked(65, 0) as f64, 1.0);
+
+    // 1 bpp: light bars (white, yellow, cyan, green) are OFF, dark bars ON
+    let bin_bars = Pix::test_pattern(TestPattern::ColorBars, 80, 10, PixelDepth::Bit1).unwrap();
+    let bin_row: Vec<u32> = (0..8)
+        .map(|bar| bin_bars.get_pixel_unchecked(bar * 10 + 5, 5))
+        .collect();
+    rp.compare_values(1.0, (bin_row == [0, 0, 0, 0, 1, 1, 1, 1]) as u8 as f64, 0.0);
+
+    let rings = Pix::test_pattern(
+        TestPattern::ConcentricCircles { spacing: 5 },
+        41,
+        41,
+        PixelDepth::Bit8,
+    )
+    .unwrap();
+    rp.compare_values(255.0, rings.get_pixel_unchecked(20, 20) as f64, 0.0);
+    rp.compare_values(0.0, rings.get_pixel_unchecked(27, 20) as f64, 0.0);
+    rp.compare_values(255.0, rings.get_pixel_unchecked(32, 20) as f64, 0.0);
+
+    let grid =
+        Pix::test_pattern(TestPattern::Grid { spacing: 10 }, 31, 21, PixelDepth::Bit1).unwrap();
+    // Lines at x = 0, 10, 20, 30 and y = 0, 10, 20
+    rp.compare_values(
+        (4 * 21 + 3 * 31 - 12) as f64,
+        grid.count_pixels() as f64,
+        0.0,
+    );
+
+    let solid = Pix::test_pattern(TestPattern::Solid(9), 5, 5, PixelDepth::Bit4).unwrap();
+    rp.compare_values(9.0, solid.get_pixel_unchecked(4, 4) as f64, 0.0);
+
+    assert!(Pix::test_pattern(TestPattern::Solid(16), 5, 5, PixelDepth::Bit4).is_err());
+    assert!(Pix::test_pattern(TestPattern::Grid { spacing: 0 }, 5, 5, PixelDepth::Bit8).is_err());
+    assert!(Pix::test_pattern(TestPattern::ColorBars, 0, 5, PixelDepth::Bit8).is_err());
+
+    assert!(rp.cleanup(), "pattern misc test failed");
+}
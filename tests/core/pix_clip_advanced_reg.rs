@@ -7,8 +7,11 @@
 //! - pixClipToForeground, pixClipBoxToForeground, pixScanForForeground
 //! - pixMakeFrameMask, pixFractionFgInMask
 //! - pixAverageOnLine
+//!
+//! Also covers `Pix::trim_borders_by_color` and
+//! `Pix::estimate_background_color`.
 
-use leptonica::{Box, Pix, PixelDepth, ScanDirection};
+use leptonica::{Box, Color, Pix, PixelDepth, ScanDirection};
 
 /// Create a 1bpp image with a foreground rectangle
 fn make_fg_image(w: u32, h: u32, fx: u32, fy: u32, fw: u32, fh: u32) -> Pix {
@@ -224,3 +227,115 @@ fn test_average_on_line_vertical() {
     let avg = pix.average_on_line(50, 0, 50, 99, 1).unwrap();
     assert!((avg - 200.0).abs() < 0.5, "expected ~200, got {avg}");
 }
+
+// ============================================================================
+// Pix::trim_borders_by_color
+// ============================================================================
+
+/// Build an image with a 5-pixel near-white margin (±3 noise) around a
+/// dark content block.
+fn make_noisy_margin(depth: PixelDepth) -> Pix {
+    let (w, h) = (60u32, 40u32);
+    let mut pm = Pix::new(w, h, depth).unwrap().try_into_mut().unwrap();
+    for y in 0..h {
+        for x in 0..w {
+            let inside = (5..w - 5).contains(&x) && (5..h - 5).contains(&y);
+            let noise = ((x * 7 + y * 3) % 7) as i32 - 3;
+            let v = if inside {
+                (60 + (x + y) % 40) as i32
+            } else {
+                252 + noise
+            };
+            let v = v.clamp(0, 255) as u8;
+            let val = if depth == PixelDepth::Bit32 {
+                leptonica::core::pixel::compose_rgb(v, v, (v as i32 - noise).clamp(0, 255) as u8)
+            } else {
+                v as u32
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    pm.into()
+}
+
+#[test]
+fn test_trim_borders_by_color_noisy_margin() {
+    let bg = Color::new(252, 252, 252);
+    for depth in [PixelDepth::Bit8, PixelDepth::Bit32] {
+        let pix = make_noisy_margin(depth);
+        let trimmed = pix.trim_borders_by_color(bg, 5).unwrap();
+        assert_eq!((trimmed.width(), trimmed.height()), (50, 30), "{depth:?}");
+        assert_eq!(trimmed.get_pixel(0, 0), pix.get_pixel(5, 5));
+
+        // A tolerance below the noise amplitude leaves the margin
+        let untrimmed = pix.trim_borders_by_color(bg, 1).unwrap();
+        assert_eq!((untrimmed.width(), untrimmed.height()), (60, 40));
+    }
+}
+
+#[test]
+fn test_trim_borders_by_color_nothing_to_trim() {
+    let pix = make_gray(30, 20, 100);
+    // Entirely background, and entirely foreground: both unchanged
+    let all_bg = pix
+        .trim_borders_by_color(Color::new(100, 100, 100), 0)
+        .unwrap();
+    assert!(all_bg.equals(&pix));
+    let all_fg = pix.trim_borders_by_color(Color::WHITE, 10).unwrap();
+    assert!(all_fg.equals(&pix));
+
+    let pix1 = Pix::new(10, 10, PixelDepth::Bit1).unwrap();
+    assert!(pix1.trim_borders_by_color(Color::WHITE, 5).is_err());
+}
+
+// ============================================================================
+// Pix::estimate_background_color
+// ============================================================================
+
+#[test]
+fn test_estimate_background_color_document() {
+    // Off-white noisy paper with dark text, some of it touching the edge
+    let (w, h) = (100u32, 80u32);
+    let mut pm = Pix::new(w, h, PixelDepth::Bit32).unwrap().to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            let noise = ((x * 5 + y * 11) % 7) as u8;
+            let text = y % 10 < 3 && (x < 30 || (20..80).contains(&x));
+            let (r, g, b) = if text {
+                (20, 20, 25)
+            } else {
+                (245 + noise, 243 + noise, 238 + noise)
+            };
+            pm.set_pixel_unchecked(x, y, leptonica::core::pixel::compose_rgb(r, g, b));
+        }
+    }
+    let pix: Pix = pm.into();
+    let bg = pix.estimate_background_color().unwrap();
+    assert!(bg.r >= 240 && bg.g >= 240 && bg.b >= 235, "{bg:?}");
+
+    // The estimate feeds the tolerant trim
+    let trimmed = pix.trim_borders_by_color(bg, 12).unwrap();
+    assert!(trimmed.width() <= w && trimmed.height() < h);
+
+    // Inverted: dark background
+    let inv = pix.invert();
+    let bg = inv.estimate_background_color().unwrap();
+    assert!(bg.r <= 15 && bg.g <= 15 && bg.b <= 20, "{bg:?}");
+
+    // The same holds for the 8 bpp luminance
+    let gray = pix.convert_rgb_to_luminance().unwrap();
+    let bg = gray.estimate_background_color().unwrap();
+    assert!(bg.r >= 235 && bg.r == bg.g && bg.g == bg.b, "{bg:?}");
+}
+
+#[test]
+fn test_estimate_background_color_binary() {
+    // Mostly white (0) border with a black frame segment
+    let pix = make_fg_image(60, 40, 0, 0, 10, 40);
+    assert_eq!(pix.estimate_background_color().unwrap(), Color::WHITE);
+    let pix = make_fg_image(60, 40, 0, 0, 60, 40);
+    assert_eq!(pix.estimate_background_color().unwrap(), Color::BLACK);
+
+    let pix16 = Pix::new(10, 10, PixelDepth::Bit16).unwrap();
+    assert!(pix16.estimate_background_color().is_err());
+}
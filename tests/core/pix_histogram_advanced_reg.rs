@@ -7,7 +7,12 @@
 //! - pixGetAverageMasked, pixGetAverageMaskedRGB
 //! - pixGetAverageTiled, pixGetAverageTiledRGB
 //! - pixGetRankValueMasked, pixGetRankValueMaskedRGB
+//!
+//! Also covers joint-channel `Pix::histogram_2d` and the
+//! partial-tile `Pix::tile_stats` (including the `Min`/`Max` stat types)
+//! and the grid-of-means `Pix::tiled_mean_numaa`.
 
+use leptonica::core::pix::RgbComponent;
 use leptonica::core::pix::statistics::PixelStatType;
 use leptonica::core::pixel;
 use leptonica::{Numaa, Pix, PixColormap, PixelDepth, RgbaQuad};
@@ -286,6 +291,92 @@ fn test_average_tiled_invalid_depth() {
     );
 }
 
+// ============================================================================
+// tile_stats
+// ============================================================================
+
+#[test]
+fn test_tile_stats_checkerboard_vs_flat() {
+    // 1-pixel checkerboard: every tile has high variance
+    let (w, h) = (45u32, 30u32);
+    let pix = Pix::new(w, h, PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..h {
+        for x in 0..w {
+            pm.set_pixel_unchecked(x, y, if (x + y) % 2 == 0 { 200 } else { 40 });
+        }
+    }
+    let checker: Pix = pm.into();
+
+    let var = checker.tile_stats(10, PixelStatType::Variance).unwrap();
+    // Partial tiles are kept: ceil(45 / 10) x ceil(30 / 10)
+    assert_eq!((var.width(), var.height()), (5, 3));
+    assert_eq!(var.depth(), PixelDepth::Bit8);
+    for y in 0..3 {
+        for x in 0..5 {
+            assert_eq!(var.get_pixel_unchecked(x, y), 255, "tile ({x}, {y})");
+        }
+    }
+    let min = checker.tile_stats(10, PixelStatType::Min).unwrap();
+    let max = checker.tile_stats(10, PixelStatType::Max).unwrap();
+    let mean = checker.tile_stats(10, PixelStatType::MeanAbsVal).unwrap();
+    assert_eq!(min.get_pixel_unchecked(4, 2), 40);
+    assert_eq!(max.get_pixel_unchecked(4, 2), 200);
+    assert_eq!(mean.get_pixel_unchecked(0, 0), 120);
+
+    let flat = make_uniform_gray(90, w, h);
+    let var = flat.tile_stats(10, PixelStatType::Variance).unwrap();
+    for y in 0..3 {
+        for x in 0..5 {
+            assert_eq!(var.get_pixel_unchecked(x, y), 0);
+        }
+    }
+    assert_eq!(flat.get_pixel_stats(1, PixelStatType::Max).unwrap(), 90);
+}
+
+#[test]
+fn test_tile_stats_invalid_input() {
+    let pix = make_uniform_gray(10, 20, 20);
+    assert!(pix.tile_stats(0, PixelStatType::Variance).is_err());
+    let pix32 = Pix::new(20, 20, PixelDepth::Bit32).unwrap();
+    assert!(pix32.tile_stats(5, PixelStatType::Variance).is_err());
+}
+
+// ============================================================================
+// tiled_mean_numaa
+// ============================================================================
+
+#[test]
+fn test_tiled_mean_numaa_gradient() {
+    // 50 x 23: left half dark (40), right half light (220)
+    let (w, h) = (50u32, 23u32);
+    let pix = Pix::new(w, h, PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..h {
+        for x in 0..w {
+            pm.set_pixel_unchecked(x, y, if x < 25 { 40 } else { 220 });
+        }
+    }
+    let pix: Pix = pm.into();
+
+    let naa = pix.tiled_mean_numaa(4, 3).unwrap();
+    assert_eq!(naa.len(), 3);
+    for row in 0..3 {
+        let na = naa.get(row).unwrap();
+        assert_eq!(na.len(), 4);
+        // Cells are 12 wide, the last 14: columns 0..12, 12..24, 24..36, 36..50
+        assert_eq!(na.get(0).unwrap(), 40.0);
+        assert_eq!(na.get(1).unwrap(), 40.0);
+        assert!((na.get(2).unwrap() - (40.0 + 11.0 * 220.0) / 12.0).abs() < 1e-3);
+        assert_eq!(na.get(3).unwrap(), 220.0);
+    }
+
+    assert!(pix.tiled_mean_numaa(0, 3).is_err());
+    assert!(pix.tiled_mean_numaa(4, 24).is_err());
+    let pix32 = Pix::new(20, 20, PixelDepth::Bit32).unwrap();
+    assert!(pix32.tiled_mean_numaa(2, 2).is_err());
+}
+
 // ============================================================================
 // pixGetAverageTiledRGB
 // ============================================================================
@@ -357,3 +448,71 @@ fn test_rank_value_masked_rgb_invalid_depth() {
     let pix = make_uniform_gray(100, 20, 20);
     assert!(pix.rank_value_masked_rgb(None, 0, 0, 1, 0.5).is_err());
 }
+
+// ============================================================================
+// Pix::histogram_2d
+// ============================================================================
+
+#[test]
+fn test_histogram_2d_single_color() {
+    let pix = make_uniform_rgb(100, 150, 200, 20, 10);
+    let hist = pix
+        .histogram_2d(RgbComponent::Red, RgbComponent::Green, 16)
+        .unwrap();
+    assert_eq!(hist.len(), 16);
+    // R = 100 -> bin 6, G = 150 -> bin 9; every other bin is empty
+    for i in 0..16 {
+        let row = hist.get(i).unwrap();
+        assert_eq!(row.len(), 16);
+        for j in 0..16 {
+            let expected = if (i, j) == (6, 9) { 200.0 } else { 0.0 };
+            assert_eq!(row.get(j).unwrap(), expected, "bin ({i}, {j})");
+        }
+    }
+}
+
+#[test]
+fn test_histogram_2d_joint_counts() {
+    // Left half red, right half blue: R vs B populates two opposite corners
+    let mut pm = Pix::new(8, 4, PixelDepth::Bit32).unwrap().to_mut();
+    for y in 0..4 {
+        for x in 0..8 {
+            let val = if x < 4 {
+                pixel::compose_rgb(255, 0, 0)
+            } else {
+                pixel::compose_rgb(0, 0, 255)
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    let pix: Pix = pm.into();
+    let hist = pix
+        .histogram_2d(RgbComponent::Red, RgbComponent::Blue, 2)
+        .unwrap();
+    assert_eq!(hist.get(1).unwrap().get(0), Some(16.0));
+    assert_eq!(hist.get(0).unwrap().get(1), Some(16.0));
+    assert_eq!(hist.get(0).unwrap().get(0), Some(0.0));
+    assert_eq!(hist.get(1).unwrap().get(1), Some(0.0));
+}
+
+#[test]
+fn test_histogram_2d_invalid() {
+    let pix = make_uniform_rgb(1, 2, 3, 4, 4);
+    assert!(
+        pix.histogram_2d(RgbComponent::Red, RgbComponent::Red, 8)
+            .is_err()
+    );
+    assert!(
+        pix.histogram_2d(RgbComponent::Red, RgbComponent::Green, 0)
+            .is_err()
+    );
+    assert!(
+        pix.histogram_2d(RgbComponent::Red, RgbComponent::Green, 257)
+            .is_err()
+    );
+    let gray = make_uniform_gray(100, 4, 4);
+    assert!(
+        gray.histogram_2d(RgbComponent::Red, RgbComponent::Green, 8)
+            .is_err()
+    );
+}
@@ -6,6 +6,9 @@
 //! - pixAbsDiffByRow, pixAbsDiffByColumn, pixAbsDiffInRect
 //! - pixRowStats, pixColumnStats
 //! - pixGetPixelAverage, pixGetPixelStats
+//!
+//! Also covers radial_profile, angular_profile and
+//! horizontal/vertical_transitions.
 
 use leptonica::core::pix::statistics::{DiffDirection, PixelStatType, StatsRequest};
 use leptonica::core::pixel;
@@ -248,3 +251,140 @@ fn test_get_pixel_stats_rms() {
         .unwrap();
     assert_eq!(val, 100);
 }
+
+// ============================================================================
+// radial_profile / angular_profile
+// ============================================================================
+
+/// Bright disk of radius `r` centered at (cx, cy) on a dark background
+fn make_disk_image(w: u32, h: u32, cx: f32, cy: f32, r: f32) -> Pix {
+    let pix = Pix::new(w, h, PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..h {
+        for x in 0..w {
+            let d = (x as f32 - cx).hypot(y as f32 - cy);
+            pm.set_pixel_unchecked(x, y, if d <= r { 200 } else { 20 });
+        }
+    }
+    pm.into()
+}
+
+#[test]
+fn test_radial_profile_disk_step() {
+    let pix = make_disk_image(81, 81, 40.0, 40.0, 20.0);
+    // rmax = distance to the far corner (~57.3); 1 bin per ~1.4 px
+    let na = pix.radial_profile(40.0, 40.0, 40).unwrap();
+    assert_eq!(na.len(), 40);
+    let (start, width) = na.parameters();
+    assert_eq!(start, 0.0);
+    let edge_bin = (20.0 / width) as usize;
+    for i in 0..edge_bin {
+        assert_eq!(na.get(i).unwrap(), 200.0, "inside bin {i}");
+    }
+    for i in edge_bin + 1..na.len() {
+        assert_eq!(na.get(i).unwrap(), 20.0, "outside bin {i}");
+    }
+}
+
+#[test]
+fn test_radial_profile_clipped_center() {
+    // Center on the left edge: only the in-bounds half of each ring is used
+    let pix = make_disk_image(60, 61, 0.0, 30.0, 15.0);
+    let na = pix.radial_profile(0.0, 30.0, 20).unwrap();
+    assert_eq!(na.get(0).unwrap(), 200.0);
+    assert_eq!(na.get(19).unwrap(), 20.0);
+}
+
+#[test]
+fn test_angular_profile() {
+    // Right half bright, left half dark
+    let pix = Pix::new(41, 41, PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..41 {
+        for x in 21..41 {
+            pm.set_pixel_unchecked(x, y, 100);
+        }
+    }
+    let pix: Pix = pm.into();
+    let na = pix.angular_profile(20.0, 20.0, 8).unwrap();
+    assert_eq!(na.len(), 8);
+    // Bins 0 and 7 lie around the +x axis; bins 3 and 4 around -x
+    assert_eq!(na.get(0).unwrap(), 100.0);
+    assert_eq!(na.get(7).unwrap(), 100.0);
+    assert_eq!(na.get(3).unwrap(), 0.0);
+    assert_eq!(na.get(4).unwrap(), 0.0);
+
+    // Symmetric disk has a flat angular profile
+    let disk = make_disk_image(41, 41, 20.0, 20.0, 10.0);
+    let na = disk.angular_profile(20.0, 20.0, 4).unwrap();
+    let vals: Vec<f32> = na.iter().collect();
+    assert!(vals.iter().all(|&v| (v - vals[0]).abs() < 2.0));
+}
+
+#[test]
+fn test_profile_invalid_input() {
+    let pix = Pix::new(10, 10, PixelDepth::Bit8).unwrap();
+    assert!(pix.radial_profile(5.0, 5.0, 0).is_err());
+    assert!(pix.angular_profile(5.0, 5.0, 0).is_err());
+    let pix32 = Pix::new(10, 10, PixelDepth::Bit32).unwrap();
+    assert!(pix32.radial_profile(5.0, 5.0, 4).is_err());
+}
+
+/// Brute-force transition count with OFF pixels outside the image.
+fn count_transitions_naive(vals: impl Iterator<Item = u32>) -> f32 {
+    let mut prev = 0;
+    let mut count = 0;
+    for v in vals.chain(std::iter::once(0)) {
+        if v != prev {
+            count += 1;
+        }
+        prev = v;
+    }
+    count as f32
+}
+
+#[test]
+fn test_horizontal_vertical_transitions() {
+    let (w, h) = (70u32, 20u32);
+    let pix = Pix::new(w, h, PixelDepth::Bit1).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    // Partial rule on row 2, full-width rule on row 4
+    for x in 3..60 {
+        pm.set_pixel_unchecked(x, 2, 1);
+    }
+    for x in 0..w {
+        pm.set_pixel_unchecked(x, 4, 1);
+    }
+    // Text-like row: short runs across word boundaries
+    for x in (1..w).step_by(5) {
+        pm.set_pixel_unchecked(x, 10, 1);
+        pm.set_pixel_unchecked(x + 1, 10, 1);
+    }
+    // Full-height vertical rule in the last column
+    for y in 0..h {
+        pm.set_pixel_unchecked(w - 1, y, 1);
+    }
+    let pix: Pix = pm.into();
+
+    let na = pix.horizontal_transitions().unwrap();
+    assert_eq!(na.len(), h as usize);
+    assert_eq!(na.get(2).unwrap(), 4.0); // rule plus last-column pixel
+    assert_eq!(na.get(4).unwrap(), 2.0);
+    assert!(na.get(10).unwrap() >= 26.0);
+    for y in 0..h {
+        let expected = count_transitions_naive((0..w).map(|x| pix.get_pixel_unchecked(x, y)));
+        assert_eq!(na.get(y as usize).unwrap(), expected, "row {y}");
+    }
+
+    let na = pix.vertical_transitions().unwrap();
+    assert_eq!(na.len(), w as usize);
+    assert_eq!(na.get((w - 1) as usize).unwrap(), 2.0);
+    for x in 0..w {
+        let expected = count_transitions_naive((0..h).map(|y| pix.get_pixel_unchecked(x, y)));
+        assert_eq!(na.get(x as usize).unwrap(), expected, "column {x}");
+    }
+
+    let pix8 = Pix::new(10, 10, PixelDepth::Bit8).unwrap();
+    assert!(pix8.horizontal_transitions().is_err());
+    assert!(pix8.vertical_transitions().is_err());
+}
@@ -330,6 +330,78 @@ fn pixa2_reg_border() {
     assert!(rp.cleanup(), "pixa2_reg border tests failed");
 }
 
+/// Test `Pix::resize_canvas`: pad or crop around an anchor.
+#[test]
+fn pixa2_reg_resize_canvas() {
+    use leptonica::{CanvasAnchor, PixColormap};
+
+    let mut rp = RegParams::new("pixa2_resize_canvas");
+
+    // Centering 10x10 into 20x20 leaves a 5-pixel fill border
+    let pix = make_uniform_gray(10, 10, 100);
+    let out = pix
+        .resize_canvas(20, 20, CanvasAnchor::Center, 255)
+        .unwrap();
+    rp.compare_values(20.0, out.width() as f64, 0.0);
+    rp.compare_values(20.0, out.height() as f64, 0.0);
+    let mut fill_ok = true;
+    let mut image_ok = true;
+    for y in 0..20 {
+        for x in 0..20 {
+            let inside = (5..15).contains(&x) && (5..15).contains(&y);
+            let v = px(&out, x, y);
+            if inside {
+                image_ok &= v == 100;
+            } else {
+                fill_ok &= v == 255;
+            }
+        }
+    }
+    rp.compare_values(1.0, fill_ok as u8 as f64, 0.0);
+    rp.compare_values(1.0, image_ok as u8 as f64, 0.0);
+
+    // Bottom-right anchor puts the image in the lower-right corner
+    let br = pix
+        .resize_canvas(16, 12, CanvasAnchor::BottomRight, 0)
+        .unwrap();
+    rp.compare_values(100.0, px(&br, 6, 2) as f64, 0.0);
+    rp.compare_values(0.0, px(&br, 5, 2) as f64, 0.0);
+    rp.compare_values(0.0, px(&br, 6, 1) as f64, 0.0);
+
+    // Shrinking crops: center 4x4 of a ramp
+    let ramp = make_gray_ramp(10, 10);
+    let crop = ramp.resize_canvas(4, 4, CanvasAnchor::Center, 0).unwrap();
+    rp.compare_values(px(&ramp, 3, 3) as f64, px(&crop, 0, 0) as f64, 0.0);
+    rp.compare_values(px(&ramp, 6, 6) as f64, px(&crop, 3, 3) as f64, 0.0);
+
+    // 1 bpp with a colormap: depth and colormap preserved
+    let mut cmap = PixColormap::new(1).unwrap();
+    cmap.add_rgb(255, 255, 255).unwrap();
+    cmap.add_rgb(255, 0, 0).unwrap();
+    let mut pm = Pix::new(7, 5, PixelDepth::Bit1).unwrap().to_mut();
+    pm.set_colormap(Some(cmap)).unwrap();
+    pm.set_pixel_unchecked(0, 0, 1);
+    let pix1: Pix = pm.into();
+    let out1 = pix1.resize_canvas(9, 9, CanvasAnchor::Top, 1).unwrap();
+    rp.compare_values(1.0, out1.depth().bits() as f64, 0.0);
+    rp.compare_values(1.0, out1.colormap().is_some() as u8 as f64, 0.0);
+    rp.compare_values(1.0, px(&out1, 1, 0) as f64, 0.0);
+    rp.compare_values(0.0, px(&out1, 2, 0) as f64, 0.0);
+    rp.compare_values(1.0, px(&out1, 4, 8) as f64, 0.0);
+
+    // 32 bpp fill color
+    let rgb = make_32bit_color(4, 4);
+    let out32 = rgb
+        .resize_canvas(6, 6, CanvasAnchor::TopLeft, 0x0000ff00)
+        .unwrap();
+    rp.compare_values(0x0000ff00 as f64, px(&out32, 5, 5) as f64, 0.0);
+    rp.compare_values(px(&rgb, 3, 3) as f64, px(&out32, 3, 3) as f64, 0.0);
+
+    assert!(pix.resize_canvas(0, 5, CanvasAnchor::Center, 0).is_err());
+
+    assert!(rp.cleanup(), "pixa2_reg resize_canvas tests failed");
+}
+
 // ==========================================================================
 // Test 8: Comparison operations
 // ==========================================================================
@@ -3,7 +3,8 @@
 //! Covers select_range, select_with_indicator, select_with_string,
 //! select_by_num_conn_comp / area_fraction / perim_size_ratio /
 //! perim_to_area_ratio / width_height_ratio, plus the Pix-level wrappers
-//! `pix_select_by_*` and `pix_add_with_indicator` / `pix_remove_with_indicator`.
+//! `pix_select_by_*` and `pix_add_with_indicator` / `pix_remove_with_indicator`,
+//! and the `dedupe_similar` near-duplicate filter.
 
 use leptonica::core::pixa::{
     ThresholdSelect, pix_add_with_indicator, pix_remove_with_indicator,
@@ -310,3 +311,43 @@ fn pix_select_rejects_non_1bpp() {
     );
     assert!(r.is_err());
 }
+
+fn make_gradient_8bpp(w: u32, h: u32, invert: bool) -> Pix {
+    let pix = Pix::new(w, h, PixelDepth::Bit8).unwrap();
+    let mut m = pix.try_into_mut().unwrap();
+    for y in 0..h {
+        for x in 0..w {
+            let v = (x + y) * 255 / (w + h - 2);
+            m.set_pixel(x, y, if invert { 255 - v } else { v }).unwrap();
+        }
+    }
+    m.into()
+}
+
+/// dedupe_similar: near-identical images collapse to one.
+#[test]
+fn dedupe_similar_collapses_near_duplicates() {
+    let a = make_gradient_8bpp(64, 48, false);
+    let mut noisy = a.to_mut();
+    for i in 0..20 {
+        let (x, y) = ((i * 13) % 64, (i * 7) % 48);
+        let v = noisy.get_pixel(x, y).unwrap();
+        noisy.set_pixel(x, y, (v + 6).min(255)).unwrap();
+    }
+    let a_noisy: Pix = noisy.into();
+    let b = make_gradient_8bpp(64, 48, true);
+
+    let mut pixa = Pixa::new();
+    pixa.push(a);
+    pixa.push(a_noisy);
+    pixa.push(b);
+
+    let (deduped, kept) = pixa.dedupe_similar(5);
+    assert_eq!(kept, vec![0, 2]);
+    assert_eq!(deduped.len(), 2);
+
+    // Threshold 0 never treats anything as a duplicate
+    let (all, kept_all) = pixa.dedupe_similar(0);
+    assert_eq!(all.len(), 3);
+    assert_eq!(kept_all, vec![0, 1, 2]);
+}
@@ -145,3 +145,24 @@ fn pixaa_size_range_empty_is_none() {
     let paa = Pixaa::new();
     assert!(paa.size_range().is_none());
 }
+
+// -- display ------------------------------------------------------------
+
+#[test]
+fn pixaa_display_stacks_group_rows() {
+    let mut paa = Pixaa::new();
+    paa.push(make_inner(&[(10, 6), (8, 12)]));
+    paa.push(Pixa::new());
+    paa.push(make_inner(&[(5, 4), (5, 4), (5, 4)]));
+    let pix = paa.display(2, 3, 255).unwrap();
+    // Row widths: 10 + 2 + 8 = 20 and 5 * 3 + 2 * 2 = 19
+    assert_eq!(pix.width(), 20);
+    // Row heights 12 and 4, one row gap of 3 (empty group skipped)
+    assert_eq!(pix.height(), 12 + 3 + 4);
+    assert_eq!(pix.depth(), PixelDepth::Bit8);
+    // Gap between rows is background; images are black
+    assert_eq!(pix.get_pixel(0, 13), Some(255));
+    assert_eq!(pix.get_pixel(0, 15), Some(0));
+
+    assert!(Pixaa::new().display(2, 3, 255).is_err());
+}
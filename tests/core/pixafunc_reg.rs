@@ -3,8 +3,16 @@
 //! # See also
 //!
 //! C Leptonica: `pixafunc1.c`
+//!
+//! Also covers `Pixa::align_sizes`, `Pixa::concat_h`,
+//! `Pixa::concat_v`, `Pixa::group_by`, `Pixa::pack_atlas` and the stack
+//! reductions `pixa_mean`, `pixa_median`, `pixa_max` and `pixa_min`.
 
-use leptonica::{Box, Pix, Pixa, PixaSortType, PixelDepth, SizeRelation, SortOrder};
+use leptonica::core::pixel;
+use leptonica::{
+    AlignMode, Box, CanvasAnchor, HAlign, Pix, Pixa, PixaSortType, PixelDepth, Sarray,
+    SizeRelation, SortOrder, VAlign, pixa_max, pixa_mean, pixa_median, pixa_min,
+};
 
 fn make_pix(w: u32, h: u32) -> Pix {
     Pix::new(w, h, PixelDepth::Bit8).unwrap()
@@ -260,6 +268,87 @@ fn test_display_tiled_single_image_exceeds_max_width() {
     assert_eq!(result.height(), 20);
 }
 
+// ============================================================================
+// Pixa::montage
+// ============================================================================
+
+/// Count black pixels of a 32 bpp image inside a rectangle
+fn count_black(pix: &Pix, x0: u32, y0: u32, w: u32, h: u32) -> u32 {
+    let mut n = 0;
+    for y in y0..y0 + h {
+        for x in x0..x0 + w {
+            if pix.get_pixel_unchecked(x, y) >> 8 == 0 {
+                n += 1;
+            }
+        }
+    }
+    n
+}
+
+#[test]
+fn test_montage_with_labels() {
+    // Two white 8bpp images so any black pixel comes from a label
+    let mut pixa = Pixa::new();
+    for _ in 0..2 {
+        let mut pm = Pix::new(20, 16, PixelDepth::Bit8).unwrap().to_mut();
+        pm.set_all_gray(255).unwrap();
+        pixa.push(pm.into());
+    }
+    let labels = Sarray::from_str_slice(&["a", "b"]);
+
+    let sheet = pixa.montage(2, &labels, 2, 10).unwrap();
+    assert_eq!(sheet.depth(), PixelDepth::Bit32);
+    // Cells are (20 + 4) x (16 + 10 + 4)
+    assert_eq!(sheet.width(), 48);
+    assert_eq!(sheet.height(), 30);
+
+    // Text rendered in the label strip under each cell, not in the images
+    let strip_y = 2 + 16;
+    assert!(count_black(&sheet, 0, strip_y, 24, 10) > 0);
+    assert!(count_black(&sheet, 24, strip_y, 24, 10) > 0);
+    assert_eq!(count_black(&sheet, 0, 0, 48, strip_y), 0);
+
+    // The two glyphs differ
+    let a = sheet.clip_rectangle(0, strip_y, 24, 10).unwrap();
+    let b = sheet.clip_rectangle(24, strip_y, 24, 10).unwrap();
+    assert!(!a.equals(&b));
+}
+
+#[test]
+fn test_montage_binary_is_black_on_white() {
+    let mut pm = Pix::new(10, 10, PixelDepth::Bit1).unwrap().to_mut();
+    pm.set_pixel_unchecked(3, 4, 1);
+    let mut pixa = Pixa::new();
+    pixa.push(pm.into());
+
+    let sheet = pixa.montage(1, &Sarray::new(), 0, 0).unwrap();
+    assert_eq!(count_black(&sheet, 0, 0, 10, 10), 1);
+    assert_eq!(count_black(&sheet, 3, 4, 1, 1), 1);
+}
+
+#[test]
+fn test_montage_fewer_labels_and_errors() {
+    let mut pixa = Pixa::new();
+    for _ in 0..3 {
+        pixa.push(make_pix(10, 10));
+    }
+
+    // One label for three images: only the first cell is labeled
+    let sheet = pixa
+        .montage(2, &Sarray::from_str_slice(&["x"]), 0, 8)
+        .unwrap();
+    assert_eq!(sheet.width(), 20);
+    assert_eq!(sheet.height(), 36);
+    assert!(count_black(&sheet, 0, 10, 10, 8) > 0);
+    assert_eq!(count_black(&sheet, 10, 10, 10, 8), 0);
+    assert_eq!(count_black(&sheet, 0, 28, 10, 8), 0);
+
+    let too_many = Sarray::from_str_slice(&["a", "b", "c", "d"]);
+    assert!(pixa.montage(2, &too_many, 0, 8).is_err());
+    assert!(pixa.montage(0, &Sarray::new(), 0, 8).is_err());
+    assert!(Pixa::new().montage(2, &Sarray::new(), 0, 8).is_err());
+}
+
 #[test]
 fn test_sort_by_aspect_ratio() {
     let mut pixa = Pixa::new();
@@ -275,3 +364,379 @@ fn test_sort_by_aspect_ratio() {
     assert_eq!(sorted[2].width(), 20);
     assert_eq!(sorted[2].height(), 10);
 }
+
+// ============================================================================
+// Pixa::align_sizes
+// ============================================================================
+
+#[test]
+fn test_align_sizes_pad() {
+    let mut pixa = Pixa::new();
+    let mut small = make_pix(10, 10).try_into_mut().unwrap();
+    for y in 0..10 {
+        for x in 0..10 {
+            small.set_pixel_unchecked(x, y, 50);
+        }
+    }
+    pixa.push(small.into());
+    pixa.push(make_pix(20, 20));
+    pixa.push(make_pix(10, 10));
+
+    let out = pixa
+        .align_sizes(AlignMode::Pad {
+            anchor: CanvasAnchor::TopLeft,
+            fill: 200,
+        })
+        .unwrap();
+    assert_eq!(out.len(), 3);
+    for pix in out.iter() {
+        assert_eq!((pix.width(), pix.height()), (20, 20));
+    }
+    let p0 = out.get(0).unwrap();
+    assert_eq!(p0.get_pixel(9, 9), Some(50));
+    assert_eq!(p0.get_pixel(10, 9), Some(200));
+    assert_eq!(p0.get_pixel(9, 10), Some(200));
+    assert_eq!(p0.get_pixel(19, 19), Some(200));
+    // Already at the target size: unchanged
+    assert_eq!(out.get(1).unwrap().get_pixel(19, 19), Some(0));
+}
+
+#[test]
+fn test_align_sizes_crop_and_scale() {
+    let mut pixa = Pixa::new();
+    pixa.push(make_pix(10, 30));
+    pixa.push(make_pix(20, 20));
+
+    let cropped = pixa
+        .align_sizes(AlignMode::Crop {
+            anchor: CanvasAnchor::Center,
+        })
+        .unwrap();
+    for pix in cropped.iter() {
+        assert_eq!((pix.width(), pix.height()), (10, 20));
+    }
+
+    let scaled = pixa
+        .align_sizes(AlignMode::Scale {
+            width: 16,
+            height: 12,
+        })
+        .unwrap();
+    for pix in scaled.iter() {
+        assert_eq!((pix.width(), pix.height()), (16, 12));
+    }
+
+    assert!(
+        pixa.align_sizes(AlignMode::Scale {
+            width: 0,
+            height: 12
+        })
+        .is_err()
+    );
+    assert!(
+        Pixa::new()
+            .align_sizes(AlignMode::Crop {
+                anchor: CanvasAnchor::TopLeft
+            })
+            .is_err()
+    );
+}
+
+// ============================================================================
+// Pixa::concat_h / Pixa::concat_v
+// ============================================================================
+
+fn make_filled_pix(w: u32, h: u32, val: u32) -> Pix {
+    let mut pm = make_pix(w, h).to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    pm.into()
+}
+
+#[test]
+fn test_concat_h() {
+    let mut pixa = Pixa::new();
+    pixa.push(make_filled_pix(10, 10, 50));
+    pixa.push(make_filled_pix(10, 20, 100));
+
+    let top = pixa.concat_h(VAlign::Top, 255).unwrap();
+    assert_eq!((top.width(), top.height()), (20, 20));
+    assert_eq!(top.depth(), PixelDepth::Bit8);
+    assert_eq!(top.get_pixel(9, 9), Some(50));
+    assert_eq!(top.get_pixel(9, 10), Some(255));
+    assert_eq!(top.get_pixel(10, 0), Some(100));
+    assert_eq!(top.get_pixel(19, 19), Some(100));
+
+    let center = pixa.concat_h(VAlign::Center, 255).unwrap();
+    assert_eq!(center.get_pixel(0, 4), Some(255));
+    assert_eq!(center.get_pixel(0, 5), Some(50));
+    assert_eq!(center.get_pixel(0, 14), Some(50));
+    assert_eq!(center.get_pixel(0, 15), Some(255));
+
+    let bottom = pixa.concat_h(VAlign::Bottom, 0).unwrap();
+    assert_eq!(bottom.get_pixel(0, 9), Some(0));
+    assert_eq!(bottom.get_pixel(0, 10), Some(50));
+
+    assert!(Pixa::new().concat_h(VAlign::Top, 0).is_err());
+}
+
+#[test]
+fn test_concat_v() {
+    let mut pixa = Pixa::new();
+    pixa.push(make_filled_pix(10, 10, 50));
+    pixa.push(make_filled_pix(30, 5, 100));
+    pixa.push(make_filled_pix(20, 5, 150));
+
+    let left = pixa.concat_v(HAlign::Left, 255).unwrap();
+    assert_eq!((left.width(), left.height()), (30, 20));
+    assert_eq!(left.get_pixel(9, 0), Some(50));
+    assert_eq!(left.get_pixel(10, 0), Some(255));
+    assert_eq!(left.get_pixel(29, 10), Some(100));
+    assert_eq!(left.get_pixel(19, 19), Some(150));
+    assert_eq!(left.get_pixel(20, 19), Some(255));
+
+    let right = pixa.concat_v(HAlign::Right, 255).unwrap();
+    assert_eq!(right.get_pixel(19, 0), Some(255));
+    assert_eq!(right.get_pixel(20, 0), Some(50));
+    assert_eq!(right.get_pixel(10, 15), Some(150));
+
+    let center = pixa.concat_v(HAlign::Center, 255).unwrap();
+    assert_eq!(center.get_pixel(9, 0), Some(255));
+    assert_eq!(center.get_pixel(10, 0), Some(50));
+    assert_eq!(center.get_pixel(19, 0), Some(50));
+    assert_eq!(center.get_pixel(20, 0), Some(255));
+}
+
+// ============================================================================
+// Pixa::group_by
+// ============================================================================
+
+#[test]
+fn test_group_by_lines() {
+    // Two words on one line (vertically overlapping) and one below
+    let mut pixa = Pixa::new();
+    pixa.push_with_box(make_pix(30, 12), Box::new(10, 10, 30, 12).unwrap());
+    pixa.push_with_box(make_pix(20, 12), Box::new(120, 50, 20, 12).unwrap());
+    pixa.push_with_box(make_pix(25, 14), Box::new(50, 8, 25, 14).unwrap());
+
+    let same_line = |a: &Box, b: &Box| a.y < b.y + b.h && b.y < a.y + a.h;
+    let lines = pixa.group_by(same_line).unwrap();
+    assert_eq!(lines.len(), 2);
+    let first = lines.get(0).unwrap();
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get_box(0).unwrap().x, 10);
+    assert_eq!(first.get_box(1).unwrap().x, 50);
+    assert_eq!(first.get(1).unwrap().width(), 25);
+    let second = lines.get(1).unwrap();
+    assert_eq!(second.len(), 1);
+    assert_eq!(second.get_box(0).unwrap().y, 50);
+
+    // A relation that never holds gives singleton groups
+    let singles = pixa.group_by(|_, _| false).unwrap();
+    assert_eq!(singles.len(), 3);
+}
+
+#[test]
+fn test_group_by_requires_boxes() {
+    let mut pixa = Pixa::new();
+    pixa.push(make_pix(10, 10));
+    assert!(pixa.group_by(|_, _| true).is_err());
+    assert!(Pixa::new().group_by(|_, _| true).unwrap().is_empty());
+}
+
+// ============================================================================
+// Pixa::pack_atlas
+// ============================================================================
+
+/// 8 bpp sprite whose pixels encode `id` and their position.
+fn make_sprite(w: u32, h: u32, id: u32) -> Pix {
+    let mut pm = make_pix(w, h).to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            pm.set_pixel_unchecked(x, y, 1 + (id * 37 + x * 3 + y * 7) % 255);
+        }
+    }
+    pm.into()
+}
+
+#[test]
+fn test_pack_atlas() {
+    let sizes = [
+        (20, 10),
+        (8, 30),
+        (15, 15),
+        (40, 5),
+        (12, 22),
+        (30, 12),
+        (5, 5),
+    ];
+    let mut pixa = Pixa::new();
+    for (i, &(w, h)) in sizes.iter().enumerate() {
+        pixa.push(make_sprite(w, h, i as u32));
+    }
+
+    let padding = 2;
+    let (atlas, boxa) = pixa.pack_atlas(50, padding).unwrap();
+    assert_eq!(atlas.depth(), PixelDepth::Bit8);
+    assert!(atlas.width() <= 50);
+    assert_eq!(boxa.len(), sizes.len());
+
+    let boxes: Vec<Box> = (0..boxa.len()).map(|i| *boxa.get(i).unwrap()).collect();
+    for (i, b) in boxes.iter().enumerate() {
+        // Box matches the sprite and lies inside the atlas
+        assert_eq!((b.w as u32, b.h as u32), sizes[i]);
+        assert!(b.x >= 0 && b.y >= 0);
+        assert!((b.x + b.w) as u32 <= atlas.width());
+        assert!((b.y + b.h) as u32 <= atlas.height());
+
+        // Cropping the atlas gives back the sprite
+        let crop = atlas
+            .clip_rectangle(b.x as u32, b.y as u32, b.w as u32, b.h as u32)
+            .unwrap();
+        assert!(crop.equals(pixa.get(i).unwrap()), "sprite {i} differs");
+
+        // No two sprites come closer than the padding
+        for (j, o) in boxes.iter().enumerate().skip(i + 1) {
+            let pad = padding as i32;
+            let apart = b.x + b.w + pad <= o.x
+                || o.x + o.w + pad <= b.x
+                || b.y + b.h + pad <= o.y
+                || o.y + o.h + pad <= b.y;
+            assert!(apart, "sprites {i} and {j} overlap");
+        }
+    }
+
+    // A single sprite gives an atlas of its own size
+    let mut one = Pixa::new();
+    one.push(make_sprite(9, 4, 0));
+    let (atlas, boxa) = one.pack_atlas(100, 3).unwrap();
+    assert_eq!((atlas.width(), atlas.height()), (9, 4));
+    assert_eq!(*boxa.get(0).unwrap(), Box::new(0, 0, 9, 4).unwrap());
+
+    assert!(pixa.pack_atlas(39, 0).is_err());
+    assert!(Pixa::new().pack_atlas(100, 0).is_err());
+}
+
+// ============================================================================
+// pixa_mean / pixa_median / pixa_max / pixa_min
+// ============================================================================
+
+/// 8 bpp image with pixel values `f(x, y)`.
+fn make_gray(w: u32, h: u32, f: impl Fn(u32, u32) -> u32) -> Pix {
+    let mut pm = make_pix(w, h).to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            pm.set_pixel_unchecked(x, y, f(x, y));
+        }
+    }
+    pm.into()
+}
+
+#[test]
+fn test_stack_reduce_gray() {
+    // Each image is brightest in a different region
+    let mut pixa = Pixa::new();
+    pixa.push(make_gray(12, 8, |x, _| if x < 4 { 200 } else { 10 }));
+    pixa.push(make_gray(
+        12,
+        8,
+        |x, _| if (4..8).contains(&x) { 180 } else { 20 },
+    ));
+    pixa.push(make_gray(12, 8, |x, y| if x >= 8 { 250 } else { 30 + y }));
+
+    let max = pixa_max(&pixa).unwrap();
+    assert_eq!(max.depth(), PixelDepth::Bit8);
+    assert_eq!((max.width(), max.height()), (12, 8));
+    for y in 0..8 {
+        for x in 0..12 {
+            let brightest = (0..3)
+                .map(|i| pixa.get(i).unwrap().get_pixel(x, y).unwrap())
+                .max()
+                .unwrap();
+            assert_eq!(max.get_pixel(x, y), Some(brightest), "max at ({x},{y})");
+        }
+    }
+    assert_eq!(max.get_pixel(0, 0), Some(200));
+    assert_eq!(max.get_pixel(5, 0), Some(180));
+    assert_eq!(max.get_pixel(11, 7), Some(250));
+
+    let min = pixa_min(&pixa).unwrap();
+    assert_eq!(min.get_pixel(0, 3), Some(20));
+    assert_eq!(min.get_pixel(5, 3), Some(10));
+    assert_eq!(min.get_pixel(10, 3), Some(10));
+
+    // (200 + 20 + 30) / 3 = 83.3; median of (10, 180, 30) is 30
+    let mean = pixa_mean(&pixa).unwrap();
+    assert_eq!(mean.get_pixel(0, 0), Some(83));
+    let median = pixa_median(&pixa).unwrap();
+    assert_eq!(median.get_pixel(5, 0), Some(30));
+
+    // Even count: mean of the two middle values, rounded
+    pixa.push(make_gray(12, 8, |_, _| 100));
+    let median = pixa_median(&pixa).unwrap();
+    // (10, 20, 100, 250) at (9, 0)
+    assert_eq!(median.get_pixel(9, 0), Some(60));
+}
+
+#[test]
+fn test_stack_reduce_rgb() {
+    let solid = |r, g, b| {
+        let mut pm = Pix::new(4, 4, PixelDepth::Bit32).unwrap().to_mut();
+        pm.set_all_arbitrary(pixel::compose_rgb(r, g, b)).unwrap();
+        Pix::from(pm)
+    };
+    let mut pixa = Pixa::new();
+    pixa.push(solid(250, 10, 100));
+    pixa.push(solid(20, 200, 110));
+    pixa.push(solid(60, 30, 240));
+
+    // Channels are reduced independently
+    let max = pixa_max(&pixa).unwrap();
+    assert_eq!(max.depth(), PixelDepth::Bit32);
+    assert_eq!(
+        pixel::extract_rgb(max.get_pixel(2, 2).unwrap()),
+        (250, 200, 240)
+    );
+    let min = pixa_min(&pixa).unwrap();
+    assert_eq!(
+        pixel::extract_rgb(min.get_pixel(0, 0).unwrap()),
+        (20, 10, 100)
+    );
+    let median = pixa_median(&pixa).unwrap();
+    assert_eq!(
+        pixel::extract_rgb(median.get_pixel(3, 3).unwrap()),
+        (60, 30, 110)
+    );
+    let mean = pixa_mean(&pixa).unwrap();
+    assert_eq!(
+        pixel::extract_rgb(mean.get_pixel(1, 1).unwrap()),
+        (110, 80, 150)
+    );
+}
+
+#[test]
+fn test_stack_reduce_validation() {
+    assert!(pixa_mean(&Pixa::new()).is_err());
+
+    let mut mixed_size = Pixa::new();
+    mixed_size.push(make_pix(10, 10));
+    mixed_size.push(make_pix(10, 11));
+    assert!(pixa_max(&mixed_size).is_err());
+
+    let mut mixed_depth = Pixa::new();
+    mixed_depth.push(make_pix(10, 10));
+    mixed_depth.push(Pix::new(10, 10, PixelDepth::Bit32).unwrap());
+    assert!(pixa_min(&mixed_depth).is_err());
+
+    let mut binary = Pixa::new();
+    binary.push(Pix::new(10, 10, PixelDepth::Bit1).unwrap());
+    assert!(pixa_median(&binary).is_err());
+
+    // A single image reduces to itself
+    let mut one = Pixa::new();
+    one.push(make_gray(5, 5, |x, y| x * 40 + y));
+    assert!(pixa_median(&one).unwrap().equals(one.get(0).unwrap()));
+}
@@ -6,7 +6,8 @@
 //!
 //! This Rust port tests the available rasterop primitives: rasterop_vip
 //! (vertical in-place shift), rasterop_hip (horizontal in-place shift),
-//! translate, and general ROP operations via algebraic properties.
+//! translate, cyclic shift, and general ROP operations via algebraic
+//! properties.
 //!
 //! # See also
 //!
@@ -118,6 +119,59 @@ fn rasterop_reg_general() {
     assert!(rp.cleanup(), "rasterop general test failed");
 }
 
+/// Test Pix::shift with and without wrap-around.
+#[test]
+fn rasterop_reg_shift_wrap() {
+    let mut rp = RegParams::new("rasterop_shift");
+
+    let pix = crate::common::load_test_image("test8.jpg").expect("load test8.jpg");
+    let w = pix.width() as i32;
+    let h = pix.height() as i32;
+
+    // Wrapping by a full period in either direction is the identity.
+    let full = pix.shift(w, 0, true).expect("shift full width");
+    rp.compare_pix(&pix, &full);
+    let full = pix.shift(-w, 2 * h, true).expect("shift full period");
+    rp.compare_pix(&pix, &full);
+
+    // A wrap shift is undone by the opposite shift.
+    let there = pix.shift(37, -11, true).expect("shift");
+    let back = there.shift(-37, 11, true).expect("shift back");
+    rp.compare_pix(&pix, &back);
+    rp.compare_values(
+        pix.get_pixel(0, 0).unwrap() as f64,
+        there.get_pixel(37, (h - 11) as u32).unwrap() as f64,
+        0.0,
+    );
+
+    // Without wrap the exposed band is zero and the rest is moved content.
+    let moved = pix.shift(5, 3, false).expect("shift no wrap");
+    for y in 0..3 {
+        for x in 0..w as u32 {
+            assert_eq!(moved.get_pixel(x, y), Some(0));
+        }
+    }
+    for y in 0..h as u32 {
+        for x in 0..5 {
+            assert_eq!(moved.get_pixel(x, y), Some(0));
+        }
+    }
+    rp.compare_values(
+        pix.get_pixel(10, 10).unwrap() as f64,
+        moved.get_pixel(15, 13).unwrap() as f64,
+        0.0,
+    );
+
+    // Sub-byte depth: a 1bpp image wraps bit-exactly.
+    let bin = crate::common::load_test_image("feyn-fract.tif").expect("load feyn-fract.tif");
+    let bw = bin.width() as i32;
+    let wrapped = bin.shift(bw - 1, 0, true).expect("shift 1bpp");
+    let back = wrapped.shift(1, 0, true).expect("shift 1bpp back");
+    rp.compare_pix(&bin, &back);
+
+    assert!(rp.cleanup(), "rasterop shift test failed");
+}
+
 /// Test dilation equivalence (C checks 0-62).
 ///
 /// Requires general region-based pixRasterop and morphological operations.
@@ -1,13 +1,16 @@
 //! String regression test
 //!
-//! Covers `Sarray`/`Sarraya` operations and serialization.
+//! Covers `Sarray`/`Sarraya` operations and serialization, and
+//! natural sort.
 //!
 //! # See also
 //!
 //! C Leptonica: `prog/string_reg.c`
 
 use crate::common::RegParams;
+use leptonica::core::sarray::string_compare_natural;
 use leptonica::{Sarray, Sarraya};
+use std::cmp::Ordering;
 
 // ============================================================================
 // C-equivalent regression test skeletons — missing string operations
@@ -159,3 +162,50 @@ fn string_reg() {
 
     assert!(rp.cleanup(), "string regression test failed");
 }
+
+/// Natural sort of page-like file names.
+#[test]
+fn string_reg_sort_natural() {
+    let mut rp = RegParams::new("string_sort_natural");
+
+    let mut sa = Sarray::from_str_slice(&["img2", "img10", "img1"]);
+    sa.sort_natural();
+    let got: Vec<&str> = sa.iter().collect();
+    rp.compare_values(1.0, (got == ["img1", "img2", "img10"]) as u8 as f64, 0.0);
+
+    // Mixed segments, multiple numbers, leading zeros and long numbers
+    let mut sa = Sarray::from_str_slice(&[
+        "page10_v2.png",
+        "page2_v10.png",
+        "page2_v9.png",
+        "page02_v9.png",
+        "page.png",
+        "page123456789012345678901.png",
+        "Page1.png",
+    ]);
+    sa.sort_natural();
+    let got: Vec<&str> = sa.iter().collect();
+    let expected = [
+        "Page1.png",
+        "page.png",
+        "page02_v9.png",
+        "page2_v9.png",
+        "page2_v10.png",
+        "page10_v2.png",
+        "page123456789012345678901.png",
+    ];
+    rp.compare_values(1.0, (got == expected) as u8 as f64, 0.0);
+
+    // Plain sort stays lexicographic
+    let mut sa = Sarray::from_str_slice(&["img2", "img10", "img1"]);
+    sa.sort();
+    rp.compare_values(1.0, (sa.get(1) == Some("img10")) as u8 as f64, 0.0);
+
+    rp.compare_values(
+        1.0,
+        (string_compare_natural("a9b", "a10a") == Ordering::Less) as u8 as f64,
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "string natural sort test failed");
+}
@@ -4,10 +4,13 @@
 //! - pixApplyVariableGrayMap
 //! - pixGlobalNormRGB
 //! - pixConvertTo8MinMax
+//!
+//! Also covers `background_remap` with an `FPix` background.
 
+use leptonica::core::colormap::{PixColormap, RgbaQuad};
 use leptonica::core::pixel;
 use leptonica::filter::adaptmap;
-use leptonica::{Pix, PixelDepth};
+use leptonica::{FPix, Pix, PixelDepth};
 
 /// Create a grayscale test image
 fn make_gray_test_image() -> Pix {
@@ -103,6 +106,70 @@ fn test_apply_variable_gray_map_invalid_map_depth() {
     assert!(adaptmap::apply_variable_gray_map(&pix, &map, 128).is_err());
 }
 
+// ============================================================================
+// background_remap
+// ============================================================================
+
+#[test]
+fn test_background_remap_flat_is_identity() {
+    let pix = make_gray_test_image();
+    let bg = FPix::new_with_value(60, 60, 200.0).unwrap();
+    let result = adaptmap::background_remap(&pix, &bg, 200).unwrap();
+    assert_eq!(result.depth(), PixelDepth::Bit8);
+    for y in 0..60 {
+        for x in 0..60 {
+            assert_eq!(
+                result.get_pixel_unchecked(x, y),
+                pix.get_pixel_unchecked(x, y)
+            );
+        }
+    }
+
+    let rgb = make_color_cast_image();
+    let bg = FPix::new_with_value(60, 60, 180.0).unwrap();
+    let result = adaptmap::background_remap(&rgb, &bg, 180).unwrap();
+    assert_eq!(
+        result.get_pixel_unchecked(7, 9) >> 8,
+        rgb.get_pixel_unchecked(7, 9) >> 8
+    );
+}
+
+#[test]
+fn test_background_remap_evens_illumination() {
+    // Constant scene (value 120) under a left-to-right illumination ramp
+    let mut bg = FPix::new(60, 60).unwrap();
+    let pix = Pix::new(60, 60, PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..60 {
+        for x in 0..60 {
+            let light = 100.0 + 2.0 * x as f32;
+            bg.set_pixel_unchecked(x, y, light);
+            pm.set_pixel_unchecked(x, y, (0.6 * light).round() as u32);
+        }
+    }
+    let pix: Pix = pm.into();
+
+    // The same background map can be reused; output is flat at 0.6 * target
+    let result = adaptmap::background_remap(&pix, &bg, 200).unwrap();
+    for x in 0..60 {
+        let v = result.get_pixel_unchecked(x, 30) as i32;
+        assert!((v - 120).abs() <= 1, "x = {x}: {v}");
+    }
+
+    let small = FPix::new(30, 60).unwrap();
+    assert!(adaptmap::background_remap(&pix, &small, 200).is_err());
+    let pix1 = Pix::new(60, 60, PixelDepth::Bit1).unwrap();
+    assert!(adaptmap::background_remap(&pix1, &bg, 200).is_err());
+
+    // Colormap indices are not intensities
+    let mut pm = pix.to_mut();
+    let mut cmap = PixColormap::new(8).unwrap();
+    cmap.add_color(RgbaQuad::rgb(255, 255, 255)).unwrap();
+    pm.set_colormap(Some(cmap)).unwrap();
+    let cmapped: Pix = pm.into();
+    assert!(adaptmap::background_remap(&cmapped, &bg, 200).is_err());
+}
+
 // ============================================================================
 // pixGlobalNormRGB
 // ============================================================================
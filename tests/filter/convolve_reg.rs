@@ -26,9 +26,9 @@
 use crate::common::{RegParams, load_test_image};
 use leptonica::NegativeHandling;
 use leptonica::filter::{
-    Kernel, blockconv, blockconv_accum, blockconv_gray, blockrank, blocksum, box_blur,
-    census_transform, convolve, gaussian_blur, windowed_mean, windowed_mean_square, windowed_stats,
-    windowed_variance,
+    BorderMode, Kernel, blockconv, blockconv_accum, blockconv_gray, blockrank, blocksum, box_blur,
+    census_transform, convolve, convolve_gray, convolve_gray_with_border, convolve_with_border,
+    gaussian_blur, windowed_mean, windowed_mean_square, windowed_stats, windowed_variance,
 };
 use leptonica::io::ImageFormat;
 
@@ -170,6 +170,48 @@ fn convolve_custom_kernel_reg() {
     );
 }
 
+/// Test convolve_gray_with_border border modes.
+///
+/// A 3x3 box kernel over the 3x3 image [10 20 30; 40 50 60; 70 80 90]
+/// gives a distinct corner value for each mode, while the center (which
+/// never touches the border) is 50 everywhere.
+#[test]
+fn convolve_border_mode_reg() {
+    let mut rp = RegParams::new("convolve_border_mode");
+
+    let mut pm = leptonica::Pix::new(3, 3, leptonica::PixelDepth::Bit8)
+        .unwrap()
+        .try_into_mut()
+        .unwrap();
+    for y in 0..3 {
+        for x in 0..3 {
+            pm.set_pixel(x, y, 10 * (3 * y + x + 1)).unwrap();
+        }
+    }
+    let pix: leptonica::Pix = pm.into();
+    let kernel = Kernel::box_kernel(3).expect("box kernel");
+
+    // Corner (0,0): Zero 120/9, Clamp 210/9, Mirror 330/9, Wrap 450/9.
+    let cases = [
+        (BorderMode::Zero, 13.0),
+        (BorderMode::Clamp, 23.0),
+        (BorderMode::Mirror, 37.0),
+        (BorderMode::Wrap, 50.0),
+    ];
+    for (mode, corner) in cases {
+        let out = convolve_gray_with_border(&pix, &kernel, mode).expect("convolve border");
+        rp.compare_values(corner, out.get_pixel(0, 0).unwrap() as f64, 0.0);
+        rp.compare_values(50.0, out.get_pixel(1, 1).unwrap() as f64, 0.0);
+    }
+
+    // The default mode is the historical clamp behavior.
+    let dflt = convolve_gray(&pix, &kernel).expect("convolve_gray");
+    let clamp = convolve_with_border(&pix, &kernel, BorderMode::default()).expect("convolve");
+    rp.compare_pix(&dflt, &clamp);
+
+    assert!(rp.cleanup(), "convolve border mode regression test failed");
+}
+
 /// Test blockconv on 32 bpp (C checkpoint 9).
 ///
 /// C: pixt = pixScaleBySampling(pixs, 0.5, 0.5); pixBlockconv(pixt, 4, 6) /* 9 */
@@ -14,11 +14,15 @@
 //! - Sharpening kernel -> sharpen
 //! - Unsharp mask -> unsharp_mask
 //! - Emboss kernel -> emboss
+//! - Variance of Laplacian -> focus_measure
+//! - Immerkaer noise sigma -> estimate_noise
+//! - Gradient direction histogram -> dominant_orientation
 
 use crate::common::{RegParams, load_test_image};
 use leptonica::color::threshold_to_binary;
 use leptonica::filter::{
-    EdgeOrientation, emboss, laplacian_edge, sharpen, sobel_edge, unsharp_mask,
+    EdgeOrientation, add_gaussian_noise, dominant_orientation, emboss, estimate_noise,
+    focus_measure, gaussian_blur, laplacian_edge, sharpen, sobel_edge, unsharp_mask,
 };
 use leptonica::io::ImageFormat;
 
@@ -96,6 +100,138 @@ fn edge_reg() {
     assert!(rp.cleanup(), "edge regression test failed");
 }
 
+/// Test noise estimation: known Gaussian noise on a smooth image is recovered.
+#[test]
+fn edge_reg_estimate_noise() {
+    let mut rp = RegParams::new("edge_noise");
+
+    // Linear ramp kept away from 0 and 255 so added noise is not clipped
+    let pix = leptonica::Pix::new(128, 256, leptonica::PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..256 {
+        for x in 0..128 {
+            pm.set_pixel_unchecked(x, y, 64 + x);
+        }
+    }
+    let smooth: leptonica::Pix = pm.into();
+    let clean = estimate_noise(&smooth).expect("estimate_noise clean");
+    rp.compare_values(0.0, clean, 0.0);
+
+    for sigma in [3.0f32, 8.0, 15.0] {
+        let noisy = add_gaussian_noise(&smooth, sigma).expect("add_gaussian_noise");
+        let est = estimate_noise(&noisy).expect("estimate_noise noisy");
+        rp.compare_values(sigma as f64, est, 0.1 * sigma as f64);
+    }
+
+    // Edges barely affect the estimate
+    let lena = load_test_image("test8.jpg").expect("load test8.jpg");
+    let base = estimate_noise(&lena).expect("estimate_noise test8");
+    let noisy = add_gaussian_noise(&lena, 10.0).expect("add_gaussian_noise");
+    let est = estimate_noise(&noisy).expect("estimate_noise noisy test8");
+    rp.compare_values(10.0, (est * est - base * base).max(0.0).sqrt(), 2.0);
+
+    let pix1 = leptonica::Pix::new(32, 32, leptonica::PixelDepth::Bit1).unwrap();
+    assert!(estimate_noise(&pix1).is_err());
+    let tiny = leptonica::Pix::new(2, 32, leptonica::PixelDepth::Bit8).unwrap();
+    assert!(estimate_noise(&tiny).is_err());
+
+    assert!(rp.cleanup(), "edge noise estimate test failed");
+}
+
+/// Test focus measure: blurring a sharp edge image lowers its score.
+#[test]
+fn edge_reg_focus_measure() {
+    let mut rp = RegParams::new("edge_focus");
+
+    // Checkerboard of 8x8 squares: many hard edges
+    let pix = leptonica::Pix::new(64, 64, leptonica::PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..64 {
+        for x in 0..64 {
+            let v = if ((x / 8) + (y / 8)) % 2 == 0 {
+                230
+            } else {
+                20
+            };
+            pm.set_pixel_unchecked(x, y, v);
+        }
+    }
+    let sharp: leptonica::Pix = pm.into();
+    let blurred = gaussian_blur(&sharp, 3, 2.0).expect("gaussian_blur");
+
+    let fm_sharp = focus_measure(&sharp).expect("focus_measure sharp");
+    let fm_blur = focus_measure(&blurred).expect("focus_measure blurred");
+    rp.compare_values(1.0, if fm_sharp > fm_blur { 1.0 } else { 0.0 }, 0.0);
+    rp.compare_values(1.0, if fm_blur > 0.0 { 1.0 } else { 0.0 }, 0.0);
+
+    // A flat image has no Laplacian response
+    let flat = leptonica::Pix::new(32, 32, leptonica::PixelDepth::Bit8).unwrap();
+    rp.compare_values(0.0, focus_measure(&flat).expect("focus_measure flat"), 0.0);
+
+    // Requires 8 bpp
+    let pix1 = leptonica::Pix::new(32, 32, leptonica::PixelDepth::Bit1).unwrap();
+    rp.compare_values(
+        1.0,
+        if focus_measure(&pix1).is_err() {
+            1.0
+        } else {
+            0.0
+        },
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "edge focus measure test failed");
+}
+
+/// Test dominant orientation on images of parallel lines.
+#[test]
+fn edge_reg_dominant_orientation() {
+    let mut rp = RegParams::new("edge_orient");
+
+    for &angle in &[0.0f32, 30.0, 90.0, 135.0] {
+        let pix = make_stripes(128, 128, angle, 12.0);
+        let found = dominant_orientation(&pix, 180).expect("dominant_orientation");
+        // Compare modulo 180 degrees
+        let diff = (found - angle + 90.0).rem_euclid(180.0) - 90.0;
+        rp.compare_values(0.0, diff as f64, 1.5);
+    }
+
+    // Flat image has no gradients
+    let flat = leptonica::Pix::new(32, 32, leptonica::PixelDepth::Bit8).unwrap();
+    rp.compare_values(0.0, dominant_orientation(&flat, 36).unwrap() as f64, 0.0);
+
+    // Invalid parameters
+    let pix = make_stripes(32, 32, 45.0, 8.0);
+    rp.compare_values(
+        1.0,
+        if dominant_orientation(&pix, 1).is_err() {
+            1.0
+        } else {
+            0.0
+        },
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "edge dominant orientation test failed");
+}
+
+/// Sinusoidal stripes running at `angle` degrees (counter-clockwise,
+/// y up) with the given period in pixels.
+fn make_stripes(w: u32, h: u32, angle: f32, period: f32) -> leptonica::Pix {
+    let (s, c) = angle.to_radians().sin_cos();
+    let pix = leptonica::Pix::new(w, h, leptonica::PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..h {
+        for x in 0..w {
+            // Distance along the normal to the stripes
+            let d = -(x as f32) * s - (y as f32) * c;
+            let v = 128.0 + 100.0 * (2.0 * std::f32::consts::PI * d / period).cos();
+            pm.set_pixel_unchecked(x, y, v.round() as u32);
+        }
+    }
+    pm.into()
+}
+
 /// Compute per-pixel maximum of two 8bpp images.
 /// Equivalent to C: pixMinOrMax(NULL, pix1, pix2, L_CHOOSE_MAX)
 fn pixel_max(pix1: &leptonica::Pix, pix2: &leptonica::Pix) -> leptonica::Pix {
@@ -3,6 +3,9 @@
 //! Tests gamma correction, hue/saturation modification, contrast enhancement,
 //! unsharp masking, and color transforms. The C version applies 20 iterations
 //! of each operation and renders tiled output for visual comparison.
+//! Also covers variance-driven `adaptive_unsharp`, tiled
+//! `local_histogram_equalize`, `match_histogram`, and the degree/factor
+//! based `shift_hue` and `scale_saturation`.
 //!
 //! # See also
 //!
@@ -10,9 +13,11 @@
 
 use crate::common::{RegParams, load_test_image};
 use leptonica::PixelDepth;
+use leptonica::core::pix::RemoveColormapTarget;
 use leptonica::filter::{
-    Kernel, contrast_trc_pix, gamma_trc, gamma_trc_pix, measure_saturation, modify_hue,
-    modify_saturation, mult_constant_color, mult_matrix_color, trc_map_general, unsharp_masking,
+    Kernel, TrcLut, adaptive_unsharp, apply_lut, apply_lut_rgb, contrast_trc_pix, gamma_trc,
+    gamma_trc_pix, measure_saturation, modify_hue, modify_saturation, mult_constant_color,
+    mult_matrix_color, posterize, solarize, threshold_band, trc_map_general, unsharp_masking,
 };
 use leptonica::io::ImageFormat;
 
@@ -234,3 +239,367 @@ fn enhance_reg_mosaic_color_shift() {
     // Applies spatially-varying color shift across image tiles
     assert!(rp.cleanup(), "enhance mosaic_color_shift test failed");
 }
+
+/// Test apply_lut / apply_lut_rgb explicit LUT remapping.
+///
+/// An identity LUT is a no-op, an inverting LUT (255 - v) produces the
+/// photographic negative, and a gamma LUT matches gamma_trc_pix.
+#[test]
+fn enhance_reg_apply_lut() {
+    let mut rp = RegParams::new("enhance_apply_lut");
+
+    let identity: TrcLut = std::array::from_fn(|i| i as u8);
+    let invert: TrcLut = std::array::from_fn(|i| 255 - i as u8);
+
+    let pix8 = load_test_image("test8.jpg").expect("load test8.jpg");
+    let same8 = apply_lut(&pix8, &identity).expect("identity 8bpp");
+    rp.compare_pix(&pix8, &same8);
+    let neg8 = apply_lut(&pix8, &invert).expect("invert 8bpp");
+    rp.compare_pix(&pix8.invert(), &neg8);
+
+    let pix32 = load_test_image("test24.jpg").expect("load test24.jpg");
+    let same32 = apply_lut(&pix32, &identity).expect("identity 32bpp");
+    rp.compare_pix(&pix32, &same32);
+    let neg32 = apply_lut(&pix32, &invert).expect("invert 32bpp");
+    rp.compare_pix(&pix32.invert(), &neg32);
+    // Alpha is carried through unchanged
+    rp.compare_values(
+        (pix32.get_pixel(3, 3).unwrap() & 0xff) as f64,
+        (neg32.get_pixel(3, 3).unwrap() & 0xff) as f64,
+        0.0,
+    );
+
+    // Equivalent to the gamma wrapper when given the gamma curve
+    let lut = gamma_trc(0.7, 0, 255).expect("gamma lut");
+    let via_lut = apply_lut(&pix8, &lut).expect("gamma via lut");
+    let via_trc = gamma_trc_pix(&pix8, 0.7, 0, 255).expect("gamma_trc_pix");
+    rp.compare_pix(&via_trc, &via_lut);
+
+    // Per-channel: zero the red channel only
+    let zero: TrcLut = [0; 256];
+    let no_red = apply_lut_rgb(&pix32, &zero, &identity, &identity).expect("per-channel");
+    let (r, g, b) = leptonica::core::pixel::extract_rgb(no_red.get_pixel(10, 10).unwrap());
+    let (_, g0, b0) = leptonica::core::pixel::extract_rgb(pix32.get_pixel(10, 10).unwrap());
+    rp.compare_values(0.0, r as f64, 0.0);
+    rp.compare_values(g0 as f64, g as f64, 0.0);
+    rp.compare_values(b0 as f64, b as f64, 0.0);
+
+    // Colormapped input remaps the palette, not the indices
+    let cmapped = load_test_image("weasel8.240c.png").expect("load weasel8.240c.png");
+    let neg_cmap = apply_lut(&cmapped, &invert).expect("invert cmap");
+    assert!(neg_cmap.has_colormap());
+    let to_rgb = |p: &leptonica::Pix| {
+        p.remove_colormap(RemoveColormapTarget::ToFullColor)
+            .unwrap()
+    };
+    rp.compare_pix(&to_rgb(&cmapped).invert(), &to_rgb(&neg_cmap));
+
+    let pix1 = leptonica::Pix::new(8, 8, PixelDepth::Bit1).unwrap();
+    assert!(apply_lut(&pix1, &identity).is_err());
+
+    assert!(rp.cleanup(), "enhance apply_lut test failed");
+}
+
+/// Test solarize / posterize / threshold_band effects.
+#[test]
+fn enhance_reg_lut_effects() {
+    let mut rp = RegParams::new("enhance_lut_effects");
+
+    // Horizontal gradient covering every 8-bit value
+    let mut pm = leptonica::Pix::new(256, 4, PixelDepth::Bit8)
+        .unwrap()
+        .to_mut();
+    for y in 0..4 {
+        for x in 0..256 {
+            pm.set_pixel(x, y, x).unwrap();
+        }
+    }
+    let gradient: leptonica::Pix = pm.into();
+
+    // Posterize to 4 levels: exactly 4 distinct outputs, including 0 and 255
+    let poster = posterize(&gradient, 4).expect("posterize");
+    let mut values: Vec<u32> = (0..256).map(|x| poster.get_pixel(x, 0).unwrap()).collect();
+    values.dedup();
+    rp.compare_values(4.0, values.len() as f64, 0.0);
+    assert_eq!(values, vec![0, 85, 170, 255]);
+    assert!(posterize(&gradient, 1).is_err());
+
+    // Solarize: values >= 128 are inverted
+    let sol = solarize(&gradient, 128).expect("solarize");
+    rp.compare_values(100.0, sol.get_pixel(100, 0).unwrap() as f64, 0.0);
+    rp.compare_values(127.0, sol.get_pixel(128, 0).unwrap() as f64, 0.0);
+    rp.compare_values(0.0, sol.get_pixel(255, 0).unwrap() as f64, 0.0);
+
+    // Threshold band keeps [50, 60] and zeroes everything else
+    let band = threshold_band(&gradient, 50, 60).expect("threshold_band");
+    rp.compare_values(0.0, band.get_pixel(49, 0).unwrap() as f64, 0.0);
+    rp.compare_values(50.0, band.get_pixel(50, 0).unwrap() as f64, 0.0);
+    rp.compare_values(60.0, band.get_pixel(60, 0).unwrap() as f64, 0.0);
+    rp.compare_values(0.0, band.get_pixel(61, 0).unwrap() as f64, 0.0);
+    assert!(threshold_band(&gradient, 60, 50).is_err());
+
+    // 32 bpp is processed per channel
+    let pix32 = load_test_image("test24.jpg").expect("load test24.jpg");
+    let poster32 = posterize(&pix32, 2).expect("posterize 32bpp");
+    let (r, g, b) = leptonica::core::pixel::extract_rgb(poster32.get_pixel(20, 20).unwrap());
+    for c in [r, g, b] {
+        assert!(c == 0 || c == 255, "channel value {c}");
+    }
+
+    assert!(rp.cleanup(), "enhance LUT effects test failed");
+}
+
+/// Test variance-driven unsharp masking.
+#[test]
+fn enhance_reg_adaptive_unsharp() {
+    let mut rp = RegParams::new("enhance_adaptive_unsharp");
+
+    // Left half: nearly flat with low-amplitude noise.
+    // Right half: high-contrast vertical stripes.
+    let mut pm = leptonica::Pix::new(80, 40, PixelDepth::Bit8)
+        .unwrap()
+        .to_mut();
+    for y in 0..40 {
+        for x in 0..80 {
+            let val = if x < 40 {
+                126 + (x * 7 + y * 13) % 5
+            } else if (x / 3) % 2 == 0 {
+                60
+            } else {
+                200
+            };
+            pm.set_pixel(x, y, val).unwrap();
+        }
+    }
+    let pix: leptonica::Pix = pm.into();
+
+    let mean_change = |out: &leptonica::Pix, x0: u32, x1: u32| {
+        let mut sum = 0.0;
+        let mut n = 0.0;
+        for y in 5..35 {
+            for x in x0..x1 {
+                let a = pix.get_pixel(x, y).unwrap() as f64;
+                let b = out.get_pixel(x, y).unwrap() as f64;
+                sum += (a - b).abs();
+                n += 1.0;
+            }
+        }
+        sum / n
+    };
+
+    let out = adaptive_unsharp(&pix, 2, 1.5, 100.0).expect("adaptive_unsharp");
+    rp.compare_values(8.0, out.depth().bits() as f64, 0.0);
+    let flat = mean_change(&out, 5, 35);
+    let textured = mean_change(&out, 45, 75);
+    assert!(
+        flat < textured,
+        "flat change {flat} should be less than textured change {textured}"
+    );
+
+    // The flat area is changed less than by plain unsharp masking
+    let plain = unsharp_masking(&pix, 2, 1.5).expect("unsharp_masking");
+    let plain_flat = mean_change(&plain, 5, 35);
+    assert!(flat < plain_flat, "{flat} vs plain {plain_flat}");
+
+    // Zero amount is the identity
+    let same = adaptive_unsharp(&pix, 2, 0.0, 100.0).expect("zero amount");
+    rp.compare_values(1.0, same.equals(&pix) as u8 as f64, 0.0);
+
+    // 32 bpp keeps its depth
+    let pix32 = load_test_image("test24.jpg").expect("load test24.jpg");
+    let out32 = adaptive_unsharp(&pix32, 3, 1.0, 200.0).expect("adaptive_unsharp 32bpp");
+    rp.compare_values(32.0, out32.depth().bits() as f64, 0.0);
+    rp.compare_values(pix32.width() as f64, out32.width() as f64, 0.0);
+
+    // Invalid parameters
+    assert!(adaptive_unsharp(&pix, 0, 1.0, 100.0).is_err());
+    assert!(adaptive_unsharp(&pix, 2, -1.0, 100.0).is_err());
+    assert!(adaptive_unsharp(&pix, 2, 1.0, 0.0).is_err());
+    let pix1 = leptonica::Pix::new(10, 10, PixelDepth::Bit1).unwrap();
+    assert!(adaptive_unsharp(&pix1, 2, 1.0, 100.0).is_err());
+
+    assert!(rp.cleanup(), "enhance adaptive_unsharp test failed");
+}
+
+/// Test `local_histogram_equalize`: contrast gain in each region
+/// without seams at tile boundaries.
+#[test]
+fn enhance_reg_local_histogram_equalize() {
+    use leptonica::Pix;
+    use leptonica::filter::local_histogram_equalize;
+
+    let mut rp = RegParams::new("enhance_local_eq");
+
+    // Two low-contrast regions: dark on the left, light on the right
+    let mut pm = Pix::new(128, 64, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..64 {
+        for x in 0..128 {
+            let v = if x < 64 {
+                60 + (x + y) % 10
+            } else {
+                180 + (3 * x + y) % 10
+            };
+            pm.set_pixel_unchecked(x, y, v);
+        }
+    }
+    let pix: Pix = pm.into();
+    let out = local_histogram_equalize(&pix, 4, 2).expect("local eq");
+    rp.compare_values(128.0, out.width() as f64, 0.0);
+
+    let range = |p: &Pix, x0: u32, x1: u32| {
+        let (mut lo, mut hi) = (255u32, 0u32);
+        for y in 0..64 {
+            for x in x0..x1 {
+                let v = p.get_pixel_unchecked(x, y);
+                lo = lo.min(v);
+                hi = hi.max(v);
+            }
+        }
+        hi - lo
+    };
+    rp.compare_values(9.0, range(&pix, 0, 64) as f64, 0.0);
+    rp.compare_values(1.0, (range(&out, 0, 64) > 150) as u8 as f64, 0.0);
+    rp.compare_values(1.0, (range(&out, 64, 128) > 150) as u8 as f64, 0.0);
+
+    // A slow ramp: each tile sees a different gray range, so per-tile
+    // LUTs alone would jump by ~255 at every tile edge
+    let mut pm = Pix::new(256, 16, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..16 {
+        for x in 0..256 {
+            pm.set_pixel_unchecked(x, y, 100 + x * 40 / 256);
+        }
+    }
+    let ramp: Pix = pm.into();
+    let out = local_histogram_equalize(&ramp, 4, 1).unwrap();
+    let mut max_jump = 0i32;
+    for x in 0..255 {
+        let a = out.get_pixel_unchecked(x, 8) as i32;
+        let b = out.get_pixel_unchecked(x + 1, 8) as i32;
+        max_jump = max_jump.max((a - b).abs());
+    }
+    rp.compare_values(1.0, (max_jump <= 40) as u8 as f64, 0.0);
+
+    assert!(local_histogram_equalize(&pix, 0, 2).is_err());
+    assert!(local_histogram_equalize(&pix, 129, 2).is_err());
+    let rgb = load_test_image("test24.jpg").unwrap();
+    assert!(local_histogram_equalize(&rgb, 4, 4).is_err());
+
+    assert!(rp.cleanup(), "enhance local equalization test failed");
+}
+
+/// Test `match_histogram`: identity on itself, and a dark image takes
+/// on the tone distribution of a bright reference.
+#[test]
+fn enhance_reg_match_histogram() {
+    use leptonica::Pix;
+    use leptonica::filter::match_histogram;
+
+    let mut rp = RegParams::new("enhance_match_hist");
+
+    let pix = load_test_image("test8.jpg").expect("load test8.jpg");
+    let same = match_histogram(&pix, &pix).expect("match self");
+    rp.compare_pix(&pix, &same);
+
+    let rgb = load_test_image("test24.jpg").expect("load test24.jpg");
+    let same = match_histogram(&rgb, &rgb).expect("match self rgb");
+    rp.compare_pix(&rgb, &same);
+
+    // Dark ramp in [20, 80) matched to a bright ramp in [150, 250)
+    let mut dark = Pix::new(100, 40, PixelDepth::Bit8).unwrap().to_mut();
+    let mut bright = Pix::new(50, 50, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..40 {
+        for x in 0..100 {
+            dark.set_pixel_unchecked(x, y, 20 + (x * 60) / 100);
+        }
+    }
+    for y in 0..50 {
+        for x in 0..50 {
+            bright.set_pixel_unchecked(x, y, 150 + ((y * 50 + x) * 100) / 2500);
+        }
+    }
+    let (dark, bright): (Pix, Pix) = (dark.into(), bright.into());
+    let matched = match_histogram(&dark, &bright).expect("match dark");
+
+    let mean = |p: &Pix| {
+        let mut sum = 0.0;
+        for y in 0..p.height() {
+            for x in 0..p.width() {
+                sum += p.get_pixel_unchecked(x, y) as f64;
+            }
+        }
+        sum / (p.width() * p.height()) as f64
+    };
+    rp.compare_values(mean(&bright), mean(&matched), 2.0);
+    // Tone order is preserved and the output spans the reference range
+    rp.compare_values(150.0, matched.get_pixel_unchecked(0, 0) as f64, 1.0);
+    rp.compare_values(249.0, matched.get_pixel_unchecked(99, 0) as f64, 2.0);
+    let monotone = (1..100)
+        .all(|x| matched.get_pixel_unchecked(x, 0) >= matched.get_pixel_unchecked(x - 1, 0));
+    rp.compare_values(1.0, monotone as u8 as f64, 0.0);
+
+    rp.compare_values(1.0, match_histogram(&dark, &rgb).is_err() as u8 as f64, 0.0);
+
+    assert!(rp.cleanup(), "enhance match_histogram test failed");
+}
+
+/// Test `shift_hue` (degrees) and `scale_saturation` (multiplier).
+#[test]
+fn enhance_reg_shift_hue_scale_saturation() {
+    use leptonica::Pix;
+    use leptonica::core::pixel::{compose_rgba, extract_rgba};
+    use leptonica::filter::{scale_saturation, shift_hue};
+
+    let mut rp = RegParams::new("enhance_shift_hue");
+
+    let single = |r: u8, g: u8, b: u8, a: u8| {
+        let mut pm = Pix::new(1, 1, PixelDepth::Bit32).unwrap().to_mut();
+        pm.set_spp(4);
+        pm.set_pixel_unchecked(0, 0, compose_rgba(r, g, b, a));
+        Pix::from(pm)
+    };
+    let rgba = |p: &Pix| extract_rgba(p.get_pixel_unchecked(0, 0));
+    let check = |rp: &mut RegParams, p: &Pix, want: (u8, u8, u8, u8)| {
+        let got = rgba(p);
+        rp.compare_values(want.0 as f64, got.0 as f64, 1.0);
+        rp.compare_values(want.1 as f64, got.1 as f64, 1.0);
+        rp.compare_values(want.2 as f64, got.2 as f64, 1.0);
+        rp.compare_values(want.3 as f64, got.3 as f64, 0.0);
+    };
+
+    let red = single(255, 0, 0, 200);
+    check(&mut rp, &shift_hue(&red, 120.0).unwrap(), (0, 255, 0, 200));
+    check(&mut rp, &shift_hue(&red, -120.0).unwrap(), (0, 0, 255, 200));
+    check(&mut rp, &shift_hue(&red, 480.0).unwrap(), (0, 255, 0, 200));
+    check(&mut rp, &shift_hue(&red, 360.0).unwrap(), (255, 0, 0, 200));
+
+    // Saturation: 0 grays out, 1 is identity, 0.5 halves the chroma
+    let orange = single(200, 100, 0, 255);
+    check(
+        &mut rp,
+        &scale_saturation(&orange, 0.0).unwrap(),
+        (200, 200, 200, 255),
+    );
+    check(
+        &mut rp,
+        &scale_saturation(&orange, 1.0).unwrap(),
+        (200, 100, 0, 255),
+    );
+    check(
+        &mut rp,
+        &scale_saturation(&orange, 0.5).unwrap(),
+        (200, 150, 100, 255),
+    );
+
+    rp.compare_values(
+        1.0,
+        scale_saturation(&orange, -1.0).is_err() as u8 as f64,
+        0.0,
+    );
+    let gray = Pix::new(4, 4, PixelDepth::Bit8).unwrap();
+    rp.compare_values(1.0, shift_hue(&gray, 10.0).is_err() as u8 as f64, 0.0);
+
+    assert!(
+        rp.cleanup(),
+        "enhance shift_hue/scale_saturation test failed"
+    );
+}
@@ -0,0 +1,71 @@
+//! Guided filter regression test
+//!
+//! Tests self-guided smoothing of flat regions with edge preservation
+//! (compared qualitatively with the bilateral filter), cross-guided
+//! filtering and parameter validation.
+
+use crate::common::{RegParams, column_stats, make_edge_image};
+use leptonica::filter::{add_gaussian_noise, bilateral_exact, guided_filter};
+use leptonica::{Pix, PixelDepth};
+
+#[test]
+fn guided_reg_self_guided() {
+    let mut rp = RegParams::new("guided_self");
+
+    let noisy = add_gaussian_noise(&make_edge_image(), 10.0).unwrap();
+    let (_, noisy_sd) = column_stats(&noisy, 4, 28);
+
+    let guided = guided_filter(&noisy, &noisy, 8, 400.0).unwrap();
+    let bilateral = bilateral_exact(&noisy, 4.0, 30.0).unwrap();
+    rp.compare_values(64.0, guided.width() as f64, 0.0);
+    rp.compare_values(64.0, guided.height() as f64, 0.0);
+
+    for (pix, name) in [(&guided, "guided"), (&bilateral, "bilateral")] {
+        // Flat areas are smoothed
+        let (left_mean, left_sd) = column_stats(pix, 4, 28);
+        let (right_mean, right_sd) = column_stats(pix, 36, 60);
+        assert!(left_sd < noisy_sd / 2.0, "{name}: left sd {left_sd}");
+        assert!(right_sd < noisy_sd / 2.0, "{name}: right sd {right_sd}");
+        rp.compare_values(60.0, left_mean, 3.0);
+        rp.compare_values(190.0, right_mean, 3.0);
+
+        // The edge keeps most of its contrast
+        let (edge_left, _) = column_stats(pix, 31, 32);
+        let (edge_right, _) = column_stats(pix, 32, 33);
+        assert!(edge_right - edge_left > 100.0, "{name}: edge contrast");
+    }
+
+    // A tiny epsilon keeps the image close to the input
+    let identity = guided_filter(&noisy, &noisy, 4, 0.01).unwrap();
+    let (_, identity_sd) = column_stats(&identity, 4, 28);
+    rp.compare_values(noisy_sd, identity_sd, 0.5);
+
+    assert!(rp.cleanup(), "guided self test failed");
+}
+
+#[test]
+fn guided_reg_cross_guided_and_params() {
+    let mut rp = RegParams::new("guided_cross");
+
+    // Filtering a flat image with an edge guide leaves it flat
+    let mut pm = Pix::new(64, 64, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..64 {
+        for x in 0..64 {
+            pm.set_pixel_unchecked(x, y, 128);
+        }
+    }
+    let flat: Pix = pm.into();
+    let guide = make_edge_image();
+    let out = guided_filter(&flat, &guide, 6, 100.0).unwrap();
+    let (mean, sd) = column_stats(&out, 0, 64);
+    rp.compare_values(128.0, mean, 0.5);
+    rp.compare_values(0.0, sd, 0.5);
+
+    assert!(guided_filter(&flat, &guide, 6, 0.0).is_err());
+    let small = Pix::new(32, 64, PixelDepth::Bit8).unwrap();
+    assert!(guided_filter(&flat, &small, 6, 100.0).is_err());
+    let pix32 = Pix::new(64, 64, PixelDepth::Bit32).unwrap();
+    assert!(guided_filter(&pix32, &pix32, 6, 100.0).is_err());
+
+    assert!(rp.cleanup(), "guided cross test failed");
+}
@@ -15,9 +15,11 @@ mod convolve_reg;
 mod edge_reg;
 mod enhance_reg;
 mod extend_replication_reg;
+mod guided_reg;
 mod kernel_reg;
 mod locminmax_reg;
 mod lowsat_reg;
+mod nlm_reg;
 mod rank_reg;
 mod rankbin_reg;
 mod rankhisto_reg;
@@ -0,0 +1,90 @@
+//! Non-local means denoising regression test
+//!
+//! Tests noise reduction on flat regions, edge preservation, the sampled
+//! search, 32 bpp input and parameter validation.
+
+use crate::common::{RegParams, column_stats, make_edge_image};
+use leptonica::core::pixel;
+use leptonica::filter::{add_gaussian_noise, nlm_denoise, nlm_denoise_sampled};
+use leptonica::{Pix, PixelDepth};
+
+#[test]
+fn nlm_reg_gray() {
+    let mut rp = RegParams::new("nlm_gray");
+
+    let clean = make_edge_image();
+    let noisy = add_gaussian_noise(&clean, 12.0).unwrap();
+    let (_, noisy_sd) = column_stats(&noisy, 4, 28);
+
+    let denoised = nlm_denoise(&noisy, 12.0, 7, 21).unwrap();
+    rp.compare_values(64.0, denoised.width() as f64, 0.0);
+    rp.compare_values(64.0, denoised.height() as f64, 0.0);
+
+    // Noise on the flat regions is strongly reduced
+    let (left_mean, left_sd) = column_stats(&denoised, 4, 28);
+    let (right_mean, right_sd) = column_stats(&denoised, 36, 60);
+    rp.compare_values(1.0, (left_sd < noisy_sd / 3.0) as u8 as f64, 0.0);
+    rp.compare_values(1.0, (right_sd < noisy_sd / 3.0) as u8 as f64, 0.0);
+    rp.compare_values(60.0, left_mean, 3.0);
+    rp.compare_values(190.0, right_mean, 3.0);
+
+    // The edge stays sharp: the columns on either side keep their levels
+    let (edge_left, _) = column_stats(&denoised, 31, 32);
+    let (edge_right, _) = column_stats(&denoised, 32, 33);
+    rp.compare_values(60.0, edge_left, 8.0);
+    rp.compare_values(190.0, edge_right, 8.0);
+
+    // Sampled search still denoises
+    let sampled = nlm_denoise_sampled(&noisy, 12.0, 7, 21, 2).unwrap();
+    let (_, sampled_sd) = column_stats(&sampled, 4, 28);
+    rp.compare_values(1.0, (sampled_sd < noisy_sd / 2.0) as u8 as f64, 0.0);
+
+    assert!(rp.cleanup(), "nlm gray test failed");
+}
+
+#[test]
+fn nlm_reg_color_and_params() {
+    let mut rp = RegParams::new("nlm_color");
+
+    let mut pm = Pix::new(48, 48, PixelDepth::Bit32).unwrap().to_mut();
+    for y in 0..48 {
+        for x in 0..48 {
+            let val = if x < 24 {
+                pixel::compose_rgb(200, 40, 40)
+            } else {
+                pixel::compose_rgb(30, 90, 220)
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    let clean: Pix = pm.into();
+    let noisy = add_gaussian_noise(&clean, 10.0).unwrap();
+    let denoised = nlm_denoise(&noisy, 10.0, 5, 15).unwrap();
+    rp.compare_values(32.0, denoised.depth().bits() as f64, 0.0);
+
+    // Mean absolute error against the clean image drops
+    let mae = |pix: &Pix| {
+        let mut total = 0.0;
+        for y in 0..48 {
+            for x in 0..48 {
+                let (r0, g0, b0) = pixel::extract_rgb(clean.get_pixel_unchecked(x, y));
+                let (r, g, b) = pixel::extract_rgb(pix.get_pixel_unchecked(x, y));
+                total += (r as f64 - r0 as f64).abs()
+                    + (g as f64 - g0 as f64).abs()
+                    + (b as f64 - b0 as f64).abs();
+            }
+        }
+        total / (48.0 * 48.0 * 3.0)
+    };
+    rp.compare_values(1.0, (mae(&denoised) < mae(&noisy) / 2.0) as u8 as f64, 0.0);
+
+    let gray = make_edge_image();
+    assert!(nlm_denoise(&gray, 0.0, 7, 21).is_err());
+    assert!(nlm_denoise(&gray, 10.0, 6, 21).is_err());
+    assert!(nlm_denoise(&gray, 10.0, 7, 20).is_err());
+    assert!(nlm_denoise_sampled(&gray, 10.0, 7, 21, 0).is_err());
+    let pix1 = Pix::new(16, 16, PixelDepth::Bit1).unwrap();
+    assert!(nlm_denoise(&pix1, 10.0, 7, 21).is_err());
+
+    assert!(rp.cleanup(), "nlm color test failed");
+}
@@ -15,7 +15,9 @@
 
 use crate::common::{RegParams, load_test_image};
 use leptonica::core::pixel;
-use leptonica::filter::{max_filter, median_filter, min_filter, rank_filter, rank_filter_gray};
+use leptonica::filter::{
+    max_filter, median_filter, min_filter, rank_filter, rank_filter_disk, rank_filter_gray,
+};
 use leptonica::{Pix, PixelDepth};
 
 /// Test 0: Basic grayscale rank filter with rank=0.4
@@ -318,6 +320,55 @@ fn rank_reg_convenience_functions() {
     assert!(rp.cleanup(), "rank_convenience regression test failed");
 }
 
+/// Test: rank_filter_disk on a circular neighborhood.
+///
+/// A single bright pixel is removed by a radius-2 disk median, and a
+/// radius-2 disk max spreads it into a disk (not a square).
+#[test]
+fn rank_reg_disk() {
+    let mut rp = RegParams::new("rank_disk");
+
+    let mut pm = Pix::new(15, 15, PixelDepth::Bit8)
+        .unwrap()
+        .try_into_mut()
+        .unwrap();
+    pm.set_all_gray(40).unwrap();
+    pm.set_pixel(7, 7, 250).unwrap();
+    let pix: Pix = pm.into();
+
+    let median = rank_filter_disk(&pix, 2, 0.5).expect("disk median");
+    rp.compare_values(40.0, median.get_pixel(7, 7).unwrap() as f64, 0.0);
+    let mut all_flat = true;
+    for y in 0..15 {
+        for x in 0..15 {
+            all_flat &= median.get_pixel(x, y) == Some(40);
+        }
+    }
+    rp.compare_values(1.0, all_flat as u8 as f64, 0.0);
+
+    let dilated = rank_filter_disk(&pix, 2, 1.0).expect("disk max");
+    rp.compare_values(250.0, dilated.get_pixel(9, 7).unwrap() as f64, 0.0);
+    rp.compare_values(250.0, dilated.get_pixel(8, 8).unwrap() as f64, 0.0);
+    rp.compare_values(40.0, dilated.get_pixel(9, 9).unwrap() as f64, 0.0);
+
+    // 32bpp: each channel is filtered independently.
+    let rgb = pix.convert_to_32().expect("convert to 32");
+    let rgb_median = rank_filter_disk(&rgb, 2, 0.5).expect("disk median rgb");
+    let (r, g, b) = pixel::extract_rgb(rgb_median.get_pixel(7, 7).unwrap());
+    rp.compare_values(40.0, r as f64, 0.0);
+    rp.compare_values(40.0, g as f64, 0.0);
+    rp.compare_values(40.0, b as f64, 0.0);
+
+    // Radius 0 is the identity; invalid inputs are rejected.
+    let same = rank_filter_disk(&pix, 0, 0.5).expect("radius 0");
+    rp.compare_pix(&pix, &same);
+    assert!(rank_filter_disk(&pix, 2, 1.5).is_err());
+    let pix1 = Pix::new(8, 8, PixelDepth::Bit1).unwrap();
+    assert!(rank_filter_disk(&pix1, 2, 0.5).is_err());
+
+    assert!(rp.cleanup(), "rank_disk regression test failed");
+}
+
 /// Test: rank_filter parameter validation.
 #[test]
 fn rank_reg_param_validation() {
@@ -1,14 +1,18 @@
 //! File-collection I/O regression test
 //!
-//! Covers `pixa_read_files` and `pixa_write_files`.
+//! Covers `pixa_read_files` and `pixa_write_files`, and the
+//! `read_pta` / `write_pta` / `read_ptaa` / `write_ptaa` point-set files.
 //!
 //! # See also
 //!
 //! C Leptonica: `prog/files_reg.c`
 
 use crate::common::RegParams;
-use leptonica::io::{ImageFormat, pixa_read_files, pixa_write_files, read_image, write_image};
-use leptonica::{Pix, PixelDepth};
+use leptonica::io::{
+    ImageFormat, pixa_read_files, pixa_write_files, read_image, read_pta, read_ptaa, write_image,
+    write_pta, write_ptaa,
+};
+use leptonica::{Pix, PixelDepth, Pta, Ptaa};
 
 #[test]
 fn files_reg() {
@@ -60,3 +64,57 @@ fn files_reg() {
     std::fs::remove_dir_all(&base).ok();
     assert!(rp.cleanup(), "files regression test failed");
 }
+
+/// Test Pta/Ptaa file round-trips.
+#[test]
+fn files_reg_pta() {
+    let mut rp = RegParams::new("files_pta");
+
+    let base = std::env::temp_dir().join("leptonica_files_reg_pta");
+    std::fs::create_dir_all(&base).expect("create dir");
+
+    // Fractional coordinates, including values without a short decimal form
+    let mut pta = Pta::new();
+    pta.push(0.5, -1.25);
+    pta.push(1.0 / 3.0, 2.0f32.sqrt());
+    pta.push(12345.678, 1e-6);
+    pta.push(-0.0, 100.0);
+
+    let pta_path = base.join("points.pta");
+    write_pta(&pta, &pta_path).expect("write_pta");
+    let pta2 = read_pta(&pta_path).expect("read_pta");
+    rp.compare_values(pta.len() as f64, pta2.len() as f64, 0.0);
+    for i in 0..pta.len() {
+        let (x1, y1) = pta.get(i).unwrap();
+        let (x2, y2) = pta2.get(i).unwrap();
+        rp.compare_values(x1 as f64, x2 as f64, f32::EPSILON as f64 * x1.abs() as f64);
+        rp.compare_values(y1 as f64, y2 as f64, f32::EPSILON as f64 * y1.abs() as f64);
+    }
+
+    // Ptaa keeps the grouping, including an empty Pta
+    let mut ptaa = Ptaa::new();
+    ptaa.push(pta.clone());
+    ptaa.push(Pta::new());
+    let mut line = Pta::new();
+    for i in 0..5 {
+        line.push(i as f32 * 0.1, 7.75);
+    }
+    ptaa.push(line);
+
+    let ptaa_path = base.join("groups.ptaa");
+    write_ptaa(&ptaa, &ptaa_path).expect("write_ptaa");
+    let ptaa2 = read_ptaa(&ptaa_path).expect("read_ptaa");
+    rp.compare_values(3.0, ptaa2.len() as f64, 0.0);
+    for i in 0..ptaa.len() {
+        let (a, b) = (ptaa.get(i).unwrap(), ptaa2.get(i).unwrap());
+        rp.compare_values(a.len() as f64, b.len() as f64, 0.0);
+        let same = a.iter().zip(b.iter()).all(|(p, q)| p == q);
+        rp.compare_values(1.0, same as u8 as f64, 0.0);
+    }
+
+    // Missing files are reported as errors
+    assert!(read_pta(base.join("missing.pta")).is_err());
+
+    std::fs::remove_dir_all(&base).ok();
+    assert!(rp.cleanup(), "files pta test failed");
+}
@@ -10,9 +10,14 @@
 //! For depths <= 8 bpp with colormap, GIF roundtrip is lossless.
 //! For 16 bpp, conversion to 8 bpp occurs (lossy).
 //! For 32 bpp, octree quantization occurs (lossy).
+//!
+//! Also covers `write_gif_with_palette`, which writes an
+//! explicit palette and transparent index.
 
 use crate::common::{RegParams, load_test_image, regout_dir};
+use leptonica::io::gif::{read_gif, write_gif_with_palette};
 use leptonica::io::{ImageFormat, read_image, read_image_mem, write_image, write_image_mem};
+use leptonica::{Pix, PixColormap, PixelDepth};
 use std::fs;
 
 const FILE_1BPP: &str = "feyn.tif";
@@ -208,3 +213,72 @@ fn gifio_reg_source_vs_gif_exact() {
     // impossible without a custom GIF implementation.
     eprintln!("SKIP: Source-vs-GIF exact comparison not supported with current gif crate");
 }
+
+/// Test writing with an explicit palette and transparent index.
+#[test]
+fn gifio_reg_palette_transparency() {
+    let mut rp = RegParams::new("gifio_palette");
+
+    let colors: [(u8, u8, u8); 6] = [
+        (0, 0, 0),
+        (255, 0, 255),
+        (200, 30, 30),
+        (30, 200, 30),
+        (30, 30, 200),
+        (250, 250, 250),
+    ];
+    let mut palette = PixColormap::new(8).unwrap();
+    for &(r, g, b) in &colors {
+        palette.add_rgb(r, g, b).unwrap();
+    }
+
+    let mut pm = Pix::new(24, 16, PixelDepth::Bit8).unwrap().to_mut();
+    pm.set_colormap(Some(palette.clone())).unwrap();
+    for y in 0..16 {
+        for x in 0..24 {
+            pm.set_pixel(x, y, (x / 4 + y) % 6).unwrap();
+        }
+    }
+    let pix: Pix = pm.into();
+
+    // Index 1 (magenta) is the sprite background
+    let mut buf = Vec::new();
+    write_gif_with_palette(&pix, &mut buf, &palette, Some(1)).expect("write");
+    let pix2 = read_gif(buf.as_slice()).expect("read");
+
+    // Pixel indices and palette colors are preserved; the palette is
+    // padded to a power of 2, and the reader picks the depth from its size
+    rp.compare_values(4.0, pix2.depth().bits() as f64, 0.0);
+    let same = (0..16).all(|y| (0..24).all(|x| pix2.get_pixel(x, y) == pix.get_pixel(x, y)));
+    rp.compare_values(1.0, same as u8 as f64, 0.0);
+    let cmap = pix2.colormap().expect("colormap");
+    rp.compare_values(8.0, cmap.len() as f64, 0.0);
+    for (i, &rgb) in colors.iter().enumerate() {
+        assert_eq!(cmap.get_rgb(i), Some(rgb), "palette entry {i}");
+    }
+
+    // The transparent index comes back as the only non-opaque entry
+    rp.compare_values(0.0, cmap.get_rgba(1).unwrap().3 as f64, 0.0);
+    let opaque = (0..cmap.len())
+        .filter(|&i| i != 1)
+        .all(|i| cmap.get_rgba(i).unwrap().3 == 255);
+    rp.compare_values(1.0, opaque as u8 as f64, 0.0);
+
+    // Without a transparent index every entry is opaque
+    let mut buf = Vec::new();
+    write_gif_with_palette(&pix, &mut buf, &palette, None).expect("write opaque");
+    let pix3 = read_gif(buf.as_slice()).expect("read opaque");
+    rp.compare_values(1.0, pix3.colormap().unwrap().is_opaque() as u8 as f64, 0.0);
+
+    // Invalid inputs
+    let mut sink = Vec::new();
+    assert!(write_gif_with_palette(&pix, &mut sink, &palette, Some(6)).is_err());
+    let mut small = PixColormap::new(8).unwrap();
+    small.add_rgb(0, 0, 0).unwrap();
+    small.add_rgb(255, 255, 255).unwrap();
+    assert!(write_gif_with_palette(&pix, &mut sink, &small, None).is_err());
+    let gray = Pix::new(4, 4, PixelDepth::Bit8).unwrap();
+    assert!(write_gif_with_palette(&gray, &mut sink, &palette, None).is_err());
+
+    assert!(rp.cleanup(), "gifio palette test failed");
+}
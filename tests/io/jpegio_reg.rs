@@ -9,6 +9,11 @@
 //! - JPEG -> PNG roundtrip preserves dimensions
 //! - JPEG write and read-back roundtrip
 //! - Header reading (resolution, comment) -- not ported
+//! - Colormapped input is expanded through the palette before encoding
+//!
+//! Also covers reduced-size decoding in `read_jpeg_scaled`
+//! and `read_image_scaled`, and the region-adaptive quality writer
+//! `write_jpeg_region_adaptive`.
 
 use crate::common::{RegParams, load_test_image, test_data_path};
 use leptonica::io::{ImageFormat, read_image_header, read_image_mem, write_image_mem};
@@ -116,6 +121,201 @@ fn jpegio_write_reg() {
     assert!(rp.cleanup(), "jpegio_write regression test failed");
 }
 
+/// Test decoding at reduced size for previews.
+#[test]
+fn jpegio_reg_read_scaled() {
+    use leptonica::io::{jpeg::read_jpeg_scaled, read_image_scaled, write_image};
+
+    let mut rp = RegParams::new("jpegio_scaled");
+
+    // A 4000 x 2000 gradient, written as JPEG and PNG
+    let mut pm = leptonica::Pix::new(4000, 2000, leptonica::PixelDepth::Bit8)
+        .unwrap()
+        .to_mut();
+    for y in 0..2000 {
+        for x in 0..4000 {
+            pm.set_pixel_unchecked(x, y, (x / 16 + y / 16) % 256);
+        }
+    }
+    let pix: leptonica::Pix = pm.into();
+    let dir = std::env::temp_dir().join("leptonica_jpegio_scaled");
+    std::fs::create_dir_all(&dir).expect("create dir");
+    let jpg_path = dir.join("big.jpg");
+    write_image(&pix, &jpg_path, ImageFormat::Jpeg).expect("write jpeg");
+
+    // DCT scaling by 1/8 gives the smallest decode of at least 500 px
+    let data = std::fs::read(&jpg_path).expect("read jpeg bytes");
+    let decoded = read_jpeg_scaled(data.as_slice(), 500).expect("read_jpeg_scaled");
+    rp.compare_values(500.0, decoded.width() as f64, 0.0);
+    rp.compare_values(250.0, decoded.height() as f64, 0.0);
+
+    // A slightly larger target needs the 1/4 reduction
+    let decoded = read_jpeg_scaled(data.as_slice(), 600).expect("read_jpeg_scaled 600");
+    rp.compare_values(1000.0, decoded.width() as f64, 0.0);
+
+    let preview = read_image_scaled(&jpg_path, 500).expect("read_image_scaled jpeg");
+    let max_dim = preview.width().max(preview.height());
+    rp.compare_values(1.0, (500..=1000).contains(&max_dim) as u8 as f64, 0.0);
+
+    // Targets larger than the image decode at full size
+    let full = read_jpeg_scaled(data.as_slice(), 5000).expect("full size");
+    rp.compare_values(4000.0, full.width() as f64, 0.0);
+
+    // Other formats are decoded and scaled to at most 2x the target
+    let small = pix.clip_rectangle(0, 0, 1200, 600).unwrap();
+    let png_path = dir.join("small.png");
+    write_image(&small, &png_path, ImageFormat::Png).expect("write png");
+    let preview = read_image_scaled(&png_path, 200).expect("read_image_scaled png");
+    rp.compare_values(400.0, preview.width() as f64, 1.0);
+    rp.compare_values(200.0, preview.height() as f64, 1.0);
+
+    assert!(read_image_scaled(&png_path, 0).is_err());
+
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(rp.cleanup(), "jpegio scaled read test failed");
+}
+
+/// Colormapped images are written with their palette colors, not indices.
+#[test]
+fn jpegio_reg_write_colormapped() {
+    use leptonica::io::jpeg::{JpegOptions, read_jpeg, write_jpeg};
+    use leptonica::{Pix, PixColormap, PixelDepth};
+
+    let mut rp = RegParams::new("jpegio_cmap");
+
+    for depth in [
+        PixelDepth::Bit1,
+        PixelDepth::Bit2,
+        PixelDepth::Bit4,
+        PixelDepth::Bit8,
+    ] {
+        // Index 1 is pure red; index 0 is black
+        let mut cmap = PixColormap::new(depth.bits()).unwrap();
+        cmap.add_rgb(0, 0, 0).unwrap();
+        cmap.add_rgb(255, 0, 0).unwrap();
+        let mut pm = Pix::new(32, 32, depth).unwrap().to_mut();
+        pm.set_colormap(Some(cmap)).unwrap();
+        for y in 0..32 {
+            for x in 16..32 {
+                pm.set_pixel_unchecked(x, y, 1);
+            }
+        }
+        let pix: Pix = pm.into();
+
+        let mut buf = Vec::new();
+        write_jpeg(&pix, &mut buf, &JpegOptions { quality: 95 }).expect("write cmap jpeg");
+        let back = read_jpeg(buf.as_slice()).expect("read cmap jpeg");
+        rp.compare_values(32.0, back.depth().bits() as f64, 0.0);
+        let (r, g, b) = leptonica::core::pixel::extract_rgb(back.get_pixel_unchecked(24, 16));
+        rp.compare_values(255.0, r as f64, 8.0);
+        rp.compare_values(0.0, g as f64, 8.0);
+        rp.compare_values(0.0, b as f64, 8.0);
+    }
+
+    // Gray palettes (including a 1 bpp black/white one) are written as
+    // grayscale with the palette values
+    for (depth, dark, light) in [(PixelDepth::Bit8, 10, 200), (PixelDepth::Bit1, 255, 0)] {
+        let mut cmap = PixColormap::new(depth.bits()).unwrap();
+        cmap.add_rgb(dark, dark, dark).unwrap();
+        cmap.add_rgb(light, light, light).unwrap();
+        let mut pm = Pix::new(32, 32, depth).unwrap().to_mut();
+        pm.set_colormap(Some(cmap)).unwrap();
+        for y in 0..32 {
+            for x in 16..32 {
+                pm.set_pixel_unchecked(x, y, 1);
+            }
+        }
+        let pix: Pix = pm.into();
+        let mut buf = Vec::new();
+        write_jpeg(&pix, &mut buf, &JpegOptions { quality: 95 }).expect("write gray cmap jpeg");
+        let back = read_jpeg(buf.as_slice()).expect("read gray cmap jpeg");
+        rp.compare_values(8.0, back.depth().bits() as f64, 0.0);
+        rp.compare_values(light as f64, back.get_pixel_unchecked(24, 16) as f64, 4.0);
+        rp.compare_values(dark as f64, back.get_pixel_unchecked(4, 16) as f64, 4.0);
+    }
+
+    assert!(rp.cleanup(), "jpegio colormapped write test failed");
+}
+
+/// Region-adaptive quality keeps the boxed region sharper than the rest
+/// and costs fewer bytes than encoding everything at the top quality.
+#[test]
+fn jpegio_reg_region_adaptive() {
+    use leptonica::io::jpeg::{JpegOptions, read_jpeg, write_jpeg, write_jpeg_region_adaptive};
+    use leptonica::{Box, Boxa, Pix, PixelDepth};
+
+    let mut rp = RegParams::new("jpegio_adaptive");
+
+    // Left half: "text" strokes; right half: halftone dots
+    let mut pm = Pix::new(128, 64, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..64 {
+        for x in 0..128 {
+            let dark = if x < 64 {
+                x % 6 < 2 || y % 12 < 2
+            } else {
+                x % 4 < 2 && y % 4 < 2
+            };
+            pm.set_pixel_unchecked(x, y, if dark { 20 } else { 235 });
+        }
+    }
+    let pix: Pix = pm.into();
+    let mut text_boxes = Boxa::new();
+    text_boxes.push(Box::new(0, 0, 64, 64).unwrap());
+
+    let mut buf = Vec::new();
+    write_jpeg_region_adaptive(&pix, &text_boxes, &[95], 30, &mut buf).expect("adaptive");
+    let back = read_jpeg(buf.as_slice()).expect("read adaptive");
+    rp.compare_values(128.0, back.width() as f64, 0.0);
+
+    let text_ssim = ssim_region(&pix, &back, 0, 64);
+    let bg_ssim = ssim_region(&pix, &back, 64, 128);
+    eprintln!("  text SSIM = {text_ssim:.3}, background SSIM = {bg_ssim:.3}");
+    rp.compare_values(1.0, (text_ssim > 0.95) as u8 as f64, 0.0);
+    rp.compare_values(1.0, (text_ssim > bg_ssim + 0.2) as u8 as f64, 0.0);
+
+    let mut full = Vec::new();
+    write_jpeg(&pix, &mut full, &JpegOptions { quality: 95 }).expect("write q95");
+    rp.compare_values(1.0, (buf.len() < full.len()) as u8 as f64, 0.0);
+
+    // One quality per box, each in 1..=100
+    let mut sink = Vec::new();
+    rp.compare_values(
+        1.0,
+        write_jpeg_region_adaptive(&pix, &text_boxes, &[], 30, &mut sink).is_err() as u8 as f64,
+        0.0,
+    );
+    rp.compare_values(
+        1.0,
+        write_jpeg_region_adaptive(&pix, &text_boxes, &[95], 0, &mut sink).is_err() as u8 as f64,
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "jpegio region-adaptive test failed");
+}
+
+/// Global SSIM of two 8 bpp images over the columns `[x0, x1)`
+fn ssim_region(a: &leptonica::Pix, b: &leptonica::Pix, x0: u32, x1: u32) -> f64 {
+    let (c1, c2) = ((0.01f64 * 255.0).powi(2), (0.03f64 * 255.0).powi(2));
+    let mut va = Vec::new();
+    let mut vb = Vec::new();
+    for y in 0..a.height() {
+        for x in x0..x1 {
+            va.push(a.get_pixel_unchecked(x, y) as f64);
+            vb.push(b.get_pixel_unchecked(x, y) as f64);
+        }
+    }
+    let n = va.len() as f64;
+    let (ma, mb) = (va.iter().sum::<f64>() / n, vb.iter().sum::<f64>() / n);
+    let (mut sa, mut sb, mut sab) = (0.0, 0.0, 0.0);
+    for (p, q) in va.iter().zip(&vb) {
+        sa += (p - ma).powi(2);
+        sb += (q - mb).powi(2);
+        sab += (p - ma) * (q - mb);
+    }
+    let (sa, sb, sab) = (sa / n, sb / n, sab / n);
+    ((2.0 * ma * mb + c1) * (2.0 * sab + c2)) / ((ma * ma + mb * mb + c1) * (sa + sb + c2))
+}
+
 fn test_jpeg_read(rp: &mut RegParams, fname: &str) {
     let pix = match load_test_image(fname) {
         Ok(p) => p,
@@ -37,4 +37,6 @@ mod writetext_reg;
 
 mod convertfiles_reg;
 mod io_coverage_reg;
+mod npyio_reg;
 mod partify_reg;
+mod rawio_reg;
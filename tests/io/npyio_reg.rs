@@ -0,0 +1,109 @@
+//! NumPy .npy I/O regression test
+//!
+//! Tests write/read round-trips at 8, 16 and 32 bpp, colormap expansion,
+//! the on-disk header layout, and rejection of malformed input.
+
+use crate::common::{RegParams, load_test_image};
+use leptonica::io::{read_npy, read_npy_file, write_npy, write_npy_file};
+use leptonica::{Pix, PixelDepth};
+use std::io::Cursor;
+
+fn npy_roundtrip(pix: &Pix) -> Pix {
+    let mut buf = Vec::new();
+    write_npy(pix, &mut buf).expect("write npy");
+    read_npy(Cursor::new(&buf)).expect("read npy")
+}
+
+#[test]
+fn npyio_reg_8bpp_roundtrip() {
+    let mut rp = RegParams::new("npyio_8bpp");
+
+    let pix = load_test_image("test8.jpg").expect("load test8.jpg");
+    let mut buf = Vec::new();
+    write_npy(&pix, &mut buf).expect("write npy");
+
+    // Header: magic, version 1.0, uint8 (h, w) array
+    assert!(buf.starts_with(b"\x93NUMPY\x01\x00"));
+    let header_len = u16::from_le_bytes([buf[8], buf[9]]) as usize;
+    let header = std::str::from_utf8(&buf[10..10 + header_len]).unwrap();
+    assert!(header.contains("'descr': '|u1'"));
+    let shape = format!("'shape': ({}, {})", pix.height(), pix.width());
+    assert!(header.contains(&shape), "header: {header}");
+    rp.compare_values(
+        (10 + header_len + (pix.width() * pix.height()) as usize) as f64,
+        buf.len() as f64,
+        0.0,
+    );
+
+    let back = read_npy(Cursor::new(&buf)).expect("read npy");
+    rp.compare_values(pix.width() as f64, back.width() as f64, 0.0);
+    rp.compare_values(pix.height() as f64, back.height() as f64, 0.0);
+    assert_eq!(back.depth(), PixelDepth::Bit8);
+    rp.compare_pix(&pix, &back);
+
+    // File-based round-trip
+    let path = std::env::temp_dir().join("leptonica_npyio_reg_8bpp.npy");
+    write_npy_file(&pix, &path).expect("write npy file");
+    let from_file = read_npy_file(&path).expect("read npy file");
+    let _ = std::fs::remove_file(&path);
+    rp.compare_pix(&pix, &from_file);
+
+    assert!(rp.cleanup(), "npyio 8bpp test failed");
+}
+
+#[test]
+fn npyio_reg_16_and_32bpp_roundtrip() {
+    let mut rp = RegParams::new("npyio_16_32bpp");
+
+    let mut pm = Pix::new(9, 5, PixelDepth::Bit16).unwrap().to_mut();
+    for y in 0..5 {
+        for x in 0..9 {
+            pm.set_pixel(x, y, x * 7000 + y).unwrap();
+        }
+    }
+    let pix16: Pix = pm.into();
+    let back16 = npy_roundtrip(&pix16);
+    assert_eq!(back16.depth(), PixelDepth::Bit16);
+    rp.compare_pix(&pix16, &back16);
+
+    // RGB (spp = 3) is stored as (h, w, 3)
+    let rgb = load_test_image("weasel32.png").expect("load weasel32.png");
+    let back_rgb = npy_roundtrip(&rgb);
+    assert_eq!(back_rgb.depth(), PixelDepth::Bit32);
+    rp.compare_values(3.0, back_rgb.spp() as f64, 0.0);
+    rp.compare_pix(&rgb, &back_rgb);
+
+    // RGBA (spp = 4) keeps alpha as (h, w, 4)
+    let rgba = load_test_image("test32-alpha.png").expect("load test32-alpha.png");
+    let back_rgba = npy_roundtrip(&rgba);
+    rp.compare_values(4.0, back_rgba.spp() as f64, 0.0);
+    assert!(rgba.equals_with_alpha(&back_rgba, true));
+
+    assert!(rp.cleanup(), "npyio 16/32bpp test failed");
+}
+
+#[test]
+fn npyio_reg_colormap_and_errors() {
+    let mut rp = RegParams::new("npyio_cmap");
+
+    // Colormapped input is expanded to RGB
+    let cmapped = load_test_image("weasel8.240c.png").expect("load weasel8.240c.png");
+    assert!(cmapped.has_colormap());
+    let back = npy_roundtrip(&cmapped);
+    assert_eq!(back.depth(), PixelDepth::Bit32);
+    assert!(!back.has_colormap());
+    rp.compare_values(1.0, cmapped.equals_with_cmap(&back) as u8 as f64, 0.0);
+
+    // 1 bpp without a colormap has no npy mapping
+    let pix1 = Pix::new(8, 8, PixelDepth::Bit1).unwrap();
+    assert!(write_npy(&pix1, Vec::new()).is_err());
+
+    // Malformed input
+    assert!(read_npy(Cursor::new(b"not an npy file")).is_err());
+    let mut buf = Vec::new();
+    write_npy(&Pix::new(4, 4, PixelDepth::Bit8).unwrap(), &mut buf).unwrap();
+    buf.truncate(buf.len() - 1);
+    assert!(read_npy(Cursor::new(&buf)).is_err());
+
+    assert!(rp.cleanup(), "npyio colormap test failed");
+}
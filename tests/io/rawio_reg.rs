@@ -0,0 +1,92 @@
+//! Raw pixel dump I/O regression test
+//!
+//! Tests write/read round-trips at several depths, the packed data size,
+//! and rejection of truncated input.
+
+use crate::common::{RegParams, load_test_image};
+use leptonica::io::{read_raw, write_raw};
+use leptonica::{Pix, PixelDepth, TestPattern};
+use std::io::Cursor;
+
+#[test]
+fn rawio_reg_8bpp_roundtrip() {
+    let mut rp = RegParams::new("rawio_8bpp");
+
+    let pix = load_test_image("test8.jpg").expect("load test8.jpg");
+    let mut buf = Vec::new();
+    write_raw(&pix, &mut buf).expect("write raw");
+    // One byte per pixel, no header and no row padding
+    rp.compare_values((pix.width() * pix.height()) as f64, buf.len() as f64, 0.0);
+
+    let back = read_raw(
+        Cursor::new(&buf),
+        pix.width(),
+        pix.height(),
+        PixelDepth::Bit8,
+    )
+    .expect("read raw");
+    rp.compare_pix(&pix, &back);
+
+    // Missing the last byte
+    assert!(
+        read_raw(
+            Cursor::new(&buf[..buf.len() - 1]),
+            pix.width(),
+            pix.height(),
+            PixelDepth::Bit8
+        )
+        .is_err()
+    );
+    assert!(read_raw(Cursor::new(&buf), 0, pix.height(), PixelDepth::Bit8).is_err());
+
+    assert!(rp.cleanup(), "rawio 8bpp test failed");
+}
+
+#[test]
+fn rawio_reg_other_depths() {
+    let mut rp = RegParams::new("rawio_depths");
+
+    let roundtrip = |pix: &Pix| {
+        let mut buf = Vec::new();
+        write_raw(pix, &mut buf).expect("write raw");
+        let back =
+            read_raw(Cursor::new(&buf), pix.width(), pix.height(), pix.depth()).expect("read raw");
+        (buf.len(), back)
+    };
+
+    // 16 bpp: two big-endian bytes per pixel
+    let mut pm = Pix::new(5, 3, PixelDepth::Bit16).unwrap().to_mut();
+    pm.set_pixel_unchecked(0, 0, 0x1234);
+    pm.set_pixel_unchecked(4, 2, 0xfedc);
+    let pix16: Pix = pm.into();
+    let mut buf = Vec::new();
+    write_raw(&pix16, &mut buf).unwrap();
+    rp.compare_values(30.0, buf.len() as f64, 0.0);
+    assert_eq!(&buf[..2], &[0x12, 0x34]);
+    let (_, back) = roundtrip(&pix16);
+    rp.compare_pix(&pix16, &back);
+
+    // 32 bpp: R, G, B, A
+    let pix32 = load_test_image("weasel32.png").expect("load weasel32.png");
+    let (len, back) = roundtrip(&pix32);
+    rp.compare_values((4 * pix32.width() * pix32.height()) as f64, len as f64, 0.0);
+    rp.compare_pix(&pix32, &back);
+
+    // 1 bpp: rows padded to whole bytes
+    let pix1 = Pix::test_pattern(
+        TestPattern::Checkerboard { cell: 3 },
+        13,
+        7,
+        PixelDepth::Bit1,
+    )
+    .unwrap();
+    let (len, back) = roundtrip(&pix1);
+    rp.compare_values(
+        (pix1.width().div_ceil(8) * pix1.height()) as f64,
+        len as f64,
+        0.0,
+    );
+    rp.compare_pix(&pix1, &back);
+
+    assert!(rp.cleanup(), "rawio depths test failed");
+}
@@ -13,11 +13,17 @@
 //!   5: compare_pix open vs sequence "o7.7"
 //!   6: write_pix_and_check close_color
 //!   7: compare_pix close vs sequence "c7.7"
+//!
+//! Also tests morph_gradient_color with a general Sel.
 
 use crate::common::{RegParams, load_test_image};
 use leptonica::PixelDepth;
+use leptonica::core::pix::RgbComponent;
 use leptonica::io::ImageFormat;
-use leptonica::morph::{close_color, color_morph_sequence, dilate_color, erode_color, open_color};
+use leptonica::morph::{
+    ColorChannel, Sel, close_color, color_morph_sequence, dilate_color, erode_color, gradient_gray,
+    morph_gradient_color, open_color,
+};
 
 const SIZE: u32 = 7;
 
@@ -116,3 +122,56 @@ fn colormorph_reg() {
 
     assert!(rp.cleanup(), "colormorph regression test failed");
 }
+
+/// Gradient on a single channel matches the grayscale gradient of that
+/// channel; the other channels are zero.
+#[test]
+fn colormorph_reg_gradient_channel() {
+    let mut rp = RegParams::new("colormorph_gradient");
+
+    // Red ramps along x, green carries unrelated blocks
+    let pix = leptonica::Pix::new(40, 30, PixelDepth::Bit32).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..30 {
+        for x in 0..40 {
+            let r = x * 6;
+            let g = if (x / 10 + y / 10) % 2 == 0 { 200 } else { 0 };
+            pm.set_pixel_unchecked(x, y, leptonica::core::pixel::compose_rgb(r as u8, g, 90));
+        }
+    }
+    let pix: leptonica::Pix = pm.into();
+
+    let sel = Sel::create_brick(5, 5).unwrap();
+    let grad = morph_gradient_color(&pix, &sel, Some(ColorChannel::Red)).unwrap();
+    rp.compare_values(32.0, grad.depth().bits() as f64, 0.0);
+
+    let red = pix.get_rgb_component(RgbComponent::Red).unwrap();
+    let expected = gradient_gray(&red, 5, 5).unwrap();
+    let got_red = grad.get_rgb_component(RgbComponent::Red).unwrap();
+    rp.compare_pix(&expected, &got_red);
+    let got_green = grad.get_rgb_component(RgbComponent::Green).unwrap();
+    rp.compare_values(0.0, got_green.count_pixels() as f64, 0.0);
+    // Interior of the ramp: 4 columns * 6 levels
+    rp.compare_values(24.0, got_red.get_pixel(20, 15).unwrap() as f64, 0.0);
+
+    // All channels: the blue channel is flat and gives no gradient
+    let all = morph_gradient_color(&pix, &sel, None).unwrap();
+    let all_blue = all.get_rgb_component(RgbComponent::Blue).unwrap();
+    rp.compare_values(0.0, all_blue.count_pixels() as f64, 0.0);
+    let all_red = all.get_rgb_component(RgbComponent::Red).unwrap();
+    rp.compare_pix(&expected, &all_red);
+
+    // A Sel with no hits is rejected
+    let empty = Sel::new(3, 3).unwrap();
+    rp.compare_values(
+        1.0,
+        if morph_gradient_color(&pix, &empty, None).is_err() {
+            1.0
+        } else {
+            0.0
+        },
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "colormorph gradient test failed");
+}
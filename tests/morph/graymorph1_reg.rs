@@ -9,6 +9,9 @@
 //!   (5) Contrast enhancement (#[ignore]: requires pixInitAccumulate, Pixacc -- Rust未実装)
 //!   (6) Feynman stamp tophat extraction (#[ignore]: requires pixRemoveColormap etc.)
 //!
+//! Also covers replicated-border `open_gray_safe` /
+//! `close_gray_safe`.
+//!
 //! Run with:
 //! ```
 //! cargo test -p leptonica-morph --test graymorph1_reg
@@ -18,8 +21,8 @@ use crate::common::{RegParams, load_test_image};
 use leptonica::PixelDepth;
 use leptonica::io::ImageFormat;
 use leptonica::morph::{
-    bottom_hat_gray, close_gray, dilate_gray, erode_gray, gray_morph_sequence, h_dome, open_gray,
-    top_hat_gray,
+    bottom_hat_gray, close_gray, close_gray_safe, dilate_gray, erode_gray, gray_morph_sequence,
+    h_dome, open_gray, open_gray_safe, top_hat_gray,
 };
 
 const WSIZE: u32 = 7;
@@ -262,3 +265,54 @@ fn graymorph1_reg_contrast_enhancement() {
 fn graymorph1_reg_feynman_stamp() {
     // C: Test 37-42 -- feynman stamp tophat extraction
 }
+
+/// Test open/close with a replicated border.
+#[test]
+fn graymorph1_reg_safe_open_close() {
+    let mut rp = RegParams::new("graymorph1_safe");
+
+    // A flat image is unchanged everywhere, including its edges
+    let mut pm = leptonica::Pix::new(40, 30, PixelDepth::Bit8)
+        .unwrap()
+        .to_mut();
+    for y in 0..30 {
+        for x in 0..40 {
+            pm.set_pixel_unchecked(x, y, 140);
+        }
+    }
+    let flat: leptonica::Pix = pm.into();
+    let closed = close_gray_safe(&flat, 9, 5).expect("close_gray_safe");
+    rp.compare_pix(&flat, &closed);
+    let opened = open_gray_safe(&flat, 9, 5).expect("open_gray_safe");
+    rp.compare_pix(&flat, &opened);
+
+    // Away from the edges the results match the plain operations
+    let pixs = load_test_image("aneurisms8.jpg").expect("load aneurisms8.jpg");
+    let (w, h) = (pixs.width(), pixs.height());
+    let inner =
+        |p: &leptonica::Pix| p.clip_rectangle(2 * WSIZE, 2 * HSIZE, w - 4 * WSIZE, h - 4 * HSIZE);
+    let safe = close_gray_safe(&pixs, WSIZE, HSIZE).expect("close_gray_safe");
+    let plain = close_gray(&pixs, WSIZE, HSIZE).expect("close_gray");
+    rp.compare_values(w as f64, safe.width() as f64, 0.0);
+    rp.compare_values(h as f64, safe.height() as f64, 0.0);
+    rp.compare_pix(&inner(&plain).unwrap(), &inner(&safe).unwrap());
+    let safe = open_gray_safe(&pixs, WSIZE, HSIZE).expect("open_gray_safe");
+    let plain = open_gray(&pixs, WSIZE, HSIZE).expect("open_gray");
+    rp.compare_pix(&inner(&plain).unwrap(), &inner(&safe).unwrap());
+
+    // Closing is extensive and opening anti-extensive, including at edges
+    let closed = close_gray_safe(&pixs, WSIZE, HSIZE).unwrap();
+    let opened = open_gray_safe(&pixs, WSIZE, HSIZE).unwrap();
+    let ordered = (0..h).all(|y| {
+        (0..w).all(|x| {
+            let v = pixs.get_pixel(x, y).unwrap();
+            opened.get_pixel(x, y).unwrap() <= v && v <= closed.get_pixel(x, y).unwrap()
+        })
+    });
+    rp.compare_values(1.0, ordered as u8 as f64, 0.0);
+
+    let pix1 = leptonica::Pix::new(10, 10, PixelDepth::Bit1).unwrap();
+    assert!(close_gray_safe(&pix1, 3, 3).is_err());
+
+    assert!(rp.cleanup(), "graymorph1 safe open/close test failed");
+}
@@ -6,7 +6,8 @@
 //!
 //! Partial port: Tests dewarp_single_page pipeline, find_textline_centers,
 //! remove_short_lines, and is_line_coverage_valid. Fpix/dpix serialization
-//! and contour rendering are not tested (not public API).
+//! and contour rendering are not tested (not public API). Also covers the
+//! single-line `straighten_line`.
 //!
 //! # See also
 //!
@@ -229,3 +230,56 @@ fn dewarp_reg_second_page() {
 
     assert!(rp.cleanup(), "dewarp second_page test failed");
 }
+
+/// Test `straighten_line`: a sinusoidally warped line is flattened
+/// onto the target row.
+#[test]
+fn dewarp_reg_straighten_line() {
+    use leptonica::recog::dewarp::straighten_line;
+    use leptonica::{Pix, Pta};
+
+    let mut rp = RegParams::new("dewarp_straighten");
+
+    let (w, h) = (200u32, 60u32);
+    let base = |x: u32| 35.0 + 6.0 * (2.0 * std::f32::consts::PI * x as f32 / 100.0).sin();
+
+    // A 10-pixel-high dark band sitting on the curved baseline
+    let mut pm = Pix::new(w, h, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            let b = base(x).round() as i32;
+            let dark = (b - 9..=b).contains(&(y as i32));
+            pm.set_pixel_unchecked(x, y, if dark { 0 } else { 255 });
+        }
+    }
+    let pix: Pix = pm.into();
+
+    let mut baseline = Pta::new();
+    for x in (0..=w).step_by(5) {
+        baseline.push(x as f32, base(x));
+    }
+
+    // Lowest dark row of each column
+    let bottoms = |p: &Pix| -> Vec<i32> {
+        (0..w)
+            .map(|x| {
+                (0..h)
+                    .rev()
+                    .find(|&y| p.get_pixel_unchecked(x, y) < 128)
+                    .map_or(-1, |y| y as i32)
+            })
+            .collect()
+    };
+    let spread = |v: &[i32]| v.iter().max().unwrap() - v.iter().min().unwrap();
+    rp.compare_values(1.0, (spread(&bottoms(&pix)) >= 10) as u8 as f64, 0.0);
+
+    let flat = straighten_line(&pix, &baseline, 35.0).expect("straighten_line");
+    rp.compare_values(w as f64, flat.width() as f64, 0.0);
+    let b = bottoms(&flat);
+    let max_err = b.iter().map(|&y| (y - 35).abs()).max().unwrap();
+    rp.compare_values(0.0, max_err as f64, 1.0);
+
+    assert!(straighten_line(&pix, &Pta::new(), 35.0).is_err());
+
+    assert!(rp.cleanup(), "dewarp straighten_line test failed");
+}
@@ -0,0 +1,142 @@
+//! Document corner detection regression test
+//!
+//! Tests `find_document_corners`, which locates a photographed page for
+//! perspective cropping.
+
+use crate::common::RegParams;
+use leptonica::recog::find_document_corners;
+use leptonica::transform::unwarp_quad;
+use leptonica::{Pix, PixelDepth, Pta};
+
+/// Corners of the synthetic sheet: TL, TR, BR, BL
+const SHEET: [(f32, f32); 4] = [(42.0, 30.0), (250.0, 48.0), (236.0, 215.0), (28.0, 190.0)];
+
+/// Gray photo of a white sheet with a few dark text lines on a dark table.
+fn make_photo(corners: &[(f32, f32); 4]) -> Pix {
+    let inside = |x: f32, y: f32| {
+        (0..4).all(|i| {
+            let (x0, y0) = corners[i];
+            let (x1, y1) = corners[(i + 1) % 4];
+            (x1 - x0) * (y - y0) - (y1 - y0) * (x - x0) >= 0.0
+        })
+    };
+    let mut pm = Pix::new(300, 240, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..240 {
+        for x in 0..300 {
+            let (fx, fy) = (x as f32, y as f32);
+            let val = if !inside(fx, fy) {
+                40 + (x + y) % 7
+            } else if (80..150).contains(&x) && y % 20 < 3 && (60..170).contains(&y) {
+                30
+            } else {
+                235
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    pm.into()
+}
+
+fn check_corners(rp: &mut RegParams, found: &Pta, expected: &[(f32, f32); 4]) {
+    rp.compare_values(4.0, found.len() as f64, 0.0);
+    for (i, &(ex, ey)) in expected.iter().enumerate() {
+        let (x, y) = found.get(i).unwrap();
+        rp.compare_values(ex as f64, x as f64, 3.0);
+        rp.compare_values(ey as f64, y as f64, 3.0);
+    }
+}
+
+#[test]
+fn document_reg_corners() {
+    let mut rp = RegParams::new("document_corners");
+
+    let photo = make_photo(&SHEET);
+    let corners = find_document_corners(&photo)
+        .expect("find_document_corners")
+        .expect("sheet should be found");
+    check_corners(&mut rp, &corners, &SHEET);
+
+    // Same result for a color photo
+    let rgb = photo.convert_to_32().unwrap();
+    let corners32 = find_document_corners(&rgb).unwrap().expect("color sheet");
+    check_corners(&mut rp, &corners32, &SHEET);
+
+    // The corners feed straight into the perspective crop: the result is
+    // the sheet only, with no dark table left around it
+    let page = unwarp_quad(&photo, &corners, 200, 160).unwrap();
+    let mut border_min = 255;
+    for x in 8..192 {
+        border_min = border_min
+            .min(page.get_pixel_unchecked(x, 8))
+            .min(page.get_pixel_unchecked(x, 151));
+    }
+    rp.compare_values(235.0, border_min as f64, 40.0);
+
+    // Strongly rotated sheet: corners still come out in TL, TR, BR, BL order
+    let diamond = [(150.0, 20.0), (270.0, 120.0), (150.0, 220.0), (30.0, 120.0)];
+    let rotated = make_photo(&diamond);
+    let found = find_document_corners(&rotated).unwrap().expect("diamond");
+    let order: Vec<(f32, f32)> = found.iter().collect();
+    let tl = order
+        .iter()
+        .enumerate()
+        .min_by(|a, b| (a.1.0 + a.1.1).total_cmp(&(b.1.0 + b.1.1)))
+        .unwrap()
+        .0;
+    rp.compare_values(0.0, tl as f64, 0.0);
+    // Clockwise on screen: the second corner is to the right of the first
+    rp.compare_values(1.0, (order[1].0 > order[0].0) as u8 as f64, 0.0);
+
+    assert!(rp.cleanup(), "document corners test failed");
+}
+
+#[test]
+fn document_reg_no_document() {
+    let mut rp = RegParams::new("document_none");
+
+    // Uniform image
+    let mut pm = Pix::new(200, 150, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..150 {
+        for x in 0..200 {
+            pm.set_pixel_unchecked(x, y, 120);
+        }
+    }
+    let flat: Pix = pm.into();
+    rp.compare_values(
+        1.0,
+        find_document_corners(&flat).unwrap().is_none() as u8 as f64,
+        0.0,
+    );
+
+    // A bright object that is too small
+    let mut pm = flat.to_mut();
+    for y in 60..80 {
+        for x in 90..110 {
+            pm.set_pixel_unchecked(x, y, 250);
+        }
+    }
+    let small: Pix = pm.into();
+    rp.compare_values(
+        1.0,
+        find_document_corners(&small).unwrap().is_none() as u8 as f64,
+        0.0,
+    );
+
+    // A bright disk is large enough but not a quadrilateral
+    let mut pm = flat.to_mut();
+    for y in 0..150i32 {
+        for x in 0..200i32 {
+            if (x - 100).pow(2) + (y - 75).pow(2) < 60 * 60 {
+                pm.set_pixel_unchecked(x as u32, y as u32, 250);
+            }
+        }
+    }
+    let disk: Pix = pm.into();
+    rp.compare_values(
+        1.0,
+        find_document_corners(&disk).unwrap().is_none() as u8 as f64,
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "document none test failed");
+}
@@ -9,6 +9,8 @@
 //! get_data, and render_page. Auto-generated template display and
 //! PDF output are not tested.
 //!
+//! Also covers JBIG2 stream export (to_pdf_streams).
+//!
 //! # See also
 //!
 //! C Leptonica: `prog/jbclass_reg.c`
@@ -238,3 +240,325 @@ fn jbclass_reg_word_boxes() {
 
     assert!(rp.cleanup(), "jbclass word_boxes test failed");
 }
+
+/// MQ decoder state for [`decode_symbols`] (T.88 Annex E.3)
+struct MqDecoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+    a: u32,
+    c: u32,
+    ct: u32,
+}
+
+/// T.88 Table E.1: (Qe, NMPS, NLPS, SWITCH)
+const MQ_STATES: [(u32, usize, usize, bool); 47] = [
+    (0x5601, 1, 1, true),
+    (0x3401, 2, 6, false),
+    (0x1801, 3, 9, false),
+    (0x0AC1, 4, 12, false),
+    (0x0521, 5, 29, false),
+    (0x0221, 38, 33, false),
+    (0x5601, 7, 6, true),
+    (0x5401, 8, 14, false),
+    (0x4801, 9, 14, false),
+    (0x3801, 10, 14, false),
+    (0x3001, 11, 17, false),
+    (0x2401, 12, 18, false),
+    (0x1C01, 13, 20, false),
+    (0x1601, 29, 21, false),
+    (0x5601, 15, 14, true),
+    (0x5401, 16, 14, false),
+    (0x5101, 17, 15, false),
+    (0x4801, 18, 16, false),
+    (0x3801, 19, 17, false),
+    (0x3401, 20, 18, false),
+    (0x3001, 21, 19, false),
+    (0x2801, 22, 19, false),
+    (0x2401, 23, 20, false),
+    (0x2201, 24, 21, false),
+    (0x1C01, 25, 22, false),
+    (0x1801, 26, 23, false),
+    (0x1601, 27, 24, false),
+    (0x1401, 28, 25, false),
+    (0x1201, 29, 26, false),
+    (0x1101, 30, 27, false),
+    (0x0AC1, 31, 28, false),
+    (0x09C1, 32, 29, false),
+    (0x08A1, 33, 30, false),
+    (0x0521, 34, 31, false),
+    (0x0441, 35, 32, false),
+    (0x02A1, 36, 33, false),
+    (0x0221, 37, 34, false),
+    (0x0141, 38, 35, false),
+    (0x0111, 39, 36, false),
+    (0x0085, 40, 37, false),
+    (0x0049, 41, 38, false),
+    (0x0025, 42, 39, false),
+    (0x0015, 43, 40, false),
+    (0x0009, 44, 41, false),
+    (0x0005, 45, 42, false),
+    (0x0001, 45, 43, false),
+    (0x5601, 46, 46, false),
+];
+
+impl<'a> MqDecoder<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        let mut dec = Self {
+            data,
+            pos: 0,
+            a: 0x8000,
+            c: (data[0] as u32) << 16,
+            ct: 0,
+        };
+        dec.byte_in();
+        dec.c <<= 7;
+        dec.ct -= 7;
+        dec
+    }
+
+    fn byte(&self, i: usize) -> u32 {
+        self.data.get(i).copied().unwrap_or(0xFF) as u32
+    }
+
+    fn byte_in(&mut self) {
+        if self.byte(self.pos) == 0xFF && self.byte(self.pos + 1) > 0x8F {
+            self.c += 0xFF00;
+            self.ct = 8;
+        } else if self.byte(self.pos) == 0xFF {
+            self.pos += 1;
+            self.c += self.byte(self.pos) << 9;
+            self.ct = 7;
+        } else {
+            self.pos += 1;
+            self.c += self.byte(self.pos) << 8;
+            self.ct = 8;
+        }
+    }
+
+    /// Decode one bit in context `cx`, held as (state index, MPS)
+    fn decode(&mut self, cx: &mut (usize, u8)) -> u8 {
+        let (qe, nmps, nlps, switch) = MQ_STATES[cx.0];
+        self.a -= qe;
+        let lps = if (self.c >> 16) < qe {
+            let lps = self.a >= qe;
+            self.a = qe;
+            lps
+        } else {
+            self.c -= qe << 16;
+            if self.a & 0x8000 != 0 {
+                return cx.1;
+            }
+            self.a < qe
+        };
+        let d = if lps {
+            let d = 1 - cx.1;
+            if switch {
+                cx.1 = d;
+            }
+            cx.0 = nlps;
+            d
+        } else {
+            cx.0 = nmps;
+            cx.1
+        };
+        while self.a & 0x8000 == 0 {
+            if self.ct == 0 {
+                self.byte_in();
+            }
+            self.a <<= 1;
+            self.c <<= 1;
+            self.ct -= 1;
+        }
+        d
+    }
+
+    /// Integer decoding procedure (T.88 A.2); `None` is out of band
+    fn decode_int(&mut self, cx: &mut [(usize, u8)]) -> Option<i32> {
+        let mut prev = 1;
+        let mut read = |dec: &mut Self, n: u32| {
+            let mut v = 0u32;
+            for _ in 0..n {
+                let d = dec.decode(&mut cx[prev]);
+                prev = if prev < 256 {
+                    (prev << 1) | d as usize
+                } else {
+                    (((prev << 1) | d as usize) & 511) | 256
+                };
+                v = (v << 1) | d as u32;
+            }
+            v
+        };
+        let s = read(self, 1);
+        let v = if read(self, 1) == 0 {
+            read(self, 2)
+        } else if read(self, 1) == 0 {
+            read(self, 4) + 4
+        } else if read(self, 1) == 0 {
+            read(self, 6) + 20
+        } else if read(self, 1) == 0 {
+            read(self, 8) + 84
+        } else if read(self, 1) == 0 {
+            read(self, 12) + 340
+        } else {
+            read(self, 32) + 4436
+        } as i32;
+        match (s, v) {
+            (1, 0) => None,
+            (1, v) => Some(-v),
+            (_, v) => Some(v),
+        }
+    }
+}
+
+/// Decode the symbols of an arithmetic, template 0 symbol dictionary
+/// segment, each a generic region (T.88 6.5, 6.2.5.7).
+fn decode_symbols(data: &[u8]) -> Vec<Pix> {
+    let at: Vec<(i32, i32)> = (0..4)
+        .map(|i| (data[2 + 2 * i] as i8 as i32, data[3 + 2 * i] as i8 as i32))
+        .collect();
+    let nsyms = u32::from_be_bytes(data[14..18].try_into().unwrap()) as usize;
+    let mut dec = MqDecoder::new(&data[18..]);
+    let mut iadh = vec![(0, 0); 512];
+    let mut iadw = vec![(0, 0); 512];
+    let mut gb = vec![(0, 0); 1 << 16];
+    // Template 0 neighbourhood, context bit 0 first (T.88 Figure 3)
+    let template = [
+        (-1, 0),
+        (-2, 0),
+        (-3, 0),
+        (-4, 0),
+        at[0],
+        (2, -1),
+        (1, -1),
+        (0, -1),
+        (-1, -1),
+        (-2, -1),
+        at[1],
+        at[2],
+        (1, -2),
+        (0, -2),
+        (-1, -2),
+        at[3],
+    ];
+
+    let mut symbols = Vec::new();
+    let mut height = 0;
+    while symbols.len() < nsyms {
+        height += dec.decode_int(&mut iadh).unwrap();
+        let mut width = 0;
+        while let Some(dw) = dec.decode_int(&mut iadw) {
+            width += dw;
+            let mut pm = Pix::new(width as u32, height as u32, PixelDepth::Bit1)
+                .unwrap()
+                .to_mut();
+            for y in 0..height {
+                for x in 0..width {
+                    let mut cx = 0;
+                    for (i, &(dx, dy)) in template.iter().enumerate() {
+                        let (px, py) = (x + dx, y + dy);
+                        if (0..width).contains(&px)
+                            && (0..height).contains(&py)
+                            && pm.get_pixel_unchecked(px as u32, py as u32) != 0
+                        {
+                            cx |= 1 << i;
+                        }
+                    }
+                    let d = dec.decode(&mut gb[cx]);
+                    pm.set_pixel_unchecked(x as u32, y as u32, d as u32);
+                }
+            }
+            symbols.push(Pix::from(pm));
+        }
+    }
+    symbols
+}
+
+/// Test JBIG2 stream export of classified pages.
+///
+/// Checks the segment layout of the globals and per-page streams, and
+/// decodes the symbol dictionary to check that its generic regions hold
+/// the bars and boxes of the input pages.  The streams are checked against
+/// golden files, which the independent hayro-jbig2 decoder decodes back to
+/// the input pages.
+#[test]
+fn jbclass_reg_pdf_streams() {
+    let mut rp = RegParams::new("jbclass_jbig2");
+
+    // Two pages of repeated 3x5 bars and 4x4 boxes
+    let mut pages = Vec::new();
+    for offset in [0u32, 7] {
+        let mut pm = Pix::new(80, 40, PixelDepth::Bit1).unwrap().to_mut();
+        for k in 0..5 {
+            let x0 = 4 + offset + 14 * k;
+            for y in 10..15 {
+                for x in x0..x0 + 3 {
+                    pm.set_pixel_unchecked(x, y, 1);
+                }
+            }
+            for y in 25..29 {
+                for x in x0..x0 + 4 {
+                    pm.set_pixel_unchecked(x, y, 1);
+                }
+            }
+        }
+        pages.push(Pix::from(pm));
+    }
+
+    let mut classer =
+        correlation_init(JbComponent::ConnComps, 0, 0, 0.8, 0.6).expect("correlation_init");
+    classer.add_pages(&pages).expect("add_pages");
+    let data = classer.get_data().expect("get_data");
+    let (globals, streams) = data.to_pdf_streams().expect("to_pdf_streams");
+    rp.write_data_and_check(&globals, "jb2")
+        .expect("write jbig2 globals");
+    for stream in &streams {
+        rp.write_data_and_check(stream, "jb2")
+            .expect("write jbig2 page");
+    }
+
+    // Globals: one symbol dictionary segment, number 0, page 0
+    rp.compare_values(
+        0.0,
+        u32::from_be_bytes(globals[0..4].try_into().unwrap()) as f64,
+        0.0,
+    );
+    rp.compare_values(0.0, (globals[4] & 0x3f) as f64, 0.0);
+    let dict_len = u32::from_be_bytes(globals[7..11].try_into().unwrap()) as usize;
+    rp.compare_values(globals.len() as f64, (11 + dict_len) as f64, 0.0);
+    let nsyms = u32::from_be_bytes(globals[11 + 10..11 + 14].try_into().unwrap());
+    rp.compare_values(data.nclass as f64, nsyms as f64, 0.0);
+
+    // Symbols are coded in order of height: the 4x4 box, then the 3x5 bar.
+    // Templates keep the 4 pixel border added by the classifier at the top
+    // and left, so each matches the page region ending at its shape.
+    let symbols = decode_symbols(&globals[11..]);
+    rp.compare_values(2.0, symbols.len() as f64, 0.0);
+    for (sym, (x, y)) in symbols.iter().zip([(0, 21), (0, 6)]) {
+        let expected = pages[0]
+            .clip_rectangle(x, y, sym.width(), sym.height())
+            .unwrap();
+        rp.compare_values(1.0, if sym.equals(&expected) { 1.0 } else { 0.0 }, 0.0);
+    }
+
+    // Each page: page information (type 48) with the page size, then a
+    // text region (type 6) referring to segment 0
+    rp.compare_values(2.0, streams.len() as f64, 0.0);
+    for stream in &streams {
+        rp.compare_values(48.0, (stream[4] & 0x3f) as f64, 0.0);
+        let w = u32::from_be_bytes(stream[11..15].try_into().unwrap());
+        let h = u32::from_be_bytes(stream[15..19].try_into().unwrap());
+        rp.compare_values(80.0, w as f64, 0.0);
+        rp.compare_values(40.0, h as f64, 0.0);
+        let text = &stream[11 + 19..];
+        rp.compare_values(6.0, (text[4] & 0x3f) as f64, 0.0);
+        rp.compare_values(1.0, (text[5] >> 5) as f64, 0.0);
+        rp.compare_values(0.0, text[6] as f64, 0.0);
+        // Region width/height follow the 12-byte header
+        let rw = u32::from_be_bytes(text[12..16].try_into().unwrap());
+        rp.compare_values(80.0, rw as f64, 0.0);
+        let ninst = u32::from_be_bytes(text[12 + 19..12 + 23].try_into().unwrap());
+        rp.compare_values(10.0, ninst as f64, 0.0);
+    }
+
+    assert!(rp.cleanup(), "jbclass jbig2 stream test failed");
+}
@@ -6,6 +6,7 @@ mod baseline_reg;
 mod classapp_reg;
 mod correlscore_reg;
 mod dewarp_reg;
+mod document_reg;
 mod estimate_background_reg;
 mod findcorners_reg;
 mod finditalic_reg;
@@ -23,6 +24,8 @@ mod pageseg_helpers_reg;
 mod pageseg_reg;
 mod partition_reg;
 mod pixadisp_reg;
+mod prep_reg;
+mod qrfinder_reg;
 mod recog_coverage_reg;
 mod skew_reg;
 mod strokes_reg;
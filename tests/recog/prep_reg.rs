@@ -0,0 +1,193 @@
+//! OCR preprocessing pipeline regression test
+//!
+//! Tests `prepare_for_ocr`, which chains binarization, deskew, speckle
+//! removal and cropping.
+//! Also covers the blank-page detection in `is_blank` / `ink_fraction`.
+
+use crate::common::RegParams;
+use leptonica::core::{Box, Pix, PixelDepth};
+use leptonica::recog::skew::find_skew;
+use leptonica::recog::{
+    BinarizeMethod, PrepOptions, SkewDetectOptions, ink_fraction, is_blank, prepare_for_ocr,
+};
+use leptonica::region::{ConnectivityType, find_connected_components};
+use leptonica::transform::rotate_by_angle;
+
+/// Text block geometry of the synthetic page
+const PAGE_W: u32 = 900;
+const PAGE_H: u32 = 700;
+const BLOCK_X: u32 = 150;
+const BLOCK_Y: u32 = 120;
+const BLOCK_W: u32 = 600;
+const BLOCK_H: u32 = 400;
+
+/// Build a noisy, skewed, margin-heavy 8 bpp page.
+///
+/// The text block is a grid of "words" (solid 24x12 rectangles) on a
+/// 32 px line pitch, rotated by `angle` degrees.  Isolated dark specks
+/// are then sprinkled over the margins.
+///
+/// Returns the page and the upright size of the text content.
+fn make_page(angle: f32) -> (Pix, u32, u32) {
+    let mut page = Pix::new(PAGE_W, PAGE_H, PixelDepth::Bit1)
+        .unwrap()
+        .try_into_mut()
+        .unwrap();
+    let mut y = BLOCK_Y;
+    while y + 12 <= BLOCK_Y + BLOCK_H {
+        let mut x = BLOCK_X;
+        while x + 24 <= BLOCK_X + BLOCK_W {
+            let word = Box::new(x as i32, y as i32, 24, 12).unwrap();
+            page.set_in_rect(&word).unwrap();
+            x += 28;
+        }
+        y += 32;
+    }
+    let page: Pix = page.into();
+    let (_, content) = page.clip_to_foreground().unwrap().unwrap();
+    let skewed = rotate_by_angle(&page, angle).unwrap();
+
+    let mut gray = skewed.convert_to_8().unwrap().try_into_mut().unwrap();
+    // Deterministic pseudo-random specks in the margins
+    let mut seed: u32 = 12345;
+    for _ in 0..200 {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        let x = (seed >> 8) % PAGE_W;
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        let y = (seed >> 8) % PAGE_H;
+        let in_block = x + 20 >= BLOCK_X
+            && x < BLOCK_X + BLOCK_W + 20
+            && y + 20 >= BLOCK_Y
+            && y < BLOCK_Y + BLOCK_H + 20;
+        if !in_block {
+            gray.set_pixel_unchecked(x, y, 20);
+        }
+    }
+    (gray.into(), content.w as u32, content.h as u32)
+}
+
+#[test]
+fn prep_reg_skewed_noisy_page() {
+    let mut rp = RegParams::new("prep_ocr");
+
+    let (page, cw, ch) = make_page(3.0);
+    let out = prepare_for_ocr(&page, &PrepOptions::default()).expect("prepare_for_ocr");
+
+    rp.compare_values(1.0, (out.depth() == PixelDepth::Bit1) as i32 as f64, 0.0);
+
+    // Tightly cropped to the (deskewed) text block
+    rp.compare_values(cw as f64, out.width() as f64, 6.0);
+    rp.compare_values(ch as f64, out.height() as f64, 6.0);
+
+    // Upright: residual skew is negligible
+    let residual = find_skew(&out, &SkewDetectOptions::default()).expect("find_skew");
+    rp.compare_values(0.0, residual.angle as f64, 0.3);
+
+    // Clean: no specks left, every component is a word
+    let comps = find_connected_components(&out, ConnectivityType::EightWay).unwrap();
+    let min_area = comps.iter().map(|c| c.pixel_count).min().unwrap_or(0);
+    rp.compare_values(1.0, (min_area >= 100) as i32 as f64, 0.0);
+
+    assert!(rp.cleanup(), "prep_ocr regression test failed");
+}
+
+#[test]
+fn prep_reg_steps_toggleable() {
+    let mut rp = RegParams::new("prep_ocr_toggle");
+
+    let (page, cw, _) = make_page(3.0);
+
+    // Binarize only: full page size, specks retained
+    let opts = PrepOptions::new()
+        .with_deskew(false)
+        .with_despeckle_size(0)
+        .with_crop(false);
+    let bin = prepare_for_ocr(&page, &opts).unwrap();
+    rp.compare_values(page.width() as f64, bin.width() as f64, 0.0);
+    rp.compare_values(page.height() as f64, bin.height() as f64, 0.0);
+    let comps = find_connected_components(&bin, ConnectivityType::EightWay).unwrap();
+    let specks = comps.iter().filter(|c| c.pixel_count == 1).count();
+    rp.compare_values(1.0, (specks > 0) as i32 as f64, 0.0);
+
+    // Crop without despeckling is pulled out to the margin specks
+    let opts = PrepOptions::new().with_despeckle_size(0);
+    let loose = prepare_for_ocr(&page, &opts).unwrap();
+    rp.compare_values(1.0, (loose.width() > cw + 50) as i32 as f64, 0.0);
+
+    // Sauvola binarization with a crop margin
+    let opts = PrepOptions::new()
+        .with_binarize(Some(BinarizeMethod::Sauvola {
+            window_size: 31,
+            k: 0.3,
+        }))
+        .with_crop_margin(5);
+    let sauv = prepare_for_ocr(&page, &opts).unwrap();
+    rp.compare_values(cw as f64 + 10.0, sauv.width() as f64, 8.0);
+
+    // Binarization disabled requires 1 bpp input
+    let opts = PrepOptions::new().with_binarize(None);
+    rp.compare_values(
+        1.0,
+        prepare_for_ocr(&page, &opts).is_err() as i32 as f64,
+        0.0,
+    );
+    rp.compare_values(1.0, prepare_for_ocr(&bin, &opts).is_ok() as i32 as f64, 0.0);
+
+    // Even Sauvola window is rejected
+    let opts = PrepOptions::new().with_binarize(Some(BinarizeMethod::Sauvola {
+        window_size: 30,
+        k: 0.3,
+    }));
+    rp.compare_values(
+        1.0,
+        prepare_for_ocr(&page, &opts).is_err() as i32 as f64,
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "prep_ocr toggle regression test failed");
+}
+
+#[test]
+fn prep_reg_blank_page() {
+    let mut rp = RegParams::new("prep_blank");
+
+    // Slightly textured paper (235..242) with one 2x2 dark speck
+    let mut pm = Pix::new(400, 300, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..300 {
+        for x in 0..400 {
+            pm.set_pixel_unchecked(x, y, 235 + (x * 3 + y * 5) % 8);
+        }
+    }
+    for (x, y) in [(200, 150), (201, 150), (200, 151), (201, 151)] {
+        pm.set_pixel_unchecked(x, y, 30);
+    }
+    let speck: Pix = pm.into();
+    rp.compare_values(0.0, ink_fraction(&speck).unwrap() as f64, 0.0);
+    rp.compare_values(1.0, is_blank(&speck, 0.001).unwrap() as u8 as f64, 0.0);
+
+    // Paper texture alone is blank too
+    let mut pm = speck.to_mut();
+    for (x, y) in [(200, 150), (201, 150), (200, 151), (201, 151)] {
+        pm.set_pixel_unchecked(x, y, 238);
+    }
+    let paper: Pix = pm.into();
+    rp.compare_values(1.0, is_blank(&paper, 0.001).unwrap() as u8 as f64, 0.0);
+
+    // One line of 20 "characters" (6x10 blocks) is not blank
+    let mut pm = paper.to_mut();
+    for c in 0..20 {
+        for y in 100..110 {
+            for x in 40 + c * 9..46 + c * 9 {
+                pm.set_pixel_unchecked(x, y, 25);
+            }
+        }
+    }
+    let text: Pix = pm.into();
+    let fract = ink_fraction(&text).unwrap() as f64;
+    rp.compare_values(1200.0 / 120_000.0, fract, 1e-6);
+    rp.compare_values(0.0, is_blank(&text, 0.001).unwrap() as u8 as f64, 0.0);
+
+    rp.compare_values(1.0, is_blank(&text, 1.5).is_err() as u8 as f64, 0.0);
+
+    assert!(rp.cleanup(), "prep blank page test failed");
+}
@@ -0,0 +1,117 @@
+//! QR finder pattern regression test
+//!
+//! Tests `find_qr_finders`, which locates the three corner patterns of
+//! a QR code.
+
+use crate::common::RegParams;
+use leptonica::recog::find_qr_finders;
+use leptonica::{Pix, PixelDepth, Pta};
+
+/// Draw a 7x7-module finder pattern with its top-left corner at (x0, y0).
+fn draw_finder(pm: &mut leptonica::PixMut, x0: u32, y0: u32, module: u32, dark: u32) {
+    for my in 0..7 {
+        for mx in 0..7 {
+            let ring = mx.min(my).min(6 - mx).min(6 - my);
+            if ring == 1 {
+                continue;
+            }
+            for dy in 0..module {
+                for dx in 0..module {
+                    pm.set_pixel_unchecked(x0 + mx * module + dx, y0 + my * module + dy, dark);
+                }
+            }
+        }
+    }
+}
+
+/// Gray image with finders at the given top-left corners.
+fn make_code(w: u32, h: u32, corners: &[(u32, u32)], module: u32) -> Pix {
+    let mut pm = Pix::new(w, h, PixelDepth::Bit8).unwrap().to_mut();
+    pm.set_all_arbitrary(230).unwrap();
+    for &(x, y) in corners {
+        draw_finder(&mut pm, x, y, module, 20);
+    }
+    pm.into()
+}
+
+/// Whether `found` matches `expected` point by point within `tol`.
+fn centers_match(found: &Pta, expected: &[(f32, f32)], tol: f32) -> bool {
+    found.len() == expected.len()
+        && expected.iter().enumerate().all(|(i, &(ex, ey))| {
+            let (x, y) = found.get(i).unwrap();
+            (x - ex).abs() <= tol && (y - ey).abs() <= tol
+        })
+}
+
+#[test]
+fn qrfinder_reg_synthetic() {
+    let mut rp = RegParams::new("qrfinder_synth");
+
+    // Three finders of a 33-module code with 4-pixel modules, with some
+    // data-like blocks between them
+    let module = 4;
+    let corners = [(20, 20), (124, 20), (20, 124)];
+    let mut pm = make_code(200, 200, &corners, module).to_mut();
+    for (bx, by) in [(60, 40), (84, 60), (64, 92), (100, 100), (140, 150)] {
+        for y in by..by + 8 {
+            for x in bx..bx + 12 {
+                pm.set_pixel_unchecked(x, y, 20);
+            }
+        }
+    }
+    let pix: Pix = pm.into();
+
+    // Pixel-center convention: a 28-pixel square at 20 is centered at 33.5
+    let expected = [(33.5, 33.5), (137.5, 33.5), (33.5, 137.5)];
+    let found = find_qr_finders(&pix).unwrap();
+    rp.compare_values(3.0, found.len() as f64, 0.0);
+    rp.compare_values(1.0, centers_match(&found, &expected, 1.0) as u8 as f64, 0.0);
+
+    // Same pattern as 1 bpp (1 = dark) and as RGB
+    let mut pm1 = Pix::new(200, 200, PixelDepth::Bit1).unwrap().to_mut();
+    for y in 0..200 {
+        for x in 0..200 {
+            if pix.get_pixel_unchecked(x, y) < 128 {
+                pm1.set_pixel_unchecked(x, y, 1);
+            }
+        }
+    }
+    let found = find_qr_finders(&pm1.into()).unwrap();
+    rp.compare_values(1.0, centers_match(&found, &expected, 1.0) as u8 as f64, 0.0);
+    let found = find_qr_finders(&pix.convert_to_32().unwrap()).unwrap();
+    rp.compare_values(1.0, centers_match(&found, &expected, 1.0) as u8 as f64, 0.0);
+
+    // Larger modules
+    let pix = make_code(400, 300, &[(30, 40), (290, 40), (30, 200)], 10);
+    let found = find_qr_finders(&pix).unwrap();
+    let expected = [(64.5, 74.5), (324.5, 74.5), (64.5, 234.5)];
+    rp.compare_values(1.0, centers_match(&found, &expected, 1.0) as u8 as f64, 0.0);
+
+    assert!(rp.cleanup(), "qrfinder synthetic test failed");
+}
+
+#[test]
+fn qrfinder_reg_no_finders() {
+    let mut rp = RegParams::new("qrfinder_none");
+
+    // Blank image
+    let blank = make_code(100, 100, &[], 4);
+    rp.compare_values(0.0, find_qr_finders(&blank).unwrap().len() as f64, 0.0);
+
+    // Solid squares and stripes are not finders
+    let mut pm = blank.to_mut();
+    for y in 10..40 {
+        for x in 10..40 {
+            pm.set_pixel_unchecked(x, y, 20);
+        }
+    }
+    for y in 60..90 {
+        for x in (10..90).filter(|x| x % 8 < 4) {
+            pm.set_pixel_unchecked(x, y, 20);
+        }
+    }
+    let found = find_qr_finders(&pm.into()).unwrap();
+    rp.compare_values(0.0, found.len() as f64, 0.0);
+
+    assert!(rp.cleanup(), "qrfinder no-finder test failed");
+}
@@ -87,7 +87,9 @@ fn skew_reg() {
 // =====================================================================
 
 use leptonica::Pix;
-use leptonica::recog::skew::{find_differential_square_sum, find_normalized_square_sum};
+use leptonica::recog::skew::{
+    find_differential_square_sum, find_normalized_square_sum, projection_profiles,
+};
 
 /// C: pixFindDifferentialSquareSum — 平坦画像では sum=0 になる
 #[test]
@@ -170,3 +172,49 @@ fn skew_reg_normalized_square_sum_non_1bpp_errors() {
     let pix = Pix::new(32, 32, leptonica::PixelDepth::Bit32).expect("new 32bpp");
     assert!(find_normalized_square_sum(&pix).is_err());
 }
+
+/// projection_profiles — 0° のテキスト行は 0° で最も鋭いプロファイルになる
+#[test]
+fn skew_reg_projection_profiles() {
+    // 5 本の「テキスト行」: 高さ 8 の行に幅 6 の文字ブロックを並べる
+    let pix = Pix::new(200, 120, leptonica::PixelDepth::Bit1).expect("new 1bpp");
+    let mut pm = pix.try_into_mut().expect("into_mut");
+    for line in 0..5u32 {
+        let y0 = 12 + line * 20;
+        for y in y0..y0 + 8 {
+            for x in 10..190u32 {
+                if x % 9 < 6 {
+                    pm.set_pixel(x, y, 1).expect("set");
+                }
+            }
+        }
+    }
+    let pix: Pix = pm.into();
+
+    let angles = [-3.0f32, -2.0, -1.0, 0.0, 1.0, 2.0, 3.0];
+    let profiles = projection_profiles(&pix, &angles).expect("profiles");
+    assert_eq!(profiles.len(), angles.len());
+
+    let sharpness: Vec<f32> = (0..profiles.len())
+        .map(|i| {
+            let na = profiles.get(i).expect("profile");
+            let v: Vec<f32> = na.iter().collect();
+            v.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum()
+        })
+        .collect();
+    let best = sharpness
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| i)
+        .unwrap();
+    assert_eq!(angles[best], 0.0, "sharpness = {:?}", sharpness);
+
+    // 0° ではシアー無し: プロファイルは画像の高さと一致し、全画素数を保存
+    let p0 = profiles.get(3).expect("0 deg");
+    assert_eq!(p0.len(), 120);
+    assert_eq!(p0.iter().sum::<f32>() as u32, pix.count_pixels() as u32);
+
+    assert!(projection_profiles(&pix, &[45.0]).is_err());
+    assert!(projection_profiles(&pix, &[f32::NAN]).is_err());
+}
@@ -8,10 +8,16 @@
 //! 3. render_borders renders borders to an image
 //! 4. Rendered border pixels are a subset of the original image pixels
 //! 5. Chain code encode/decode roundtrip preserves border points
+//!
+//! Also covers Douglas-Peucker `ComponentBorders::approx_polygon`
+//! and `mask_to_polygons`.
 
 use crate::common::{RegParams, load_test_image};
 use leptonica::io::ImageFormat;
-use leptonica::region::{from_chain_code, get_all_borders, render_borders, to_chain_code};
+use leptonica::region::{
+    ConnectivityType, from_chain_code, get_all_borders, mask_to_polygons, render_borders,
+    to_chain_code,
+};
 use leptonica::{Pix, PixelDepth};
 
 /// Equivalent of the C version's RunCCBordTest function.
@@ -170,3 +176,119 @@ fn ccbord_reg_dreyfus1_smoke() {
 
     assert!(rp.cleanup(), "ccbord dreyfus1 smoke test failed");
 }
+
+/// Test polygon approximation of component borders.
+#[test]
+fn ccbord_reg_approx_polygon() {
+    let mut rp = RegParams::new("ccbord_approx_polygon");
+
+    // A filled rectangle, and a frame with a rectangular hole
+    let mut pm = Pix::new(80, 60, PixelDepth::Bit1).unwrap().to_mut();
+    for y in 10..30u32 {
+        for x in 5..25u32 {
+            pm.set_pixel_unchecked(x, y, 1);
+        }
+    }
+    for y in 10..50u32 {
+        for x in 40..75u32 {
+            if !((48..67).contains(&x) && (20..40).contains(&y)) {
+                pm.set_pixel_unchecked(x, y, 1);
+            }
+        }
+    }
+    let pixs: Pix = pm.into();
+    let all_borders = get_all_borders(&pixs).expect("get_all_borders");
+    rp.compare_values(2.0, all_borders.components.len() as f64, 0.0);
+
+    let rect = all_borders
+        .components
+        .iter()
+        .find(|c| !c.has_holes())
+        .expect("rectangle");
+    rp.compare_values(1.0, (rect.outer.len() > 70) as u8 as f64, 0.0);
+    let poly = rect.approx_polygon(1.0);
+    rp.compare_values(1.0, poly.len() as f64, 0.0);
+    let outer = poly.get(0).unwrap();
+    rp.compare_values(4.0, outer.len() as f64, 0.0);
+    // The vertices are the corner pixels, in global coordinates
+    let mut corners: Vec<(f32, f32)> = outer.iter().collect();
+    corners.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(
+        corners,
+        vec![(5.0, 10.0), (5.0, 29.0), (24.0, 10.0), (24.0, 29.0)]
+    );
+
+    // The frame simplifies to 4 outer and 4 hole vertices
+    let frame = all_borders
+        .components
+        .iter()
+        .find(|c| c.has_holes())
+        .expect("frame");
+    let poly = frame.approx_polygon(1.0);
+    rp.compare_values(2.0, poly.len() as f64, 0.0);
+    rp.compare_values(4.0, poly.get(0).unwrap().len() as f64, 0.0);
+    rp.compare_values(4.0, poly.get(1).unwrap().len() as f64, 0.0);
+
+    // Zero tolerance keeps every corner of the traced staircase, but
+    // still drops the collinear points along straight edges
+    let exact = rect.approx_polygon(0.0);
+    rp.compare_values(4.0, exact.get(0).unwrap().len() as f64, 0.0);
+
+    assert!(rp.cleanup(), "ccbord approx_polygon test failed");
+}
+
+/// Test mask to polygon conversion.
+#[test]
+fn ccbord_reg_mask_to_polygons() {
+    let mut rp = RegParams::new("ccbord_mask_to_polygons");
+
+    // A single rectangle
+    let mut pm = Pix::new(60, 40, PixelDepth::Bit1).unwrap().to_mut();
+    for y in 8..30u32 {
+        for x in 12..50u32 {
+            pm.set_pixel_unchecked(x, y, 1);
+        }
+    }
+    let pixs: Pix = pm.into();
+    let polys = mask_to_polygons(&pixs, ConnectivityType::EightWay, 1.0).expect("polygons");
+    rp.compare_values(1.0, polys.len() as f64, 0.0);
+    let poly = polys.get(0).unwrap();
+    rp.compare_values(4.0, poly.len() as f64, 0.0);
+    let mut corners: Vec<(f32, f32)> = poly.iter().collect();
+    corners.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(
+        corners,
+        vec![(12.0, 8.0), (12.0, 29.0), (49.0, 8.0), (49.0, 29.0)]
+    );
+
+    // Two squares touching at a corner: one component with 8-connectivity,
+    // two with 4-connectivity
+    let mut pm = Pix::new(40, 40, PixelDepth::Bit1).unwrap().to_mut();
+    for y in 5..15u32 {
+        for x in 5..15u32 {
+            pm.set_pixel_unchecked(x, y, 1);
+            pm.set_pixel_unchecked(x + 10, y + 10, 1);
+        }
+    }
+    let pixs: Pix = pm.into();
+    let polys8 = mask_to_polygons(&pixs, ConnectivityType::EightWay, 1.0).expect("8-cc");
+    rp.compare_values(1.0, polys8.len() as f64, 0.0);
+    let polys4 = mask_to_polygons(&pixs, ConnectivityType::FourWay, 1.0).expect("4-cc");
+    rp.compare_values(2.0, polys4.len() as f64, 0.0);
+    for pta in polys4.iter() {
+        rp.compare_values(4.0, pta.len() as f64, 0.0);
+    }
+
+    // Empty mask gives no polygons; non-binary input is rejected
+    let empty = Pix::new(10, 10, PixelDepth::Bit1).unwrap();
+    let none = mask_to_polygons(&empty, ConnectivityType::EightWay, 1.0).expect("empty");
+    rp.compare_values(0.0, none.len() as f64, 0.0);
+    let gray = Pix::new(10, 10, PixelDepth::Bit8).unwrap();
+    rp.compare_values(
+        1.0,
+        mask_to_polygons(&gray, ConnectivityType::EightWay, 1.0).is_err() as u8 as f64,
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "ccbord mask_to_polygons test failed");
+}
@@ -0,0 +1,122 @@
+//! Column detection regression test
+//!
+//! Tests `detect_columns` and `detect_text_lines` on synthetic page
+//! layouts.
+
+use crate::common::RegParams;
+use leptonica::core::Box;
+use leptonica::region::{detect_columns, detect_text_lines};
+use leptonica::{Pix, PixelDepth};
+
+/// Two columns of "words" (small bars with 4 px spacing) separated by a
+/// 20 px gutter at x = 90..110
+fn make_two_column_page() -> Pix {
+    let mut pm = Pix::new(200, 150, PixelDepth::Bit1).unwrap().to_mut();
+    for line in 0..8 {
+        let y = 20 + line * 14;
+        for col_x in [10, 110] {
+            for word in 0..5 {
+                let x = col_x + word * 16;
+                pm.set_in_rect(&Box::new(x, y, 12, 8).unwrap()).unwrap();
+            }
+        }
+    }
+    pm.into()
+}
+
+/// Compare a box with the expected `(x, y, w, h)`
+fn compare_box(rp: &mut RegParams, expected: (i32, i32, i32, i32), b: &Box) {
+    rp.compare_values(expected.0 as f64, b.x as f64, 0.0);
+    rp.compare_values(expected.1 as f64, b.y as f64, 0.0);
+    rp.compare_values(expected.2 as f64, b.w as f64, 0.0);
+    rp.compare_values(expected.3 as f64, b.h as f64, 0.0);
+}
+
+#[test]
+fn columns_reg_two_columns() {
+    let mut rp = RegParams::new("columns_two");
+
+    let pix = make_two_column_page();
+    let boxa = detect_columns(&pix, 10).unwrap();
+    rp.compare_values(2.0, boxa.len() as f64, 0.0);
+    compare_box(&mut rp, (10, 20, 76, 106), boxa.get(0).unwrap());
+    compare_box(&mut rp, (110, 20, 76, 106), boxa.get(1).unwrap());
+
+    // A gap threshold wider than the gutter keeps a single column
+    let boxa = detect_columns(&pix, 30).unwrap();
+    rp.compare_values(1.0, boxa.len() as f64, 0.0);
+    compare_box(&mut rp, (10, 20, 176, 106), boxa.get(0).unwrap());
+
+    // Word spacing alone does not split columns
+    let boxa = detect_columns(&pix, 5).unwrap();
+    rp.compare_values(2.0, boxa.len() as f64, 0.0);
+
+    assert!(rp.cleanup(), "columns two-column test failed");
+}
+
+#[test]
+fn columns_reg_edge_cases() {
+    let mut rp = RegParams::new("columns_edge");
+
+    let empty = Pix::new(50, 50, PixelDepth::Bit1).unwrap();
+    rp.compare_values(0.0, detect_columns(&empty, 10).unwrap().len() as f64, 0.0);
+
+    let pix = make_two_column_page();
+    assert!(detect_columns(&pix, 0).is_err());
+    let gray = Pix::new(50, 50, PixelDepth::Bit8).unwrap();
+    assert!(detect_columns(&gray, 10).is_err());
+
+    assert!(rp.cleanup(), "columns edge case test failed");
+}
+
+#[test]
+fn columns_reg_text_lines() {
+    let mut rp = RegParams::new("columns_lines");
+
+    // Three lines of 10 px high words at y = 20, 40, 60, with an ascender
+    // on the first line and a descender on the second that touches the
+    // third line's ascender
+    let mut pm = Pix::new(160, 100, PixelDepth::Bit1).unwrap().to_mut();
+    for line in 0..3 {
+        let y = 20 + line * 20;
+        for word in 0..6 {
+            pm.set_in_rect(&Box::new(10 + word * 22, y, 18, 10).unwrap())
+                .unwrap();
+        }
+    }
+    pm.set_in_rect(&Box::new(12, 15, 2, 5).unwrap()).unwrap();
+    pm.set_in_rect(&Box::new(50, 50, 2, 10).unwrap()).unwrap();
+    let pix: Pix = pm.into();
+
+    let lines = detect_text_lines(&pix, None).unwrap();
+    rp.compare_values(3.0, lines.len() as f64, 0.0);
+    // Heights lie in 10..=20
+    for b in lines.iter() {
+        rp.compare_values(15.0, b.h as f64, 5.0);
+    }
+    compare_box(&mut rp, (10, 15, 128, 15), lines.get(0).unwrap());
+    // Lines are ordered and do not overlap
+    for i in 1..lines.len() {
+        let (a, b) = (lines.get(i - 1).unwrap(), lines.get(i).unwrap());
+        rp.compare_values(1.0, (a.y + a.h <= b.y) as u8 as f64, 0.0);
+    }
+
+    // Restricting to one column of a two-column page
+    let page = make_two_column_page();
+    let columns = detect_columns(&page, 10).unwrap();
+    let right = columns.get(1).unwrap();
+    let lines = detect_text_lines(&page, Some(right)).unwrap();
+    rp.compare_values(8.0, lines.len() as f64, 0.0);
+    for (i, b) in lines.iter().enumerate() {
+        compare_box(&mut rp, (110, 20 + 14 * i as i32, 76, 8), b);
+    }
+
+    // Empty region, and a region outside the image
+    let region = Box::new(0, 0, 10, 10).unwrap();
+    let empty = detect_text_lines(&page, Some(&region)).unwrap();
+    rp.compare_values(0.0, empty.len() as f64, 0.0);
+    let outside = Box::new(500, 500, 10, 10).unwrap();
+    assert!(detect_text_lines(&page, Some(&outside)).is_err());
+
+    assert!(rp.cleanup(), "columns text line test failed");
+}
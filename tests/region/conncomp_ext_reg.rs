@@ -4,9 +4,11 @@
 //!
 //! C Leptonica: `conncomp.c`, `pixlabel.c`
 //! - pixConnCompPixa, pixGetSortedNeighborValues
+//!
+//! Also covers oriented_boxes and euler_number.
 
-use leptonica::region::ConnectivityType;
 use leptonica::region::conncomp::{conncomp_pixa, get_sorted_neighbor_values};
+use leptonica::region::{ConnectivityType, count_conn_comp, euler_number, oriented_boxes};
 use leptonica::{Pix, PixelDepth};
 
 /// Create a binary image with specific pixels set to foreground
@@ -239,3 +241,154 @@ fn test_get_sorted_neighbor_values_out_of_bounds() {
     // y out of bounds
     assert!(get_sorted_neighbor_values(&pix, 2, 5, ConnectivityType::FourWay).is_err());
 }
+
+// ============================================================================
+// oriented_boxes
+// ============================================================================
+
+/// Draw a filled rectangle of size `len` x `thick` rotated by `deg` degrees
+fn draw_rotated_rect(pm: &mut leptonica::PixMut, cx: f32, cy: f32, len: f32, thick: f32, deg: f32) {
+    let (s, c) = deg.to_radians().sin_cos();
+    for y in 0..pm.height() {
+        for x in 0..pm.width() {
+            let dx = x as f32 - cx;
+            let dy = y as f32 - cy;
+            let u = dx * c + dy * s;
+            let v = -dx * s + dy * c;
+            if u.abs() <= len / 2.0 && v.abs() <= thick / 2.0 {
+                pm.set_pixel_unchecked(x, y, 1);
+            }
+        }
+    }
+}
+
+/// Angle in degrees of the longer side of a 4-corner box, in (-90, 90]
+fn long_side_angle(pta: &leptonica::Pta) -> (f32, f32, f32) {
+    let p: Vec<(f32, f32)> = pta.iter().collect();
+    let side = |a: (f32, f32), b: (f32, f32)| ((b.0 - a.0).hypot(b.1 - a.1), b.1 - a.1, b.0 - a.0);
+    let (l1, dy1, dx1) = side(p[0], p[1]);
+    let (l2, dy2, dx2) = side(p[1], p[2]);
+    let (long, short, dy, dx) = if l1 >= l2 {
+        (l1, l2, dy1, dx1)
+    } else {
+        (l2, l1, dy2, dx2)
+    };
+    let mut angle = dy.atan2(dx).to_degrees();
+    if angle <= -90.0 {
+        angle += 180.0;
+    } else if angle > 90.0 {
+        angle -= 180.0;
+    }
+    (angle, long, short)
+}
+
+#[test]
+fn test_oriented_boxes_rotated_rect() {
+    let mut pm = Pix::new(200, 120, PixelDepth::Bit1).unwrap().to_mut();
+    draw_rotated_rect(&mut pm, 60.0, 60.0, 80.0, 20.0, 20.0);
+    draw_rotated_rect(&mut pm, 150.0, 60.0, 60.0, 12.0, -35.0);
+    let pix: Pix = pm.into();
+
+    let boxes = oriented_boxes(&pix, ConnectivityType::EightWay).unwrap();
+    assert_eq!(boxes.len(), 2);
+    for pta in boxes.iter() {
+        assert_eq!(pta.len(), 4);
+    }
+
+    let (angle, long, short) = long_side_angle(boxes.get(0).unwrap());
+    assert!((angle - 20.0).abs() < 1.0, "angle {angle}");
+    assert!((long - 81.0).abs() < 3.0, "long side {long}");
+    assert!((short - 21.0).abs() < 3.0, "short side {short}");
+
+    let (angle, _, _) = long_side_angle(boxes.get(1).unwrap());
+    assert!((angle + 35.0).abs() < 1.0, "angle {angle}");
+}
+
+#[test]
+fn test_oriented_boxes_axis_aligned_and_single_pixel() {
+    let mut pixels: Vec<(u32, u32)> = Vec::new();
+    for y in 5..9 {
+        for x in 2..12 {
+            pixels.push((x, y));
+        }
+    }
+    pixels.push((20, 15));
+    let pix = make_binary_image(30, 20, &pixels);
+    let boxes = oriented_boxes(&pix, ConnectivityType::FourWay).unwrap();
+    assert_eq!(boxes.len(), 2);
+
+    // 10 x 4 block: box spans the pixel extents
+    let (_, long, short) = long_side_angle(boxes.get(0).unwrap());
+    assert!((long - 10.0).abs() < 1e-3);
+    assert!((short - 4.0).abs() < 1e-3);
+
+    // Single pixel: unit square around its center
+    let (_, long, short) = long_side_angle(boxes.get(1).unwrap());
+    assert!((long - 1.0).abs() < 1e-3 && (short - 1.0).abs() < 1e-3);
+    let (xs, ys): (Vec<f32>, Vec<f32>) = boxes.get(1).unwrap().iter().unzip();
+    let cx = xs.iter().sum::<f32>() / 4.0;
+    let cy = ys.iter().sum::<f32>() / 4.0;
+    assert!((cx - 20.0).abs() < 1e-3 && (cy - 15.0).abs() < 1e-3);
+
+    let pix8 = Pix::new(10, 10, PixelDepth::Bit8).unwrap();
+    assert!(oriented_boxes(&pix8, ConnectivityType::FourWay).is_err());
+}
+
+// ============================================================================
+// euler_number
+// ============================================================================
+
+/// Draw a filled disk, optionally with a concentric hole of radius `r_in`
+fn make_disk(size: u32, r_out: f32, r_in: f32) -> Pix {
+    let c = (size as f32 - 1.0) / 2.0;
+    let mut pixels = Vec::new();
+    for y in 0..size {
+        for x in 0..size {
+            let r = ((x as f32 - c).powi(2) + (y as f32 - c).powi(2)).sqrt();
+            if r <= r_out && r > r_in {
+                pixels.push((x, y));
+            }
+        }
+    }
+    make_binary_image(size, size, &pixels)
+}
+
+#[test]
+fn test_euler_number_disk_and_ring() {
+    let disk = make_disk(41, 15.0, -1.0);
+    assert_eq!(euler_number(&disk, ConnectivityType::EightWay).unwrap(), 1);
+    assert_eq!(euler_number(&disk, ConnectivityType::FourWay).unwrap(), 1);
+
+    let ring = make_disk(41, 15.0, 8.0);
+    assert_eq!(euler_number(&ring, ConnectivityType::EightWay).unwrap(), 0);
+
+    // Two rings and a disk touching the border: 3 components, 2 holes
+    let mut pm = make_binary_rect(60, 30, 0, 0, 6, 6).to_mut();
+    for (ox, oy) in [(10, 5), (35, 5)] {
+        for y in 0..15 {
+            for x in 0..15 {
+                let inner = (4..11).contains(&x) && (4..11).contains(&y);
+                if !inner {
+                    pm.set_pixel_unchecked(ox + x, oy + y, 1);
+                }
+            }
+        }
+    }
+    let pix: Pix = pm.into();
+    assert_eq!(euler_number(&pix, ConnectivityType::FourWay).unwrap(), 1);
+
+    let empty = Pix::new(10, 10, PixelDepth::Bit1).unwrap();
+    assert_eq!(euler_number(&empty, ConnectivityType::EightWay).unwrap(), 0);
+
+    let pix8 = Pix::new(10, 10, PixelDepth::Bit8).unwrap();
+    assert!(euler_number(&pix8, ConnectivityType::EightWay).is_err());
+}
+
+#[test]
+fn test_euler_number_diagonal_connectivity() {
+    // Two pixels touching only at a corner: one 8-component, two 4-components
+    let pix = make_binary_image(6, 6, &[(2, 2), (3, 3)]);
+    assert_eq!(euler_number(&pix, ConnectivityType::EightWay).unwrap(), 1);
+    assert_eq!(euler_number(&pix, ConnectivityType::FourWay).unwrap(), 2);
+    assert_eq!(count_conn_comp(&pix, ConnectivityType::FourWay).unwrap(), 2);
+}
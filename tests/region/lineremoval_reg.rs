@@ -0,0 +1,85 @@
+//! Ruled line removal regression test
+//!
+//! Tests `remove_lines` on synthetic forms.
+
+use crate::common::RegParams;
+use leptonica::core::Box;
+use leptonica::region::{ConnectivityType, find_connected_components, remove_lines};
+use leptonica::{Color, Pix, PixelDepth};
+
+/// 1 bpp text rendered with the built-in font at scale 3
+fn make_text() -> Pix {
+    let mut pm = Pix::new(300, 80, PixelDepth::Bit8).unwrap().to_mut();
+    pm.set_all_gray(255).unwrap();
+    pm.draw_text("FORM 1234", 20, 20, Color::BLACK, 3).unwrap();
+    let gray: Pix = pm.into();
+    leptonica::color::threshold_to_binary(&gray, 128).unwrap()
+}
+
+/// `text` with a 3-pixel horizontal rule through the glyphs and a
+/// vertical rule at the left margin
+fn make_form(text: &Pix) -> Pix {
+    let mut pm = text.to_mut();
+    pm.set_in_rect(&Box::new(0, 36, 300, 3).unwrap()).unwrap();
+    pm.set_in_rect(&Box::new(6, 0, 2, 80).unwrap()).unwrap();
+    pm.into()
+}
+
+#[test]
+fn lineremoval_reg_horizontal_rule() {
+    let mut rp = RegParams::new("lineremoval_both");
+
+    let text = make_text();
+    let form = make_form(&text);
+
+    let (cleaned, lines) = remove_lines(&form, 60, true, true).expect("remove_lines");
+    rp.compare_values(1.0, cleaned.depth().bits() as f64, 0.0);
+    rp.compare_values(1.0, lines.depth().bits() as f64, 0.0);
+
+    // Both rules are extracted; the rules are gone outside the text
+    let px = |p: &Pix, x, y| p.get_pixel(x, y).unwrap() as f64;
+    rp.compare_values(1.0, px(&lines, 280, 37), 0.0);
+    rp.compare_values(1.0, px(&lines, 7, 70), 0.0);
+    rp.compare_values(0.0, px(&cleaned, 280, 37), 0.0);
+    rp.compare_values(0.0, px(&cleaned, 7, 70), 0.0);
+    rp.compare_values(0.0, px(&lines, 30, 25), 0.0);
+
+    // Text is largely intact: under 5% of its pixels differ, and the
+    // glyph count is unchanged
+    let n_text = text.count_pixels() as f64;
+    let n_diff = cleaned.xor(&text).unwrap().count_pixels() as f64;
+    rp.compare_values(0.0, n_diff / n_text, 0.05);
+    let ncc = |p: &Pix| {
+        find_connected_components(p, ConnectivityType::EightWay)
+            .unwrap()
+            .len() as f64
+    };
+    rp.compare_values(ncc(&text), ncc(&cleaned), 0.0);
+
+    assert!(rp.cleanup(), "lineremoval both directions test failed");
+}
+
+#[test]
+fn lineremoval_reg_direction_and_errors() {
+    let mut rp = RegParams::new("lineremoval_direction");
+
+    let form = make_form(&make_text());
+
+    // Horizontal only: the vertical rule stays in the text image
+    let (cleaned, lines) = remove_lines(&form, 60, true, false).unwrap();
+    let px = |p: &Pix, x, y| p.get_pixel(x, y).unwrap() as f64;
+    rp.compare_values(1.0, px(&cleaned, 7, 70), 0.0);
+    rp.compare_values(0.0, px(&lines, 7, 70), 0.0);
+    rp.compare_values(0.0, px(&cleaned, 280, 37), 0.0);
+
+    // Neither direction: nothing removed
+    let (cleaned, lines) = remove_lines(&form, 60, false, false).unwrap();
+    rp.compare_pix(&form, &cleaned);
+    rp.compare_values(0.0, lines.count_pixels() as f64, 0.0);
+
+    assert!(remove_lines(&form, 1, true, true).is_err());
+    let gray = Pix::new(10, 10, PixelDepth::Bit8).unwrap();
+    assert!(remove_lines(&gray, 20, true, true).is_err());
+
+    assert!(rp.cleanup(), "lineremoval direction test failed");
+}
@@ -3,11 +3,13 @@ mod common;
 
 mod ccbord_reg;
 mod checkerboard_reg;
+mod columns_reg;
 mod conncomp_ext_reg;
 mod conncomp_reg;
 mod distance_reg;
 mod grayfill_reg;
 mod label_reg;
+mod lineremoval_reg;
 mod maze_reg;
 mod partition_whitespace_reg;
 mod quadtree_reg;
@@ -18,5 +20,6 @@ mod seedspread_reg;
 mod smoothedge_reg;
 mod speckle_reg;
 mod splitcomp_reg;
+mod strokewidth_reg;
 mod texturefill_reg;
 mod watershed_reg;
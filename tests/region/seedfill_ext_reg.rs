@@ -5,11 +5,14 @@
 //! C Leptonica: `seedfill.c`
 //! - pixDistanceFunction, pixFindEqualValues, pixFillClosedBorders
 //! - pixRemoveSeededComponents, pixSeedfillGrayInv, pixSeedfillBinaryRestricted
+//!
+//! Also covers color-tolerant `floodfill_color` and
+//! `find_holes`.
 
 use leptonica::region::ConnectivityType;
 use leptonica::region::seedfill::{
-    BoundaryCondition, distance_function, fill_closed_borders, find_equal_values,
-    remove_seeded_components, seedfill_binary_restricted, seedfill_gray_inv,
+    BoundaryCondition, distance_function, fill_closed_borders, find_equal_values, find_holes,
+    floodfill_color, remove_seeded_components, seedfill_binary_restricted, seedfill_gray_inv,
 };
 use leptonica::{Pix, PixelDepth};
 
@@ -321,3 +324,122 @@ fn test_seedfill_binary_restricted_no_limit() {
     // Everything should be filled
     assert_eq!(result.get_pixel_unchecked(9, 9), 1);
 }
+
+// ============================================================================
+// floodfill_color
+// ============================================================================
+
+#[test]
+fn test_floodfill_color_stops_at_boundary() {
+    use leptonica::core::pixel::compose_rgb;
+
+    // Left half: noisy red; right half: blue, touching along x = 20
+    let (w, h) = (40u32, 20u32);
+    let pix = Pix::new(w, h, PixelDepth::Bit32).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..h {
+        for x in 0..w {
+            let jitter = ((x * 7 + y * 3) % 9) as u8;
+            let val = if x < 20 {
+                compose_rgb(200 + jitter, 30, 30)
+            } else {
+                compose_rgb(30, 30, 200 + jitter)
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    let pix: Pix = pm.into();
+
+    let mask = floodfill_color(&pix, 5, 5, 10, ConnectivityType::FourWay).unwrap();
+    assert_eq!(mask.depth(), PixelDepth::Bit1);
+    assert_eq!(mask.count_pixels(), 20 * 20);
+    assert_eq!(mask.get_pixel_unchecked(19, 0), 1);
+    assert_eq!(mask.get_pixel_unchecked(20, 0), 0);
+
+    // Zero tolerance selects only pixels identical to the seed
+    let exact = floodfill_color(&pix, 5, 5, 0, ConnectivityType::EightWay).unwrap();
+    assert!(exact.count_pixels() < 20 * 20);
+    assert_eq!(exact.get_pixel_unchecked(5, 5), 1);
+}
+
+#[test]
+fn test_floodfill_color_gray_and_errors() {
+    // 8bpp: two flat regions separated by a vertical step
+    let pix = Pix::new(30, 10, PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..10 {
+        for x in 0..30 {
+            pm.set_pixel_unchecked(x, y, if x < 12 { 100 } else { 160 });
+        }
+    }
+    let pix: Pix = pm.into();
+    let mask = floodfill_color(&pix, 20, 5, 20, ConnectivityType::FourWay).unwrap();
+    assert_eq!(mask.count_pixels(), 18 * 10);
+    let all = floodfill_color(&pix, 20, 5, 60, ConnectivityType::FourWay).unwrap();
+    assert_eq!(all.count_pixels(), 30 * 10);
+
+    assert!(floodfill_color(&pix, 30, 0, 5, ConnectivityType::FourWay).is_err());
+    let pix1 = Pix::new(10, 10, PixelDepth::Bit1).unwrap();
+    assert!(floodfill_color(&pix1, 0, 0, 5, ConnectivityType::FourWay).is_err());
+}
+
+// ============================================================================
+// find_holes
+// ============================================================================
+
+#[test]
+fn test_find_holes_ring() {
+    // Ring: 20x20 square with a 8x6 hole, plus a separate solid block
+    let mut pm = make_binary_rect(40, 30, 2, 2, 22, 22).to_mut();
+    for y in 8..14 {
+        for x in 6..14 {
+            pm.set_pixel_unchecked(x, y, 0);
+        }
+    }
+    for y in 5..15 {
+        for x in 28..36 {
+            pm.set_pixel_unchecked(x, y, 1);
+        }
+    }
+    let pix: Pix = pm.into();
+
+    let holes = find_holes(&pix, ConnectivityType::FourWay).unwrap();
+    assert_eq!(holes.len(), 1);
+    let b = holes.get_box(0).unwrap();
+    assert_eq!((b.x, b.y, b.w, b.h), (6, 8, 8, 6));
+    assert_eq!(holes.get(0).unwrap().count_pixels(), 48);
+}
+
+#[test]
+fn test_find_holes_figure_eight() {
+    // "8": a 12x21 block with two 6x6 holes, plus a notch open to the
+    // image border that must not count as a hole
+    let mut pm = make_binary_rect(20, 25, 0, 2, 12, 23).to_mut();
+    for (y0, y1) in [(5, 11), (14, 20)] {
+        for y in y0..y1 {
+            for x in 3..9 {
+                pm.set_pixel_unchecked(x, y, 0);
+            }
+        }
+    }
+    for y in 8..16 {
+        pm.set_pixel_unchecked(0, y, 0);
+    }
+    let pix: Pix = pm.into();
+
+    let holes = find_holes(&pix, ConnectivityType::FourWay).unwrap();
+    assert_eq!(holes.len(), 2);
+    for i in 0..2 {
+        assert_eq!(holes.get(i).unwrap().count_pixels(), 36);
+    }
+
+    // No holes in a solid block; non-binary input is rejected
+    let solid = make_binary_rect(10, 10, 2, 2, 8, 8);
+    assert!(
+        find_holes(&solid, ConnectivityType::FourWay)
+            .unwrap()
+            .is_empty()
+    );
+    let gray = make_uniform_8bpp(10, 10, 0);
+    assert!(find_holes(&gray, ConnectivityType::FourWay).is_err());
+}
@@ -8,20 +8,22 @@
 //!
 //! C version: `prog/speckle_reg.c`
 //!
+//! Also covers `despeckle` component-size filter.
+//!
 //! # See also
 //!
 //! C Leptonica: `prog/speckle_reg.c`
 
 use crate::common::RegParams;
-use leptonica::PixelDepth;
 use leptonica::color::threshold_to_binary;
 use leptonica::filter::{FlexNormOptions, background_norm_flex, gamma_trc_masked};
 use leptonica::io::ImageFormat;
 use leptonica::morph::{Sel, dilate, hit_miss_transform};
 use leptonica::region::{
-    ConnectivityType, SizeSelectRelation, SizeSelectType, clear_border, find_connected_components,
-    pix_count_components, pix_select_by_size,
+    ConnectivityType, SizeSelectRelation, SizeSelectType, clear_border, despeckle,
+    find_connected_components, pix_count_components, pix_select_by_size,
 };
+use leptonica::{Pix, PixelDepth};
 
 /// Test clear_border on a binary image (border noise removal).
 ///
@@ -212,3 +214,46 @@ fn speckle_reg_full_pipeline() {
 
     assert!(rp.cleanup(), "speckle full_pipeline test failed");
 }
+
+/// Test despeckle removes isolated pixels but keeps larger blobs.
+#[test]
+fn speckle_reg_despeckle() {
+    let mut rp = RegParams::new("speckle_despeckle");
+
+    let mut pm = Pix::new(60, 40, PixelDepth::Bit1).unwrap().to_mut();
+    // 10x5 = 50-pixel blob
+    for y in 10..15 {
+        for x in 20..30 {
+            pm.set_pixel_unchecked(x, y, 1);
+        }
+    }
+    // One-pixel-wide vertical stroke of 12 pixels
+    for y in 20..32 {
+        pm.set_pixel_unchecked(45, y, 1);
+    }
+    // Salt noise: isolated single pixels
+    for &(x, y) in &[(2, 2), (50, 5), (10, 35), (33, 25), (57, 38)] {
+        pm.set_pixel_unchecked(x, y, 1);
+    }
+    let pix: Pix = pm.into();
+
+    let cleaned = despeckle(&pix, 10, ConnectivityType::EightWay).expect("despeckle");
+    rp.compare_values(62.0, cleaned.count_pixels() as f64, 0.0);
+    let comps = find_connected_components(&cleaned, ConnectivityType::EightWay).unwrap();
+    rp.compare_values(2.0, comps.len() as f64, 0.0);
+    rp.compare_values(0.0, cleaned.get_pixel(2, 2).unwrap() as f64, 0.0);
+    rp.compare_values(1.0, cleaned.get_pixel(25, 12).unwrap() as f64, 0.0);
+
+    // min_size above the stroke length removes it too
+    let cleaned = despeckle(&pix, 13, ConnectivityType::FourWay).unwrap();
+    rp.compare_values(50.0, cleaned.count_pixels() as f64, 0.0);
+
+    // min_size of 0 keeps everything
+    let kept = despeckle(&pix, 0, ConnectivityType::FourWay).unwrap();
+    assert!(kept.equals(&pix));
+
+    let pix8 = Pix::new(10, 10, PixelDepth::Bit8).unwrap();
+    assert!(despeckle(&pix8, 10, ConnectivityType::FourWay).is_err());
+
+    assert!(rp.cleanup(), "speckle despeckle test failed");
+}
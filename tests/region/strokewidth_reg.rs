@@ -0,0 +1,71 @@
+//! Stroke width transform regression test
+//!
+//! Tests the grayscale stroke width transform on synthetic bars.
+
+use leptonica::region::find_stroke_width_transform;
+use leptonica::{Pix, PixelDepth};
+
+/// 8 bpp image with a vertical bar of value `fg` on background `bg`
+fn make_bar(x0: u32, bar_w: u32, fg: u32, bg: u32) -> Pix {
+    let pix = Pix::new(60, 40, PixelDepth::Bit8).unwrap();
+    let mut pm = pix.try_into_mut().unwrap();
+    for y in 0..40 {
+        for x in 0..60 {
+            let v = if (x0..x0 + bar_w).contains(&x) && (5..35).contains(&y) {
+                fg
+            } else {
+                bg
+            };
+            pm.set_pixel_unchecked(x, y, v);
+        }
+    }
+    pm.into()
+}
+
+/// Width values over the interior rows of the bar
+fn bar_widths(swt: &leptonica::FPix, x0: u32, bar_w: u32) -> Vec<f32> {
+    let mut vals = Vec::new();
+    for y in 10..30 {
+        for x in x0..x0 + bar_w {
+            vals.push(swt.get_pixel(x, y).unwrap());
+        }
+    }
+    vals
+}
+
+/// Test that a constant-width bar yields a constant stroke width
+#[test]
+fn strokewidth_reg_constant_bar() {
+    let pix = make_bar(20, 7, 0, 255);
+    let swt = find_stroke_width_transform(&pix, true).unwrap();
+    assert_eq!(swt.width(), 60);
+    assert_eq!(swt.height(), 40);
+
+    for v in bar_widths(&swt, 20, 7) {
+        assert!((v - 7.0).abs() <= 1.0, "width {v} inside bar");
+    }
+    // Background away from the bar has no strokes
+    assert_eq!(swt.get_pixel(5, 20).unwrap(), 0.0);
+    assert_eq!(swt.get_pixel(50, 20).unwrap(), 0.0);
+}
+
+/// Test polarity selection for light strokes on dark
+#[test]
+fn strokewidth_reg_polarity() {
+    let pix = make_bar(20, 5, 230, 20);
+    let swt = find_stroke_width_transform(&pix, false).unwrap();
+    for v in bar_widths(&swt, 20, 5) {
+        assert!((v - 5.0).abs() <= 1.0, "width {v} inside bar");
+    }
+
+    // With the wrong polarity the bar is not detected as a stroke
+    let swt = find_stroke_width_transform(&pix, true).unwrap();
+    assert!(bar_widths(&swt, 20, 5).iter().all(|&v| v == 0.0));
+}
+
+/// Test that non-8bpp input is rejected
+#[test]
+fn strokewidth_reg_invalid_depth() {
+    let pix = Pix::new(10, 10, PixelDepth::Bit1).unwrap();
+    assert!(find_stroke_width_transform(&pix, true).is_err());
+}
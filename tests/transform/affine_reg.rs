@@ -14,11 +14,16 @@
 //!   7. Affine transforms on pix and boxa — requires `boxaAffineTransform` etc.
 //!
 //! Point data from `MakePtas()` function in C version.
+//!
+//! Also covers `affine_batch` over a Pixa.
 
 use crate::common::{RegParams, load_test_image};
 use leptonica::io::ImageFormat;
-use leptonica::transform::{AffineFill, Point, ScaleMethod, affine_pta, affine_sampled_pta, scale};
-use leptonica::{Pix, PixelDepth};
+use leptonica::transform::{
+    AffineFill, AffineMatrix, Point, ScaleMethod, affine, affine_batch, affine_pta,
+    affine_sampled_pta, scale,
+};
+use leptonica::{Box, Pix, Pixa, PixelDepth};
 
 // Point data from C version (affine_reg.c MakePtas function)
 const X1: [i32; 5] = [300, 300, 300, 95, 32];
@@ -400,3 +405,67 @@ fn affine_reg_sequential_invertability() {
 fn affine_reg_boxa_transform() {
     // C: Tests affine transforms and inverses on pix and boxa
 }
+
+/// Test one affine transform applied to every image of a Pixa
+#[test]
+fn affine_reg_batch() {
+    let mut rp = RegParams::new("affine_batch");
+
+    let pix8 = load_test_image("test8.jpg").expect("load test8.jpg");
+    let pix32 = load_test_image("weasel32.png").expect("load weasel32.png");
+    let pix1 = load_test_image("feyn-fract.tif").expect("load feyn-fract.tif");
+    let mut pixa = Pixa::new();
+    pixa.push_with_box(pix8.clone(), Box::new(0, 0, 100, 80).unwrap());
+    pixa.push_with_box(pix32.clone(), Box::new(120, 40, 60, 50).unwrap());
+    pixa.push_with_box(pix1.clone(), Box::new(10, 200, 30, 20).unwrap());
+
+    // Identity: equivalent images and unchanged boxes.  Interpolation
+    // leaves the last row and column as fill, as in C pixAffine().
+    let same = affine_batch(&pixa, &AffineMatrix::identity(), AffineFill::White).unwrap();
+    rp.compare_values(3.0, same.len() as f64, 0.0);
+    for i in 0..3 {
+        let (src, dst) = (pixa.get(i).unwrap(), same.get(i).unwrap());
+        let mut mismatches = 0;
+        for y in 0..src.height() - 1 {
+            for x in 0..src.width() - 1 {
+                mismatches +=
+                    (src.get_pixel_unchecked(x, y) != dst.get_pixel_unchecked(x, y)) as u32;
+            }
+        }
+        rp.compare_values(0.0, mismatches as f64, 0.0);
+        assert_eq!(same.boxa().get(i), pixa.boxa().get(i));
+    }
+
+    // Translation: every box moves by the same amount and every image
+    // matches the single-image transform
+    let shift = AffineMatrix::translation(7.0, -3.0);
+    let moved = affine_batch(&pixa, &shift, AffineFill::White).unwrap();
+    for i in 0..3 {
+        let (b0, b1) = (pixa.boxa().get(i).unwrap(), moved.boxa().get(i).unwrap());
+        rp.compare_values((b0.x + 7) as f64, b1.x as f64, 0.0);
+        rp.compare_values((b0.y - 3) as f64, b1.y as f64, 0.0);
+        rp.compare_values(b0.w as f64, b1.w as f64, 0.0);
+        rp.compare_values(b0.h as f64, b1.h as f64, 0.0);
+        let single = affine(pixa.get(i).unwrap(), &shift, AffineFill::White).unwrap();
+        rp.compare_pix(&single, moved.get(i).unwrap());
+    }
+    let (x, y) = (20, 30);
+    rp.compare_values(
+        pix8.get_pixel_unchecked(x, y) as f64,
+        moved.get(0).unwrap().get_pixel_unchecked(x + 7, y - 3) as f64,
+        0.0,
+    );
+
+    // Without boxes the result has none
+    let mut bare = Pixa::new();
+    bare.push(pix8.clone());
+    let out = affine_batch(&bare, &shift, AffineFill::Black).unwrap();
+    rp.compare_values(1.0, out.len() as f64, 0.0);
+    rp.compare_values(0.0, out.boxa_count() as f64, 0.0);
+
+    // A singular matrix is rejected
+    let singular = AffineMatrix::from_coeffs([1.0, 2.0, 0.0, 2.0, 4.0, 0.0]);
+    assert!(affine_batch(&pixa, &singular, AffineFill::White).is_err());
+
+    assert!(rp.cleanup(), "affine batch test failed");
+}
@@ -0,0 +1,99 @@
+//! ASCII art regression test
+//!
+//! Tests the output shape, the darkest and lightest characters, binary
+//! and color input, and parameter validation.
+
+use crate::common::{RegParams, load_test_image};
+use leptonica::transform::to_ascii_art;
+use leptonica::{Pix, PixelDepth};
+
+/// Lines of `art`, checking that each has `cols` characters.
+fn lines_of_width(art: &str, cols: usize) -> Option<Vec<&str>> {
+    let lines: Vec<&str> = art.lines().collect();
+    lines.iter().all(|l| l.len() == cols).then_some(lines)
+}
+
+#[test]
+fn ascii_reg_black_and_white() {
+    let mut rp = RegParams::new("ascii_bw");
+
+    // 8 bpp black: every character is the darkest one
+    let black = Pix::new(200, 100, PixelDepth::Bit8).unwrap();
+    let art = to_ascii_art(&black, 40).unwrap();
+    let lines = lines_of_width(&art, 40).unwrap();
+    // 200x100 at 40 columns, rows halved for the cell aspect
+    rp.compare_values(10.0, lines.len() as f64, 0.0);
+    rp.compare_values(1.0, art.ends_with('\n') as u8 as f64, 0.0);
+    rp.compare_values(
+        1.0,
+        lines.iter().all(|l| l.chars().all(|c| c == '@')) as u8 as f64,
+        0.0,
+    );
+
+    // 8 bpp white: every character is a space
+    let mut pm = Pix::new(200, 100, PixelDepth::Bit8).unwrap().to_mut();
+    pm.set_all_arbitrary(255).unwrap();
+    let white: Pix = pm.into();
+    let art = to_ascii_art(&white, 40).unwrap();
+    let lines = lines_of_width(&art, 40).unwrap();
+    rp.compare_values(10.0, lines.len() as f64, 0.0);
+    rp.compare_values(
+        1.0,
+        lines.iter().all(|l| l.chars().all(|c| c == ' ')) as u8 as f64,
+        0.0,
+    );
+
+    // Left half black, right half white
+    let mut pm = Pix::new(100, 100, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..100 {
+        for x in 50..100 {
+            pm.set_pixel_unchecked(x, y, 255);
+        }
+    }
+    let half: Pix = pm.into();
+    let art = to_ascii_art(&half, 20).unwrap();
+    let lines = lines_of_width(&art, 20).unwrap();
+    rp.compare_values(10.0, lines.len() as f64, 0.0);
+    rp.compare_values(
+        1.0,
+        lines
+            .iter()
+            .all(|l| l.starts_with("@@@@@@@@") && l.ends_with("        ")) as u8 as f64,
+        0.0,
+    );
+
+    // Wider than the image: upscaled, still exactly `cols` wide
+    let art = to_ascii_art(&black, 300).unwrap();
+    rp.compare_values(1.0, lines_of_width(&art, 300).is_some() as u8 as f64, 0.0);
+
+    // A very flat image still gets one line
+    let flat = Pix::new(100, 2, PixelDepth::Bit8).unwrap();
+    let art = to_ascii_art(&flat, 10).unwrap();
+    rp.compare_values(1.0, lines_of_width(&art, 10).unwrap().len() as f64, 0.0);
+
+    assert!(to_ascii_art(&black, 0).is_err());
+
+    assert!(rp.cleanup(), "ascii black/white test failed");
+}
+
+#[test]
+fn ascii_reg_real_images() {
+    let mut rp = RegParams::new("ascii_real");
+
+    // Binary text: blank margins and gray text lines
+    let pix1 = load_test_image("feyn-fract.tif").unwrap();
+    let art = to_ascii_art(&pix1, 80).unwrap();
+    let lines = lines_of_width(&art, 80).unwrap();
+    let expected_rows = (80.0 * pix1.height() as f64 / pix1.width() as f64 / 2.0).round();
+    rp.compare_values(expected_rows, lines.len() as f64, 0.0);
+    let spaces = art.chars().filter(|&c| c == ' ').count();
+    let ink = art.chars().filter(|&c| c != ' ' && c != '\n').count();
+    rp.compare_values(1.0, (spaces > 0 && ink > 0) as u8 as f64, 0.0);
+
+    // RGB
+    let pix32 = load_test_image("weasel32.png").unwrap();
+    let art = to_ascii_art(&pix32, 64).unwrap();
+    rp.compare_values(1.0, lines_of_width(&art, 64).is_some() as u8 as f64, 0.0);
+
+    assert!(rp.cleanup(), "ascii real image test failed");
+}
@@ -0,0 +1,130 @@
+//! Bayer demosaicing regression test
+//!
+//! Tests solid-color reconstruction for every pattern, 16 bpp input,
+//! bilinear interpolation of a ramp, and parameter validation.
+
+use crate::common::RegParams;
+use leptonica::core::pixel;
+use leptonica::transform::{BayerPattern, demosaic_bayer};
+use leptonica::{Pix, PixelDepth};
+
+const PATTERNS: [BayerPattern; 4] = [
+    BayerPattern::Rggb,
+    BayerPattern::Bggr,
+    BayerPattern::Grbg,
+    BayerPattern::Gbrg,
+];
+
+/// Channel (0 = R, 1 = G, 2 = B) sampled at (x, y) for `pattern`.
+fn channel(pattern: BayerPattern, x: u32, y: u32) -> usize {
+    let cell = match pattern {
+        BayerPattern::Rggb => [0, 1, 1, 2],
+        BayerPattern::Bggr => [2, 1, 1, 0],
+        BayerPattern::Grbg => [1, 0, 2, 1],
+        BayerPattern::Gbrg => [1, 2, 0, 1],
+    };
+    cell[((y % 2) * 2 + x % 2) as usize]
+}
+
+/// Mosaic that a sensor with `pattern` records for a scene `scene(x, y)`.
+fn make_mosaic(
+    w: u32,
+    h: u32,
+    depth: PixelDepth,
+    pattern: BayerPattern,
+    scene: impl Fn(u32, u32) -> [u32; 3],
+) -> Pix {
+    let mut pm = Pix::new(w, h, depth).unwrap().to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            pm.set_pixel_unchecked(x, y, scene(x, y)[channel(pattern, x, y)]);
+        }
+    }
+    pm.into()
+}
+
+fn all_pixels_equal(pix: &Pix, rgb: (u8, u8, u8)) -> bool {
+    (0..pix.height())
+        .all(|y| (0..pix.width()).all(|x| pixel::extract_rgb(pix.get_pixel_unchecked(x, y)) == rgb))
+}
+
+#[test]
+fn demosaic_reg_solid_color() {
+    let mut rp = RegParams::new("demosaic_solid");
+
+    for pattern in PATTERNS {
+        let mosaic = make_mosaic(15, 10, PixelDepth::Bit8, pattern, |_, _| [200, 120, 40]);
+        let rgb = demosaic_bayer(&mosaic, pattern).unwrap();
+        rp.compare_values(32.0, rgb.depth().bits() as f64, 0.0);
+        rp.compare_values(15.0, rgb.width() as f64, 0.0);
+        rp.compare_values(10.0, rgb.height() as f64, 0.0);
+        rp.compare_values(
+            1.0,
+            all_pixels_equal(&rgb, (200, 120, 40)) as u8 as f64,
+            0.0,
+        );
+    }
+
+    // 16 bpp samples are scaled to 8 bits
+    let mosaic16 = make_mosaic(8, 8, PixelDepth::Bit16, BayerPattern::Rggb, |_, _| {
+        [65535, 257 * 100, 0]
+    });
+    let rgb = demosaic_bayer(&mosaic16, BayerPattern::Rggb).unwrap();
+    rp.compare_values(1.0, all_pixels_equal(&rgb, (255, 100, 0)) as u8 as f64, 0.0);
+
+    // Smallest mosaic: one filter cell
+    let tiny = make_mosaic(2, 2, PixelDepth::Bit8, BayerPattern::Gbrg, |_, _| {
+        [10, 20, 30]
+    });
+    let rgb = demosaic_bayer(&tiny, BayerPattern::Gbrg).unwrap();
+    rp.compare_values(1.0, all_pixels_equal(&rgb, (10, 20, 30)) as u8 as f64, 0.0);
+
+    assert!(rp.cleanup(), "demosaic solid test failed");
+}
+
+#[test]
+fn demosaic_reg_interpolation() {
+    let mut rp = RegParams::new("demosaic_interp");
+
+    // A horizontal ramp is linear, so interior pixels are recovered exactly
+    let ramp = |x: u32, _y: u32| [10 + 4 * x, 20 + 2 * x, 200 - 3 * x];
+    let mosaic = make_mosaic(40, 12, PixelDepth::Bit8, BayerPattern::Rggb, ramp);
+    let rgb = demosaic_bayer(&mosaic, BayerPattern::Rggb).unwrap();
+    let mut exact = true;
+    for y in 1..11 {
+        for x in 1..39 {
+            let [r, g, b] = ramp(x, y);
+            exact &=
+                pixel::extract_rgb(rgb.get_pixel_unchecked(x, y)) == (r as u8, g as u8, b as u8);
+        }
+    }
+    rp.compare_values(1.0, exact as u8 as f64, 0.0);
+
+    // Decoding with the wrong pattern swaps red and blue
+    let solid = make_mosaic(8, 8, PixelDepth::Bit8, BayerPattern::Rggb, |_, _| {
+        [250, 100, 5]
+    });
+    let swapped = demosaic_bayer(&solid, BayerPattern::Bggr).unwrap();
+    rp.compare_values(
+        1.0,
+        all_pixels_equal(&swapped, (5, 100, 250)) as u8 as f64,
+        0.0,
+    );
+
+    assert!(
+        demosaic_bayer(
+            &Pix::new(8, 8, PixelDepth::Bit32).unwrap(),
+            BayerPattern::Rggb
+        )
+        .is_err()
+    );
+    assert!(
+        demosaic_bayer(
+            &Pix::new(1, 8, PixelDepth::Bit8).unwrap(),
+            BayerPattern::Rggb
+        )
+        .is_err()
+    );
+
+    assert!(rp.cleanup(), "demosaic interpolation test failed");
+}
@@ -3,10 +3,12 @@ mod common;
 
 mod affine_reg;
 mod alphaxform_reg;
+mod ascii_reg;
 mod bilinear_reg;
 mod checkerboard_reg;
 mod circle_reg;
 mod crop_reg;
+mod demosaic_reg;
 mod expand_reg;
 mod multitype_reg;
 mod projection_reg;
@@ -12,13 +12,15 @@
 //!
 //! Projective uses 4 point correspondences (same as bilinear) but implements
 //! a perspective (homography) mapping.
+//!
+//! Also covers perspective crop (unwarp_quad).
 
 use crate::common::{RegParams, load_test_image};
 use leptonica::io::ImageFormat;
 use leptonica::transform::{
-    AffineFill, Point, ScaleMethod, projective_pta, projective_sampled_pta, scale,
+    AffineFill, Point, ScaleMethod, projective_pta, projective_sampled_pta, scale, unwarp_quad,
 };
-use leptonica::{Pix, PixelDepth};
+use leptonica::{Pix, PixelDepth, Pta};
 
 // Point data from C version (projective_reg.c MakePtas function)
 const X1: [i32; 5] = [300, 300, 300, 300, 32];
@@ -294,3 +296,69 @@ fn projective_reg_color_interpolation() {
 
     assert!(rp.cleanup(), "projective color interpolation test failed");
 }
+
+/// Test perspective crop of a quadrilateral to a rectangle
+#[test]
+fn projective_reg_unwarp_quad() {
+    let mut rp = RegParams::new("projective_unwarp");
+
+    // The image's own corners give an exact copy
+    let pixc = load_test_image("weasel32.png").expect("load weasel32.png");
+    let (w, h) = (pixc.width(), pixc.height());
+    let mut corners = Pta::new();
+    corners.push(0.0, 0.0);
+    corners.push(w as f32 - 1.0, 0.0);
+    corners.push(w as f32 - 1.0, h as f32 - 1.0);
+    corners.push(0.0, h as f32 - 1.0);
+    let out = unwarp_quad(&pixc, &corners, w, h).expect("unwarp_quad identity");
+    rp.compare_values(1.0, if out.equals(&pixc) { 1.0 } else { 0.0 }, 0.0);
+
+    // A dark skewed quad on white becomes a fully dark rectangle
+    let quad = [
+        (40.0f32, 30.0f32),
+        (170.0, 50.0),
+        (160.0, 170.0),
+        (30.0, 150.0),
+    ];
+    let mut pm = Pix::new(200, 200, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..200 {
+        for x in 0..200 {
+            let (px, py) = (x as f32, y as f32);
+            let inside = (0..4).all(|k| {
+                let (ax, ay) = quad[k];
+                let (bx, by) = quad[(k + 1) % 4];
+                (bx - ax) * (py - ay) - (by - ay) * (px - ax) >= 0.0
+            });
+            pm.set_pixel_unchecked(x, y, if inside { 20 } else { 235 });
+        }
+    }
+    let pixg: Pix = pm.into();
+    let mut corners = Pta::new();
+    for &(x, y) in &quad {
+        corners.push(x, y);
+    }
+    let out = unwarp_quad(&pixg, &corners, 120, 90).expect("unwarp_quad skewed");
+    rp.compare_values(120.0, out.width() as f64, 0.0);
+    rp.compare_values(90.0, out.height() as f64, 0.0);
+    let mut max_inner = 0;
+    for y in 2..88 {
+        for x in 2..118 {
+            max_inner = max_inner.max(out.get_pixel_unchecked(x, y));
+        }
+    }
+    rp.compare_values(20.0, max_inner as f64, 0.0);
+
+    // Wrong number of corners is rejected
+    corners.push(0.0, 0.0);
+    rp.compare_values(
+        1.0,
+        if unwarp_quad(&pixg, &corners, 10, 10).is_err() {
+            1.0
+        } else {
+            0.0
+        },
+        0.0,
+    );
+
+    assert!(rp.cleanup(), "projective unwarp_quad test failed");
+}
@@ -13,14 +13,16 @@
 //! four methods (Shear, Sampling, AreaMap, AMCorner) and multiple depths.
 //!
 //! C version: tests repeated arbitrary-angle rotation on multiple depths.
+//!
+//! Also covers explicit fill colors (`RotateFill::Color`).
 
 use crate::common::{RegParams, load_test_image};
-use leptonica::PixelDepth;
 use leptonica::io::ImageFormat;
 use leptonica::transform::{
     RotateFill, RotateMethod, RotateOptions, flip_lr, flip_tb, rotate, rotate_90, rotate_180,
     rotate_am_color_corner, rotate_am_corner,
 };
+use leptonica::{Pix, PixelDepth};
 
 /// Test basic orthogonal rotations and flips on a 1bpp image
 ///
@@ -293,3 +295,49 @@ fn rotate1_reg_am_corner() {
 
     assert!(rp.cleanup(), "rotate1 am_corner test failed");
 }
+
+/// Test rotation with an explicit gray fill color
+///
+/// Exposed corners take the given pixel value for every method, on
+/// both 8bpp and 32bpp images.
+#[test]
+fn rotate1_reg_color_fill() {
+    let mut rp = RegParams::new("rotate1_color_fill");
+
+    let methods = [
+        RotateMethod::Sampling,
+        RotateMethod::AreaMap,
+        RotateMethod::Shear,
+    ];
+    for (depth, fg, gray) in [
+        (PixelDepth::Bit8, 20u32, 0x80u32),
+        (PixelDepth::Bit32, 0xff000000, 0x80808000),
+    ] {
+        let mut pm = Pix::new(80, 60, depth).unwrap().to_mut();
+        for y in 0..60 {
+            for x in 0..80 {
+                pm.set_pixel_unchecked(x, y, fg);
+            }
+        }
+        let pix: Pix = pm.into();
+        for method in methods {
+            let options = RotateOptions {
+                method,
+                fill: RotateFill::Color(gray),
+                expand: false,
+                ..Default::default()
+            };
+            let rotated = rotate(&pix, 0.4, &options).expect("rotate with color fill");
+            // At least two opposite corners are exposed, whatever the
+            // rotation sense of the method, and none is black
+            let corners = [(0, 0), (79, 0), (0, 59), (79, 59)]
+                .map(|(x, y)| rotated.get_pixel_unchecked(x, y));
+            let nfill = corners.iter().filter(|&&v| v == gray).count();
+            let ok = nfill >= 2 && corners.iter().all(|&v| v == gray || v == fg);
+            rp.compare_values(1.0, if ok { 1.0 } else { 0.0 }, 0.0);
+            rp.compare_values(fg as f64, rotated.get_pixel_unchecked(40, 30) as f64, 0.0);
+        }
+    }
+
+    assert!(rp.cleanup(), "rotate1 color fill test failed");
+}
@@ -8,6 +8,8 @@
 //!   3. Two successive LR flips = identity
 //!   4. Two successive TB flips = identity
 //!   5. `rotate_orth(quads)` matches equivalent `rotate_90`/`rotate_180`
+//!   6. Metadata (resolution, colormap, spp, text) survives rotation, with
+//!      xres/yres swapped for 90/270 degrees
 //!
 //! C version runs the RotateOrthTest on 5 image types:
 //! binary (test1.png), 4bpp colormapped (weasel4.8g.png),
@@ -106,3 +108,41 @@ fn test_orth_rotation(rp: &mut RegParams, pixs: &leptonica::Pix, label: &str) {
     rp.compare_values(1.0, if same { 1.0 } else { 0.0 }, 0.0);
     eprintln!("  {}: 4x orth(1) == identity: {}", label, same);
 }
+
+/// Rotations carry metadata over; 90/270 degree rotations swap xres and yres.
+#[test]
+fn rotateorth_reg_metadata() {
+    let mut rp = RegParams::new("rotateorth_metadata");
+
+    let pixs = load_test_image("dreyfus8.png").expect("load dreyfus8.png");
+    assert!(pixs.has_colormap(), "dreyfus8.png should be colormapped");
+    let mut pm = pixs.to_mut();
+    pm.set_resolution(300, 150);
+    pm.set_text(Some("rotated".to_string()));
+    let pixs: leptonica::Pix = pm.into();
+    let ncolors = pixs.colormap().unwrap().len();
+
+    for quads in 0..4 {
+        let pixd = rotate_orth(&pixs, quads).expect("rotate_orth");
+        let (xres, yres) = if quads % 2 == 1 {
+            (150, 300)
+        } else {
+            (300, 150)
+        };
+        rp.compare_values(xres as f64, pixd.xres() as f64, 0.0);
+        rp.compare_values(yres as f64, pixd.yres() as f64, 0.0);
+        rp.compare_values(
+            ncolors as f64,
+            pixd.colormap().map_or(0, |c| c.len()) as f64,
+            0.0,
+        );
+        rp.compare_values(pixs.spp() as f64, pixd.spp() as f64, 0.0);
+        rp.compare_values(1.0, (pixd.text() == Some("rotated")) as u8 as f64, 0.0);
+    }
+
+    let ccw = rotate_90(&pixs, false).expect("rotate_90 ccw");
+    rp.compare_values(150.0, ccw.xres() as f64, 0.0);
+    rp.compare_values(300.0, ccw.yres() as f64, 0.0);
+
+    assert!(rp.cleanup(), "rotateorth metadata test failed");
+}
@@ -11,15 +11,21 @@
 //!   6. Anisotropic scaling (different x/y factors)
 //!   7. Scale with different methods (Linear, Sampling)
 //!   8. Scale on binary (1bpp) image
+//!   9. Alpha-aware RGBA scaling (`scale_rgba_li`)
+//!  10. Linear-light scaling (`scale_linear_light`)
+//!  11. Rank reduction pyramid (`rank_cascade_levels`)
+//!  12. Chromatic aberration correction (`correct_chromatic_aberration`)
 //!
 //! C version tests `pixScale` on 10 images of varying depth/colormap,
 //! and also tests `pixScaleToGray*`, `pixScaleSmoothToSize`, etc.
 
 use crate::common::{RegParams, load_test_image};
+use leptonica::core::pixel;
 use leptonica::io::ImageFormat;
 use leptonica::transform::{
-    ScaleMethod, scale, scale_by_sampling, scale_to_gray_2, scale_to_gray_3, scale_to_gray_4,
-    scale_to_gray_6, scale_to_gray_8, scale_to_size,
+    ScaleMethod, correct_chromatic_aberration, rank_cascade_levels, scale, scale_by_sampling,
+    scale_gray_rank_cascade, scale_linear_light, scale_rgba_li, scale_to_gray_2, scale_to_gray_3,
+    scale_to_gray_4, scale_to_gray_6, scale_to_gray_8, scale_to_size,
 };
 
 /// Test scaling operations on grayscale and binary images
@@ -141,3 +147,271 @@ fn scale_reg() {
 
     assert!(rp.cleanup(), "scale regression test failed");
 }
+
+/// Build an RGBA image: opaque red for x < `edge`, transparent black beyond
+fn make_red_transparent_edge(w: u32, h: u32, edge: u32) -> leptonica::Pix {
+    let mut pm = leptonica::Pix::new(w, h, leptonica::PixelDepth::Bit32)
+        .unwrap()
+        .to_mut();
+    pm.set_spp(4);
+    for y in 0..h {
+        for x in 0..w {
+            let val = if x < edge {
+                pixel::compose_rgba(255, 0, 0, 255)
+            } else {
+                pixel::compose_rgba(0, 0, 0, 0)
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    pm.into()
+}
+
+/// Test alpha-aware RGBA scaling.
+///
+/// Downscaling an opaque-red/transparent boundary must not darken the
+/// red edge; the edge pixel only becomes partially transparent.  An
+/// spp=3 image is opaque whatever its alpha bytes hold.
+#[test]
+fn scale_reg_rgba_li() {
+    let mut rp = RegParams::new("scale_rgba_li");
+
+    // Boundary at an odd column so one output pixel straddles it
+    let pix = make_red_transparent_edge(16, 8, 5);
+
+    let down = scale_rgba_li(&pix, 8, 4).expect("scale_rgba_li down");
+    rp.compare_values(8.0, down.width() as f64, 0.0);
+    rp.compare_values(4.0, down.height() as f64, 0.0);
+    rp.compare_values(4.0, down.spp() as f64, 0.0);
+    let mut min_red = 255u8;
+    for y in 0..4 {
+        for x in 0..8 {
+            let (r, g, b, a) = pixel::extract_rgba(down.get_pixel_unchecked(x, y));
+            if a > 0 {
+                min_red = min_red.min(r);
+                rp.compare_values(0.0, (g as u32 + b as u32) as f64, 0.0);
+            }
+        }
+    }
+    rp.compare_values(255.0, min_red as f64, 0.0);
+    // The straddling pixel (source columns 4 and 5) is half transparent
+    let (_, _, _, a) = pixel::extract_rgba(down.get_pixel_unchecked(2, 1));
+    rp.compare_values(128.0, a as f64, 1.0);
+    let (_, _, _, a) = pixel::extract_rgba(down.get_pixel_unchecked(6, 1));
+    rp.compare_values(0.0, a as f64, 0.0);
+
+    // Upscaling keeps the edge pure red as well
+    let up = scale_rgba_li(&pix, 40, 20).expect("scale_rgba_li up");
+    let mut ok = true;
+    for y in 0..20 {
+        for x in 0..40 {
+            let (r, g, b, a) = pixel::extract_rgba(up.get_pixel_unchecked(x, y));
+            if a > 0 {
+                ok &= r == 255 && g == 0 && b == 0;
+            }
+        }
+    }
+    rp.compare_values(1.0, ok as u32 as f64, 0.0);
+
+    // spp=3 red/blue edge with zero alpha bytes: plain color averaging
+    let mut pm = leptonica::Pix::new(16, 8, leptonica::PixelDepth::Bit32)
+        .unwrap()
+        .to_mut();
+    pm.set_spp(3);
+    for y in 0..8 {
+        for x in 0..16 {
+            let val = if x < 5 {
+                pixel::compose_rgba(255, 0, 0, 0)
+            } else {
+                pixel::compose_rgba(0, 0, 255, 0)
+            };
+            pm.set_pixel_unchecked(x, y, val);
+        }
+    }
+    let rgb: leptonica::Pix = pm.into();
+    let down = scale_rgba_li(&rgb, 8, 4).expect("scale_rgba_li spp=3");
+    rp.compare_values(3.0, down.spp() as f64, 0.0);
+    let (r, g, b) = pixel::extract_rgb(down.get_pixel_unchecked(2, 1));
+    rp.compare_values(128.0, r as f64, 1.0);
+    rp.compare_values(0.0, g as f64, 0.0);
+    rp.compare_values(128.0, b as f64, 1.0);
+    let (r, _, b) = pixel::extract_rgb(down.get_pixel_unchecked(6, 1));
+    rp.compare_values(0.0, r as f64, 0.0);
+    rp.compare_values(255.0, b as f64, 0.0);
+
+    let pix8 = leptonica::Pix::new(8, 8, leptonica::PixelDepth::Bit8).unwrap();
+    assert!(scale_rgba_li(&pix8, 4, 4).is_err());
+    assert!(scale_rgba_li(&pix, 0, 4).is_err());
+
+    assert!(rp.cleanup(), "scale_rgba_li test failed");
+}
+
+/// Test scaling in linear light.
+///
+/// Halving a 1-pixel black/white checkerboard averages the linear
+/// intensities 0 and 1, which encodes to sRGB 188; gamma-space area
+/// mapping gives 128.
+#[test]
+fn scale_reg_linear_light() {
+    let mut rp = RegParams::new("scale_linear_light");
+
+    let mut pm8 = leptonica::Pix::new(16, 16, leptonica::PixelDepth::Bit8)
+        .unwrap()
+        .to_mut();
+    let mut pm32 = leptonica::Pix::new(16, 16, leptonica::PixelDepth::Bit32)
+        .unwrap()
+        .to_mut();
+    for y in 0..16 {
+        for x in 0..16 {
+            let v = if (x + y) % 2 == 0 { 255 } else { 0 };
+            pm8.set_pixel_unchecked(x, y, v);
+            pm32.set_pixel_unchecked(x, y, pixel::compose_rgba(v as u8, v as u8, 0, 255));
+        }
+    }
+    let pix8: leptonica::Pix = pm8.into();
+    let pix32: leptonica::Pix = pm32.into();
+
+    let down = scale_linear_light(&pix8, 0.5, 0.5).expect("linear light gray");
+    rp.compare_values(8.0, down.width() as f64, 0.0);
+    rp.compare_values(188.0, down.get_pixel_unchecked(3, 3) as f64, 1.0);
+    let gamma = scale(&pix8, 0.5, 0.5, ScaleMethod::AreaMap).expect("area map");
+    rp.compare_values(128.0, gamma.get_pixel_unchecked(3, 3) as f64, 1.0);
+
+    let down = scale_linear_light(&pix32, 0.5, 0.5).expect("linear light color");
+    let (r, g, b, a) = pixel::extract_rgba(down.get_pixel_unchecked(3, 3));
+    rp.compare_values(188.0, r as f64, 1.0);
+    rp.compare_values(188.0, g as f64, 1.0);
+    rp.compare_values(0.0, b as f64, 0.0);
+    rp.compare_values(255.0, a as f64, 0.0);
+
+    // Uniform values are unchanged, including on enlargement
+    let mut flat = leptonica::Pix::new(6, 6, leptonica::PixelDepth::Bit8)
+        .unwrap()
+        .to_mut();
+    flat.set_all_gray(77).unwrap();
+    let flat: leptonica::Pix = flat.into();
+    let up = scale_linear_light(&flat, 2.5, 2.5).expect("linear light up");
+    rp.compare_values(15.0, up.width() as f64, 0.0);
+    rp.compare_values(77.0, up.get_pixel_unchecked(7, 7) as f64, 0.0);
+
+    let pix1 = leptonica::Pix::new(8, 8, leptonica::PixelDepth::Bit1).unwrap();
+    assert!(scale_linear_light(&pix1, 0.5, 0.5).is_err());
+    assert!(scale_linear_light(&pix8, 0.0, 0.5).is_err());
+
+    assert!(rp.cleanup(), "scale_linear_light test failed");
+}
+
+/// Test the rank reduction pyramid.
+#[test]
+fn scale_reg_rank_cascade_levels() {
+    let mut rp = RegParams::new("scale_rank_cascade_levels");
+
+    // Sparse dark dots on white: rank 1 (darkest) keeps them, rank 4 drops them
+    let mut pm = leptonica::Pix::new(64, 48, leptonica::PixelDepth::Bit8)
+        .unwrap()
+        .to_mut();
+    pm.set_all_gray(255).unwrap();
+    for y in (0..48).step_by(8) {
+        for x in (0..64).step_by(8) {
+            pm.set_pixel_unchecked(x, y, 0);
+        }
+    }
+    let pix: leptonica::Pix = pm.into();
+
+    let levels = rank_cascade_levels(&pix, &[1, 1, 1]).expect("cascade levels");
+    rp.compare_values(3.0, levels.len() as f64, 0.0);
+    for (i, (w, h)) in [(32, 24), (16, 12), (8, 6)].into_iter().enumerate() {
+        let level = levels.get(i).unwrap();
+        rp.compare_values(w as f64, level.width() as f64, 0.0);
+        rp.compare_values(h as f64, level.height() as f64, 0.0);
+        // Every 8x8 cell still holds its dark dot
+        rp.compare_values(0.0, level.get_pixel_unchecked(0, 0) as f64, 0.0);
+    }
+
+    // The last level matches the 3-stage cascade
+    let last = scale_gray_rank_cascade(&pix, 1, 1, 1, 0).expect("cascade");
+    rp.compare_pix(levels.get(2).unwrap(), &last);
+
+    let light = rank_cascade_levels(&pix, &[4, 4]).expect("cascade light");
+    rp.compare_values(
+        255.0,
+        light.get(1).unwrap().get_pixel_unchecked(0, 0) as f64,
+        0.0,
+    );
+
+    assert!(rank_cascade_levels(&pix, &[]).is_err());
+    assert!(rank_cascade_levels(&pix, &[1, 5]).is_err());
+    assert!(rank_cascade_levels(&pix, &[1; 7]).is_err());
+
+    assert!(rp.cleanup(), "rank_cascade_levels test failed");
+}
+
+#[test]
+fn scale_reg_chromatic_aberration() {
+    let mut rp = RegParams::new("scale_chromatic_aberration");
+
+    // Smooth neutral gray pattern: R = G = B everywhere
+    let (w, h) = (121u32, 101u32);
+    let mut pm = leptonica::Pix::new(w, h, leptonica::PixelDepth::Bit32)
+        .unwrap()
+        .to_mut();
+    for y in 0..h {
+        for x in 0..w {
+            let v = 128.0 + 100.0 * (x as f32 / 6.0).sin() * (y as f32 / 7.0).cos();
+            let v = v.round() as u8;
+            pm.set_pixel_unchecked(x, y, pixel::compose_rgb(v, v, v));
+        }
+    }
+    let pix: leptonica::Pix = pm.into();
+
+    // Mean |R - G| + |B - G| and mean |P - Q| over the interior
+    let margin = 6;
+    let fringe = |p: &leptonica::Pix| {
+        let mut sum = 0.0;
+        for y in margin..h - margin {
+            for x in margin..w - margin {
+                let (r, g, b) = pixel::extract_rgb(p.get_pixel_unchecked(x, y));
+                sum += (r as f64 - g as f64).abs() + (b as f64 - g as f64).abs();
+            }
+        }
+        sum / ((w - 2 * margin) * (h - 2 * margin)) as f64
+    };
+    let diff = |p: &leptonica::Pix, q: &leptonica::Pix| {
+        let mut sum = 0.0;
+        for y in margin..h - margin {
+            for x in margin..w - margin {
+                let (r1, g1, b1) = pixel::extract_rgb(p.get_pixel_unchecked(x, y));
+                let (r2, g2, b2) = pixel::extract_rgb(q.get_pixel_unchecked(x, y));
+                sum += (r1 as f64 - r2 as f64).abs()
+                    + (g1 as f64 - g2 as f64).abs()
+                    + (b1 as f64 - b2 as f64).abs();
+            }
+        }
+        sum / ((w - 2 * margin) * (h - 2 * margin)) as f64
+    };
+
+    // Unit factors change nothing
+    let same = correct_chromatic_aberration(&pix, 1.0, 1.0).expect("identity");
+    rp.compare_pix(&pix, &same);
+
+    // Deliberate misalignment: red too large, blue too small
+    let fringed = correct_chromatic_aberration(&pix, 1.03, 0.97).expect("misalign");
+    let before = fringe(&fringed);
+    rp.compare_values(1.0, (before > 10.0) as u8 as f64, 0.0);
+
+    // The inverse factors restore the original and remove the fringes
+    let fixed = correct_chromatic_aberration(&fringed, 1.0 / 1.03, 1.0 / 0.97).expect("correct");
+    let after = fringe(&fixed);
+    eprintln!("  fringe before = {before:.2}, after = {after:.2}");
+    rp.compare_values(1.0, (after < before / 5.0) as u8 as f64, 0.0);
+    rp.compare_values(0.0, diff(&pix, &fixed), 3.0);
+    // Green is never touched
+    let green = |p: &leptonica::Pix| pixel::extract_rgb(p.get_pixel_unchecked(60, 50)).1;
+    rp.compare_values(green(&pix) as f64, green(&fixed) as f64, 0.0);
+
+    let gray = leptonica::Pix::new(8, 8, leptonica::PixelDepth::Bit8).unwrap();
+    assert!(correct_chromatic_aberration(&gray, 1.0, 1.0).is_err());
+    assert!(correct_chromatic_aberration(&pix, 0.0, 1.0).is_err());
+
+    assert!(rp.cleanup(), "chromatic aberration test failed");
+}
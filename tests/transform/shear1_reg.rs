@@ -7,6 +7,8 @@
 //! Partial migration: colormap shear tests require pixOctreeColorQuant
 //! which is not available.
 //!
+//! Also covers explicit fill colors (`ShearFill::Color`).
+//!
 //! # See also
 //!
 //! C Leptonica: `prog/shear1_reg.c`
@@ -14,8 +16,10 @@
 use crate::common::RegParams;
 use leptonica::io::ImageFormat;
 use leptonica::transform::{
-    ShearFill, h_shear, h_shear_center, h_shear_corner, v_shear, v_shear_center, v_shear_corner,
+    ShearFill, h_shear, h_shear_center, h_shear_corner, h_shear_ip, h_shear_li, v_shear,
+    v_shear_center, v_shear_corner, v_shear_ip,
 };
+use leptonica::{Pix, PixelDepth};
 
 const ANGLE1: f32 = std::f32::consts::PI / 12.0;
 
@@ -167,3 +171,37 @@ fn shear1_reg_colormap() {
     // 3. Read 8bpp color cmap via pixOctreeColorQuant, shear
     // All require colormap handling in shear functions
 }
+
+/// Test shears with an explicit gray fill color
+#[test]
+fn shear1_reg_color_fill() {
+    let mut rp = RegParams::new("shear1_color_fill");
+
+    let mut pm = Pix::new(60, 40, PixelDepth::Bit8).unwrap().to_mut();
+    for y in 0..40 {
+        for x in 0..60 {
+            pm.set_pixel_unchecked(x, y, 10);
+        }
+    }
+    let pix: Pix = pm.into();
+    let fill = ShearFill::Color(128);
+
+    let sheared = h_shear_corner(&pix, ANGLE1, fill).expect("h_shear_corner color");
+    rp.compare_values(128.0, sheared.get_pixel_unchecked(59, 39) as f64, 0.0);
+    let sheared = v_shear_corner(&pix, ANGLE1, fill).expect("v_shear_corner color");
+    rp.compare_values(128.0, sheared.get_pixel_unchecked(59, 0) as f64, 0.0);
+    let sheared = h_shear_li(&pix, 0, ANGLE1, fill).expect("h_shear_li color");
+    rp.compare_values(128.0, sheared.get_pixel_unchecked(59, 39) as f64, 0.0);
+
+    // In-place shears repaint the exposed pixels
+    let mut pm = pix.to_mut();
+    h_shear_ip(&mut pm, 0, ANGLE1, fill).expect("h_shear_ip color");
+    rp.compare_values(128.0, pm.get_pixel_unchecked(59, 39) as f64, 0.0);
+    rp.compare_values(10.0, pm.get_pixel_unchecked(0, 39) as f64, 0.0);
+    let mut pm = pix.to_mut();
+    v_shear_ip(&mut pm, 0, ANGLE1, fill).expect("v_shear_ip color");
+    rp.compare_values(128.0, pm.get_pixel_unchecked(59, 0) as f64, 0.0);
+    rp.compare_values(10.0, pm.get_pixel_unchecked(59, 39) as f64, 0.0);
+
+    assert!(rp.cleanup(), "shear1 color fill test failed");
+}